@@ -0,0 +1,330 @@
+use crate::config::{ProjectConfig, TrackedFile};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{DEFAULT_MAX_HASH_SIZE, FileHash, FileOps};
+use crate::format;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Remove the files deployed by Specforge
+#[derive(Args)]
+pub struct CleanCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Skip the confirmation prompt and also remove files modified since deployment
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Files larger than this many bytes are not hashed and count as modified
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_HASH_SIZE)]
+    pub max_file_size: u64,
+}
+
+/// State of a tracked file compared to what was deployed
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FileState {
+    /// File content matches the deployed hash
+    Unmodified,
+    /// File content differs from the deployed hash
+    Modified,
+    /// File is too large to verify; holds its size in bytes
+    TooLarge(u64),
+    /// File no longer exists on disk
+    Missing,
+}
+
+impl CleanCommand {
+    /// Execute the clean command
+    pub fn execute(&self) -> Result<()> {
+        status!("{} Cleaning deployed Specforge files...", Status::Info);
+
+        let config_path = FileOps::locate_config(&self.directory);
+        let mut config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        if config.files.is_empty() {
+            status!("{} No deployed files are recorded in {}", Status::Info, config_path.display());
+            if output::is_json() {
+                output::print_json(Self::json_summary(&[], &[]));
+            }
+            return Ok(());
+        }
+
+        let plan = self.plan(&config)?;
+        self.display_plan(&plan);
+
+        let removable = plan
+            .iter()
+            .filter(|(_, state)| self.should_remove(state))
+            .count();
+
+        if removable > 0 && !self.force {
+            let prompt = format!("Delete {} deployed file(s)?", removable);
+            if !FileOps::confirm_action(&prompt)? {
+                status!("{} Operation cancelled by user", Status::Error);
+                return Err(ConfigError::user_cancelled("Clean cancelled"));
+            }
+        }
+
+        let mut kept = Vec::new();
+        let mut removed = Vec::new();
+        for (file, state) in plan {
+            match state {
+                FileState::Missing => {}
+                FileState::Modified if !self.force => {
+                    status!(
+                        "{} Skipping modified file: {} (use --force to remove it anyway)",
+                        Status::Warn,
+                        file.path
+                    );
+                    kept.push(file);
+                }
+                FileState::TooLarge(size) if !self.force => {
+                    status!(
+                        "{} Skipping {}: {} is above the {} verification limit (use --force to remove it anyway)",
+                        Status::Warn,
+                        file.path, format::size(size), format::size(self.max_file_size)
+                    );
+                    kept.push(file);
+                }
+                _ => {
+                    self.remove_file(&file)?;
+                    removed.push(file.path);
+                }
+            }
+        }
+
+        config.files = kept;
+        FileOps::write_config(&config, &config_path).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Updating deployed files in {}", config_path.display()),
+            )
+        })?;
+
+        if output::is_json() {
+            output::print_json(Self::json_summary(&removed, &config.files));
+        }
+        status!("{} Removed {} deployed file(s)", Status::Ok, removed.len());
+        if !config.files.is_empty() {
+            status!(
+                "{} {} modified file(s) are still tracked in {}",
+                Status::Info,
+                config.files.len(),
+                config_path.display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(removed: &[String], kept: &[TrackedFile]) -> serde_json::Value {
+        serde_json::json!({
+            "status": "success",
+            "removed": removed,
+            "kept": kept.iter().map(|f| &f.path).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Determine the state of every tracked file
+    fn plan(&self, config: &ProjectConfig) -> Result<Vec<(TrackedFile, FileState)>> {
+        config
+            .files
+            .iter()
+            .map(|file| {
+                let state = Self::file_state(&self.directory, file, self.max_file_size)?;
+                Ok((file.clone(), state))
+            })
+            .collect()
+    }
+
+    /// Compare a tracked file on disk against its recorded hash
+    pub(crate) fn file_state(directory: &Path, file: &TrackedFile, max_size: u64) -> Result<FileState> {
+        let path = directory.join(&file.path);
+        if !path.is_file() {
+            return Ok(FileState::Missing);
+        }
+
+        match FileOps::hash_file_with_limit(&path, max_size)? {
+            FileHash::Hashed(sha256) if sha256 == file.sha256 => Ok(FileState::Unmodified),
+            FileHash::Hashed(_) => Ok(FileState::Modified),
+            FileHash::TooLarge(size) => Ok(FileState::TooLarge(size)),
+        }
+    }
+
+    /// Whether a file in the given state will be deleted
+    fn should_remove(&self, state: &FileState) -> bool {
+        match state {
+            FileState::Unmodified => true,
+            FileState::Modified | FileState::TooLarge(_) => self.force,
+            FileState::Missing => false,
+        }
+    }
+
+    /// Show which files will be removed, skipped, or are already gone
+    fn display_plan(&self, plan: &[(TrackedFile, FileState)]) {
+        status!("📄 Deployed files:");
+        let mut plan: Vec<_> = plan.iter().collect();
+        plan.sort_by(|(a, _), (b, _)| format::listing_cmp(&a.path, &b.path));
+        for (file, state) in plan {
+            let note = match state {
+                FileState::Unmodified => "remove",
+                FileState::Modified if self.force => "remove (modified)",
+                FileState::Modified => "skip (modified)",
+                FileState::TooLarge(_) if self.force => "remove (too large to verify)",
+                FileState::TooLarge(_) => "skip (too large to verify)",
+                FileState::Missing => "already removed",
+            };
+            status!("   • {} - {}", file.path, note);
+        }
+        status!();
+    }
+
+    /// Delete a tracked file from disk
+    fn remove_file(&self, file: &TrackedFile) -> Result<()> {
+        let path = self.directory.join(&file.path);
+        fs::remove_file(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&path),
+            _ => ConfigError::from(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Agent;
+    use tempfile::TempDir;
+
+    /// Write a config tracking the given files, deploying them with their content
+    fn setup_project(dir: &Path, files: &[(&str, &str)]) {
+        let mut config = ProjectConfig::new(Agent::Claude);
+        for (path, content) in files {
+            fs::write(dir.join(path), content).unwrap();
+            config.track_file(TrackedFile::new(*path, &FileOps::hash_content(content)));
+        }
+        FileOps::write_config_to_directory(&config, dir).unwrap();
+    }
+
+    #[test]
+    fn test_clean_removes_unmodified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), &[("CLAUDE.md", "claude"), ("README.md", "readme")]);
+
+        let cmd = CleanCommand {
+            directory: temp_dir.path().to_path_buf(),
+            force: true,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        };
+        assert!(cmd.execute().is_ok());
+
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+        assert!(!temp_dir.path().join("README.md").exists());
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert!(config.files.is_empty());
+    }
+
+    #[test]
+    fn test_clean_force_removes_modified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), &[("CLAUDE.md", "claude")]);
+        fs::write(temp_dir.path().join("CLAUDE.md"), "user edits").unwrap();
+
+        let cmd = CleanCommand {
+            directory: temp_dir.path().to_path_buf(),
+            force: true,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        };
+        assert!(cmd.execute().is_ok());
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+    }
+
+    #[test]
+    fn test_clean_handles_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), &[("CLAUDE.md", "claude")]);
+        fs::remove_file(temp_dir.path().join("CLAUDE.md")).unwrap();
+
+        let cmd = CleanCommand {
+            directory: temp_dir.path().to_path_buf(),
+            force: true,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        };
+        assert!(cmd.execute().is_ok());
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert!(config.files.is_empty());
+    }
+
+    #[test]
+    fn test_file_state() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.md"), "original").unwrap();
+
+        let file = TrackedFile::new("a.md", &FileOps::hash_content("original"));
+        assert_eq!(
+            CleanCommand::file_state(temp_dir.path(), &file, DEFAULT_MAX_HASH_SIZE).unwrap(),
+            FileState::Unmodified
+        );
+
+        fs::write(temp_dir.path().join("a.md"), "changed").unwrap();
+        assert_eq!(
+            CleanCommand::file_state(temp_dir.path(), &file, DEFAULT_MAX_HASH_SIZE).unwrap(),
+            FileState::Modified
+        );
+
+        assert_eq!(
+            CleanCommand::file_state(temp_dir.path(), &file, 4).unwrap(),
+            FileState::TooLarge(7)
+        );
+
+        let missing = TrackedFile::new("b.md", &FileOps::hash_content("original"));
+        assert_eq!(
+            CleanCommand::file_state(temp_dir.path(), &missing, DEFAULT_MAX_HASH_SIZE).unwrap(),
+            FileState::Missing
+        );
+    }
+
+    #[test]
+    fn test_should_remove_respects_force() {
+        let cmd = CleanCommand {
+            directory: PathBuf::from("."),
+            force: false,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        };
+        assert!(cmd.should_remove(&FileState::Unmodified));
+        assert!(!cmd.should_remove(&FileState::Modified));
+        assert!(!cmd.should_remove(&FileState::TooLarge(1)));
+        assert!(!cmd.should_remove(&FileState::Missing));
+
+        let forced = CleanCommand {
+            directory: PathBuf::from("."),
+            force: true,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        };
+        assert!(forced.should_remove(&FileState::Modified));
+        assert!(forced.should_remove(&FileState::TooLarge(1)));
+    }
+
+    #[test]
+    fn test_clean_without_config_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = CleanCommand {
+            directory: temp_dir.path().to_path_buf(),
+            force: true,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        };
+        assert!(cmd.execute().is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::{Args, Command};
+use clap_complete::Shell;
+use std::path::PathBuf;
+
+/// Print a shell completion script
+#[derive(Args)]
+pub struct CompletionsCommand {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+
+    /// Write the script to a file in this directory instead of stdout
+    #[arg(short, long)]
+    pub output_directory: Option<PathBuf>,
+}
+
+impl CompletionsCommand {
+    /// Execute the completions command for the given command-line definition
+    pub fn execute(&self, command: &mut Command) -> Result<()> {
+        let bin_name = command.get_name().to_string();
+
+        let Some(directory) = &self.output_directory else {
+            if output::is_json() {
+                let mut script = Vec::new();
+                clap_complete::generate(self.shell, command, bin_name, &mut script);
+                let script = String::from_utf8_lossy(&script);
+                output::print_json(serde_json::json!({ "shell": self.shell.to_string(), "script": script }));
+            } else {
+                clap_complete::generate(self.shell, command, bin_name, &mut std::io::stdout());
+            }
+            return Ok(());
+        };
+
+        FileOps::ensure_directory_exists(directory)?;
+        let path = clap_complete::generate_to(self.shell, command, bin_name, directory).map_err(|e| {
+            match e.kind() {
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(directory),
+                _ => ConfigError::io_error(format!(
+                    "Failed to write {} completions to '{}': {}",
+                    self.shell,
+                    directory.display(),
+                    e
+                )),
+            }
+        })?;
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "shell": self.shell.to_string(), "path": path }));
+        } else {
+            status!("{} Wrote {} completions to {}", Status::Ok, self.shell, path.display());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ValueEnum};
+    use tempfile::TempDir;
+
+    #[derive(Clone, ValueEnum)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    fn test_command() -> Command {
+        Command::new("specforge").subcommand(
+            Command::new("init").arg(
+                Arg::new("color")
+                    .long("color")
+                    .value_parser(clap::builder::EnumValueParser::<Color>::new()),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_completions_written_to_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = CompletionsCommand {
+            shell: Shell::Bash,
+            output_directory: Some(temp_dir.path().join("completions")),
+        };
+        assert!(cmd.execute(&mut test_command()).is_ok());
+
+        let script =
+            std::fs::read_to_string(temp_dir.path().join("completions").join("specforge.bash")).unwrap();
+        assert!(script.contains("specforge"));
+        assert!(script.contains("red green"));
+    }
+}
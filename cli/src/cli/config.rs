@@ -0,0 +1,40 @@
+use crate::cli::prune_metadata::PruneMetadataCommand;
+use crate::cli::rename_project::RenameProjectCommand;
+use crate::cli::user_config::{ConfigGetCommand, ConfigListCommand, ConfigSetCommand};
+use crate::error::Result;
+use clap::{Args, Subcommand};
+
+/// Change the project configuration or the user defaults
+#[derive(Args)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub command: ConfigSubcommand,
+}
+
+/// Available configuration subcommands
+#[derive(Subcommand)]
+pub enum ConfigSubcommand {
+    /// Rename the project and update the deployed files that contain its name
+    RenameProject(RenameProjectCommand),
+    /// List metadata keys by size and remove the ones that are no longer needed
+    PruneMetadata(PruneMetadataCommand),
+    /// Store a default in the user configuration (default_agent, default_on_conflict, no_color, max_backups)
+    Set(ConfigSetCommand),
+    /// Print a default from the user configuration
+    Get(ConfigGetCommand),
+    /// List the defaults in the user configuration and where they come from
+    List(ConfigListCommand),
+}
+
+impl ConfigCommand {
+    /// Execute the selected configuration subcommand
+    pub fn execute(&self) -> Result<()> {
+        match &self.command {
+            ConfigSubcommand::RenameProject(rename_cmd) => rename_cmd.execute(),
+            ConfigSubcommand::PruneMetadata(prune_cmd) => prune_cmd.execute(),
+            ConfigSubcommand::Set(set_cmd) => set_cmd.execute(),
+            ConfigSubcommand::Get(get_cmd) => get_cmd.execute(),
+            ConfigSubcommand::List(list_cmd) => list_cmd.execute(),
+        }
+    }
+}
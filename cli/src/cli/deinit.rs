@@ -0,0 +1,356 @@
+use crate::cli::clean::{CleanCommand, FileState};
+use crate::cli::export::{GITHUB_ACTIONS_WORKFLOW_HEADER, GITHUB_ACTIONS_WORKFLOW_PATH};
+use crate::cli::init::relative_path;
+use crate::cli::install_hooks::{self, GitRepository};
+use crate::config::{ProjectConfig, TrackedFile};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::gitattributes::{self, GITATTRIBUTES_FILE_NAME};
+use crate::file_ops::gitignore::{self, GITIGNORE_FILE_NAME};
+use crate::file_ops::{DEFAULT_MAX_HASH_SIZE, FileOps, LOCK_FILE_NAME, backup};
+use crate::format;
+use crate::output;
+use crate::status;
+use crate::templates::{self, TemplateSystem};
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Remove Specforge from a project: its configuration, every deployed file
+/// and what the other commands added to the project
+#[derive(Args)]
+pub struct DeinitCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Skip the confirmation prompt and also remove files modified since deployment
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Files larger than this many bytes are not hashed and count as modified
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_HASH_SIZE)]
+    pub max_file_size: u64,
+}
+
+impl DeinitCommand {
+    /// Execute the deinit command
+    ///
+    /// Deployed files come from the configuration. Configurations without
+    /// tracked files fall back to the built-in templates of their agents, which
+    /// count as unmodified only while they match the template rendered for
+    /// this project.
+    ///
+    /// Backups, the exported workflow and the pre-commit hook installed for
+    /// this project are removed as well, and the entries Specforge added to
+    /// `.gitignore` and `.gitattributes` are taken out again. A hook left
+    /// behind would fail every commit once the configuration is gone.
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        if !config_path.is_file() {
+            status!("{} No Specforge configuration found in {}; nothing to remove", Status::Info, self.directory.display());
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "removed": [], "skipped": [] }));
+            }
+            return Ok(());
+        }
+
+        let config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let files = self.deployed_files(&config);
+        // Checked before anything is deleted, so a bad entry leaves the project untouched
+        for file in &files {
+            file.validate().map_err(|e| {
+                e.add_context("deinit", format!("Checking deployed files in {}", config_path.display()))
+            })?;
+        }
+
+        let plan = files
+            .into_iter()
+            .map(|file| {
+                let state = CleanCommand::file_state(&self.directory, &file, self.max_file_size)?;
+                Ok((file, state))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let specforge_files = self.specforge_files(&config_path, &plan)?;
+        let hook = self.installed_hook();
+        let gitignore_path = self.directory.join(GITIGNORE_FILE_NAME);
+        let gitignore_entries = gitignore::present_entries(&gitignore_path, &self.gitignore_entries(&config_path))?;
+        let attributes_path = self.directory.join(GITATTRIBUTES_FILE_NAME);
+        let attributes = gitattributes::has_managed_block(&attributes_path);
+
+        self.display_plan(&plan, &specforge_files);
+        if let Some(hook) = &hook {
+            status!("   • {} - remove", hook.display());
+        }
+        if !gitignore_entries.is_empty() {
+            status!("   • {} - remove {}", gitignore_path.display(), gitignore_entries.join(", "));
+        }
+        if attributes {
+            status!("   • {} - remove the Specforge block", attributes_path.display());
+        }
+        status!();
+
+        if !self.force {
+            let removable = plan.iter().filter(|(_, state)| self.should_remove(state)).count();
+            let prompt = format!(
+                "Remove Specforge from {} ({} file(s) will be deleted)?",
+                self.directory.display(),
+                removable + specforge_files.len() + usize::from(hook.is_some())
+            );
+            if !FileOps::confirm_action(&prompt)? {
+                status!("{} Operation cancelled by user", Status::Error);
+                return Err(ConfigError::user_cancelled("Deinit cancelled"));
+            }
+        }
+
+        let mut removed = Vec::new();
+        let mut skipped = Vec::new();
+        for (file, state) in &plan {
+            let path = self.directory.join(&file.path);
+            if self.should_remove(state) {
+                Self::remove_file(&path)?;
+                removed.push(path);
+            } else if *state != FileState::Missing {
+                status!("{} Keeping modified file: {} (use --force to remove it anyway)", Status::Warn, file.path);
+                skipped.push(path);
+            }
+        }
+        for path in specforge_files {
+            Self::remove_file(&path)?;
+            removed.push(path);
+        }
+        let mut updated = Vec::new();
+        if !gitignore::remove_entries(&gitignore_path, &gitignore_entries)?.is_empty() {
+            updated.push(gitignore_path);
+        }
+        if gitattributes::remove_managed_block(&attributes_path)? {
+            updated.push(attributes_path);
+        }
+
+        // The hooks directory belongs to Git, so it is not removed when empty
+        let directories = self.remove_empty_directories(&removed);
+        if let Some(hook) = hook {
+            Self::remove_file(&hook)?;
+            removed.push(hook);
+        }
+
+        status!("{} Removed {} file(s) and {} empty directory(ies)", Status::Ok, removed.len(), directories.len());
+        for path in &updated {
+            status!("{} Removed the Specforge entries from {}", Status::Ok, path.display());
+        }
+        if !skipped.is_empty() {
+            status!("{} {} modified file(s) were kept", Status::Info, skipped.len());
+        }
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "removed": removed,
+                "skipped": skipped,
+                "directories": directories,
+                "updated": updated,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Files deployed into the project, falling back to the agents' templates
+    /// with the hash of their rendered content when none are tracked
+    fn deployed_files(&self, config: &ProjectConfig) -> Vec<TrackedFile> {
+        if !config.files.is_empty() {
+            return config.files.clone();
+        }
+
+        let context = TemplateSystem::template_context(config, &self.directory);
+        let (resolved, _) = TemplateSystem::resolve_templates(&config.agents);
+        resolved
+            .iter()
+            .map(|template| {
+                let (rendered, _) = templates::render_template(
+                    &template.content,
+                    &TemplateSystem::agent_context(&context, &template.agent),
+                );
+                let content = TemplateSystem::with_provenance(template, &rendered, &context);
+                TrackedFile::new(template.name.to_string(), FileOps::hash_tracked_content(&content))
+            })
+            .collect()
+    }
+
+    /// Files Specforge wrote next to the deployed ones: the configuration, the
+    /// lock file, backups of the configuration and the deployed files, and the
+    /// exported workflow
+    fn specforge_files(&self, config_path: &Path, plan: &[(TrackedFile, FileState)]) -> Result<Vec<PathBuf>> {
+        let mut files = vec![config_path.to_path_buf()];
+        let lock_path = config_path.parent().unwrap_or(Path::new(".")).join(LOCK_FILE_NAME);
+        if lock_path.is_file() {
+            files.push(lock_path);
+        }
+        files.extend(FileOps::config_backups(config_path)?);
+        for (file, _) in plan {
+            files.extend(backup::find(&self.directory.join(&file.path))?);
+        }
+
+        let workflow = self.directory.join(GITHUB_ACTIONS_WORKFLOW_PATH);
+        let generated = fs::read_to_string(&workflow)
+            .is_ok_and(|content| content.starts_with(GITHUB_ACTIONS_WORKFLOW_HEADER));
+        if generated {
+            files.push(workflow);
+        }
+        Ok(files)
+    }
+
+    /// The pre-commit hook `specforge install-hooks` installed for this
+    /// project, if it is still the one it wrote
+    fn installed_hook(&self) -> Option<PathBuf> {
+        let repository = GitRepository::discover(&self.directory).ok()?;
+        let project_dir = fs::canonicalize(&self.directory).ok()?;
+        let relative = relative_path(&repository.work_tree, &project_dir);
+        let script = install_hooks::pre_commit_script(if relative.is_empty() { "." } else { &relative });
+
+        let hook_path = repository.hook_path("pre-commit");
+        (fs::read_to_string(&hook_path).ok()? == script).then_some(hook_path)
+    }
+
+    /// The `.gitignore` entries `specforge init --update-gitignore` adds
+    fn gitignore_entries(&self, config_path: &Path) -> Vec<String> {
+        vec![
+            gitignore::BACKUP_PATTERN.to_string(),
+            backup::BACKUP_FILE_PATTERN.to_string(),
+            relative_path(&self.directory, config_path),
+        ]
+    }
+
+    /// Whether a deployed file in the given state will be deleted
+    fn should_remove(&self, state: &FileState) -> bool {
+        match state {
+            FileState::Unmodified => true,
+            FileState::Modified | FileState::TooLarge(_) => self.force,
+            FileState::Missing => false,
+        }
+    }
+
+    /// Show which files will be removed or kept
+    fn display_plan(&self, plan: &[(TrackedFile, FileState)], specforge_files: &[PathBuf]) {
+        status!("🗑️  Removing Specforge from {}", self.directory.display());
+        let mut plan: Vec<_> = plan.iter().collect();
+        plan.sort_by(|(a, _), (b, _)| format::listing_cmp(&a.path, &b.path));
+        for (file, state) in plan {
+            let note = match state {
+                FileState::Unmodified => "remove",
+                FileState::Modified if self.force => "remove (modified)",
+                FileState::Modified => "keep (modified)",
+                FileState::TooLarge(_) if self.force => "remove (too large to verify)",
+                FileState::TooLarge(_) => "keep (too large to verify)",
+                FileState::Missing => "already removed",
+            };
+            status!("   • {} - {}", file.path, note);
+        }
+        for path in specforge_files {
+            status!("   • {} - remove", path.display());
+        }
+    }
+
+    /// Delete a file from disk
+    fn remove_file(path: &Path) -> Result<()> {
+        fs::remove_file(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(path),
+            _ => ConfigError::from(e),
+        })
+    }
+
+    /// Remove the directories left empty by the removed files, such as
+    /// `.claude/commands`, without ever removing the project directory itself
+    fn remove_empty_directories(&self, removed: &[PathBuf]) -> Vec<PathBuf> {
+        let mut directories = Vec::new();
+        for path in removed {
+            let mut dir = path.parent();
+            while let Some(current) = dir {
+                if current == self.directory || !current.starts_with(&self.directory) {
+                    break;
+                }
+                // Fails for directories that still hold other files
+                if fs::remove_dir(current).is_err() {
+                    break;
+                }
+                directories.push(current.to_path_buf());
+                dir = current.parent();
+            }
+        }
+        directories
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Agent;
+    use tempfile::TempDir;
+
+    fn deinit(directory: &Path, force: bool) -> DeinitCommand {
+        DeinitCommand {
+            directory: directory.to_path_buf(),
+            force,
+            max_file_size: DEFAULT_MAX_HASH_SIZE,
+        }
+    }
+
+    #[test]
+    fn test_deinit_removes_config_files_and_empty_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let commands = temp_dir.path().join(".claude").join("commands");
+        fs::create_dir_all(&commands).unwrap();
+        fs::write(commands.join("plan.md"), "plan").unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "edited").unwrap();
+
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.track_file(TrackedFile::new(".claude/commands/plan.md", &FileOps::hash_content("plan")));
+        config.track_file(TrackedFile::new("CLAUDE.md", &FileOps::hash_content("claude")));
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        deinit(temp_dir.path(), true).execute().unwrap();
+
+        assert!(!temp_dir.path().join(".specforge.json").exists());
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+        assert!(!temp_dir.path().join(".claude").exists());
+        assert!(temp_dir.path().exists());
+    }
+
+    #[test]
+    fn test_deinit_falls_back_to_agent_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ProjectConfig::new(Agent::Claude);
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        let cmd = deinit(temp_dir.path(), true);
+        let files = cmd.deployed_files(&config);
+        let names: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(names, ["CLAUDE.md", "README.md"]);
+    }
+
+    #[test]
+    fn test_deinit_rejects_paths_outside_the_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside = temp_dir.path().join("outside.md");
+        fs::write(&outside, "keep me").unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir(&project).unwrap();
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.files.push(TrackedFile::new("../outside.md", &FileOps::hash_content("keep me")));
+        fs::write(project.join(".specforge.json"), serde_json::to_string(&config).unwrap()).unwrap();
+
+        let err = deinit(&project, true).execute().unwrap_err();
+        assert!(err.to_string().contains("cannot point outside the project directory"), "{}", err);
+        assert!(outside.exists());
+        assert!(project.join(".specforge.json").exists());
+    }
+
+    #[test]
+    fn test_deinit_without_config_succeeds() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(deinit(temp_dir.path(), false).execute().is_ok());
+    }
+}
@@ -0,0 +1,50 @@
+use crate::config::{ConfigDiff, ProjectConfig};
+use crate::error::Result;
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// Compare two configuration files and summarize what changed
+#[derive(Args)]
+pub struct DiffCommand {
+    /// Configuration file before the change
+    #[arg(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// Configuration file after the change
+    #[arg(value_name = "NEW")]
+    pub new: PathBuf,
+}
+
+impl DiffCommand {
+    /// Execute the diff command
+    ///
+    /// Both files are read as other commands read them, so they are validated
+    /// and the configurations they extend are merged in before comparing.
+    pub fn execute(&self) -> Result<()> {
+        let old = Self::read(&self.old)?;
+        let new = Self::read(&self.new)?;
+        let diff = ConfigDiff::between(&old, &new);
+
+        if output::is_json() {
+            output::print_json(serde_json::json!(diff));
+            return Ok(());
+        }
+        if diff.is_empty() {
+            status!("{} {} and {} are the same", Status::Ok, self.old.display(), self.new.display());
+            return Ok(());
+        }
+        for entry in &diff.entries {
+            status!("{}", entry);
+        }
+        Ok(())
+    }
+
+    fn read(path: &Path) -> Result<ProjectConfig> {
+        FileOps::read_config(path)
+            .map_err(|e| e.add_context("configuration loading", format!("Reading {}", path.display())))
+    }
+}
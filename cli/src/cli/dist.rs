@@ -0,0 +1,384 @@
+use crate::config;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, SCHEMA_FILE_NAME};
+use crate::output;
+use crate::status;
+use clap::{Args, Command, ValueEnum};
+use clap_complete::{Generator, Shell};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest listing every generated artifact
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Generate the auxiliary files a distribution package ships
+#[derive(Args)]
+pub struct DistCommand {
+    /// Directory to write the artifacts to
+    #[arg(long)]
+    pub out_dir: PathBuf,
+}
+
+/// A generated file, identified by its `/`-separated path inside the output directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artifact {
+    pub path: String,
+    pub content: String,
+}
+
+impl DistCommand {
+    /// Execute the dist command for the given command-line definition
+    ///
+    /// The output only depends on the command-line definition and this release,
+    /// so running it twice produces byte-identical files.
+    pub fn execute(&self, command: &mut Command) -> Result<()> {
+        let artifacts = Self::artifacts(command)?;
+        for artifact in &artifacts {
+            Self::write_artifact(&self.out_dir, artifact)?;
+        }
+
+        if output::is_json() {
+            output::print_json(Self::manifest(&artifacts));
+        } else {
+            status!("📦 Wrote {} artifacts to {}", artifacts.len(), self.out_dir.display());
+        }
+        Ok(())
+    }
+
+    /// All artifacts in a stable order, followed by the manifest describing them
+    pub fn artifacts(command: &mut Command) -> Result<Vec<Artifact>> {
+        command.build();
+
+        let mut artifacts = Self::completions(command)?;
+        artifacts.extend(Self::man_pages(command));
+        artifacts.push(Artifact {
+            path: "exit-codes.json".to_string(),
+            content: Self::exit_codes(),
+        });
+        artifacts.push(Artifact {
+            path: SCHEMA_FILE_NAME.to_string(),
+            content: serde_json::to_string_pretty(&config::config_schema())? + "\n",
+        });
+        artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let manifest = serde_json::to_string_pretty(&Self::manifest(&artifacts))?;
+        artifacts.push(Artifact {
+            path: MANIFEST_FILE_NAME.to_string(),
+            content: manifest + "\n",
+        });
+        Ok(artifacts)
+    }
+
+    /// Completion scripts for every supported shell
+    fn completions(command: &mut Command) -> Result<Vec<Artifact>> {
+        let bin_name = command.get_name().to_string();
+        Shell::value_variants()
+            .iter()
+            .map(|shell| {
+                let mut script = Vec::new();
+                clap_complete::generate(*shell, command, &bin_name, &mut script);
+                let content = String::from_utf8(script).map_err(|e| {
+                    ConfigError::validation_error(format!("{} completions are not valid UTF-8: {}", shell, e))
+                })?;
+                let file_name = shell.file_name(&bin_name);
+                Ok(Artifact {
+                    path: format!("completions/{}", file_name),
+                    content,
+                })
+            })
+            .collect()
+    }
+
+    /// A man page for the command and one for each of its subcommands
+    fn man_pages(command: &Command) -> Vec<Artifact> {
+        let version = command.get_version().unwrap_or(env!("CARGO_PKG_VERSION"));
+        let mut pages = Vec::new();
+        Self::collect_man_pages(command, &[], version, &mut pages);
+        pages
+    }
+
+    fn collect_man_pages(command: &Command, parents: &[&str], version: &str, pages: &mut Vec<Artifact>) {
+        let mut names = parents.to_vec();
+        names.push(command.get_name());
+
+        pages.push(Artifact {
+            path: format!("man/{}.1", names.join("-")),
+            content: render_man_page(command, &names, version),
+        });
+        for subcommand in visible_subcommands(command) {
+            Self::collect_man_pages(subcommand, &names, version, pages);
+        }
+    }
+
+    /// Error codes and the exit codes they produce, as a JSON array
+    fn exit_codes() -> String {
+        let table: Vec<_> = ConfigError::exit_code_table()
+            .into_iter()
+            .map(|(code, exit_code)| serde_json::json!({ "code": code, "exit_code": exit_code }))
+            .collect();
+        let mut content = serde_json::to_string_pretty(&table).unwrap_or_default();
+        content.push('\n');
+        content
+    }
+
+    /// Path, size and SHA-256 checksum of each artifact
+    fn manifest(artifacts: &[Artifact]) -> serde_json::Value {
+        let files: Vec<_> = artifacts
+            .iter()
+            .map(|artifact| {
+                serde_json::json!({
+                    "path": artifact.path,
+                    "size": artifact.content.len(),
+                    "sha256": FileOps::hash_content(&artifact.content),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "artifacts": files,
+        })
+    }
+
+    fn write_artifact(out_dir: &Path, artifact: &Artifact) -> Result<()> {
+        let path = artifact
+            .path
+            .split('/')
+            .fold(out_dir.to_path_buf(), |path, part| path.join(part));
+        if let Some(parent) = path.parent() {
+            FileOps::ensure_directory_exists(parent)?;
+        }
+        fs::write(&path, &artifact.content).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&path),
+            _ => ConfigError::io_error(format!("Failed to write '{}': {}", path.display(), e)),
+        })
+    }
+}
+
+/// Subcommands that are documented, skipping the generated `help` subcommand
+fn visible_subcommands(command: &Command) -> impl Iterator<Item = &Command> {
+    command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set() && subcommand.get_name() != "help")
+}
+
+/// Render a roff man page for a command; `names` is the command path from the binary down
+fn render_man_page(command: &Command, names: &[&str], version: &str) -> String {
+    let title = names.join("-");
+    let about = command.get_about().map(|about| about.to_string()).unwrap_or_default();
+
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\"\n",
+        roff_escape(&title.to_uppercase()),
+        roff_escape(names[0]),
+        roff_escape(version)
+    );
+
+    page.push_str(".SH NAME\n");
+    page.push_str(&format!("{} \\- {}\n", roff_escape(&title), roff_escape(&about)));
+
+    page.push_str(".SH SYNOPSIS\n");
+    page.push_str(&format!("\\fB{}\\fR", roff_escape(&names.join(" "))));
+    if command.get_arguments().any(|arg| !arg.is_positional()) {
+        page.push_str(" [OPTIONS]");
+    }
+    for arg in command.get_positionals() {
+        let name = value_name(arg);
+        if arg.is_required_set() {
+            page.push_str(&format!(" <{}>", roff_escape(&name)));
+        } else {
+            page.push_str(&format!(" [{}]", roff_escape(&name)));
+        }
+    }
+    if command.has_subcommands() {
+        page.push_str(" <COMMAND>");
+    }
+    page.push('\n');
+
+    let description = command
+        .get_long_about()
+        .map(|about| about.to_string())
+        .unwrap_or(about);
+    if !description.is_empty() {
+        page.push_str(".SH DESCRIPTION\n");
+        page.push_str(&roff_paragraph(&description));
+    }
+
+    let options: Vec<_> = command.get_arguments().filter(|arg| !arg.is_hide_set()).collect();
+    if !options.is_empty() {
+        page.push_str(".SH OPTIONS\n");
+        for arg in options {
+            page.push_str(".TP\n");
+            page.push_str(&roff_argument(arg));
+            page.push('\n');
+            if let Some(help) = arg.get_long_help().or(arg.get_help()) {
+                page.push_str(&roff_paragraph(&help.to_string()));
+            }
+            let values: Vec<_> = arg
+                .get_possible_values()
+                .into_iter()
+                .filter(|value| !value.is_hide_set())
+                .map(|value| value.get_name().to_string())
+                .collect();
+            if !values.is_empty() {
+                page.push_str(".br\n");
+                page.push_str(&roff_paragraph(&format!("[possible values: {}]", values.join(", "))));
+            }
+        }
+    }
+
+    let subcommands: Vec<_> = visible_subcommands(command).collect();
+    if !subcommands.is_empty() {
+        page.push_str(".SH SUBCOMMANDS\n");
+        for subcommand in subcommands {
+            page.push_str(".TP\n");
+            page.push_str(&format!(
+                "\\fB{}\\-{}\\fR(1)\n",
+                roff_escape(&title),
+                roff_escape(subcommand.get_name())
+            ));
+            if let Some(about) = subcommand.get_about() {
+                page.push_str(&roff_paragraph(&about.to_string()));
+            }
+        }
+    }
+
+    page
+}
+
+/// The flags or value name of an argument, e.g. `-a, --agent <AGENT>`
+fn roff_argument(arg: &clap::Arg) -> String {
+    let mut flags = Vec::new();
+    if let Some(short) = arg.get_short() {
+        flags.push(format!("\\fB\\-{}\\fR", short));
+    }
+    if let Some(long) = arg.get_long() {
+        flags.push(format!("\\fB\\-\\-{}\\fR", roff_escape(long)));
+    }
+
+    let value_name = value_name(arg);
+    let takes_value = arg.get_action().takes_values();
+
+    if flags.is_empty() {
+        return format!("<{}>", roff_escape(&value_name));
+    }
+    let mut rendered = flags.join(", ");
+    if takes_value {
+        rendered.push_str(&format!(" <{}>", roff_escape(&value_name)));
+    }
+    rendered
+}
+
+/// The placeholder shown for an argument's value, as in `--help`
+fn value_name(arg: &clap::Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| arg.get_id().to_string())
+}
+
+/// Escape text so roff prints it literally
+fn roff_escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+/// Escaped text as lines that cannot be mistaken for roff requests
+fn roff_paragraph(text: &str) -> String {
+    let mut paragraph = String::new();
+    for line in text.lines() {
+        let line = roff_escape(line.trim());
+        if line.is_empty() {
+            paragraph.push_str(".PP\n");
+            continue;
+        }
+        if line.starts_with('.') || line.starts_with('\'') {
+            paragraph.push_str("\\&");
+        }
+        paragraph.push_str(&line);
+        paragraph.push('\n');
+    }
+    paragraph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+    use tempfile::TempDir;
+
+    fn test_command() -> Command {
+        Command::new("specforge")
+            .version("1.2.3")
+            .about("Forge specs")
+            .subcommand(
+                Command::new("init")
+                    .about("Initialize a project")
+                    .arg(Arg::new("agent").short('a').long("agent").help("The agent to use")),
+            )
+    }
+
+    #[test]
+    fn test_artifacts_are_sorted_with_manifest_last() {
+        let artifacts = DistCommand::artifacts(&mut test_command()).unwrap();
+        let paths: Vec<_> = artifacts.iter().map(|a| a.path.as_str()).collect();
+
+        assert!(paths.contains(&"completions/specforge.bash"));
+        assert!(paths.contains(&"completions/_specforge.ps1"));
+        assert!(paths.contains(&"man/specforge.1"));
+        assert!(paths.contains(&"man/specforge-init.1"));
+        assert!(!paths.contains(&"man/specforge-help.1"));
+        assert!(paths.contains(&"exit-codes.json"));
+        assert!(paths.contains(&SCHEMA_FILE_NAME));
+        assert_eq!(paths.last(), Some(&MANIFEST_FILE_NAME));
+
+        let mut sorted = paths[..paths.len() - 1].to_vec();
+        sorted.sort();
+        assert_eq!(sorted, paths[..paths.len() - 1]);
+    }
+
+    #[test]
+    fn test_manifest_checksums_match_content() {
+        let artifacts = DistCommand::artifacts(&mut test_command()).unwrap();
+        let manifest: serde_json::Value =
+            serde_json::from_str(&artifacts.last().unwrap().content).unwrap();
+
+        assert_eq!(manifest["version"], env!("CARGO_PKG_VERSION"));
+        let entries = manifest["artifacts"].as_array().unwrap();
+        assert_eq!(entries.len(), artifacts.len() - 1);
+        for (entry, artifact) in entries.iter().zip(&artifacts) {
+            assert_eq!(entry["path"], artifact.path);
+            assert_eq!(entry["sha256"], FileOps::hash_content(&artifact.content));
+        }
+    }
+
+    #[test]
+    fn test_render_man_page() {
+        let mut command = test_command();
+        command.build();
+        let init = command.find_subcommand("init").unwrap();
+        let page = render_man_page(init, &["specforge", "init"], "1.2.3");
+
+        assert!(page.starts_with(".TH SPECFORGE\\-INIT 1 \"\" \"specforge 1.2.3\"\n"));
+        assert!(page.contains("specforge\\-init \\- Initialize a project\n"));
+        assert!(page.contains("\\fB\\-a\\fR, \\fB\\-\\-agent\\fR <agent>\n"));
+        assert!(page.contains("The agent to use\n"));
+    }
+
+    #[test]
+    fn test_roff_paragraph_escapes_requests() {
+        assert_eq!(roff_paragraph(".specforge.json is read"), "\\&.specforge.json is read\n");
+        assert_eq!(roff_paragraph("a\\b"), "a\\eb\n");
+    }
+
+    #[test]
+    fn test_dist_writes_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = DistCommand {
+            out_dir: temp_dir.path().join("dist"),
+        };
+        cmd.execute(&mut test_command()).unwrap();
+
+        assert!(temp_dir.path().join("dist/completions/specforge.fish").is_file());
+        assert!(temp_dir.path().join("dist/man/specforge-init.1").is_file());
+        assert!(temp_dir.path().join("dist").join(MANIFEST_FILE_NAME).is_file());
+    }
+}
@@ -0,0 +1,638 @@
+use crate::config::{Agent, ProjectConfig, migration};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{ConfigFormat, FileOps};
+use crate::format;
+use crate::output;
+use crate::status;
+use crate::templates::TemplateSystem;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Diagnose common configuration problems in a Specforge project
+#[derive(Args)]
+pub struct DoctorCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+}
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosticResult {
+    /// Short name of the check
+    pub name: String,
+    /// Whether the check passed
+    pub passed: bool,
+    /// What was found, or how to resolve the problem
+    pub message: String,
+    /// Whether the result is a finding to be aware of rather than a check
+    pub informational: bool,
+    /// File the result is about, if any
+    pub path: Option<PathBuf>,
+}
+
+impl DiagnosticResult {
+    /// Create a passing result
+    pub fn pass<S1: Into<String>, S2: Into<String>>(name: S1, message: S2) -> Self {
+        Self {
+            name: name.into(),
+            passed: true,
+            message: message.into(),
+            informational: false,
+            path: None,
+        }
+    }
+
+    /// Create a failing result
+    pub fn fail<S1: Into<String>, S2: Into<String>>(name: S1, message: S2) -> Self {
+        Self {
+            name: name.into(),
+            passed: false,
+            message: message.into(),
+            informational: false,
+            path: None,
+        }
+    }
+
+    /// Create an informational finding, which never fails the diagnosis
+    pub fn info<S1: Into<String>, S2: Into<String>>(name: S1, message: S2) -> Self {
+        Self {
+            informational: true,
+            ..Self::pass(name, message)
+        }
+    }
+
+    /// Attach the file the result is about
+    pub fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl DoctorCommand {
+    /// Execute the doctor command
+    pub fn execute(&self) -> Result<()> {
+        status!("{} Checking Specforge project in {}...", Status::Info, self.directory.display());
+        status!();
+
+        let results = self.run_checks();
+        for result in &results {
+            let icon = match (result.passed, result.informational) {
+                (_, true) => Status::Info,
+                (true, false) => Status::Ok,
+                (false, false) => Status::Error,
+            };
+            status!("{} {}: {}", icon, result.name, result.message);
+        }
+        status!();
+
+        let failed = results.iter().filter(|r| !r.passed).count();
+        if output::is_json() {
+            output::print_json(Self::json_summary(&results));
+        }
+        if failed > 0 {
+            return Err(ConfigError::diagnostics_failed(failed));
+        }
+
+        let checks = results.iter().filter(|r| !r.informational).count();
+        status!("🎉 All {} checks passed", checks);
+        Ok(())
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(results: &[DiagnosticResult]) -> serde_json::Value {
+        let passed = results.iter().all(|r| r.passed);
+        let checks: Vec<_> = results
+            .iter()
+            .map(|r| {
+                let mut check = serde_json::json!({ "name": r.name, "passed": r.passed, "message": r.message });
+                if r.informational {
+                    check["informational"] = true.into();
+                }
+                if let Some(path) = &r.path {
+                    check["path"] = path.display().to_string().into();
+                }
+                check
+            })
+            .collect();
+        serde_json::json!({
+            "status": if passed { "success" } else { "failed" },
+            "checks": checks,
+        })
+    }
+
+    /// Run all diagnostic checks against the project directory
+    pub fn run_checks(&self) -> Vec<DiagnosticResult> {
+        self.run_checks_with_home(home_directory().as_deref())
+    }
+
+    /// Run the checks, looking for user-level agent files under `home`
+    fn run_checks_with_home(&self, home: Option<&Path>) -> Vec<DiagnosticResult> {
+        let mut results = Vec::new();
+
+        let (config, invalid_versions) = match self.check_config(&mut results) {
+            Some(checked) => checked,
+            // The remaining checks need a readable configuration
+            None => return results,
+        };
+
+        results.extend(
+            config
+                .warnings()
+                .into_iter()
+                .map(|warning| DiagnosticResult::info("Configuration warning", warning.to_string())),
+        );
+
+        for agent in &config.agents {
+            results.push(Self::check_agent_detected(&self.directory, agent));
+        }
+        results.extend(Self::find_user_level_files(&config.agents, home, std::env::consts::OS));
+
+        for file in &config.files {
+            results.push(Self::check_deployed_file(&self.directory, &file.path));
+        }
+        results.extend(Self::check_instructions_size(&self.directory, &config.agents));
+
+        for package in &config.packages {
+            results.push(DiagnosticResult::pass(
+                format!("Package {} version", package.id),
+                format!("{} follows semantic versioning", package.version),
+            ));
+        }
+        results.extend(invalid_versions);
+
+        results
+    }
+
+    /// Check that the configuration file exists and can be parsed
+    ///
+    /// Packages with an invalid version are reported separately and left out
+    /// of the returned configuration, so the other checks can still run.
+    fn check_config(
+        &self,
+        results: &mut Vec<DiagnosticResult>,
+    ) -> Option<(ProjectConfig, Vec<DiagnosticResult>)> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let name = "Configuration file";
+
+        if !config_path.is_file() {
+            results.push(DiagnosticResult::fail(
+                name,
+                format!("{} not found. Run 'specforge init' to create it", config_path.display()),
+            ));
+            return None;
+        }
+
+        let content = match fs::read_to_string(&config_path) {
+            Ok(content) => content,
+            Err(e) => {
+                results.push(DiagnosticResult::fail(
+                    name,
+                    format!("{} could not be read: {}", config_path.display(), e),
+                ));
+                return None;
+            }
+        };
+
+        let format = ConfigFormat::from_path(&config_path);
+        let mut value = match Self::parse_document(format, &content) {
+            Ok(value) => value,
+            Err(e) => {
+                results.push(DiagnosticResult::fail(
+                    name,
+                    format!("{} is not valid {}: {}", config_path.display(), format, e),
+                ));
+                return None;
+            }
+        };
+
+        value = match FileOps::resolve_extends(&config_path, value) {
+            Ok(value) => value,
+            Err(e) => {
+                results.push(DiagnosticResult::fail(name, e.to_string()));
+                return None;
+            }
+        };
+
+        if let Err(e) = migration::migrate_to_current(&mut value) {
+            results.push(DiagnosticResult::fail(name, e.to_string()));
+            return None;
+        }
+
+        let invalid_versions = Self::take_invalid_versions(&mut value);
+        let backslash_paths = ProjectConfig::backslash_paths(&value);
+
+        // Parse without validating so the remaining checks can report individual problems
+        match serde_json::from_value::<ProjectConfig>(value) {
+            Ok(config) => {
+                results.push(DiagnosticResult::pass(
+                    name,
+                    format!("{} exists and is valid {}", config_path.display(), format),
+                ));
+                if let Some(example) = backslash_paths.first() {
+                    results.push(DiagnosticResult::fail(
+                        "Path separators",
+                        format!(
+                            "{} tracked path(s) such as '{}' use backslashes; run 'specforge fmt --fix' to store them with forward slashes",
+                            backslash_paths.len(),
+                            example
+                        ),
+                    ));
+                }
+                Some((config, invalid_versions))
+            }
+            Err(e) => {
+                results.push(DiagnosticResult::fail(
+                    name,
+                    format!("{} does not match the expected structure: {}", config_path.display(), e),
+                ));
+                None
+            }
+        }
+    }
+
+    /// Remove packages whose version does not parse, returning a failed check for each
+    fn take_invalid_versions(value: &mut serde_json::Value) -> Vec<DiagnosticResult> {
+        let invalid = ProjectConfig::invalid_package_versions(value);
+        let Some(packages) = value.get_mut("packages").and_then(|p| p.as_array_mut()) else {
+            return Vec::new();
+        };
+
+        let results = invalid
+            .iter()
+            .map(|(index, _)| {
+                let package = &packages[*index];
+                DiagnosticResult::fail(
+                    format!("Package {} version", package["id"].as_str().unwrap_or("?")),
+                    format!(
+                        "'{}' is not a semantic version (expected e.g. '1.0.0')",
+                        package["version"].as_str().unwrap_or_default()
+                    ),
+                )
+            })
+            .collect();
+        for (index, _) in invalid.iter().rev() {
+            packages.remove(*index);
+        }
+        results
+    }
+
+    /// Parse a configuration document without interpreting its structure
+    fn parse_document(format: ConfigFormat, content: &str) -> std::result::Result<serde_json::Value, String> {
+        match format {
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            #[cfg(not(feature = "toml"))]
+            ConfigFormat::Toml => Err("TOML support is not enabled in this build".to_string()),
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            #[cfg(not(feature = "yaml"))]
+            ConfigFormat::Yaml => Err("YAML support is not enabled in this build".to_string()),
+        }
+    }
+
+    /// Check whether the tooling for an agent appears to be set up
+    fn check_agent_detected(directory: &Path, agent: &Agent) -> DiagnosticResult {
+        let name = format!("Agent {}", agent);
+        let extensions_path = directory.join(".vscode").join("extensions.json");
+
+        match agent {
+            Agent::Copilot => {
+                if Self::recommends_extension(&extensions_path, "github.copilot") {
+                    DiagnosticResult::pass(name, ".vscode/extensions.json recommends github.copilot")
+                } else {
+                    DiagnosticResult::fail(
+                        name,
+                        "GitHub Copilot not detected. Add \"github.copilot\" to the recommendations in .vscode/extensions.json",
+                    )
+                }
+            }
+            Agent::Claude => {
+                if directory.join(".claude").is_dir() {
+                    DiagnosticResult::pass(name, ".claude directory found")
+                } else if find_on_path("claude").is_some() {
+                    DiagnosticResult::pass(name, "claude executable found on PATH")
+                } else {
+                    DiagnosticResult::fail(
+                        name,
+                        "Claude Code not detected. Install Claude Code or create a .claude directory in the project",
+                    )
+                }
+            }
+            Agent::Codeium => {
+                if Self::recommends_extension(&extensions_path, "codeium.codeium") {
+                    DiagnosticResult::pass(name, ".vscode/extensions.json recommends codeium.codeium")
+                } else {
+                    DiagnosticResult::fail(
+                        name,
+                        "Codeium not detected. Add \"codeium.codeium\" to the recommendations in .vscode/extensions.json",
+                    )
+                }
+            }
+            Agent::Continue => {
+                if directory.join(".continue").is_dir() {
+                    DiagnosticResult::pass(name, ".continue directory found")
+                } else if Self::recommends_extension(&extensions_path, "continue.continue") {
+                    DiagnosticResult::pass(name, ".vscode/extensions.json recommends continue.continue")
+                } else {
+                    DiagnosticResult::fail(
+                        name,
+                        "Continue not detected. Run specforge init to create .continue/config.json or add \"continue.continue\" to .vscode/extensions.json",
+                    )
+                }
+            }
+            Agent::AmazonQ => {
+                if Self::recommends_extension(&extensions_path, "amazonwebservices.amazon-q-vscode") {
+                    DiagnosticResult::pass(name, ".vscode/extensions.json recommends amazonwebservices.amazon-q-vscode")
+                } else {
+                    DiagnosticResult::fail(
+                        name,
+                        "Amazon Q Developer not detected. Add \"amazonwebservices.amazon-q-vscode\" to the recommendations in .vscode/extensions.json",
+                    )
+                }
+            }
+        }
+    }
+
+    /// Report user-level agent files that take precedence over, or add to,
+    /// the files deployed in the project
+    ///
+    /// Only the existence and size of the files are looked at, never their
+    /// content.
+    fn find_user_level_files(agents: &[Agent], home: Option<&Path>, os: &str) -> Vec<DiagnosticResult> {
+        let Some(home) = home else {
+            return vec![DiagnosticResult::info(
+                "User-level files",
+                "Home directory not found, so user-level agent files were not looked for",
+            )];
+        };
+
+        agents
+            .iter()
+            .flat_map(|agent| agent.user_level_files(home, os).into_iter().map(move |path| (agent, path)))
+            .filter_map(|(agent, path)| {
+                let metadata = fs::metadata(&path).ok()?;
+                let found = if metadata.is_dir() {
+                    format!("{} exists", path.display())
+                } else {
+                    format!("{} exists ({})", path.display(), format::human_size(metadata.len()))
+                };
+                Some(
+                    DiagnosticResult::info(
+                        format!("User-level {} file", agent),
+                        format!(
+                            "{}; it is read on top of the project files and may override them",
+                            found
+                        ),
+                    )
+                    .with_path(path),
+                )
+            })
+            .collect()
+    }
+
+    /// Check whether a VS Code extensions file mentions the given extension ID
+    fn recommends_extension(extensions_path: &Path, extension_id: &str) -> bool {
+        // extensions.json may contain comments, so search the raw text instead of parsing
+        fs::read_to_string(extensions_path)
+            .map(|content| content.to_lowercase().contains(&format!("\"{}\"", extension_id)))
+            .unwrap_or(false)
+    }
+
+    /// Check that a deployed template file still exists and has content
+    /// Report instructions files over an agent's soft size limit as findings
+    /// and over its hard limit as failures
+    fn check_instructions_size(directory: &Path, agents: &[Agent]) -> Vec<DiagnosticResult> {
+        TemplateSystem::check_deployed_instructions(directory, agents)
+            .into_iter()
+            .map(|issue| {
+                let path = directory.join(&issue.file);
+                let result = if issue.is_error() {
+                    DiagnosticResult::fail("Instructions size", issue.to_string())
+                } else {
+                    DiagnosticResult::info("Instructions size", issue.to_string())
+                };
+                result.with_path(path)
+            })
+            .collect()
+    }
+
+    fn check_deployed_file(directory: &Path, relative_path: &str) -> DiagnosticResult {
+        let name = format!("Template {}", relative_path);
+        let path = directory.join(relative_path);
+
+        match fs::read_to_string(&path) {
+            Ok(content) if content.trim().is_empty() => DiagnosticResult::fail(
+                name,
+                "File is empty. Run 'specforge upgrade' or restore it from version control",
+            ),
+            Ok(_) => DiagnosticResult::pass(name, "File exists and has content"),
+            Err(_) => DiagnosticResult::fail(
+                name,
+                "File is missing. Run 'specforge upgrade' or restore it from version control",
+            ),
+        }
+    }
+}
+
+/// Home directory of the current user, if the environment names one
+fn home_directory() -> Option<PathBuf> {
+    let name = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    std::env::var_os(name).filter(|home| !home.is_empty()).map(PathBuf::from)
+}
+
+/// Look up an executable on the PATH
+fn find_on_path(executable: &str) -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var).find_map(|dir| {
+        let candidate = dir.join(executable);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let candidate = dir.join(format!("{}.exe", executable));
+        candidate.is_file().then_some(candidate)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Package, TrackedFile};
+    use crate::file_ops::ConfigLayout;
+    use tempfile::TempDir;
+
+    fn doctor(dir: &Path) -> DoctorCommand {
+        DoctorCommand {
+            directory: dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_missing_config() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let results = doctor(temp_dir.path()).run_checks();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].passed);
+        assert!(results[0].message.contains("specforge init"));
+    }
+
+    #[test]
+    fn test_invalid_json_config() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(FileOps::get_config_path(temp_dir.path(), ConfigLayout::File), "{ not json").unwrap();
+
+        let results = doctor(temp_dir.path()).run_checks();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].message.contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_healthy_project() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".vscode")).unwrap();
+        fs::write(
+            temp_dir.path().join(".vscode").join("extensions.json"),
+            r#"{ "recommendations": ["GitHub.copilot"] }"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "instructions").unwrap();
+
+        let mut config = ProjectConfig::new(Agent::Copilot);
+        config.add_package(Package::new("specforge-copilot-templates", "1.0.0").unwrap()).unwrap();
+        config.track_file(TrackedFile::new("CLAUDE.md", &FileOps::hash_content("instructions")));
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        let results = doctor(temp_dir.path()).run_checks_with_home(Some(temp_dir.path()));
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.passed), "{:?}", results);
+        assert!(doctor(temp_dir.path()).execute().is_ok());
+    }
+
+    #[test]
+    fn test_reports_individual_problems() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "  \n").unwrap();
+
+        // Write the file directly so the invalid version bypasses validation
+        let mut config = ProjectConfig::new(Agent::Codeium);
+        config.packages.push(Package::new("specforge-codeium-templates", "1.0.0").unwrap());
+        config.track_file(TrackedFile::new("CLAUDE.md", &FileOps::hash_content("x")));
+        config.track_file(TrackedFile::new("codeium.json", &FileOps::hash_content("x")));
+        let mut value = serde_json::to_value(&config).unwrap();
+        value["packages"][0]["version"] = "1.0".into();
+        fs::write(FileOps::get_config_path(temp_dir.path(), ConfigLayout::File), value.to_string()).unwrap();
+
+        let results = doctor(temp_dir.path()).run_checks();
+        let failed: Vec<&str> = results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(
+            failed,
+            vec![
+                "Agent codeium",
+                "Template CLAUDE.md",
+                "Template codeium.json",
+                "Package specforge-codeium-templates version",
+            ]
+        );
+        assert!(results.last().unwrap().message.contains("'1.0' is not a semantic version"));
+
+        let error = doctor(temp_dir.path()).execute().unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_reports_oversized_instructions() {
+        let temp_dir = TempDir::new().unwrap();
+        let limits = Agent::Codeium.instructions_size_limits();
+        FileOps::write_config_to_directory(&ProjectConfig::new(Agent::Codeium), temp_dir.path()).unwrap();
+
+        fs::write(temp_dir.path().join("CLAUDE.md"), "x".repeat(limits.soft + 1)).unwrap();
+        let results = doctor(temp_dir.path()).run_checks_with_home(None);
+        let size = results.iter().find(|r| r.name == "Instructions size").unwrap();
+        assert!(size.informational && size.passed, "{:?}", size);
+
+        fs::write(temp_dir.path().join("CLAUDE.md"), "x".repeat(limits.hard + 1)).unwrap();
+        let results = doctor(temp_dir.path()).run_checks_with_home(None);
+        let size = results.iter().find(|r| r.name == "Instructions size").unwrap();
+        assert!(!size.passed, "{:?}", size);
+        assert_eq!(size.path.as_deref(), Some(temp_dir.path().join("CLAUDE.md").as_path()));
+    }
+
+    #[test]
+    fn test_config_inherits_from_extended_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut parent = ProjectConfig::new(Agent::Codeium);
+        parent.add_package(Package::new("specforge-codeium-templates", "1.0.0").unwrap()).unwrap();
+        FileOps::write_config_to_directory(&parent, temp_dir.path()).unwrap();
+        let project = temp_dir.path().join("web");
+        fs::create_dir(&project).unwrap();
+        let config_path = FileOps::get_config_path(&project, ConfigLayout::File);
+        fs::write(config_path, r#"{ "extends": "../.specforge.json" }"#).unwrap();
+
+        let results = doctor(&project).run_checks();
+        assert!(results[0].passed, "{:?}", results[0]);
+        assert!(results.iter().any(|r| r.name == "Package specforge-codeium-templates version"));
+    }
+
+    #[test]
+    fn test_flags_backslash_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("templates")).unwrap();
+        fs::write(temp_dir.path().join("templates").join("CLAUDE.md"), "claude").unwrap();
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.track_file(TrackedFile::new("templates/CLAUDE.md", &FileOps::hash_content("claude")));
+        let json = config.to_json_string().unwrap().replace("templates/CLAUDE.md", "templates\\\\CLAUDE.md");
+        fs::write(temp_dir.path().join(".specforge.json"), json).unwrap();
+
+        let results = doctor(temp_dir.path()).run_checks();
+        let check = results.iter().find(|r| r.name == "Path separators").unwrap();
+        assert!(!check.passed);
+        assert!(check.message.contains("specforge fmt --fix"));
+        // The file itself is still found through the normalized path
+        assert!(results.iter().any(|r| r.name.contains("templates/CLAUDE.md") && r.passed));
+    }
+
+    #[test]
+    fn test_reports_user_level_files() {
+        let home = TempDir::new().unwrap();
+        fs::create_dir(home.path().join(".claude")).unwrap();
+        fs::write(home.path().join(".claude").join("CLAUDE.md"), "Always answer in French").unwrap();
+
+        let results = DoctorCommand::find_user_level_files(&[Agent::Claude, Agent::Codeium], Some(home.path()), "linux");
+        assert_eq!(results.len(), 1);
+        let finding = &results[0];
+        assert_eq!(finding.name, "User-level claude file");
+        assert!(finding.passed && finding.informational);
+        assert_eq!(finding.path.as_deref(), Some(home.path().join(".claude").join("CLAUDE.md").as_path()));
+        assert!(finding.message.contains("(23 B)"), "{}", finding.message);
+
+        let json = DoctorCommand::json_summary(&results);
+        assert_eq!(json["status"], "success");
+        assert_eq!(json["checks"][0]["informational"], true);
+        assert_eq!(json["checks"][0]["path"], home.path().join(".claude").join("CLAUDE.md").display().to_string());
+    }
+
+    #[test]
+    fn test_user_level_files_without_home() {
+        let results = DoctorCommand::find_user_level_files(&[Agent::Claude], None, "linux");
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed && results[0].informational);
+        assert!(results[0].message.contains("Home directory not found"));
+    }
+
+    #[test]
+    fn test_claude_detected_by_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".claude")).unwrap();
+
+        let result = DoctorCommand::check_agent_detected(temp_dir.path(), &Agent::Claude);
+        assert!(result.passed);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::config::{ConditionContext, ProjectConfig, condition::PROFILE_ENV_VAR};
+use crate::error::Result;
+use crate::file_ops::{CONFIG_ENV_VAR, FileOps};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Show which packages apply in the current environment
+#[derive(Args)]
+pub struct EnvCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Print export statements for the given shell instead of a report; JSON
+    /// output lists the variables under `variables` instead
+    #[arg(long, value_enum)]
+    pub shell: Option<ShellSyntax>,
+
+    /// Apply the configuration overlay for this environment, e.g. `ci` for
+    /// .specforge.ci.json (defaults to $SPECFORGE_ENV)
+    #[arg(long, value_name = "NAME")]
+    pub env: Option<String>,
+}
+
+/// Shell dialect used for export statements
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShellSyntax {
+    /// POSIX sh, bash and zsh
+    Sh,
+    /// fish
+    Fish,
+    /// PowerShell
+    Powershell,
+}
+
+impl ShellSyntax {
+    /// Statement that sets an environment variable to a literal value
+    pub fn export(&self, name: &str, value: &str) -> String {
+        match self {
+            // Single quotes disable all expansion; a quote ends the string,
+            // so it is closed, escaped, and reopened
+            ShellSyntax::Sh => format!("export {}='{}'", name, value.replace('\'', "'\\''")),
+            // Inside single quotes fish only interprets \\ and \'
+            ShellSyntax::Fish => format!(
+                "set -gx {} '{}'",
+                name,
+                value.replace('\\', "\\\\").replace('\'', "\\'")
+            ),
+            // PowerShell also treats typographic single quotes as delimiters;
+            // any of them is escaped by doubling it
+            ShellSyntax::Powershell => {
+                let mut quoted = String::with_capacity(value.len());
+                for c in value.chars() {
+                    if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                format!("$env:{} = '{}'", name, quoted)
+            }
+        }
+    }
+}
+
+/// Project facts and environment reported by the env command
+#[derive(Debug, Clone)]
+pub struct ResolvedEnvironment {
+    /// Absolute path of the configuration file
+    pub config_path: PathBuf,
+    /// Project configuration, with the overlay for `env` applied
+    pub config: ProjectConfig,
+    /// Environment whose configuration overlay was applied, if any
+    pub env: Option<String>,
+    /// Environment that package conditions are evaluated against
+    pub context: ConditionContext,
+}
+
+impl ResolvedEnvironment {
+    /// Read the project configuration in a directory, applying the overlay
+    /// for `env`, and capture the current environment
+    pub fn resolve(directory: &std::path::Path, env: Option<&str>) -> Result<Self> {
+        let config_path = FileOps::locate_config(directory);
+        let config = FileOps::read_config_with_env(&config_path, env).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+        let config_path = fs::canonicalize(&config_path).unwrap_or(config_path);
+
+        Ok(Self {
+            config_path,
+            config,
+            env: env.map(str::to_string),
+            context: ConditionContext::from_environment(),
+        })
+    }
+
+    /// Variables exported by `env --shell`, in output order
+    pub fn shell_variables(&self) -> Vec<(&'static str, String)> {
+        let agents: Vec<_> = self.config.agents.iter().map(|a| a.to_string()).collect();
+        vec![
+            (
+                "SPECFORGE_AGENT",
+                self.config.primary_agent().map(|a| a.to_string()).unwrap_or_default(),
+            ),
+            ("SPECFORGE_AGENTS", agents.join(" ")),
+            (
+                "SPECFORGE_PROJECT_NAME",
+                self.config.project_name().unwrap_or_default().to_string(),
+            ),
+            (
+                "SPECFORGE_CONFIG_PATH",
+                self.config_path.to_string_lossy().into_owned(),
+            ),
+        ]
+    }
+}
+
+impl EnvCommand {
+    /// Execute the env command
+    pub fn execute(&self) -> Result<()> {
+        let env = FileOps::selected_env(self.env.as_deref());
+        if let Some(shell) = self.shell.filter(|_| !output::is_json()) {
+            // The output is meant to be eval-ed, so warnings must not end up on stdout
+            output::reserve_stdout();
+            let environment = ResolvedEnvironment::resolve(&self.directory, env.as_deref())?;
+            for (name, value) in environment.shell_variables() {
+                println!("{}", shell.export(name, &value));
+            }
+            return Ok(());
+        }
+
+        let environment = ResolvedEnvironment::resolve(&self.directory, env.as_deref())?;
+        let (config, context) = (&environment.config, &environment.context);
+
+        if output::is_json() {
+            let mut summary = Self::json_summary(config, context);
+            summary["env"] = serde_json::json!(environment.env);
+            summary["config_path"] = serde_json::json!(environment.config_path);
+            summary["variables"] = environment.shell_variables().into_iter().collect();
+            output::print_json(summary);
+            return Ok(());
+        }
+
+        status!("{} Environment:", Status::Info);
+        status!("   • Configuration: {}", environment.config_path.display());
+        status!("   • OS: {} ({})", context.os, context.os_family);
+        match &context.profile {
+            Some(profile) => status!("   • Profile: {}", profile),
+            None => status!("   • Profile: none (set {} to select one)", PROFILE_ENV_VAR),
+        }
+        match &environment.env {
+            Some(env) => status!("   • Configuration overlay: {}", env),
+            None => status!("   • Configuration overlay: none (use --env or set {})", CONFIG_ENV_VAR),
+        }
+        status!();
+
+        status!("📦 Packages:");
+        for package in &config.packages {
+            let icon = if package.is_active(context) { Status::Ok } else { Status::Error };
+            match &package.when {
+                Some(condition) => status!(
+                    "   {} {} {} - when {} → {}",
+                    icon,
+                    package.id,
+                    package.version,
+                    condition,
+                    condition.evaluate(context)
+                ),
+                None => status!("   {} {} {} - always applies", icon, package.id, package.version),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(config: &ProjectConfig, context: &ConditionContext) -> serde_json::Value {
+        let packages: Vec<_> = config
+            .packages
+            .iter()
+            .map(|package| {
+                serde_json::json!({
+                    "id": package.id,
+                    "version": package.version,
+                    "when": package.when,
+                    "active": package.is_active(context),
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "os": context.os,
+            "os_family": context.os_family,
+            "profile": context.profile,
+            "packages": packages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, Package};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_json_summary() {
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.add_package(Package::new("always", "1.0.0").unwrap()).unwrap();
+        config
+            .add_package(
+                Package::new("prod-only", "2.0.0").unwrap().with_condition("profile(\"prod\")".parse().unwrap()),
+            )
+            .unwrap();
+        let context = ConditionContext {
+            os: "linux".to_string(),
+            os_family: "unix".to_string(),
+            ..ConditionContext::default()
+        };
+
+        let summary = EnvCommand::json_summary(&config, &context);
+        assert_eq!(summary["profile"], serde_json::Value::Null);
+        assert_eq!(summary["packages"][0]["active"], true);
+        assert_eq!(summary["packages"][0]["when"], serde_json::Value::Null);
+        assert_eq!(summary["packages"][1]["active"], false);
+        assert_eq!(summary["packages"][1]["when"], "profile(\"prod\")");
+    }
+
+    #[test]
+    fn test_env_requires_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = EnvCommand {
+            directory: temp_dir.path().to_path_buf(),
+            shell: None,
+            env: None,
+        };
+        assert!(cmd.execute().is_err());
+    }
+
+    #[test]
+    fn test_resolve_applies_overlay() {
+        let temp_dir = TempDir::new().unwrap();
+        FileOps::write_config_to_directory(&ProjectConfig::new(Agent::Claude), temp_dir.path()).unwrap();
+        fs::write(temp_dir.path().join(".specforge.ci.json"), r#"{"agent": "copilot"}"#).unwrap();
+
+        let environment = ResolvedEnvironment::resolve(temp_dir.path(), Some("ci")).unwrap();
+        assert_eq!(environment.config.agents, vec![Agent::Copilot]);
+        assert_eq!(environment.env.as_deref(), Some("ci"));
+        assert!(ResolvedEnvironment::resolve(temp_dir.path(), Some("dev")).is_err());
+    }
+
+    #[test]
+    fn test_shell_export_quoting() {
+        let cases = [
+            ("plain", "export X='plain'", "set -gx X 'plain'", "$env:X = 'plain'"),
+            ("two words", "export X='two words'", "set -gx X 'two words'", "$env:X = 'two words'"),
+            ("it's", "export X='it'\\''s'", "set -gx X 'it\\'s'", "$env:X = 'it''s'"),
+            (
+                "say \"hi\"",
+                "export X='say \"hi\"'",
+                "set -gx X 'say \"hi\"'",
+                "$env:X = 'say \"hi\"'",
+            ),
+            ("$HOME `ls`", "export X='$HOME `ls`'", "set -gx X '$HOME `ls`'", "$env:X = '$HOME `ls`'"),
+            ("C:\\dir\\", "export X='C:\\dir\\'", "set -gx X 'C:\\\\dir\\\\'", "$env:X = 'C:\\dir\\'"),
+            ("Café ☕", "export X='Café ☕'", "set -gx X 'Café ☕'", "$env:X = 'Café ☕'"),
+            (
+                "don\u{2019}t",
+                "export X='don\u{2019}t'",
+                "set -gx X 'don\u{2019}t'",
+                "$env:X = 'don\u{2019}\u{2019}t'",
+            ),
+            ("a\nb", "export X='a\nb'", "set -gx X 'a\nb'", "$env:X = 'a\nb'"),
+            ("", "export X=''", "set -gx X ''", "$env:X = ''"),
+        ];
+
+        for (value, sh, fish, powershell) in cases {
+            assert_eq!(ShellSyntax::Sh.export("X", value), sh, "sh: {:?}", value);
+            assert_eq!(ShellSyntax::Fish.export("X", value), fish, "fish: {:?}", value);
+            assert_eq!(
+                ShellSyntax::Powershell.export("X", value),
+                powershell,
+                "powershell: {:?}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_shell_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = ProjectConfig::with_agents(vec![Agent::Copilot, Agent::Claude]);
+        config.project_name = Some("Café \"demo\"".to_string());
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        let environment = ResolvedEnvironment::resolve(temp_dir.path(), None).unwrap();
+        let variables = environment.shell_variables();
+        assert_eq!(variables[0], ("SPECFORGE_AGENT", "copilot".to_string()));
+        assert_eq!(variables[1], ("SPECFORGE_AGENTS", "copilot claude".to_string()));
+        assert_eq!(variables[2], ("SPECFORGE_PROJECT_NAME", "Café \"demo\"".to_string()));
+        assert_eq!(variables[3].0, "SPECFORGE_CONFIG_PATH");
+        assert!(PathBuf::from(&variables[3].1).is_absolute());
+    }
+}
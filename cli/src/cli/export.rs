@@ -0,0 +1,149 @@
+use crate::cli::init::relative_path;
+use crate::config::{Agent, format_agents};
+use crate::error::Result;
+use crate::file_ops::{ConflictPolicy, ConflictResolver, FileOps, WriteOutcome};
+use crate::output;
+use crate::status;
+use crate::templates;
+use crate::ui::Status;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Path of the generated GitHub Actions workflow, relative to the output directory
+pub const GITHUB_ACTIONS_WORKFLOW_PATH: &str = ".github/workflows/specforge-validate.yml";
+
+/// First line of the generated GitHub Actions workflow, telling it apart from
+/// a workflow written by hand
+pub const GITHUB_ACTIONS_WORKFLOW_HEADER: &str = "# Generated by `specforge export --format github-actions`";
+
+/// Write configuration for other tools, such as a CI workflow that validates the project
+#[derive(Args)]
+pub struct ExportCommand {
+    /// What to generate
+    #[arg(long, value_enum)]
+    pub format: ExportFormat,
+
+    /// Project directory containing the .specforge.json file; the file is written below it
+    #[arg(short, long, default_value = ".")]
+    pub output_directory: PathBuf,
+
+    /// What to do with an existing file at the export path
+    #[arg(long, value_enum, default_value_t = ConflictPolicy::Prompt)]
+    pub on_conflict: ConflictPolicy,
+}
+
+/// Formats the export command can generate
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// GitHub Actions workflow running `specforge validate` on pushes and pull requests
+    GithubActions,
+}
+
+/// Quote a string as a YAML single-quoted scalar
+fn yaml_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// GitHub Actions workflow that validates the configuration of a project using `agents`
+pub fn github_actions_workflow(agents: &[Agent]) -> String {
+    let job_name = format!("Validate Specforge configuration ({})", format_agents(agents));
+    let lines = [
+        GITHUB_ACTIONS_WORKFLOW_HEADER.to_string(),
+        "name: Specforge".to_string(),
+        String::new(),
+        "on:".to_string(),
+        "  push:".to_string(),
+        "  pull_request:".to_string(),
+        String::new(),
+        "jobs:".to_string(),
+        "  validate:".to_string(),
+        format!("    name: {}", yaml_quote(&job_name)),
+        "    runs-on: ubuntu-latest".to_string(),
+        "    steps:".to_string(),
+        "      - name: Check out the repository".to_string(),
+        "        uses: actions/checkout@v4".to_string(),
+        "      - name: Install specforge".to_string(),
+        "        run: cargo install --locked --git https://github.com/wmeints/specforge specforge".to_string(),
+        "      - name: Validate the configuration".to_string(),
+        "        run: specforge validate".to_string(),
+    ];
+    let mut workflow = lines.join("\n");
+    workflow.push('\n');
+    workflow
+}
+
+impl ExportCommand {
+    /// Execute the export command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.output_directory);
+        let config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let (content, relative) = match self.format {
+            ExportFormat::GithubActions => (github_actions_workflow(&config.agents), GITHUB_ACTIONS_WORKFLOW_PATH),
+        };
+
+        let target = self.output_directory.join(relative);
+        if let Some(parent) = target.parent() {
+            FileOps::ensure_directory_exists(parent)?;
+        }
+        let mut resolver = ConflictResolver::new(self.on_conflict);
+        let written = templates::deploy_rendered_file(&content, &self.output_directory, relative, &mut resolver)
+            .map_err(|e| e.add_context("export writing", format!("Writing {}", target.display())))?;
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "path": relative_path(&self.output_directory, &written.path),
+                "written": written.outcome != WriteOutcome::Skipped,
+            }));
+            return Ok(());
+        }
+
+        match written.outcome {
+            WriteOutcome::Created | WriteOutcome::Overwritten => {
+                status!("{} Wrote {}", Status::Ok, written.path.display());
+                status!("   Commit it to validate the Specforge configuration on every push and pull request");
+            }
+            WriteOutcome::Unchanged | WriteOutcome::ModeRestored => status!("{} {} is already up to date", Status::Info, written.path.display()),
+            WriteOutcome::Skipped => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProjectConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_github_actions_workflow() {
+        let workflow = github_actions_workflow(&[Agent::Claude, Agent::Copilot]);
+        assert!(workflow.contains("    name: 'Validate Specforge configuration (claude, copilot)'\n"));
+        assert!(workflow.contains("  pull_request:\n"));
+        assert!(workflow.contains("uses: actions/checkout@v4"));
+        assert!(workflow.ends_with("        run: specforge validate\n"));
+        assert_eq!(yaml_quote("it's"), "'it''s'");
+    }
+
+    #[test]
+    fn test_export_writes_workflow() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = ExportCommand {
+            format: ExportFormat::GithubActions,
+            output_directory: temp_dir.path().to_path_buf(),
+            on_conflict: ConflictPolicy::Skip,
+        };
+        assert!(cmd.execute().is_err());
+
+        FileOps::write_config_to_directory(&ProjectConfig::new(Agent::Codeium), temp_dir.path()).unwrap();
+        cmd.execute().unwrap();
+        let workflow = std::fs::read_to_string(temp_dir.path().join(GITHUB_ACTIONS_WORKFLOW_PATH)).unwrap();
+        assert!(workflow.contains("(codeium)"));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::config::{ProjectConfig, normalize_stored_path};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Check that stored paths in the configuration use forward slashes
+#[derive(Args)]
+pub struct FmtCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Rewrite the configuration with normalized paths instead of only reporting them
+    #[arg(long)]
+    pub fix: bool,
+}
+
+impl FmtCommand {
+    /// Execute the fmt command
+    ///
+    /// Fails without --fix when a stored path uses backslashes, so it can
+    /// guard configurations written on Windows in CI.
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let document = FileOps::read_config_document(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let paths = ProjectConfig::backslash_paths(&document);
+        if output::is_json() {
+            let paths: Vec<_> = paths
+                .iter()
+                .map(|path| serde_json::json!({ "path": path, "normalized": normalize_stored_path(path) }))
+                .collect();
+            output::print_json(serde_json::json!({ "paths": paths, "fixed": self.fix }));
+        }
+        if paths.is_empty() {
+            status!("{} Stored paths in {} use forward slashes", Status::Ok, config_path.display());
+            return Ok(());
+        }
+
+        status!("📄 Stored paths with backslashes:");
+        for path in &paths {
+            status!("   • {} → {}", path, normalize_stored_path(path));
+        }
+        if !self.fix {
+            return Err(ConfigError::validation_error(format!(
+                "{} stored path(s) use backslashes; run 'specforge fmt --fix' to normalize them",
+                paths.len()
+            )));
+        }
+
+        // Reading normalizes the paths and writing keeps them that way
+        let config = FileOps::read_config(&config_path)?;
+        FileOps::write_config(&config, &config_path).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Normalizing paths in {}", config_path.display()),
+            )
+        })?;
+        status!("{} Normalized {} path(s) in {}", Status::Ok, paths.len(), config_path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, TrackedFile};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fmt_fix_normalizes_separators() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.track_file(TrackedFile::new("templates/claude/CLAUDE.md", &FileOps::hash_content("claude")));
+        let config_path = FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+        let windows = fs::read_to_string(&config_path)
+            .unwrap()
+            .replace("templates/claude/CLAUDE.md", r"templates\\claude\\CLAUDE.md");
+        fs::write(&config_path, windows).unwrap();
+
+        let check = FmtCommand { directory: temp_dir.path().to_path_buf(), fix: false };
+        assert!(check.execute().is_err());
+
+        let fix = FmtCommand { directory: temp_dir.path().to_path_buf(), fix: true };
+        fix.execute().unwrap();
+        assert!(fs::read_to_string(&config_path).unwrap().contains("\"templates/claude/CLAUDE.md\""));
+        assert!(check.execute().is_ok());
+    }
+}
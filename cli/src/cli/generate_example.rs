@@ -0,0 +1,151 @@
+use crate::config::{Agent, Condition, Package, PackageVersion, ProjectConfig, ProjectConfigBuilder, TrackedFile};
+use crate::error::Result;
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::templates::{TemplateSystem, VARIABLES_METADATA_KEY};
+use clap::Args;
+
+/// Creation time recorded in the examples, so the output does not change between runs
+const EXAMPLE_CREATED_AT: &str = "2025-01-01T00:00:00+00:00";
+
+/// Print an example .specforge.json built and validated by this release
+#[derive(Args)]
+pub struct GenerateExampleCommand {
+    /// Which features the example uses
+    #[arg(long, value_enum, default_value_t = ExampleFeatures::All)]
+    pub features: ExampleFeatures,
+}
+
+/// Feature sets the example configurations show
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExampleFeatures {
+    /// Every configuration feature at once
+    All,
+    /// A single agent with its default package, as `init` writes it
+    Minimal,
+    /// Several agents in order of precedence, with the files they deployed
+    MultiAgent,
+    /// Packages downloaded from a URL, pinned by checksum, with dependencies
+    RemotePackages,
+}
+
+impl ExampleFeatures {
+    /// Every feature set, for checking that each example stays valid
+    pub fn all() -> [ExampleFeatures; 4] {
+        [Self::All, Self::Minimal, Self::MultiAgent, Self::RemotePackages]
+    }
+}
+
+impl GenerateExampleCommand {
+    /// Execute the generate-example command
+    pub fn execute(&self) -> Result<()> {
+        let example = example(self.features)?;
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "example": example }));
+        } else {
+            println!("{}", example.to_json_string()?);
+        }
+        Ok(())
+    }
+}
+
+/// Build the example configuration for a feature set
+///
+/// The builder validates the result, so an example that no longer fits the
+/// configuration model fails here rather than ending up in documentation.
+pub fn example(features: ExampleFeatures) -> Result<ProjectConfig> {
+    match features {
+        ExampleFeatures::Minimal => with_default_packages(&[Agent::Claude]).build(),
+        ExampleFeatures::MultiAgent => {
+            let agents = [Agent::Copilot, Agent::Claude];
+            with_tracked_files(with_default_packages(&agents), &agents).build()
+        }
+        ExampleFeatures::RemotePackages => with_remote_packages(with_default_packages(&[Agent::Claude])).build(),
+        ExampleFeatures::All => {
+            let agents = [Agent::Claude, Agent::Codeium];
+            let builder = with_remote_packages(with_default_packages(&agents))
+                .add_package(
+                    Package::new("team-prod-guidelines", "1.0.0")
+                        .map(|p| p.with_condition(Condition::Profile("prod".to_string()))),
+                )
+                .add_package(Package::new("windows-setup", "0.3.0").map(|p| {
+                    p.with_condition(Condition::All(vec![
+                        Condition::Os("windows".to_string()),
+                        Condition::Not(Box::new(Condition::EnvSet("CI".to_string()))),
+                    ]))
+                }))
+                .metadata(VARIABLES_METADATA_KEY, serde_json::json!({ "team": "platform", "language": "Rust" }))
+                .min_specforge_version(PackageVersion::current().to_string());
+            with_tracked_files(builder, &agents).build()
+        }
+    }
+}
+
+/// Configuration for the agents with the packages and metadata `init` adds
+fn with_default_packages(agents: &[Agent]) -> ProjectConfigBuilder {
+    let mut builder = ProjectConfig::builder(agents[0].clone());
+    for agent in agents {
+        builder = builder
+            .agent(agent.clone())
+            .add_package(Package::with_version(agent.default_package_id(), PackageVersion::current()));
+    }
+    builder
+        .project_name("example-project")
+        .metadata("created_at", EXAMPLE_CREATED_AT)
+        .metadata("initialized_by", "specforge-cli")
+        .metadata("version", env!("CARGO_PKG_VERSION"))
+}
+
+/// Add a package fetched from a URL, pinned by checksum, and one that builds on it
+fn with_remote_packages(builder: ProjectConfigBuilder) -> ProjectConfigBuilder {
+    let archive_checksum = format!("sha256:{}", FileOps::hash_content("example archive"));
+    builder
+        .add_package(
+            Package::with_url("rust-guidelines", "https://example.com/packages/rust-guidelines-1.2.0.tar.gz", "1.2.0")
+                .map(|p| p.with_checksum(archive_checksum)),
+        )
+        .add_package(
+            Package::new("rust-api-guidelines", "0.4.1").map(|p| p.with_dependencies(["rust-guidelines"])),
+        )
+}
+
+/// Record the template files the agents deploy, hashed as deployed
+fn with_tracked_files(mut builder: ProjectConfigBuilder, agents: &[Agent]) -> ProjectConfigBuilder {
+    let (templates, _) = TemplateSystem::resolve_templates(agents);
+    for template in templates {
+        builder = builder.track_file(TrackedFile::new(
+            template.name.to_string(),
+            FileOps::hash_content(template.content.as_bytes()),
+        ));
+    }
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_round_trip_and_validate() {
+        for features in ExampleFeatures::all() {
+            let config = example(features).unwrap();
+            let parsed = ProjectConfig::from_json_string(&config.to_json_string().unwrap())
+                .unwrap_or_else(|e| panic!("{:?}: {}", features, e));
+            parsed.validate().unwrap();
+            assert_eq!(parsed, config, "{:?}", features);
+        }
+    }
+
+    #[test]
+    fn test_all_example_uses_every_feature() {
+        let config = example(ExampleFeatures::All).unwrap();
+
+        assert!(config.agents.len() > 1);
+        assert!(config.min_specforge_version.is_some());
+        assert!(!config.files.is_empty());
+        assert!(config.get_metadata(VARIABLES_METADATA_KEY).is_some());
+        assert!(config.packages.iter().any(|p| p.when.is_some()));
+        assert!(config.packages.iter().any(|p| p.url.is_some() && p.checksum.is_some()));
+        assert!(config.packages.iter().any(|p| !p.dependencies.is_empty()));
+    }
+}
@@ -1,29 +1,130 @@
-use crate::config::{Agent, Package, ProjectConfig};
+#[cfg(feature = "remote")]
+use crate::cli::install::package_for_url;
+use crate::cli::migrate::MigrateCommand;
+use crate::config::{
+    Agent, BatchEntry, InitAnswers, LockFile, Package, PackageVersion, ProjectConfig, TrackedFile, UserConfig, WorkspaceConfig,
+    format_agents,
+};
 use crate::error::{ConfigError, Result};
-use crate::file_ops::FileOps;
-use crate::templates::{TemplateDeployer, TemplateSystem};
+use crate::init::InitOutcome;
+use crate::file_ops::{
+    ConfigFormat, ConfigLayout, ConflictPolicy, ConflictResolver, FileOps, backup, FileWrite, LOCK_FILE_NAME,
+    ProjectPaths, WriteJournal, WriteOutcome, gitignore,
+};
+use crate::templates::provenance::PROVENANCE_METADATA_KEY;
+use crate::templates::diff::DiffStyle;
+use crate::templates::{self, ResolvedTemplate, TemplateDeployer, TemplateSystem, VARIABLES_METADATA_KEY};
+#[cfg(feature = "remote")]
+use crate::templates::remote::RemotePackage;
+use crate::output;
+use crate::prompt;
+use crate::status;
+use crate::ui::Status;
 use clap::Args;
-use dialoguer::{Select, theme::ColorfulTheme};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "remote")]
+use std::time::Duration;
 
 /// Initialize a new Specforge project with agent configuration
-#[derive(Args)]
+#[derive(Args, Clone)]
 pub struct InitCommand {
-    /// The AI agent to configure for this project
-    #[arg(short, long, value_enum)]
-    pub agent: Option<AgentType>,
+    /// The AI agents to configure for this project (repeat or list several)
+    #[arg(short, long, value_enum, num_args(1..))]
+    pub agent: Vec<AgentType>,
 
     /// Output directory for the configuration file
-    #[arg(short, long, default_value = ".", value_parser = validate_output_directory)]
+    #[arg(short, long, default_value = ".")]
     pub output_directory: PathBuf,
 
     /// Project name (optional)
     #[arg(short, long)]
     pub project_name: Option<String>,
 
-    /// Force overwrite existing configuration
+    /// Overwrite existing files without asking (same as --on-conflict overwrite)
     #[arg(short, long)]
     pub force: bool,
+
+    /// What to do with an existing configuration or template file
+    #[arg(long, value_enum, conflicts_with = "force")]
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// Copy template files to <name>.specforge-backup-<timestamp> before
+    /// overwriting them
+    #[arg(long)]
+    pub backup: bool,
+
+    /// Preview changes to existing template files as word diffs, marking the
+    /// changed words instead of repeating whole lines
+    #[arg(long)]
+    pub word_diff: bool,
+
+    /// File format of the configuration file to write
+    #[arg(long, value_enum, default_value_t = ConfigFormat::Json)]
+    pub config_format: ConfigFormat,
+
+    /// Write the configuration to .specforge/config.json instead of a single
+    /// .specforge.json file
+    #[arg(long)]
+    pub use_directory: bool,
+
+    /// Template variable to substitute for {{KEY}} placeholders (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_template_variable)]
+    pub vars: Vec<(String, String)>,
+
+    /// Directory with custom templates that replace the built-in files of the
+    /// same name (e.g. CLAUDE.md); missing files fall back to the built-in ones.
+    /// Defaults to .specforge/templates when that directory exists
+    #[arg(long)]
+    pub template_dir: Option<PathBuf>,
+
+    /// Fail instead of warning when rendered templates contain unresolved
+    /// placeholders or links to files that do not exist (recommended in CI)
+    #[arg(long)]
+    pub strict_templates: bool,
+
+    /// Do not add a provenance line naming the template package, version and
+    /// source hash to deployed Markdown and JSON files
+    #[arg(long)]
+    pub no_provenance: bool,
+
+    /// Add ignore entries for the files Specforge creates, such as
+    /// configuration backups, to the project's .gitignore
+    #[arg(long)]
+    pub update_gitignore: bool,
+
+    /// With --update-gitignore, also ignore the configuration file itself
+    #[arg(long, requires = "update_gitignore")]
+    pub gitignore_config: bool,
+
+    /// Also install the template package archive at this URL
+    /// (http://, https:// or file://)
+    #[arg(long, value_name = "URL")]
+    pub package_url: Option<String>,
+
+    /// Seconds to wait for the package server before giving up
+    #[arg(long, value_name = "SECONDS", default_value_t = 30, requires = "package_url")]
+    pub timeout: u64,
+
+    /// JSON or TOML file answering the init questions (agent, project name,
+    /// conflict policy, variables, ...); flags given as well take precedence
+    #[arg(long, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+
+    /// Initialize every project listed in this file (`name,directory` per
+    /// line, or a JSON array of {"name", "directory"}) with the other flags
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["output_directory", "project_name", "answers"])]
+    pub batch: Option<PathBuf>,
+
+    /// With --batch, keep initializing the remaining projects after one fails
+    #[arg(long, requires = "batch")]
+    pub continue_on_error: bool,
+}
+
+/// Outcome of initializing one project of a batch
+struct BatchResult<'a> {
+    entry: &'a BatchEntry,
+    outcome: Result<Option<serde_json::Value>>,
 }
 
 /// Supported AI agent types for CLI
@@ -33,6 +134,13 @@ pub enum AgentType {
     Copilot,
     /// Anthropic Claude
     Claude,
+    /// Codeium
+    Codeium,
+    /// Continue
+    Continue,
+    /// Amazon Q Developer
+    #[value(name = "amazonq")]
+    AmazonQ,
 }
 
 impl From<AgentType> for Agent {
@@ -40,6 +148,9 @@ impl From<AgentType> for Agent {
         match agent_type {
             AgentType::Copilot => Agent::Copilot,
             AgentType::Claude => Agent::Claude,
+            AgentType::Codeium => Agent::Codeium,
+            AgentType::Continue => Agent::Continue,
+            AgentType::AmazonQ => Agent::AmazonQ,
         }
     }
 }
@@ -49,12 +160,15 @@ impl From<Agent> for AgentType {
         match agent {
             Agent::Copilot => AgentType::Copilot,
             Agent::Claude => AgentType::Claude,
+            Agent::Codeium => AgentType::Codeium,
+            Agent::Continue => AgentType::Continue,
+            Agent::AmazonQ => AgentType::AmazonQ,
         }
     }
 }
 
 /// Validate output directory path with comprehensive checks
-fn validate_output_directory(s: &str) -> Result<PathBuf> {
+pub(crate) fn validate_output_directory(s: &str) -> Result<PathBuf> {
     let path = PathBuf::from(s);
 
     // Validate and canonicalize the path
@@ -79,64 +193,213 @@ fn validate_output_directory(s: &str) -> Result<PathBuf> {
         })?;
     } else {
         // For non-existent paths, check if parent directories exist and are writable
-        if let Some(parent) = canonical_path.parent() {
-            if parent.exists() {
-                if !parent.is_dir() {
-                    return Err(ConfigError::validation_error(format!(
-                        "Parent path '{}' exists but is not a directory",
-                        parent.display()
-                    )));
-                }
-
-                // Check write permissions on parent directory
-                FileOps::check_write_permissions(parent).map_err(|e| {
-                    ConfigError::validation_error(format!(
-                        "Cannot create directory in '{}': {}",
-                        parent.display(),
-                        e
-                    ))
-                })?;
+        // If parent doesn't exist, that's okay - we'll create the full path later
+        if let Some(parent) = canonical_path.parent()
+            && parent.exists()
+        {
+            if !parent.is_dir() {
+                return Err(ConfigError::validation_error(format!(
+                    "Parent path '{}' exists but is not a directory",
+                    parent.display()
+                )));
             }
-            // If parent doesn't exist, that's okay - we'll create the full path later
+
+            // Check write permissions on parent directory
+            FileOps::check_write_permissions(parent).map_err(|e| {
+                ConfigError::validation_error(format!(
+                    "Cannot create directory in '{}': {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
         }
     }
 
     Ok(canonical_path)
 }
 
+/// Check that a custom template directory exists
+fn validate_template_dir(path: &Path) -> Result<()> {
+    if let Some(link) = FileOps::find_symlink_loop(path) {
+        return Err(ConfigError::validation_error(format!(
+            "Template directory '{}' cannot be read: {} is part of a symbolic link loop",
+            path.display(),
+            link.display()
+        )));
+    }
+    if !path.is_dir() {
+        return Err(ConfigError::validation_error(format!(
+            "Template directory '{}' does not exist or is not a directory",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a `KEY=VALUE` template variable
+pub(crate) fn parse_template_variable(s: &str) -> Result<(String, String)> {
+    let (key, value) = s.split_once('=').ok_or_else(|| {
+        ConfigError::validation_error(format!("Invalid variable '{}': expected KEY=VALUE", s))
+    })?;
+
+    let key = key.trim();
+    if !templates::is_placeholder_name(key) {
+        return Err(ConfigError::validation_error(format!(
+            "Invalid variable name '{}': use letters, digits and underscores, not starting with a digit",
+            key
+        )));
+    }
+    if key == "agent" {
+        return Err(ConfigError::validation_error(
+            "The 'agent' variable is set per template and cannot be overridden",
+        ));
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Express a path relative to a base directory using forward slashes
+pub(crate) fn relative_path(base: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(base).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 impl InitCommand {
     /// Execute the init command
     pub fn execute(&self) -> Result<()> {
-        println!("ℹ️  Initializing Specforge project...");
+        if let Some(path) = &self.batch {
+            return self.execute_batch(path);
+        }
+
+        let summary = self.run()?;
+        if output::is_json()
+            && let Some(summary) = summary
+        {
+            output::print_json(summary);
+        }
+        Ok(())
+    }
+
+    /// Initialize the project, returning the summary printed in JSON output
+    /// mode, or nothing when a reforge configuration was migrated instead
+    fn run(&self) -> Result<Option<serde_json::Value>> {
+        Ok(self.initialize()?.map(|outcome| Self::json_summary(&outcome)))
+    }
+
+    /// Initialize the project, or return nothing when a reforge configuration
+    /// was migrated instead
+    ///
+    /// Status lines and questions go through the reporter and prompter of the
+    /// current thread, see [`crate::init::run`].
+    pub(crate) fn initialize(&self) -> Result<Option<InitOutcome>> {
+        self.initialize_with(&TemplateSystem::new())
+    }
+
+    /// Initialize the project as [`InitCommand::initialize`] does, deploying
+    /// the template files with `deployer`
+    pub(crate) fn initialize_with(&self, deployer: &dyn TemplateDeployer) -> Result<Option<InitOutcome>> {
+        // Checked here rather than while parsing, so relative paths resolve
+        // against the directory selected with -C
+        let output_directory = validate_output_directory(&self.output_directory.to_string_lossy())?;
+        if output_directory != self.output_directory {
+            let command = InitCommand { output_directory, ..self.clone() };
+            return command.initialize_with(deployer);
+        }
+
+        if let Some(path) = &self.answers {
+            let command = self.with_answers(path).map_err(|e| {
+                e.add_context("answers file", format!("Reading answers from {}", path.display()))
+            })?;
+            return command.initialize_with(deployer);
+        }
+
+        status!("{} Initializing Specforge project...", Status::Info);
 
         // Validate command arguments with context
         self.validate()
             .map_err(|e| e.add_context("command validation", "Checking init command parameters"))?;
 
-        // Determine agent (either from flag or interactive selection)
-        let agent = self.determine_agent().map_err(|e| {
+        if self.offer_legacy_migration()? {
+            return Ok(None);
+        }
+
+        // Defaults from the user configuration apply where flags are not given
+        let defaults = UserConfig::load()
+            .map_err(|e| e.add_context("user configuration", "Reading user defaults"))?;
+
+        // A workspace configuration further up applies to the projects below it
+        let workspace = self.workspace_config()?;
+
+        // Determine agents (from flags, the workspace, user defaults or interactive selection)
+        let agents = self.determine_agents(&defaults, workspace.as_ref()).map_err(|e| {
             e.add_context("agent selection", "Determining which AI agent to configure")
         })?;
-        println!("ℹ️  Selected agent: {}", agent);
+        let agent_names = format_agents(&agents);
+        if agents.len() == 1 {
+            status!("{} Selected agent: {}", Status::Info, agent_names);
+        } else {
+            status!("{} Selected agents: {}", Status::Info, agent_names);
+        }
+
+        let project_name = self.determine_project_name()?;
 
         // Create project configuration with enhanced error context
-        let config = self.create_project_config(agent.clone()).map_err(|e| {
+        let mut config = self.create_project_config(agents.clone(), project_name.as_deref()).map_err(|e| {
             e.add_context(
                 "configuration creation",
-                &format!("Creating configuration for {} agent", agent),
+                format!("Creating configuration for {} agent", agent_names),
             )
         })?;
+        if let Some(workspace) = &workspace {
+            Self::add_shared_packages(&mut config, workspace)?;
+        }
+
+        // Load custom templates and check target paths up front so deployment cannot fail halfway
+        let (mut templates, conflicts) = TemplateSystem::resolve_templates(&agents);
+        if let Some(template_dir) = &self.custom_template_dir() {
+            TemplateSystem::apply_template_dir(&mut templates, template_dir).map_err(|e| {
+                e.add_context(
+                    "template loading",
+                    format!("Reading custom templates from {}", template_dir.display()),
+                )
+            })?;
+        }
+        TemplateSystem::validate_target_paths(&templates, &self.output_directory)
+            .map_err(|e| e.add_context("path validation", "Checking template target paths"))?;
+        let context = TemplateSystem::template_context(&config, &self.output_directory);
+        let issues = TemplateSystem::check_templates(&templates, &self.output_directory, &context);
+        TemplateSystem::report_template_issues(&issues, self.strict_templates)
+            .map_err(|e| e.add_context("template validation", "Checking rendered templates"))?;
+
+        // A lock file that is already there pins the packages this project may use
+        let config_path = self.output_directory.join(self.config_file_name());
+        FileOps::check_lock_file(&config, &config_path)
+            .map_err(|e| e.add_context("lock file check", format!("Checking packages against {}", LOCK_FILE_NAME)))?;
+        let lock_dir = FileOps::lock_directory(&config_path).to_path_buf();
+        let existing_lock = FileOps::read_existing_lock_file(&lock_dir)?;
+
+        // Download the package before writing anything, so a failed download leaves no trace
+        #[cfg(feature = "remote")]
+        let remote_package = self.fetch_remote_package(&mut config, existing_lock.as_ref(), &lock_dir)?;
+
+        // Everything below is undone when a later step fails, so a failed init leaves no trace
+        let mut journal = self.record_writes(&templates)?;
 
         // Ensure output directory exists, with enhanced error handling
         if !self.output_directory.exists() {
-            println!(
-                "ℹ️  Creating output directory: {}",
+            status!(
+                "{} Creating output directory: {}",
+                Status::Info,
                 self.output_directory.display()
             );
             FileOps::ensure_directory_exists(&self.output_directory).map_err(|e| {
                 e.add_context(
                     "directory creation",
-                    &format!(
+                    format!(
                         "Creating output directory at {}",
                         self.output_directory.display()
                     ),
@@ -144,52 +407,456 @@ impl InitCommand {
             })?;
         }
 
+        // Read before the configuration is replaced, so redeployed files get their modes back
+        let recorded_modes = self.recorded_modes();
+
         // Write configuration file with context-aware error handling
-        let config_path = FileOps::write_config_to_directory_with_confirmation(
+        let mut resolver = ConflictResolver::new(self.conflict_policy(&defaults))
+            .with_backups(self.backup)
+            .with_diff_style(DiffStyle::from_word_diff(self.word_diff));
+        let config_file = FileOps::write_config_to_directory_with_confirmation(
             &config,
             &self.output_directory,
-            self.force,
+            self.config_format,
+            self.config_layout(),
+            &mut resolver,
         )
         .map_err(|e| {
             e.add_context(
                 "configuration file writing",
-                &format!(
-                    "Writing .specforge.json to {}",
+                format!(
+                    "Writing {} to {}",
+                    self.config_file_name(),
                     self.output_directory.display()
                 ),
             )
         })?;
 
         // Deploy template files
-        println!("ℹ️  Deploying {} template files...", agent);
-        let deployed_files = TemplateSystem::deploy_templates(&agent, &self.output_directory)
-            .map_err(|e| {
+        status!("{} Deploying {} template files...", Status::Info, agent_names);
+        let deployed_files = deployer.deploy_resolved(
+            &templates,
+            &conflicts,
+            &self.output_directory,
+            &context,
+            &mut resolver,
+        )
+        .map_err(|e| {
+            e.add_context(
+                "template deployment",
+                format!(
+                    "Deploying {} templates to {}",
+                    agent_names,
+                    self.output_directory.display()
+                ),
+            )
+        })?;
+        #[cfg(feature = "remote")]
+        let deployed_files = match &remote_package {
+            Some(remote) => {
+                let remote_files = remote
+                    .deploy(&self.output_directory, &agents, &context, &mut resolver)
+                    .map_err(|e| {
+                        e.add_context(
+                            "template deployment",
+                            format!("Deploying package {}", remote.package.id),
+                        )
+                    })?;
+                [deployed_files, remote_files].concat()
+            }
+            None => deployed_files,
+        };
+
+        for backup in std::iter::once(&config_file).chain(&deployed_files).filter_map(|file| file.backup.as_ref()) {
+            journal.track_created(backup);
+        }
+
+        // Record the deployed files so `specforge clean` can remove them later
+        let config_path = config_file.path;
+        let mut deployed_files = deployed_files;
+        self.restore_recorded_modes(&mut deployed_files, &recorded_modes)?;
+        self.track_deployed_files(&mut config, &deployed_files)?;
+        if config_file.outcome == WriteOutcome::Skipped {
+            status!(
+                "{} Kept existing {}; deployed files were not recorded",
+                Status::Info,
+                config_path.display()
+            );
+        } else {
+            FileOps::write_config(&config, &config_path).map_err(|e| {
                 e.add_context(
-                    "template deployment",
-                    &format!(
-                        "Deploying {} templates to {}",
-                        agent,
-                        self.output_directory.display()
-                    ),
+                    "configuration file writing",
+                    format!("Recording deployed files in {}", config_path.display()),
                 )
             })?;
 
-        // Display success message
-        println!(
-            "✅ Successfully created Specforge configuration at: {}",
-            config_path.display()
-        );
-        println!("📄 Deployed {} template files:", deployed_files.len());
-        for file in &deployed_files {
-            println!("   • {}", file.display());
+            // Pin the packages this project was initialized with, keeping existing pins
+            let mut lock = LockFile::from_config(&config);
+            #[cfg(feature = "remote")]
+            if let Some(remote) = &remote_package {
+                lock.refresh(&config, vec![remote.locked_package()]);
+            }
+            if let Some(existing) = &existing_lock {
+                let pins = existing.packages.iter().filter(|pin| config.get_package(&pin.id).is_some());
+                lock.refresh(&config, pins.cloned().collect());
+            }
+            FileOps::write_lock_file(&lock, &lock_dir).map_err(|e| {
+                e.add_context("lock file writing", format!("Writing {}", LOCK_FILE_NAME))
+            })?;
+
+            // Display success message
+            status!(
+                "{} Successfully created Specforge configuration at: {}",
+                Status::Ok,
+                config_path.display()
+            );
+            crate::cli::validate::print_warnings(&config.warnings());
         }
+        journal.commit();
+        Self::display_deploy_summary(&deployed_files);
+
+        let gitignore_updated = if self.update_gitignore {
+            self.update_gitignore(&config_path)?
+        } else {
+            false
+        };
 
         // Display next steps
-        self.display_next_steps(&agent);
+        self.display_next_steps(&agents);
+
+        Ok(Some(InitOutcome {
+            config_path,
+            config,
+            deployed_files,
+            gitignore_updated,
+        }))
+    }
+
+    /// Initialize each project listed in a batch file with the flags of this command
+    ///
+    /// Every project is initialized on its own, with its own lock file and
+    /// report; a failing project stops the batch unless --continue-on-error
+    /// is given. Fails when any project failed.
+    fn execute_batch(&self, path: &Path) -> Result<()> {
+        let entries = BatchEntry::read_all(path)
+            .map_err(|e| e.add_context("batch file", format!("Reading projects from {}", path.display())))?;
+
+        // Nobody answers prompts for twenty projects in a row
+        let defaults = UserConfig::load()
+            .map_err(|e| e.add_context("user configuration", "Reading user defaults"))?;
+        if self.agent.is_empty() && defaults.default_agent.is_none() {
+            return Err(ConfigError::validation_error(
+                "--batch needs --agent, or a default agent in the user configuration",
+            ));
+        }
+
+        let mut results = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            status!();
+            status!("{} [{}/{}] {}", Status::Info, index + 1, entries.len(), entry.name);
+            let outcome = self.batch_entry_command(entry).and_then(|command| command.run());
+            if let Err(e) = &outcome {
+                status!("{} {}", Status::Error, e);
+            }
+            let failed = outcome.is_err();
+            results.push(BatchResult { entry, outcome });
+            if failed && !self.continue_on_error {
+                status!("{} Stopping the batch; pass --continue-on-error to initialize the remaining projects", Status::Info);
+                break;
+            }
+        }
+
+        Self::display_batch_report(&entries, &results);
+        if output::is_json() {
+            output::print_json(Self::batch_json(&entries, &results));
+        }
 
+        let failed = results.iter().filter(|r| r.outcome.is_err()).count();
+        if failed > 0 {
+            return Err(ConfigError::batch_failed(failed, entries.len()));
+        }
         Ok(())
     }
 
+    /// This command for one project of a batch
+    fn batch_entry_command(&self, entry: &BatchEntry) -> Result<InitCommand> {
+        let mut command = self.clone();
+        command.batch = None;
+        command.project_name = Some(entry.name.clone());
+        command.output_directory = validate_output_directory(&entry.directory().to_string_lossy())?;
+        Ok(command)
+    }
+
+    /// List the result of every project in a batch
+    fn display_batch_report(entries: &[BatchEntry], results: &[BatchResult]) {
+        let succeeded = results.iter().filter(|r| r.outcome.is_ok()).count();
+        status!();
+        status!(
+            "📋 Batch results ({} initialized, {} failed, {} not attempted):",
+            succeeded,
+            results.len() - succeeded,
+            entries.len() - results.len()
+        );
+        for result in results {
+            let marker = if result.outcome.is_ok() { Status::Ok } else { Status::Error };
+            status!("   {} {} ({})", marker, result.entry.name, result.entry.directory().display());
+        }
+        for entry in &entries[results.len()..] {
+            status!("   ⏭️  {} ({})", entry.name, entry.directory().display());
+        }
+    }
+
+    /// Machine-readable batch report printed to stdout in JSON output mode
+    fn batch_json(entries: &[BatchEntry], results: &[BatchResult]) -> serde_json::Value {
+        let mut projects: Vec<serde_json::Value> = results
+            .iter()
+            .map(|result| {
+                let mut project = serde_json::json!({
+                    "name": result.entry.name,
+                    "directory": result.entry.directory(),
+                });
+                match &result.outcome {
+                    Ok(summary) => {
+                        project["status"] = serde_json::json!("initialized");
+                        project["result"] = summary.clone().unwrap_or_default();
+                    }
+                    Err(e) => {
+                        project["status"] = serde_json::json!("failed");
+                        project["error"] = e.to_json();
+                    }
+                }
+                project
+            })
+            .collect();
+        projects.extend(entries[results.len()..].iter().map(|entry| {
+            serde_json::json!({
+                "name": entry.name,
+                "directory": entry.directory(),
+                "status": "not_attempted",
+            })
+        }));
+        serde_json::json!({ "projects": projects })
+    }
+
+    /// Command with the answers from an answers file filled in
+    ///
+    /// Flags given on the command line win over the file; each answer they
+    /// replace is reported with a warning. Flags left at their default value
+    /// count as not given.
+    fn with_answers(&self, path: &Path) -> Result<InitCommand> {
+        let (answers, warnings) = InitAnswers::read(path)?;
+        for warning in warnings {
+            status!("{} {}", Status::Warn, warning);
+        }
+        let overridden = |flag: &str, key: &str| {
+            status!("{} {} overrides '{}' from {}", Status::Warn, flag, key, path.display());
+        };
+
+        let mut command = self.clone();
+        command.answers = None;
+
+        if !answers.agent.is_empty() {
+            if self.agent.is_empty() {
+                command.agent = answers.agent.into_iter().map(AgentType::from).collect();
+            } else {
+                overridden("--agent", "agent");
+            }
+        }
+        if answers.project_name.is_some() {
+            if self.project_name.is_none() {
+                command.project_name = answers.project_name;
+            } else {
+                overridden("--project-name", "project_name");
+            }
+        }
+        if answers.on_conflict.is_some() {
+            if self.on_conflict.is_none() && !self.force {
+                command.on_conflict = answers.on_conflict;
+            } else {
+                overridden(if self.force { "--force" } else { "--on-conflict" }, "on_conflict");
+            }
+        }
+        if let Some(config_format) = answers.config_format {
+            if self.config_format == ConfigFormat::default() {
+                command.config_format = config_format;
+            } else {
+                overridden("--config-format", "config_format");
+            }
+        }
+        if let Some(template_dir) = answers.template_dir {
+            if self.template_dir.is_none() {
+                command.template_dir = Some(template_dir);
+            } else {
+                overridden("--template-dir", "template_dir");
+            }
+        }
+        if let Some(strict_templates) = answers.strict_templates {
+            if !self.strict_templates {
+                command.strict_templates = strict_templates;
+            } else if !strict_templates {
+                overridden("--strict-templates", "strict_templates");
+            }
+        }
+
+        // Variables are merged per key, with --var winning
+        let mut vars = Vec::new();
+        for (key, value) in answers.variables {
+            if self.vars.iter().any(|(flag_key, _)| *flag_key == key) {
+                overridden(&format!("--var {}", key), &format!("variables.{}", key));
+                continue;
+            }
+            vars.push(parse_template_variable(&format!("{}={}", key, value))?);
+        }
+        vars.extend(self.vars.iter().cloned());
+        command.vars = vars;
+
+        Ok(command)
+    }
+
+    /// Offer to migrate a .reforge.json in the output directory instead of
+    /// creating a new configuration; returns whether it was migrated
+    fn offer_legacy_migration(&self) -> Result<bool> {
+        let legacy_path = ProjectPaths::new(&self.output_directory).legacy_config_file();
+        if !legacy_path.is_file() || FileOps::config_exists_in_directory(&self.output_directory) {
+            return Ok(false);
+        }
+
+        status!("{} Found {} from a reforge release", Status::Info, legacy_path.display());
+        if !prompt::is_interactive() {
+            status!("   Run `specforge migrate` to keep its settings instead of starting over");
+            return Ok(false);
+        }
+        if !FileOps::confirm_action("Migrate it instead of creating a new configuration?")? {
+            return Ok(false);
+        }
+
+        MigrateCommand::migrate(&self.output_directory)?;
+        Ok(true)
+    }
+
+    /// Layout selected by --use-directory
+    fn config_layout(&self) -> ConfigLayout {
+        if self.use_directory {
+            ConfigLayout::Directory
+        } else {
+            ConfigLayout::File
+        }
+    }
+
+    /// Record every file initialization may write: the configuration in each
+    /// layout and format, the lock file next to it and the template targets
+    fn record_writes(&self, templates: &[ResolvedTemplate]) -> Result<WriteJournal> {
+        let mut journal = WriteJournal::new();
+        let config_path = self.output_directory.join(self.config_file_name());
+        let configs: Vec<PathBuf> = std::iter::once(config_path)
+            .chain(FileOps::existing_configs(&self.output_directory))
+            .collect();
+        for config in &configs {
+            journal.record(config)?;
+            let lock_dir = config.parent().unwrap_or(&self.output_directory);
+            journal.record(&lock_dir.join(LOCK_FILE_NAME))?;
+        }
+        for template in templates {
+            journal.record(&self.output_directory.join(template.name))?;
+        }
+        Ok(journal)
+    }
+
+    /// Configuration file written by this command, relative to the output directory
+    fn config_file_name(&self) -> String {
+        match self.config_layout() {
+            ConfigLayout::File => self.config_format.file_name().to_string(),
+            ConfigLayout::Directory => relative_path(
+                &self.output_directory,
+                &FileOps::get_config_path(&self.output_directory, ConfigLayout::Directory),
+            ),
+        }
+    }
+
+    /// Custom template directory given with --template-dir, falling back to
+    /// .specforge/templates when it exists
+    fn custom_template_dir(&self) -> Option<PathBuf> {
+        self.template_dir.clone().or_else(|| {
+            let templates_dir = ProjectPaths::new(&self.output_directory).templates_dir();
+            templates_dir.is_dir().then(|| {
+                status!("{} Using custom templates from {}", Status::Info, templates_dir.display());
+                templates_dir
+            })
+        })
+    }
+
+    /// Conflict policy selected by --on-conflict or --force, falling back to the user default
+    fn conflict_policy(&self, defaults: &UserConfig) -> ConflictPolicy {
+        if self.force {
+            ConflictPolicy::Overwrite
+        } else {
+            self.on_conflict.or(defaults.default_on_conflict).unwrap_or_default()
+        }
+    }
+
+    /// Add the ignore entries selected by --update-gitignore and
+    /// --gitignore-config; returns whether .gitignore was modified
+    fn update_gitignore(&self, config_path: &Path) -> Result<bool> {
+        let mut entries = vec![gitignore::BACKUP_PATTERN.to_string()];
+        if self.backup {
+            entries.push(backup::BACKUP_FILE_PATTERN.to_string());
+        }
+        if self.gitignore_config {
+            entries.push(relative_path(&self.output_directory, config_path));
+        }
+
+        let path = self.output_directory.join(gitignore::GITIGNORE_FILE_NAME);
+        let added = gitignore::append_entries(&path, &entries)
+            .map_err(|e| e.add_context("gitignore update", format!("Updating {}", path.display())))?;
+        if added.is_empty() {
+            status!("{} {} already ignores Specforge files; left it unchanged", Status::Info, path.display());
+        } else {
+            status!("📝 Added {} to {}", added.join(", "), path.display());
+        }
+        Ok(!added.is_empty())
+    }
+
+    /// List the template files with counts of created, overwritten and skipped files
+    fn display_deploy_summary(deployed_files: &[FileWrite]) {
+        let count = |outcome| deployed_files.iter().filter(|f| f.outcome == outcome).count();
+        let written = deployed_files.iter().filter(|f| f.written()).count();
+        status!(
+            "📄 Deployed {} template files ({} created, {} overwritten, {} unchanged, {} skipped):",
+            written,
+            count(WriteOutcome::Created),
+            count(WriteOutcome::Overwritten),
+            count(WriteOutcome::Unchanged) + count(WriteOutcome::ModeRestored),
+            count(WriteOutcome::Skipped)
+        );
+        for file in deployed_files {
+            match (&file.outcome, &file.backup) {
+                (WriteOutcome::Skipped, _) => status!("   • {} (skipped, already exists)", file.path.display()),
+                (WriteOutcome::ModeRestored, _) => status!("   • {} (mode restored)", file.path.display()),
+                (_, Some(backup)) => {
+                    status!("   • {} (previous version in {})", file.path.display(), backup.display())
+                }
+                _ => status!("   • {}", file.path.display()),
+            }
+        }
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(outcome: &InitOutcome) -> serde_json::Value {
+        let config = &outcome.config;
+        let backups: Vec<_> = outcome.deployed_files.iter().filter_map(|f| f.backup.as_ref()).collect();
+        serde_json::json!({
+            "status": "success",
+            "config_path": outcome.config_path,
+            "agent": config.primary_agent(),
+            "agents": config.agents,
+            "deployed_files": config.files.iter().map(|f| &f.path).collect::<Vec<_>>(),
+            "project_name": config.project_name(),
+            "gitignore_updated": outcome.gitignore_updated,
+            "backups": backups,
+            "warnings": config.warnings(),
+        })
+    }
+
     /// Validate command arguments
     fn validate(&self) -> Result<()> {
         // Validate project name if provided
@@ -207,26 +874,87 @@ impl InitCommand {
             }
         }
 
-        // Output directory validation is handled by clap value_parser
+        if let Some(template_dir) = &self.template_dir {
+            validate_template_dir(template_dir)?;
+        }
+
+        if self.use_directory && self.config_format != ConfigFormat::Json {
+            return Err(ConfigError::validation_error(
+                "--use-directory writes .specforge/config.json and cannot be combined with --config-format toml or yaml",
+            ));
+        }
+
+        if self.package_url.is_some() && !cfg!(feature = "remote") {
+            return Err(ConfigError::validation_error(
+                "--package-url needs a specforge build with the `remote` feature",
+            ));
+        }
 
         Ok(())
     }
 
-    /// Determine which agent to use (from flag or interactive prompt)
-    fn determine_agent(&self) -> Result<Agent> {
-        if let Some(agent_type) = &self.agent {
-            // Agent specified via flag
-            Ok(Agent::from(agent_type.clone()))
-        } else {
+    /// The workspace configuration that applies to the output directory, if any
+    fn workspace_config(&self) -> Result<Option<WorkspaceConfig>> {
+        let Some(path) = FileOps::find_workspace_config(&self.output_directory) else {
+            return Ok(None);
+        };
+        let workspace = FileOps::read_workspace_config(&path).map_err(|e| {
+            e.add_context("workspace configuration", format!("Reading {}", path.display()))
+        })?;
+        let root = path.parent().unwrap_or(Path::new("."));
+        if !workspace.includes(root, &self.output_directory) {
+            return Ok(None);
+        }
+        status!("{} Using workspace configuration {}", Status::Info, path.display());
+        Ok(Some(workspace))
+    }
+
+    /// Add the packages shared by the workspace that the project does not list yet
+    fn add_shared_packages(config: &mut ProjectConfig, workspace: &WorkspaceConfig) -> Result<()> {
+        for package in &workspace.shared_packages {
+            if config.packages.iter().all(|p| p.id != package.id) {
+                config.add_package(package.clone())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Determine which agents to use (from flags, the workspace default, the
+    /// user default or an interactive prompt)
+    fn determine_agents(&self, defaults: &UserConfig, workspace: Option<&WorkspaceConfig>) -> Result<Vec<Agent>> {
+        if self.agent.is_empty() {
+            if let Some(workspace) = workspace {
+                status!("{} Using default agent {} from the workspace configuration", Status::Info, workspace.default_agent);
+                return Ok(vec![workspace.default_agent.clone()]);
+            }
+            if let Some(agent) = &defaults.default_agent {
+                status!("{} Using default agent {} from the user configuration", Status::Info, agent);
+                return Ok(vec![agent.clone()]);
+            }
             // Interactive agent selection
-            self.interactive_agent_selection()
+            return Ok(vec![self.interactive_agent_selection()?]);
         }
+
+        // Agents specified via flags, ignoring repeats but keeping their order
+        let mut agents = Vec::new();
+        for agent_type in &self.agent {
+            let agent = Agent::from(agent_type.clone());
+            if !agents.contains(&agent) {
+                agents.push(agent);
+            }
+        }
+        Ok(agents)
     }
 
-    /// Perform interactive agent selection using dialoguer
+    /// Ask which agent to configure with the current prompter
     fn interactive_agent_selection(&self) -> Result<Agent> {
-        println!("ℹ️  No agent specified. Please select an AI agent for this project:");
-        println!();
+        if !prompt::is_interactive() {
+            return Err(ConfigError::validation_error(
+                "No agent specified and there is no terminal to select one. Use --agent",
+            ));
+        }
+        status!("{} No agent specified. Please select an AI agent for this project:", Status::Info);
+        status!();
 
         let agents = Agent::all();
         let agent_options: Vec<String> = agents
@@ -234,51 +962,130 @@ impl InitCommand {
             .map(|agent| format!("{} - {}", agent, agent.description()))
             .collect();
 
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select your AI agent")
-            .default(0)
-            .items(&agent_options)
-            .interact_opt()
-            .map_err(|e| ConfigError::io_error(format!("Failed to read user input: {}", e)))?;
+        let selection = prompt::select("Select your AI agent", &agent_options, 0)?;
 
         match selection {
             Some(index) => {
                 let selected_agent = agents[index].clone();
-                println!();
-                println!("✅ Selected agent: {}", selected_agent);
+                status!();
+                status!("{} Selected agent: {}", Status::Ok, selected_agent);
                 Ok(selected_agent)
             }
             None => {
                 // User cancelled (Ctrl+C or Esc)
-                println!();
-                println!("❌ Agent selection cancelled by user");
+                status!();
+                status!("{} Agent selection cancelled by user", Status::Error);
                 Err(ConfigError::user_cancelled("Agent selection was cancelled"))
             }
         }
     }
 
+    /// Project name given with --project-name, or asked for when there is
+    /// a terminal to ask on
+    fn determine_project_name(&self) -> Result<Option<String>> {
+        if self.project_name.is_some() || !prompt::is_interactive() {
+            return Ok(self.project_name.clone());
+        }
+
+        let default = self.default_project_name();
+        loop {
+            let Some(answer) = prompt::input("Project name", &default)? else {
+                return Err(ConfigError::user_cancelled("Project name entry was cancelled"));
+            };
+            match ProjectConfig::validate_project_name(&answer) {
+                Ok(()) => return Ok(Some(answer.trim().to_string())),
+                Err(e) => status!("{} {}", Status::Error, e),
+            }
+        }
+    }
+
+    /// Name offered when asking for the project name: the one of an existing
+    /// configuration, or the name of the output directory
+    fn default_project_name(&self) -> String {
+        let existing = FileOps::config_exists_in_directory(&self.output_directory)
+            .then(|| FileOps::read_config_from_directory(&self.output_directory, None).ok())
+            .flatten()
+            .and_then(|config| config.project_name().map(str::to_string));
+        existing.unwrap_or_else(|| {
+            let directory = self.output_directory.canonicalize().unwrap_or_else(|_| self.output_directory.clone());
+            directory
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+    }
+
     /// Create project configuration based on command arguments
-    fn create_project_config(&self, agent: Agent) -> Result<ProjectConfig> {
-        let mut config = if let Some(ref project_name) = self.project_name {
-            ProjectConfig::with_project_name(agent, project_name)
-        } else {
-            ProjectConfig::new(agent)
-        };
+    fn create_project_config(&self, agents: Vec<Agent>, project_name: Option<&str>) -> Result<ProjectConfig> {
+        let mut config = ProjectConfig::with_agents(agents);
+        config.project_name = project_name.map(str::to_string);
+        if !self.vars.is_empty() {
+            let variables: serde_json::Map<_, _> = self
+                .vars
+                .iter()
+                .map(|(key, value)| (key.clone(), serde_json::Value::String(value.clone())))
+                .collect();
+            config.try_set_metadata(VARIABLES_METADATA_KEY, variables)?;
+        }
 
-        // Add default template packages based on agent
-        let default_package = self.create_default_package(&config.agent);
-        config.add_package(default_package)?;
+        if self.no_provenance {
+            config.set_metadata(PROVENANCE_METADATA_KEY, false);
+        }
+
+        // Add default template packages based on agents
+        for agent in config.agents.clone() {
+            config.add_package(self.create_default_package(&agent))?;
+        }
 
         // Set additional metadata
         config.set_metadata("initialized_by", "specforge-cli");
         config.set_metadata("version", env!("CARGO_PKG_VERSION"));
 
+        // Re-running init keeps what the existing configuration recorded, so
+        // the result only depends on the inputs
+        if FileOps::config_exists_in_directory(&self.output_directory) {
+            match FileOps::read_config_from_directory(&self.output_directory, None) {
+                Ok(existing) => Self::carry_over_metadata(&mut config, &existing),
+                Err(e) => status!("{} Could not read the existing configuration, so its metadata is not kept: {}", Status::Warn, e),
+            }
+        }
+
         // Validate the configuration
         config.validate()?;
 
         Ok(config)
     }
 
+    /// Keep the metadata of an earlier init in a new configuration
+    ///
+    /// The creation time always comes from the existing configuration; the
+    /// project name, variables and custom keys do unless this run sets them.
+    /// Provenance follows `--no-provenance` of this run only.
+    /// `updated_at` is refreshed when anything else changed, so re-running
+    /// init with the same inputs leaves the file as it was.
+    fn carry_over_metadata(config: &mut ProjectConfig, existing: &ProjectConfig) {
+        if config.project_name.is_none() {
+            config.project_name = existing.project_name.clone();
+        }
+        for (key, value) in &existing.metadata {
+            if key == PROVENANCE_METADATA_KEY {
+                continue;
+            }
+            if key == "created_at" || !config.metadata.contains_key(key) {
+                config.metadata.insert(key.clone(), value.clone());
+            }
+        }
+
+        let without_updated_at = |config: &ProjectConfig| {
+            let mut metadata = config.metadata.clone();
+            metadata.remove("updated_at");
+            (config.agents.clone(), config.packages.clone(), config.project_name.clone(), metadata)
+        };
+        if without_updated_at(config) != without_updated_at(existing) {
+            config.touch();
+        }
+    }
+
     /// Create default template package based on selected agent
     ///
     /// Creates a package entry with:
@@ -286,12 +1093,7 @@ impl InitCommand {
     /// - Current crate version for version tracking
     /// - Proper structure for future template deployment features
     fn create_default_package(&self, agent: &Agent) -> Package {
-        let package_version = env!("CARGO_PKG_VERSION");
-
-        match agent {
-            Agent::Copilot => Package::new("specforge-copilot-templates", package_version),
-            Agent::Claude => Package::new("specforge-claude-templates", package_version),
-        }
+        Package::with_version(agent.default_package_id(), PackageVersion::current())
     }
 
     /// Create multiple template packages for an agent (if needed in the future)
@@ -300,34 +1102,125 @@ impl InitCommand {
     /// different template categories or specialized packages.
     #[allow(dead_code)] // Future feature
     fn create_agent_packages(&self, agent: &Agent) -> Vec<Package> {
-        let package_version = env!("CARGO_PKG_VERSION");
+        let package_version = PackageVersion::current();
 
         match agent {
             Agent::Copilot => vec![
-                Package::new("specforge-copilot-templates", package_version),
+                Package::with_version("specforge-copilot-templates", package_version),
                 // Future: Additional packages like "specforge-copilot-advanced-templates"
             ],
             Agent::Claude => vec![
-                Package::new("specforge-claude-templates", package_version),
+                Package::with_version("specforge-claude-templates", package_version),
                 // Future: Additional packages like "specforge-claude-advanced-templates"
             ],
+            Agent::Codeium => vec![
+                Package::with_version("specforge-codeium-templates", package_version),
+            ],
+            Agent::Continue => vec![
+                Package::with_version("specforge-continue-templates", package_version),
+            ],
+            Agent::AmazonQ => vec![
+                Package::with_version("specforge-amazonq-templates", package_version),
+            ],
         }
     }
 
-    /// Display helpful next steps to the user
-    fn display_next_steps(&self, agent: &Agent) {
-        println!();
-        println!("🎉 Next steps:");
-        println!("   1. Review the generated .specforge.json configuration");
-        println!("   2. Customize the configuration as needed");
-        println!("   3. Start using your AI agent with the configured templates");
+    /// Download the package given with --package-url and add it to the configuration
+    ///
+    /// A package that is locked must match its pin, and its download is
+    /// verified against the pinned checksum.
+    #[cfg(feature = "remote")]
+    fn fetch_remote_package(
+        &self,
+        config: &mut ProjectConfig,
+        lock: Option<&LockFile>,
+        lock_dir: &Path,
+    ) -> Result<Option<RemotePackage>> {
+        let Some(url) = &self.package_url else {
+            return Ok(None);
+        };
 
-        match agent {
-            Agent::Copilot => {
-                println!("   4. Make sure GitHub Copilot is enabled in your editor");
+        let package = package_for_url(url, None, "1.0.0", None)
+            .map_err(|e| e.add_context("package validation", "Checking --package-url"))?;
+        if let Some(mismatch) = lock.and_then(|lock| lock.package_mismatch(&package)) {
+            return Err(ConfigError::lock_mismatch(lock_dir.join(LOCK_FILE_NAME), vec![mismatch]));
+        }
+        let fetched = lock.map_or_else(|| package.clone(), |lock| lock.pinned(&package));
+        status!("{} Downloading {} from {}...", Status::Info, package.id, url);
+        let remote = RemotePackage::fetch(&fetched, Duration::from_secs(self.timeout))
+            .map_err(|e| e.add_context("package download", format!("Downloading {}", url)))?;
+        config.add_package(package)?;
+        Ok(Some(remote))
+    }
+
+    /// Modes the existing configuration recorded for its tracked files
+    fn recorded_modes(&self) -> HashMap<String, u32> {
+        if !FileOps::config_exists_in_directory(&self.output_directory) {
+            return HashMap::new();
+        }
+        FileOps::read_config_from_directory(&self.output_directory, None)
+            .map(|existing| {
+                existing
+                    .files
+                    .into_iter()
+                    .filter_map(|file| Some((file.path, file.mode?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Give redeployed files the modes recorded for them, since writing the
+    /// content does not bring back a lost executable bit
+    fn restore_recorded_modes(&self, deployed_files: &mut [FileWrite], modes: &HashMap<String, u32>) -> Result<()> {
+        for file in deployed_files.iter_mut() {
+            let relative = relative_path(&self.output_directory, &file.path);
+            if let Some(mode) = modes.get(&relative) {
+                file.restore_mode(*mode).map_err(|e| {
+                    e.add_context("mode restore", format!("Restoring the mode of {}", file.path.display()))
+                })?;
             }
-            Agent::Claude => {
-                println!("   4. Make sure Claude Code extension is installed and configured");
+        }
+        Ok(())
+    }
+
+    /// Record the written files in the configuration, relative to the output directory
+    ///
+    /// Skipped files belong to the user, so they are not tracked.
+    fn track_deployed_files(&self, config: &mut ProjectConfig, deployed_files: &[FileWrite]) -> Result<()> {
+        for file in deployed_files.iter().filter(|f| f.written()) {
+            let relative = relative_path(&self.output_directory, &file.path);
+            let sha256 = file.content_hash()?;
+            config.track_file(TrackedFile::new(relative, sha256).with_mode(FileOps::file_mode(&file.path)));
+        }
+        Ok(())
+    }
+
+    /// Display helpful next steps to the user
+    fn display_next_steps(&self, agents: &[Agent]) {
+        status!();
+        status!("🎉 Next steps:");
+        status!("   1. Review the generated {} configuration", self.config_file_name());
+        status!("   2. Customize the configuration as needed");
+        status!("   3. Start using your AI agent with the configured templates");
+
+        for (index, agent) in agents.iter().enumerate() {
+            let step = index + 4;
+            match agent {
+                Agent::Copilot => {
+                    status!("   {}. Make sure GitHub Copilot is enabled in your editor", step);
+                }
+                Agent::Claude => {
+                    status!("   {}. Make sure Claude Code extension is installed and configured", step);
+                }
+                Agent::Codeium => {
+                    status!("   {}. Make sure the Codeium extension is installed and picks up codeium.json", step);
+                }
+                Agent::Continue => {
+                    status!("   {}. Make sure the Continue extension is installed and add a model to .continue/config.json", step);
+                }
+                Agent::AmazonQ => {
+                    status!("   {}. Make sure the Amazon Q extension is installed and signed in to your AWS Builder ID or IAM Identity Center", step);
+                }
             }
         }
     }
@@ -336,10 +1229,12 @@ impl InitCommand {
     pub fn get_summary(&self) -> String {
         let mut summary = Vec::new();
 
-        if let Some(ref agent) = self.agent {
-            summary.push(format!("Agent: {:?}", agent));
-        } else {
+        if self.agent.is_empty() {
             summary.push("Agent: Interactive selection".to_string());
+        } else {
+            let agents: Vec<String> = self.agent.iter().map(|a| format!("{:?}", a)).collect();
+            let label = if agents.len() == 1 { "Agent" } else { "Agents" };
+            summary.push(format!("{}: {}", label, agents.join(", ")));
         }
 
         summary.push(format!(
@@ -369,10 +1264,12 @@ mod tests {
         // Test AgentType to Agent conversion
         assert_eq!(Agent::from(AgentType::Copilot), Agent::Copilot);
         assert_eq!(Agent::from(AgentType::Claude), Agent::Claude);
+        assert_eq!(Agent::from(AgentType::Codeium), Agent::Codeium);
 
         // Test Agent to AgentType conversion
         assert_eq!(AgentType::from(Agent::Copilot), AgentType::Copilot);
         assert_eq!(AgentType::from(Agent::Claude), AgentType::Claude);
+        assert_eq!(AgentType::from(Agent::Codeium), AgentType::Codeium);
     }
 
     #[test]
@@ -449,42 +1346,166 @@ mod tests {
     fn test_init_command_validation() {
         // Valid command
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: PathBuf::from("."),
             project_name: Some("test-project".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         assert!(cmd.validate().is_ok());
 
         // Empty project name should fail
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: PathBuf::from("."),
             project_name: Some("".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         assert!(cmd.validate().is_err());
 
         // Too long project name should fail
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: PathBuf::from("."),
             project_name: Some("a".repeat(201)),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         assert!(cmd.validate().is_err());
     }
 
     #[test]
-    fn test_determine_agent() {
+    fn test_parse_template_variable() {
+        assert_eq!(
+            parse_template_variable("team=platform=core").unwrap(),
+            ("team".to_string(), "platform=core".to_string())
+        );
+        assert_eq!(
+            parse_template_variable("empty=").unwrap(),
+            ("empty".to_string(), String::new())
+        );
+        assert!(parse_template_variable("team").is_err());
+        assert!(parse_template_variable("1team=x").is_err());
+        assert!(parse_template_variable("my team=x").is_err());
+        assert!(parse_template_variable("agent=x").is_err());
+    }
+
+    #[test]
+    fn test_determine_agents() {
         // Agent specified via flag
         let cmd = InitCommand {
-            agent: Some(AgentType::Claude),
+            agent: vec![AgentType::Claude],
             output_directory: PathBuf::from("."),
             project_name: None,
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
-        assert_eq!(cmd.determine_agent().unwrap(), Agent::Claude);
+        assert_eq!(cmd.determine_agents(&UserConfig::default(), None).unwrap(), vec![Agent::Claude]);
+
+        // Several agents keep their order and repeats are ignored
+        let cmd = InitCommand {
+            agent: vec![AgentType::Codeium, AgentType::Copilot, AgentType::Codeium],
+            output_directory: PathBuf::from("."),
+            project_name: None,
+            force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
+        };
+        assert_eq!(
+            cmd.determine_agents(&UserConfig::default(), None).unwrap(),
+            vec![Agent::Codeium, Agent::Copilot]
+        );
+
+        // Flags win over the user default
+        let defaults = UserConfig {
+            default_agent: Some(Agent::Copilot),
+            ..UserConfig::default()
+        };
+        assert_eq!(
+            cmd.determine_agents(&defaults, None).unwrap(),
+            vec![Agent::Codeium, Agent::Copilot]
+        );
+        let cmd = InitCommand { agent: Vec::new(), ..cmd };
+        assert_eq!(cmd.determine_agents(&defaults, None).unwrap(), vec![Agent::Copilot]);
+
+        // The workspace default wins over the user default
+        let workspace = WorkspaceConfig::new(Agent::Claude);
+        assert_eq!(cmd.determine_agents(&defaults, Some(&workspace)).unwrap(), vec![Agent::Claude]);
 
         // No agent specified requires interactive selection which we can't test in unit tests
         // Interactive selection tests would be in integration tests
@@ -493,15 +1514,31 @@ mod tests {
     #[test]
     fn test_create_project_config() {
         let cmd = InitCommand {
-            agent: Some(AgentType::Claude),
+            agent: vec![AgentType::Claude],
             output_directory: PathBuf::from("."),
             project_name: Some("test-project".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
-        let config = cmd.create_project_config(Agent::Claude).unwrap();
+        let config = cmd.create_project_config(vec![Agent::Claude], cmd.project_name.as_deref()).unwrap();
 
-        assert_eq!(config.agent, Agent::Claude);
+        assert_eq!(config.agents, vec![Agent::Claude]);
         assert_eq!(config.project_name(), Some("test-project"));
         assert_eq!(config.packages.len(), 1);
         assert_eq!(config.packages[0].id, "specforge-claude-templates");
@@ -509,13 +1546,64 @@ mod tests {
         assert!(config.get_metadata("version").is_some());
     }
 
+    #[test]
+    fn test_default_project_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("my-app");
+        std::fs::create_dir(&project_dir).unwrap();
+        let cmd = InitCommand {
+            agent: vec![AgentType::Claude],
+            output_directory: project_dir.clone(),
+            project_name: None,
+            force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
+        };
+        assert_eq!(cmd.default_project_name(), "my-app");
+
+        // An existing configuration keeps its name
+        let config = ProjectConfig::with_project_name(Agent::Claude, "demo");
+        FileOps::write_config(&config, project_dir.join(".specforge.json")).unwrap();
+        assert_eq!(cmd.default_project_name(), "demo");
+    }
+
     #[test]
     fn test_create_default_package() {
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: PathBuf::from("."),
             project_name: None,
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         let expected_version = env!("CARGO_PKG_VERSION");
@@ -529,13 +1617,58 @@ mod tests {
         assert_eq!(claude_package.version, expected_version);
     }
 
+    #[test]
+    fn test_json_summary() {
+        let mut config = ProjectConfig::with_agents(vec![Agent::Claude, Agent::Copilot]);
+        config.project_name = Some("demo".to_string());
+        config.track_file(TrackedFile::new("CLAUDE.md", &"a".repeat(64)));
+
+        let outcome = InitOutcome {
+            config_path: PathBuf::from("/demo/.specforge.json"),
+            config,
+            deployed_files: Vec::new(),
+            gitignore_updated: false,
+        };
+        let summary = InitCommand::json_summary(&outcome);
+        assert_eq!(
+            summary,
+            serde_json::json!({
+                "status": "success",
+                "config_path": "/demo/.specforge.json",
+                "agent": "claude",
+                "agents": ["claude", "copilot"],
+                "deployed_files": ["CLAUDE.md"],
+                "project_name": "demo",
+                "gitignore_updated": false,
+                "backups": [],
+                "warnings": [],
+            })
+        );
+    }
+
     #[test]
     fn test_get_summary() {
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: PathBuf::from("/test/dir"),
             project_name: Some("my-project".to_string()),
             force: true,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         let summary = cmd.get_summary();
@@ -550,10 +1683,26 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: temp_dir.path().to_path_buf(),
             project_name: Some("test-project".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         // This should work in the temporary directory
@@ -570,10 +1719,26 @@ mod tests {
 
         // Create initial config
         let cmd1 = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: temp_dir.path().to_path_buf(),
             project_name: None,
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         cmd1.execute().unwrap();
 
@@ -583,16 +1748,32 @@ mod tests {
 
         // Try to create again with force - should succeed
         let cmd3 = InitCommand {
-            agent: Some(AgentType::Claude),
+            agent: vec![AgentType::Claude],
             output_directory: temp_dir.path().to_path_buf(),
             project_name: None,
             force: true,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         assert!(cmd3.execute().is_ok());
 
         // Verify the config was overwritten (agent should be Claude now)
-        let config = FileOps::read_config_from_directory(temp_dir.path()).unwrap();
-        assert_eq!(config.agent, Agent::Claude);
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert_eq!(config.agents, vec![Agent::Claude]);
     }
 
     #[test]
@@ -601,15 +1782,31 @@ mod tests {
 
         // Test Copilot packages array creation
         let copilot_cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: temp_dir.path().join("copilot").to_path_buf(),
             project_name: Some("copilot-project".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         copilot_cmd.execute().unwrap();
 
         let copilot_config =
-            FileOps::read_config_from_directory(&temp_dir.path().join("copilot")).unwrap();
+            FileOps::read_config_from_directory(temp_dir.path().join("copilot"), None).unwrap();
 
         // Verify packages array structure
         assert_eq!(copilot_config.packages.len(), 1);
@@ -632,15 +1829,31 @@ mod tests {
 
         // Test Claude packages array creation
         let claude_cmd = InitCommand {
-            agent: Some(AgentType::Claude),
+            agent: vec![AgentType::Claude],
             output_directory: temp_dir.path().join("claude").to_path_buf(),
             project_name: Some("claude-project".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
         claude_cmd.execute().unwrap();
 
         let claude_config =
-            FileOps::read_config_from_directory(&temp_dir.path().join("claude")).unwrap();
+            FileOps::read_config_from_directory(temp_dir.path().join("claude"), None).unwrap();
 
         // Verify Claude packages array
         assert_eq!(claude_config.packages.len(), 1);
@@ -667,10 +1880,26 @@ mod tests {
     #[test]
     fn test_package_versioning_accuracy() {
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: PathBuf::from("."),
             project_name: None,
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         let package = cmd.create_default_package(&Agent::Copilot);
@@ -678,21 +1907,7 @@ mod tests {
         // Version should match current crate version exactly
         assert_eq!(package.version, env!("CARGO_PKG_VERSION"));
 
-        // Version should follow semantic versioning
-        let version_parts: Vec<&str> = package.version.split('.').collect();
-        assert!(
-            version_parts.len() >= 3,
-            "Version should have at least major.minor.patch"
-        );
-
-        // Each version component should be numeric
-        for part in &version_parts[0..3] {
-            assert!(
-                part.parse::<u32>().is_ok(),
-                "Version component '{}' should be numeric",
-                part
-            );
-        }
+        assert_eq!(package.version, PackageVersion::current());
 
         // Package should pass validation
         assert!(package.validate().is_ok());
@@ -703,10 +1918,26 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: temp_dir.path().to_path_buf(),
             project_name: Some("test-project".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         // Execute init command with template deployment
@@ -720,6 +1951,15 @@ mod tests {
         assert!(temp_dir.path().join("CLAUDE.md").exists());
         assert!(temp_dir.path().join("README.md").exists());
 
+        // Verify deployed files were recorded with their hashes
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert_eq!(config.files.len(), 2);
+        let tracked = config.tracked_file("CLAUDE.md").unwrap();
+        assert_eq!(
+            tracked.sha256,
+            FileOps::hash_file(temp_dir.path().join("CLAUDE.md")).unwrap()
+        );
+
         // Verify template content
         let claude_content = std::fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
         assert!(claude_content.contains("GitHub Copilot"));
@@ -733,10 +1973,26 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         let cmd = InitCommand {
-            agent: Some(AgentType::Claude),
+            agent: vec![AgentType::Claude],
             output_directory: temp_dir.path().to_path_buf(),
             project_name: Some("claude-test".to_string()),
             force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         // Execute init command with Claude template deployment
@@ -767,10 +2023,26 @@ mod tests {
         std::fs::write(temp_dir.path().join("README.md"), "old readme").unwrap();
 
         let cmd = InitCommand {
-            agent: Some(AgentType::Copilot),
+            agent: vec![AgentType::Copilot],
             output_directory: temp_dir.path().to_path_buf(),
             project_name: None,
             force: true, // Force overwrite
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
         };
 
         // Execute should succeed and overwrite templates
@@ -786,4 +2058,75 @@ mod tests {
         assert!(readme_content.contains("GitHub Copilot Configuration"));
         assert!(!readme_content.contains("old readme"));
     }
+
+    /// Records the deployments it is asked for instead of writing files
+    #[derive(Default)]
+    struct RecordingDeployer {
+        calls: std::cell::RefCell<Vec<(Vec<Agent>, PathBuf)>>,
+    }
+
+    impl TemplateDeployer for RecordingDeployer {
+        fn deploy_resolved(
+            &self,
+            templates: &[templates::ResolvedTemplate],
+            _conflicts: &[templates::TemplateConflict],
+            target_dir: &Path,
+            _context: &std::collections::HashMap<String, String>,
+            _resolver: &mut ConflictResolver,
+        ) -> Result<Vec<FileWrite>> {
+            let mut agents = Vec::new();
+            for template in templates {
+                if !agents.contains(&template.agent) {
+                    agents.push(template.agent.clone());
+                }
+            }
+            self.calls.borrow_mut().push((agents, target_dir.to_path_buf()));
+            Ok(templates
+                .iter()
+                .map(|t| FileWrite {
+                    path: target_dir.join(t.name),
+                    outcome: WriteOutcome::Created,
+                    sha256: Some(FileOps::hash_content(t.content.as_bytes())),
+                    backup: None,
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_init_deploys_through_deployer() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = InitCommand {
+            agent: vec![AgentType::Claude],
+            output_directory: temp_dir.path().to_path_buf(),
+            project_name: None,
+            force: false,
+            on_conflict: None,
+            backup: false,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars: Vec::new(),
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
+        };
+
+        let deployer = RecordingDeployer::default();
+        let outcome = cmd.initialize_with(&deployer).unwrap().unwrap();
+
+        assert_eq!(
+            *deployer.calls.borrow(),
+            [(vec![Agent::Claude], temp_dir.path().to_path_buf())]
+        );
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+        assert!(outcome.config.tracked_file("CLAUDE.md").is_some());
+    }
 }
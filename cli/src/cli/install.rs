@@ -0,0 +1,216 @@
+use crate::cli::init::relative_path;
+use crate::config::{Package, TrackedFile};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{ConflictPolicy, ConflictResolver, FileOps, FileWrite, LOCK_FILE_NAME, WriteOutcome};
+use crate::templates::TemplateSystem;
+use crate::templates::diff::DiffStyle;
+use crate::templates::remote::{self, RemotePackage};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Download a template package and deploy its templates into the project
+#[derive(Args)]
+pub struct InstallCommand {
+    /// URL of a .tar.gz, .tar or .zip archive of templates (http://, https:// or file://)
+    #[arg(value_name = "URL")]
+    pub url: String,
+
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Package ID to record; defaults to the archive name without its extension
+    #[arg(long)]
+    pub id: Option<String>,
+
+    /// Version to record for the package
+    #[arg(long, value_name = "VERSION", default_value = "1.0.0")]
+    pub package_version: String,
+
+    /// Expected digest of the archive, e.g. sha256:<hex>
+    #[arg(long)]
+    pub checksum: Option<String>,
+
+    /// Note on what the package is for, shown by list-packages
+    #[arg(long, value_name = "TEXT")]
+    pub description: Option<String>,
+
+    /// Seconds to wait for the package server before giving up
+    #[arg(long, value_name = "SECONDS", default_value_t = remote::DEFAULT_TIMEOUT.as_secs())]
+    pub timeout: u64,
+
+    /// What to do with an existing file that the package also contains
+    #[arg(long, value_enum, default_value_t = ConflictPolicy::Prompt)]
+    pub on_conflict: ConflictPolicy,
+
+    /// Copy files to <name>.specforge-backup-<timestamp> before overwriting them
+    #[arg(long)]
+    pub backup: bool,
+
+    /// Preview changes to existing files as word diffs instead of line diffs
+    #[arg(long)]
+    pub word_diff: bool,
+}
+
+/// Build the package recorded for an archive URL
+///
+/// The ID defaults to the archive name, e.g. `team-prompts` for
+/// `https://example.com/team-prompts.tar.gz`.
+pub(crate) fn package_for_url(
+    url: &str,
+    id: Option<&str>,
+    version: &str,
+    checksum: Option<&str>,
+) -> Result<Package> {
+    let id = match id {
+        Some(id) => id.to_string(),
+        None => remote::package_id_from_url(url).ok_or_else(|| {
+            ConfigError::validation_error(format!(
+                "Cannot derive a package ID from '{}'; pass one with --id",
+                url
+            ))
+        })?,
+    };
+
+    let mut package = Package::with_url(id, url.to_string(), version)?;
+    if let Some(checksum) = checksum {
+        package = package.with_checksum(checksum);
+    }
+    package.validate()?;
+    Ok(package)
+}
+
+impl InstallCommand {
+    /// Execute the install command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let mut config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let mut package = package_for_url(
+            &self.url,
+            self.id.as_deref(),
+            &self.package_version,
+            self.checksum.as_deref(),
+        )
+        .map_err(|e| e.add_context("package validation", "Checking install parameters"))?;
+        if let Some(description) = &self.description {
+            package = package.with_description(description.trim());
+            package
+                .validate()
+                .map_err(|e| e.add_context("package validation", "Checking install parameters"))?;
+        }
+
+        // A locked package must match its pin, and its download is verified against it
+        let lock_dir = FileOps::lock_directory(&config_path);
+        let lock = FileOps::read_existing_lock_file(lock_dir)?;
+        if let Some(mismatch) = lock.as_ref().and_then(|lock| lock.package_mismatch(&package)) {
+            return Err(ConfigError::lock_mismatch(lock_dir.join(LOCK_FILE_NAME), vec![mismatch]));
+        }
+        let fetched = lock.as_ref().map_or_else(|| package.clone(), |lock| lock.pinned(&package));
+
+        status!("{} Downloading {} from {}...", Status::Info, package.id, self.url);
+        let remote = RemotePackage::fetch(&fetched, Duration::from_secs(self.timeout)).map_err(|e| {
+            e.add_context("package download", format!("Downloading {}", self.url))
+        })?;
+
+        // Reinstalling a package replaces its configured entry
+        let replaced = config.remove_package(&package.id).is_some();
+        config.add_package(package.clone())?;
+
+        let context = TemplateSystem::template_context(&config, &self.directory);
+        let mut resolver = ConflictResolver::new(self.on_conflict)
+            .with_backups(self.backup)
+            .with_diff_style(DiffStyle::from_word_diff(self.word_diff));
+        let deployed_files = remote.deploy(&self.directory, &config.agents, &context, &mut resolver).map_err(|e| {
+            e.add_context(
+                "template deployment",
+                format!("Deploying {} to {}", package.id, self.directory.display()),
+            )
+        })?;
+
+        for file in deployed_files.iter().filter(|f| f.written()) {
+            let sha256 = file.content_hash()?;
+            let mode = FileOps::file_mode(&file.path);
+            config.track_file(TrackedFile::new(relative_path(&self.directory, &file.path), sha256).with_mode(mode));
+        }
+        FileOps::write_config(&config, &config_path).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Recording package {} in {}", package.id, config_path.display()),
+            )
+        })?;
+        FileOps::refresh_lock_file(&config, &config_path, vec![remote.locked_package()])
+            .map_err(|e| e.add_context("lock file writing", format!("Updating {}", LOCK_FILE_NAME)))?;
+
+        if output::is_json() {
+            output::print_json(Self::json_summary(&package, &self.directory, &deployed_files));
+            return Ok(());
+        }
+
+        let written = deployed_files.iter().filter(|f| f.written()).count();
+        status!(
+            "{} {} {} {} ({} of {} files written)",
+            Status::Ok,
+            if replaced { "Reinstalled" } else { "Installed" },
+            package.id,
+            package.version,
+            written,
+            deployed_files.len()
+        );
+        for file in &deployed_files {
+            match file.outcome {
+                WriteOutcome::Skipped => status!("   • {} (skipped, already exists)", file.path.display()),
+                _ => status!("   • {}", file.path.display()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(package: &Package, project_dir: &Path, deployed_files: &[FileWrite]) -> serde_json::Value {
+        let files: Vec<_> = deployed_files
+            .iter()
+            .filter(|f| f.written())
+            .map(|f| relative_path(project_dir, &f.path))
+            .collect();
+        let backups: Vec<_> = deployed_files.iter().filter_map(|f| f.backup.as_ref()).collect();
+        serde_json::json!({
+            "id": package.id,
+            "url": package.url,
+            "version": package.version,
+            "description": package.description,
+            "files": files,
+            "backups": backups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_for_url() {
+        let package = package_for_url("https://example.com/team-prompts.tar.gz", None, "1.2.0", None).unwrap();
+        assert_eq!(package.id, "team-prompts");
+        assert_eq!(package.version, "1.2.0");
+        assert_eq!(package.url.as_deref(), Some("https://example.com/team-prompts.tar.gz"));
+
+        let package = package_for_url("https://example.com/p.zip", Some("custom"), "1.0.0", None).unwrap();
+        assert_eq!(package.id, "custom");
+
+        assert!(package_for_url("https://example.com/", None, "1.0.0", None).is_err());
+        assert!(package_for_url("ftp://example.com/p.zip", None, "1.0.0", None).is_err());
+        assert!(package_for_url("https://example.com/p.zip", None, "1.0", None).is_err());
+        assert!(package_for_url("https://example.com/p.zip", None, "1.0.0", Some("md5:abc")).is_err());
+    }
+}
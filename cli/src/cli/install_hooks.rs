@@ -0,0 +1,309 @@
+use crate::error::{ConfigError, Result};
+use crate::file_ops::gitattributes::{self, GITATTRIBUTES_FILE_NAME, MERGE_DRIVER_NAME};
+use crate::file_ops::{ConflictPolicy, ConflictResolver};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Install a Git pre-commit hook that validates the Specforge configuration
+#[derive(Args)]
+pub struct InstallHooksCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub output_directory: PathBuf,
+
+    /// What to do with an existing pre-commit hook
+    #[arg(long, value_enum, default_value_t = ConflictPolicy::Prompt)]
+    pub on_conflict: ConflictPolicy,
+
+    /// Also declare LF line endings and the structural merge driver for the
+    /// configuration and lock file in .gitattributes, and register the driver
+    /// in the repository's Git configuration
+    #[arg(long)]
+    pub attributes: bool,
+}
+
+/// Git repository a project directory belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitRepository {
+    /// Directory containing `.git`
+    pub work_tree: PathBuf,
+    /// The `.git` directory itself, or the directory a `.git` file points at
+    pub git_dir: PathBuf,
+}
+
+impl GitRepository {
+    /// Find the repository containing `directory` by walking up the directory tree
+    ///
+    /// A `.git` file, as used by worktrees and submodules, is followed to the
+    /// directory named in its `gitdir:` line.
+    pub fn discover(directory: &Path) -> Result<Self> {
+        let start = fs::canonicalize(directory).map_err(|e| {
+            ConfigError::io_error(format!("Failed to resolve '{}': {}", directory.display(), e))
+        })?;
+
+        for work_tree in start.ancestors() {
+            let dot_git = work_tree.join(".git");
+            if dot_git.is_dir() {
+                return Ok(Self { work_tree: work_tree.to_path_buf(), git_dir: dot_git });
+            }
+            if dot_git.is_file() {
+                let content = fs::read_to_string(&dot_git).map_err(ConfigError::from)?;
+                let git_dir = content
+                    .lines()
+                    .find_map(|line| line.strip_prefix("gitdir:"))
+                    .map(|path| work_tree.join(path.trim()))
+                    .ok_or_else(|| {
+                        ConfigError::git_repository_required(
+                            work_tree,
+                            format!("'{}' does not name a Git directory", dot_git.display()),
+                        )
+                    })?;
+                return Ok(Self { work_tree: work_tree.to_path_buf(), git_dir });
+            }
+        }
+
+        Err(ConfigError::git_repository_required(
+            &start,
+            format!("'{}' is not inside a Git repository", start.display()),
+        ))
+    }
+
+    /// Path of a hook script in this repository
+    pub fn hook_path(&self, name: &str) -> PathBuf {
+        self.git_dir.join("hooks").join(name)
+    }
+
+    /// Path of the repository's Git configuration
+    ///
+    /// Worktrees share the configuration of the main repository, whose
+    /// directory their `commondir` file names.
+    pub fn config_path(&self) -> PathBuf {
+        let common_dir = fs::read_to_string(self.git_dir.join("commondir"))
+            .map(|path| self.git_dir.join(path.trim()))
+            .unwrap_or_else(|_| self.git_dir.clone());
+        common_dir.join("config")
+    }
+
+    /// Register the `specforge merge-driver` command as the merge driver the
+    /// managed `.gitattributes` block names
+    ///
+    /// Returns whether the configuration changed; a driver registered before
+    /// is left as it is.
+    pub fn register_merge_driver(&self) -> Result<bool> {
+        let config_path = self.config_path();
+        let content = match fs::read_to_string(&config_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(ConfigError::from(e)),
+        };
+        let section = format!("[merge \"{}\"]", MERGE_DRIVER_NAME);
+        if content.lines().any(|line| line.trim() == section) {
+            return Ok(false);
+        }
+
+        let mut updated = content;
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str(&format!(
+            "{}\n\tname = Structural merge of Specforge configurations\n\tdriver = specforge merge-driver %O %A %B\n",
+            section
+        ));
+        fs::write(&config_path, updated).map_err(ConfigError::from)?;
+        Ok(true)
+    }
+}
+
+/// Pre-commit hook script validating the configuration in `project_dir`
+///
+/// Hooks run from the top of the work tree, so `project_dir` is relative to it.
+/// Commits are not blocked when specforge is not installed.
+pub fn pre_commit_script(project_dir: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+# Installed by `specforge install-hooks`: keeps the Specforge configuration valid
+SPECFORGE=$(which specforge 2>/dev/null)
+if [ -z "$SPECFORGE" ]; then
+    echo "specforge not found on PATH; skipping configuration validation" >&2
+    exit 0
+fi
+exec "$SPECFORGE" validate --directory '{}'
+"#,
+        project_dir.replace('\'', "'\\''")
+    )
+}
+
+impl InstallHooksCommand {
+    /// Execute the install-hooks command
+    pub fn execute(&self) -> Result<()> {
+        let repository = GitRepository::discover(&self.output_directory)
+            .map_err(|e| e.add_context("repository detection", "Looking for the .git directory"))?;
+        let project_dir = fs::canonicalize(&self.output_directory).map_err(ConfigError::from)?;
+
+        let hook_path = repository.hook_path("pre-commit");
+        let installed = self.install_pre_commit(&repository, &project_dir, &hook_path)?;
+        if !self.attributes {
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "hook": hook_path, "installed": installed }));
+            }
+            return Ok(());
+        }
+
+        let attributes_path = project_dir.join(GITATTRIBUTES_FILE_NAME);
+        let attributes_changed = gitattributes::write_managed_block(&attributes_path, &gitattributes::managed_lines())
+            .map_err(|e| e.add_context("attribute installation", format!("Writing {}", attributes_path.display())))?;
+        let config_path = repository.config_path();
+        let driver_registered = repository.register_merge_driver().map_err(|e| {
+            e.add_context("merge driver registration", format!("Writing {}", config_path.display()))
+        })?;
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "hook": hook_path,
+                "installed": installed,
+                "attributes": attributes_path,
+                "git_config": config_path,
+            }));
+            return Ok(());
+        }
+        if attributes_changed {
+            status!("{} Declared line endings and the merge driver in {}", Status::Ok, attributes_path.display());
+        } else {
+            status!("{} {} is already up to date", Status::Info, attributes_path.display());
+        }
+        if driver_registered {
+            status!("{} Registered the {} merge driver in {}", Status::Ok, MERGE_DRIVER_NAME, config_path.display());
+            status!("   Merges now combine configuration changes with `specforge merge-driver`");
+        }
+        Ok(())
+    }
+
+    /// Install the pre-commit hook, returning whether it was written
+    fn install_pre_commit(&self, repository: &GitRepository, project_dir: &Path, hook_path: &Path) -> Result<bool> {
+        let relative = crate::cli::init::relative_path(&repository.work_tree, project_dir);
+        let script = pre_commit_script(if relative.is_empty() { "." } else { &relative });
+
+        if hook_path.exists() {
+            let existing = fs::read_to_string(hook_path).unwrap_or_default();
+            if existing == script {
+                status!("{} {} is already installed", Status::Info, hook_path.display());
+                return Ok(false);
+            }
+            let mut resolver = ConflictResolver::new(self.on_conflict);
+            if !resolver.allow_overwrite(hook_path)? {
+                status!("{} Keeping existing hook: {}", Status::Info, hook_path.display());
+                return Ok(false);
+            }
+        }
+
+        Self::write_hook(hook_path, &script).map_err(|e| {
+            e.add_context("hook installation", format!("Writing {}", hook_path.display()))
+        })?;
+        status!("{} Installed pre-commit hook at {}", Status::Ok, hook_path.display());
+        status!("   Commits now run `specforge validate` first");
+        Ok(true)
+    }
+
+    /// Write a hook script and make it executable
+    fn write_hook(hook_path: &Path, script: &str) -> Result<()> {
+        if let Some(hooks_dir) = hook_path.parent() {
+            fs::create_dir_all(hooks_dir).map_err(ConfigError::from)?;
+        }
+        fs::write(hook_path, script).map_err(ConfigError::from)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(hook_path, fs::Permissions::from_mode(0o755)).map_err(ConfigError::from)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_walks_up_to_git_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp_dir.path()).unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("app/src")).unwrap();
+
+        let repository = GitRepository::discover(&root.join("app/src")).unwrap();
+        assert_eq!(repository.work_tree, root);
+        assert_eq!(repository.hook_path("pre-commit"), root.join(".git/hooks/pre-commit"));
+    }
+
+    #[test]
+    fn test_discover_follows_git_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp_dir.path()).unwrap();
+        fs::write(root.join(".git"), "gitdir: ../main/.git/worktrees/feature\n").unwrap();
+
+        let repository = GitRepository::discover(&root).unwrap();
+        assert_eq!(repository.git_dir, root.join("../main/.git/worktrees/feature"));
+
+        fs::write(root.join(".git"), "not a git file\n").unwrap();
+        let error = GitRepository::discover(&root).unwrap_err();
+        assert_eq!(error.code(), "git_repository_required");
+        assert!(error.to_string().contains("does not name a Git directory"), "{}", error);
+        assert!(error.to_string().contains("git init"), "{}", error);
+    }
+
+    #[test]
+    fn test_pre_commit_script() {
+        let script = pre_commit_script("packages/it's");
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("which specforge"));
+        assert!(script.contains("validate --directory 'packages/it'\\''s'"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_hook_is_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        let cmd = InstallHooksCommand {
+            output_directory: temp_dir.path().to_path_buf(),
+            on_conflict: ConflictPolicy::Skip,
+            attributes: false,
+        };
+        cmd.execute().unwrap();
+
+        let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
+        let mode = fs::metadata(&hook_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert!(fs::read_to_string(&hook_path).unwrap().contains("--directory '.'"));
+
+        // An unrelated hook is kept when skipping conflicts
+        fs::write(&hook_path, "#!/bin/sh\nexit 0\n").unwrap();
+        cmd.execute().unwrap();
+        assert_eq!(fs::read_to_string(&hook_path).unwrap(), "#!/bin/sh\nexit 0\n");
+    }
+
+    #[test]
+    fn test_register_merge_driver_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp_dir.path()).unwrap();
+        fs::create_dir_all(root.join(".git/worktrees/feature")).unwrap();
+        fs::write(root.join(".git/config"), "[core]\n\tbare = false").unwrap();
+        fs::write(root.join(".git/worktrees/feature/commondir"), "../..\n").unwrap();
+        let worktree = GitRepository { work_tree: root.join("feature"), git_dir: root.join(".git/worktrees/feature") };
+
+        assert!(worktree.register_merge_driver().unwrap());
+        assert!(!worktree.register_merge_driver().unwrap());
+        assert_eq!(
+            fs::read_to_string(root.join(".git/config")).unwrap(),
+            "[core]\n\tbare = false\n[merge \"specforge-json\"]\n\tname = Structural merge of Specforge configurations\n\tdriver = specforge merge-driver %O %A %B\n"
+        );
+    }
+}
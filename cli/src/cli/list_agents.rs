@@ -0,0 +1,129 @@
+use crate::config::Agent;
+use crate::error::Result;
+use crate::output;
+use crate::status;
+use crate::templates::TemplateSystem;
+use clap::Args;
+
+/// List the supported AI agents with the package and template files init deploys for them
+/// and the size limits of their instructions files
+#[derive(Args)]
+pub struct ListAgentsCommand {
+    /// Only show this agent; unknown names fail as `init --agent` does
+    #[arg(short, long, value_name = "NAME")]
+    pub agent: Option<String>,
+}
+
+impl ListAgentsCommand {
+    /// Execute the list-agents command
+    ///
+    /// Agents come from [`Agent::all`], the list the init prompt offers.
+    pub fn execute(&self) -> Result<()> {
+        let agents = match &self.agent {
+            Some(name) => vec![name.parse::<Agent>()?],
+            None => Agent::all(),
+        };
+
+        if output::is_json() {
+            output::print_json(Self::json_summary(&agents));
+            return Ok(());
+        }
+        for line in format_table(&agents) {
+            status!("{}", line);
+        }
+        Ok(())
+    }
+
+    /// Machine-readable agent list printed to stdout in JSON output mode
+    fn json_summary(agents: &[Agent]) -> serde_json::Value {
+        let agents: Vec<_> = agents
+            .iter()
+            .map(|agent| {
+                serde_json::json!({
+                    "name": agent.to_string(),
+                    "description": agent.description(),
+                    "package_id": agent.default_package_id(),
+                    "templates": TemplateSystem::list_template_files(agent),
+                    "instructions_size_limits": agent.instructions_size_limits(),
+                })
+            })
+            .collect();
+        serde_json::json!({ "agents": agents })
+    }
+}
+
+/// Lay out agents as an aligned table with a header row
+fn format_table(agents: &[Agent]) -> Vec<String> {
+    let header = ["AGENT", "PACKAGE", "TEMPLATES", "SIZE LIMITS", "DESCRIPTION"];
+    let rows: Vec<[String; 5]> = agents
+        .iter()
+        .map(|agent| {
+            let limits = agent.instructions_size_limits();
+            [
+                agent.to_string(),
+                agent.default_package_id().to_string(),
+                TemplateSystem::list_template_files(agent).join(", "),
+                format!("{}/{} B", limits.soft, limits.hard),
+                agent.description().to_string(),
+            ]
+        })
+        .collect();
+
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .chain(std::iter::once(header[column].len()))
+            .max()
+            .unwrap_or(0)
+    };
+    let (agent_width, package_width, templates_width, limits_width) = (width(0), width(1), width(2), width(3));
+
+    std::iter::once(header.map(str::to_string))
+        .chain(rows)
+        .map(|[agent, package, templates, limits, description]| {
+            format!(
+                "{:<agent_width$}  {:<package_width$}  {:<templates_width$}  {:<limits_width$}  {}",
+                agent, package, templates, limits, description
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ConfigError;
+
+    #[test]
+    fn test_format_table_lists_every_agent() {
+        let lines = format_table(&Agent::all());
+
+        assert_eq!(lines.len(), Agent::all().len() + 1);
+        assert!(lines[0].starts_with("AGENT     PACKAGE"));
+        assert!(lines[2].starts_with("claude    specforge-claude-templates    CLAUDE.md, README.md"));
+        assert!(lines[3].contains("codeium.json"));
+        assert!(lines[4].contains(".continue/config.json"));
+        assert!(lines[5].starts_with("amazonq   specforge-amazonq-templates"));
+    }
+
+    #[test]
+    fn test_json_summary() {
+        let summary = ListAgentsCommand::json_summary(&[Agent::Codeium]);
+        let summary = &summary["agents"];
+
+        assert_eq!(summary[0]["name"], "codeium");
+        assert_eq!(summary[0]["package_id"], "specforge-codeium-templates");
+        assert_eq!(summary[0]["description"], Agent::Codeium.description());
+        assert_eq!(summary[0]["templates"], serde_json::json!(TemplateSystem::list_template_files(&Agent::Codeium)));
+        assert_eq!(summary[0]["instructions_size_limits"], serde_json::json!({"soft": 6000, "hard": 12000}));
+    }
+
+    #[test]
+    fn test_unknown_agent_filter() {
+        let command = ListAgentsCommand { agent: Some("gemini".to_string()) };
+
+        assert!(matches!(command.execute(), Err(ConfigError::InvalidAgent(_))));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::config::{Package, ProjectConfig};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::format;
+use crate::output;
+use crate::status;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Columns the description is wrapped to in the package table
+const DESCRIPTION_WIDTH: usize = 60;
+
+/// List the packages in the project configuration with their descriptions
+#[derive(Args)]
+pub struct ListPackagesCommand {
+    /// Show everything recorded for this package, including its full description
+    #[arg(value_name = "ID")]
+    pub id: Option<String>,
+
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+}
+
+impl ListPackagesCommand {
+    /// Execute the list-packages command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context("configuration loading", format!("Reading {}", config_path.display()))
+        })?;
+
+        let Some(id) = &self.id else {
+            let mut packages: Vec<&Package> = config.packages.iter().collect();
+            packages.sort_by(|a, b| format::listing_cmp(&a.id, &b.id));
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "packages": packages }));
+                return Ok(());
+            }
+            for line in format_table(&packages) {
+                status!("{}", line);
+            }
+            return Ok(());
+        };
+
+        let package = find_package(&config, id)?;
+        if output::is_json() {
+            output::print_json(serde_json::json!(package));
+            return Ok(());
+        }
+        for line in format_info(package) {
+            status!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+/// Package with the given ID, or an error naming the configured ones
+fn find_package<'a>(config: &'a ProjectConfig, id: &str) -> Result<&'a Package> {
+    config.get_package(id).ok_or_else(|| {
+        let configured: Vec<&str> = config.packages.iter().map(|p| p.id.as_str()).collect();
+        ConfigError::invalid_package(format!(
+            "No package '{}' in the configuration (configured: {})",
+            id,
+            configured.join(", ")
+        ))
+    })
+}
+
+/// Lay out packages as an aligned table with a header row, wrapping
+/// descriptions onto continuation lines
+fn format_table(packages: &[&Package]) -> Vec<String> {
+    let header = ["ID", "VERSION", "DESCRIPTION"];
+    let rows: Vec<[String; 3]> = packages
+        .iter()
+        .map(|package| {
+            [
+                package.id.clone(),
+                package.version.to_string(),
+                package.description.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| format::display_width(&row[column]))
+            .chain(std::iter::once(header[column].len()))
+            .max()
+            .unwrap_or(0)
+    };
+    let (id_width, version_width) = (width(0), width(1));
+
+    std::iter::once(header.map(str::to_string))
+        .chain(rows)
+        .flat_map(|[id, version, description]| {
+            format::wrap(&description, DESCRIPTION_WIDTH)
+                .into_iter()
+                .enumerate()
+                .map(|(index, description)| {
+                    let (id, version) = if index == 0 { (id.as_str(), version.as_str()) } else { ("", "") };
+                    format!(
+                        "{}  {}  {}",
+                        format::pad(id, id_width),
+                        format::pad(version, version_width),
+                        description
+                    )
+                    .trim_end()
+                    .to_string()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Everything recorded for one package, one field per line
+fn format_info(package: &Package) -> Vec<String> {
+    let mut lines = vec![
+        format!("ID: {}", package.id),
+        format!("Version: {}", package.version),
+    ];
+    if let Some(url) = &package.url {
+        lines.push(format!("URL: {}", url));
+    }
+    if let Some(condition) = &package.when {
+        lines.push(format!("When: {}", condition));
+    }
+    if let Some(checksum) = &package.checksum {
+        lines.push(format!("Checksum: {}", checksum));
+    }
+    if !package.dependencies.is_empty() {
+        lines.push(format!("Dependencies: {}", package.dependencies.join(", ")));
+    }
+    if let Some(description) = &package.description {
+        lines.push(format!("Description: {}", description));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Agent;
+
+    #[test]
+    fn test_format_table_wraps_descriptions() {
+        let described = Package::new("internal-compliance-prompts", "1.0.0")
+            .unwrap()
+            .with_description(format!("Added for the 2025 audit: {}", "x".repeat(70)));
+        let plain = Package::new("漢字-prompts", "0.2.0").unwrap();
+        let lines = format_table(&[&described, &plain]);
+
+        assert_eq!(lines[0], "ID                           VERSION  DESCRIPTION");
+        assert_eq!(lines[1], "internal-compliance-prompts  1.0.0    Added for the 2025 audit:");
+        assert_eq!(lines[2], format!("{}{}", " ".repeat(38), "x".repeat(60)));
+        assert_eq!(lines[3], format!("{}{}", " ".repeat(38), "x".repeat(10)));
+        // Wide characters count double when aligning columns
+        assert_eq!(lines[4], "漢字-prompts                 0.2.0");
+    }
+
+    #[test]
+    fn test_format_info_shows_full_description() {
+        let description = "d".repeat(200);
+        let package = Package::new("team-prompts", "1.0.0")
+            .unwrap()
+            .with_dependencies(["base"])
+            .with_description(description.clone());
+
+        let lines = format_info(&package);
+        assert_eq!(lines[0], "ID: team-prompts");
+        assert!(lines.contains(&"Dependencies: base".to_string()));
+        assert_eq!(lines.last().unwrap(), &format!("Description: {}", description));
+    }
+
+    #[test]
+    fn test_find_package() {
+        let config = ProjectConfig::new(Agent::Claude);
+        let error = find_package(&config, "missing").unwrap_err();
+        assert!(error.to_string().contains("No package 'missing'"), "{}", error);
+    }
+}
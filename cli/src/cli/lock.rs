@@ -0,0 +1,170 @@
+use crate::config::LockFile;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, LOCK_FILE_NAME};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Pin the project's packages to their current versions in specforge.lock
+#[derive(Args)]
+pub struct LockCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+}
+
+/// Remove packages from specforge.lock, or the whole lock file
+#[derive(Args)]
+pub struct UnlockCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Only unlock these packages (repeatable); without it the lock file is deleted
+    #[arg(short, long)]
+    pub package: Vec<String>,
+}
+
+impl LockCommand {
+    /// Execute the lock command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let lock = LockFile::from_config(&config);
+        let lock_dir = FileOps::lock_directory(&config_path);
+        let lock_path = FileOps::write_lock_file(&lock, lock_dir)
+            .map_err(|e| e.add_context("lock file writing", format!("Writing {}", LOCK_FILE_NAME)))?;
+
+        status!("📄 Locked packages:");
+        for package in &lock.packages {
+            status!("   • {} {}", package.id, package.resolved_version);
+        }
+        status!();
+        status!("{} Wrote {}", Status::Ok, lock_path.display());
+
+        if output::is_json() {
+            output::print_json(serde_json::to_value(&lock)?);
+        }
+        Ok(())
+    }
+}
+
+impl UnlockCommand {
+    /// Execute the unlock command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let lock_dir = FileOps::lock_directory(&config_path);
+        let lock_path = lock_dir.join(LOCK_FILE_NAME);
+
+        if self.package.is_empty() {
+            if !lock_path.exists() {
+                status!("{} No {} found; nothing to unlock", Status::Info, LOCK_FILE_NAME);
+                return Ok(());
+            }
+            fs::remove_file(&lock_path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&lock_path),
+                _ => ConfigError::from(e),
+            })?;
+            status!("{} Removed {}", Status::Ok, lock_path.display());
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "removed": lock_path, "unlocked": [] }));
+            }
+            return Ok(());
+        }
+
+        let mut lock = FileOps::read_lock_file(lock_dir)?;
+        for id in &self.package {
+            if lock.get(id).is_none() {
+                return Err(ConfigError::validation_error(format!(
+                    "Package '{}' is not locked in {}",
+                    id,
+                    lock_path.display()
+                )));
+            }
+        }
+        for id in &self.package {
+            lock.remove(id);
+            status!("   - {}", id);
+        }
+        FileOps::write_lock_file(&lock, lock_dir)?;
+        status!("{} Unlocked {} package(s) in {}", Status::Ok, self.package.len(), lock_path.display());
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "removed": null, "unlocked": self.package }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, Package, ProjectConfig};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_project(dir: &Path) {
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config
+            .add_package(Package::new(Agent::Claude.default_package_id(), "0.1.0").unwrap())
+            .unwrap();
+        config.add_package(Package::new("team-prompts", "2.0.0").unwrap()).unwrap();
+        FileOps::write_config_to_directory(&config, dir).unwrap();
+    }
+
+    #[test]
+    fn test_lock_writes_lock_file() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path());
+
+        LockCommand { directory: temp_dir.path().to_path_buf() }.execute().unwrap();
+
+        let lock = FileOps::read_lock_file(temp_dir.path()).unwrap();
+        assert_eq!(lock.packages.len(), 2);
+        assert_eq!(lock.get("team-prompts").unwrap().resolved_version, "2.0.0");
+    }
+
+    #[test]
+    fn test_unlock_single_package() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path());
+        LockCommand { directory: temp_dir.path().to_path_buf() }.execute().unwrap();
+
+        let unlock = |package: &str| UnlockCommand {
+            directory: temp_dir.path().to_path_buf(),
+            package: vec![package.to_string()],
+        };
+        unlock("team-prompts").execute().unwrap();
+        assert!(unlock("unknown").execute().is_err());
+
+        let lock = FileOps::read_lock_file(temp_dir.path()).unwrap();
+        assert!(lock.get("team-prompts").is_none());
+        assert!(lock.get(Agent::Claude.default_package_id()).is_some());
+    }
+
+    #[test]
+    fn test_unlock_removes_lock_file() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path());
+        LockCommand { directory: temp_dir.path().to_path_buf() }.execute().unwrap();
+
+        let unlock = UnlockCommand {
+            directory: temp_dir.path().to_path_buf(),
+            package: Vec::new(),
+        };
+        unlock.execute().unwrap();
+        assert!(!temp_dir.path().join(LOCK_FILE_NAME).exists());
+
+        // Nothing left to remove is not an error
+        unlock.execute().unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+use crate::config::{LockFile, merge_configs, merge_documents};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Merge two versions of a configuration or lock file for Git
+///
+/// Registered by `specforge install-hooks --attributes` and run by Git as
+/// `specforge merge-driver %O %A %B`.
+#[derive(Args)]
+pub struct MergeDriverCommand {
+    /// Common ancestor of both versions; empty when both sides added the file
+    #[arg(value_name = "BASE")]
+    pub base: PathBuf,
+
+    /// Our version, replaced with the merge result
+    #[arg(value_name = "OURS")]
+    pub ours: PathBuf,
+
+    /// Their version
+    #[arg(value_name = "THEIRS")]
+    pub theirs: PathBuf,
+}
+
+impl MergeDriverCommand {
+    /// Execute the merge-driver command
+    ///
+    /// When the versions cannot be merged, `ours` is replaced with both
+    /// versions between conflict markers and the command fails, which Git
+    /// reports as a merge conflict.
+    pub fn execute(&self) -> Result<()> {
+        let base = fs::read_to_string(&self.base).map_err(ConfigError::from)?;
+        let ours = fs::read_to_string(&self.ours).map_err(ConfigError::from)?;
+        let theirs = fs::read_to_string(&self.theirs).map_err(ConfigError::from)?;
+
+        match self.merge(&base, &ours, &theirs) {
+            Ok(()) => {
+                if output::is_json() {
+                    output::print_json(serde_json::json!({ "merged": self.ours }));
+                } else {
+                    status!("{} Merged {}", Status::Ok, self.ours.display());
+                }
+                Ok(())
+            }
+            Err(error) => {
+                fs::write(&self.ours, conflict_markers(&ours, &theirs)).map_err(ConfigError::from)?;
+                Err(error.add_context("structural merge", "Both versions were left between conflict markers"))
+            }
+        }
+    }
+
+    /// Merge the documents and write the result to `ours`
+    fn merge(&self, base: &str, ours: &str, theirs: &str) -> Result<()> {
+        let base = if base.trim().is_empty() { serde_json::Value::Null } else { serde_json::from_str(base)? };
+        let ours: serde_json::Value = serde_json::from_str(ours)?;
+        let theirs: serde_json::Value = serde_json::from_str(theirs)?;
+
+        // Lock files have no agents, so they are merged as plain documents
+        if ours.get("agent").is_some() || theirs.get("agent").is_some() {
+            let config = merge_configs(&base, &ours, &theirs)?;
+            return FileOps::write_config(&config, &self.ours);
+        }
+        let merged = merge_documents(&base, &ours, &theirs)?;
+        let mut lock = LockFile::from_json_string(&merged.to_string())?;
+        lock.packages.sort_by(|a, b| a.id.cmp(&b.id));
+        fs::write(&self.ours, lock.to_json_string()?).map_err(ConfigError::from)
+    }
+}
+
+/// Both versions of a file between Git's conflict markers
+fn conflict_markers(ours: &str, theirs: &str) -> String {
+    let line = |text: &str| if text.ends_with('\n') { text.to_string() } else { format!("{}\n", text) };
+    format!("<<<<<<< ours\n{}=======\n{}>>>>>>> theirs\n", line(ours), line(theirs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn command(dir: &Path) -> MergeDriverCommand {
+        MergeDriverCommand {
+            base: dir.join("base"),
+            ours: dir.join("ours"),
+            theirs: dir.join("theirs"),
+        }
+    }
+
+    #[test]
+    fn test_conflicting_agents_leave_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = command(temp_dir.path());
+        fs::write(&cmd.base, r#"{"schema_version": 3, "agent": "claude", "packages": [], "files": []}"#).unwrap();
+        fs::write(&cmd.ours, r#"{"schema_version": 3, "agent": "copilot", "packages": [], "files": []}"#).unwrap();
+        fs::write(&cmd.theirs, r#"{"schema_version": 3, "agent": "codeium", "packages": [], "files": []}"#).unwrap();
+
+        let error = cmd.execute().unwrap_err();
+        assert!(error.to_string().contains("Both sides changed 'agent'"), "{}", error);
+        let result = fs::read_to_string(&cmd.ours).unwrap();
+        assert!(result.starts_with("<<<<<<< ours\n{\"schema_version\": 3, \"agent\": \"copilot\""), "{}", result);
+        assert!(result.ends_with("\"codeium\", \"packages\": [], \"files\": []}\n>>>>>>> theirs\n"), "{}", result);
+    }
+
+    #[test]
+    fn test_merges_lock_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = command(temp_dir.path());
+        fs::write(&cmd.base, "").unwrap();
+        fs::write(&cmd.ours, r#"{"version": 1, "packages": [{"id": "b", "resolved_version": "1.0.0"}]}"#).unwrap();
+        fs::write(&cmd.theirs, r#"{"version": 1, "packages": [{"id": "a", "resolved_version": "2.0.0"}]}"#).unwrap();
+
+        cmd.execute().unwrap();
+        let lock = LockFile::from_json_string(&fs::read_to_string(&cmd.ours).unwrap()).unwrap();
+        let ids: Vec<_> = lock.packages.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+}
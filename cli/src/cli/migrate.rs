@@ -0,0 +1,163 @@
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, ProjectPaths};
+use crate::output;
+use crate::prompt;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Convert a .reforge.json from reforge releases into a .specforge.json
+#[derive(Args)]
+pub struct MigrateCommand {
+    /// Project directory containing the .reforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Overwrite an existing specforge configuration
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Delete .reforge.json after migrating instead of asking
+    #[arg(long, conflicts_with = "keep_legacy")]
+    pub delete_legacy: bool,
+
+    /// Keep .reforge.json after migrating instead of asking
+    #[arg(long)]
+    pub keep_legacy: bool,
+}
+
+impl MigrateCommand {
+    /// Execute the migrate command
+    pub fn execute(&self) -> Result<()> {
+        let legacy_path = ProjectPaths::new(&self.directory).legacy_config_file();
+        if !legacy_path.is_file() {
+            return Err(ConfigError::not_found(&legacy_path));
+        }
+        if FileOps::config_exists_in_directory(&self.directory) && !self.force {
+            return Err(ConfigError::file_exists(FileOps::locate_config(&self.directory)));
+        }
+
+        let config_path = Self::migrate(&self.directory)?;
+        let removed = self.should_delete_legacy()?;
+        if removed {
+            fs::remove_file(&legacy_path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&legacy_path),
+                _ => ConfigError::from(e),
+            })?;
+            status!("{} Removed {}", Status::Ok, legacy_path.display());
+        } else {
+            status!(
+                "{} Kept {}; delete it once you no longer need it",
+                Status::Info,
+                legacy_path.display()
+            );
+        }
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "config_path": config_path,
+                "migrated_from": legacy_path,
+                "legacy_removed": removed,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Convert the .reforge.json in a directory and write it as .specforge.json
+    pub fn migrate(directory: &Path) -> Result<PathBuf> {
+        let legacy_path = ProjectPaths::new(directory).legacy_config_file();
+        let config = FileOps::read_legacy_config(directory).map_err(|e| {
+            e.add_context(
+                "legacy configuration loading",
+                format!("Reading {}", legacy_path.display()),
+            )
+        })?;
+        let config_path = FileOps::write_config_to_directory(&config, directory).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Writing the migrated configuration to {}", directory.display()),
+            )
+        })?;
+
+        status!(
+            "{} Migrated {} to {}",
+            Status::Ok,
+            legacy_path.display(),
+            config_path.display()
+        );
+        Ok(config_path)
+    }
+
+    /// Whether to delete the legacy file, asking when neither flag was given
+    fn should_delete_legacy(&self) -> Result<bool> {
+        if self.delete_legacy || self.keep_legacy {
+            return Ok(self.delete_legacy);
+        }
+        if !prompt::is_interactive() {
+            return Ok(false);
+        }
+        FileOps::confirm_action(&format!("Delete {}?", ProjectPaths::legacy_config_file_name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const LEGACY_CONFIG: &str = r#"{
+        "agent": "claude",
+        "packages": [{"id": "reforge-claude-templates", "url": null, "version": "0.1.0"}],
+        "metadata": {"created_at": "2025-01-01T00:00:00Z"}
+    }"#;
+
+    fn command(dir: &Path, delete_legacy: bool) -> MigrateCommand {
+        MigrateCommand {
+            directory: dir.to_path_buf(),
+            force: false,
+            delete_legacy,
+            keep_legacy: !delete_legacy,
+        }
+    }
+
+    #[test]
+    fn test_migrate_writes_specforge_config() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(ProjectPaths::new(temp_dir.path()).legacy_config_file(), LEGACY_CONFIG).unwrap();
+
+        command(temp_dir.path(), true).execute().unwrap();
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert!(config.get_package("specforge-claude-templates").is_some());
+        assert!(!ProjectPaths::new(temp_dir.path()).legacy_config_file().exists());
+    }
+
+    #[test]
+    fn test_migrate_keeps_legacy_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(ProjectPaths::new(temp_dir.path()).legacy_config_file(), LEGACY_CONFIG).unwrap();
+
+        command(temp_dir.path(), false).execute().unwrap();
+        assert!(ProjectPaths::new(temp_dir.path()).legacy_config_file().exists());
+    }
+
+    #[test]
+    fn test_migrate_does_not_overwrite_existing_config() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(ProjectPaths::new(temp_dir.path()).legacy_config_file(), LEGACY_CONFIG).unwrap();
+        fs::write(temp_dir.path().join(".specforge.json"), "{}").unwrap();
+
+        let result = command(temp_dir.path(), false).execute();
+        assert!(matches!(result, Err(ConfigError::FileExists(_))));
+        assert_eq!(fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_migrate_requires_legacy_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = command(temp_dir.path(), false).execute();
+        assert!(matches!(result, Err(ConfigError::NotFound(_))));
+    }
+}
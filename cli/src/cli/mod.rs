@@ -1,3 +1,77 @@
+pub mod clean;
+pub mod completions;
+pub mod config;
+pub mod deinit;
+pub mod diff;
+#[cfg(feature = "dev-tools")]
+pub mod dist;
+pub mod doctor;
+pub mod env;
+pub mod export;
+pub mod fmt;
+pub mod generate_example;
 pub mod init;
+#[cfg(feature = "remote")]
+pub mod install;
+pub mod install_hooks;
+pub mod list_agents;
+pub mod list_packages;
+pub mod lock;
+pub mod merge_driver;
+pub mod migrate;
+pub mod preview_templates;
+pub mod prune_metadata;
+pub mod rename_project;
+pub mod restore_backups;
+pub mod rollback;
+pub mod schema;
+#[cfg(feature = "remote")]
+pub mod search_packages;
+pub mod show;
+pub mod switch_agent;
+pub mod templates;
+pub mod upgrade;
+pub mod user_config;
+pub mod validate;
+pub mod verify;
+pub mod version;
+pub mod workspace;
 
-pub use init::{InitCommand, AgentType};
\ No newline at end of file
+pub use clean::CleanCommand;
+pub use completions::CompletionsCommand;
+pub use config::{ConfigCommand, ConfigSubcommand};
+pub use deinit::DeinitCommand;
+pub use diff::DiffCommand;
+#[cfg(feature = "dev-tools")]
+pub use dist::DistCommand;
+pub use doctor::{DiagnosticResult, DoctorCommand};
+pub use env::{EnvCommand, ResolvedEnvironment, ShellSyntax};
+pub use export::{ExportCommand, ExportFormat};
+pub use fmt::FmtCommand;
+pub use generate_example::{ExampleFeatures, GenerateExampleCommand};
+pub use init::{InitCommand, AgentType};
+#[cfg(feature = "remote")]
+pub use install::InstallCommand;
+pub use install_hooks::{GitRepository, InstallHooksCommand};
+pub use list_agents::ListAgentsCommand;
+pub use list_packages::ListPackagesCommand;
+pub use lock::{LockCommand, UnlockCommand};
+pub use merge_driver::MergeDriverCommand;
+pub use migrate::MigrateCommand;
+pub use preview_templates::PreviewTemplatesCommand;
+pub use prune_metadata::PruneMetadataCommand;
+pub use rename_project::RenameProjectCommand;
+pub use restore_backups::RestoreBackupsCommand;
+pub use rollback::RollbackCommand;
+pub use schema::SchemaCommand;
+#[cfg(feature = "remote")]
+pub use search_packages::SearchPackagesCommand;
+pub use show::ShowCommand;
+pub use switch_agent::SwitchAgentCommand;
+pub use templates::{TemplatesChangelogCommand, TemplatesCommand, TemplatesSubcommand};
+pub use upgrade::UpgradeCommand;
+pub use user_config::{ConfigGetCommand, ConfigListCommand, ConfigSetCommand};
+pub use validate::ValidateCommand;
+pub use verify::VerifyCommand;
+pub use version::VersionCommand;
+pub use workspace::{WorkspaceCommand, WorkspaceInitCommand, WorkspaceSubcommand};
@@ -0,0 +1,99 @@
+use crate::cli::init::AgentType;
+use crate::config::Agent;
+use crate::error::Result;
+use crate::output;
+use crate::templates::{ResolvedTemplate, TemplateSystem};
+use clap::Args;
+
+/// Print the template files for the given agents without deploying them
+#[derive(Args)]
+pub struct PreviewTemplatesCommand {
+    /// The AI agents whose templates to show (repeat or list several)
+    #[arg(short, long, value_enum, num_args(1..), required = true)]
+    pub agent: Vec<AgentType>,
+}
+
+impl PreviewTemplatesCommand {
+    /// Execute the preview-templates command
+    ///
+    /// Placeholders such as `{{project_name}}` are shown as-is, since they are
+    /// only filled in when a project is initialized.
+    pub fn execute(&self) -> Result<()> {
+        let (templates, _) = TemplateSystem::resolve_templates(&self.agents());
+
+        if output::is_json() {
+            output::print_json(Self::json_summary(&templates));
+        } else {
+            print!("{}", Self::render(&templates));
+        }
+        Ok(())
+    }
+
+    /// Selected agents in order, ignoring repeats
+    fn agents(&self) -> Vec<Agent> {
+        let mut agents = Vec::new();
+        for agent_type in &self.agent {
+            let agent = Agent::from(agent_type.clone());
+            if !agents.contains(&agent) {
+                agents.push(agent);
+            }
+        }
+        agents
+    }
+
+    /// Template contents, each preceded by a `--- <file name> ---` header
+    fn render(templates: &[ResolvedTemplate]) -> String {
+        let mut rendered = String::new();
+        for template in templates {
+            rendered.push_str(&format!("--- {} ---\n", template.name));
+            rendered.push_str(&template.content);
+            if !template.content.ends_with('\n') {
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(templates: &[ResolvedTemplate]) -> serde_json::Value {
+        let files: Vec<_> = templates
+            .iter()
+            .map(|template| {
+                serde_json::json!({
+                    "name": template.name,
+                    "agent": template.agent,
+                    "content": template.content,
+                })
+            })
+            .collect();
+        serde_json::json!({ "files": files })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_separates_files_with_headers() {
+        let cmd = PreviewTemplatesCommand {
+            agent: vec![AgentType::Codeium, AgentType::Codeium],
+        };
+        let (templates, _) = TemplateSystem::resolve_templates(&cmd.agents());
+        let rendered = PreviewTemplatesCommand::render(&templates);
+
+        assert!(rendered.starts_with("--- CLAUDE.md ---\n# {{project_name}}"));
+        assert!(rendered.contains("\n--- codeium.json ---\n{"));
+        assert_eq!(rendered.matches("--- ").count(), 2);
+    }
+
+    #[test]
+    fn test_json_summary() {
+        let (templates, _) = TemplateSystem::resolve_templates(&[Agent::Codeium]);
+        let summary = PreviewTemplatesCommand::json_summary(&templates);
+
+        assert_eq!(summary["files"][0]["name"], "CLAUDE.md");
+        assert_eq!(summary["files"][0]["agent"], "codeium");
+        assert_eq!(summary["files"][1]["name"], "codeium.json");
+    }
+}
@@ -0,0 +1,252 @@
+use crate::config::{ProjectConfig, is_reserved_metadata_key};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::format;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use dialoguer::MultiSelect;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+/// List metadata keys by size and remove the ones that are no longer needed
+#[derive(Args)]
+pub struct PruneMetadataCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Remove these keys (comma-separated or repeated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub keys: Vec<String>,
+
+    /// Remove every key whose value is larger than this many bytes
+    #[arg(long, value_name = "BYTES")]
+    pub larger_than: Option<usize>,
+
+    /// Show what would be removed without writing the configuration
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl PruneMetadataCommand {
+    /// Execute the prune-metadata command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let mut config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        status!("📄 Metadata keys by size:");
+        for (key, size) in config.metadata_sizes() {
+            let note = if is_reserved_metadata_key(key) { " (managed by specforge)" } else { "" };
+            status!("   • {} - {}{}", key, format::size(size as u64), note);
+        }
+        status!();
+
+        let selected = if self.keys.is_empty() && self.larger_than.is_none() {
+            Self::select_interactively(&config)?
+        } else {
+            self.select(&config)?
+        };
+        let size_before = config.to_json_string()?.len();
+        if selected.is_empty() {
+            status!("{} Nothing to prune", Status::Info);
+            if output::is_json() {
+                output::print_json(serde_json::json!({
+                    "removed": selected,
+                    "size_before": size_before,
+                    "size_after": size_before,
+                    "dry_run": self.dry_run,
+                }));
+            }
+            return Ok(());
+        }
+
+        for key in &selected {
+            config.remove_metadata(key)?;
+            status!("   - {}", key);
+        }
+        let size_after = config.to_json_string()?.len();
+
+        if self.dry_run {
+            status!(
+                "{} Dry run: removing {} key(s) would shrink the configuration from {} to {}",
+                Status::Info,
+                selected.len(),
+                format::size(size_before as u64),
+                format::size(size_after as u64)
+            );
+        } else {
+            FileOps::write_config(&config, &config_path).map_err(|e| {
+                e.add_context(
+                    "configuration file writing",
+                    format!("Updating {}", config_path.display()),
+                )
+            })?;
+            status!(
+                "{} Removed {} key(s); the configuration shrank from {} to {}",
+                Status::Ok,
+                selected.len(),
+                format::size(size_before as u64),
+                format::size(size_after as u64)
+            );
+        }
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "removed": selected,
+                "size_before": size_before,
+                "size_after": size_after,
+                "dry_run": self.dry_run,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Keys named with --keys plus unreserved keys over --larger-than
+    ///
+    /// Naming a reserved or unknown key is an error; --larger-than skips
+    /// reserved keys.
+    pub fn select(&self, config: &ProjectConfig) -> Result<Vec<String>> {
+        let mut selected = Vec::new();
+        for key in &self.keys {
+            if is_reserved_metadata_key(key) {
+                return Err(ConfigError::validation_error(format!(
+                    "Metadata key '{}' is managed by specforge and cannot be removed",
+                    key
+                )));
+            }
+            if config.get_metadata(key).is_none() {
+                return Err(ConfigError::validation_error(format!(
+                    "Metadata key '{}' does not exist",
+                    key
+                )));
+            }
+            if !selected.contains(key) {
+                selected.push(key.clone());
+            }
+        }
+
+        if let Some(limit) = self.larger_than {
+            for (key, size) in config.metadata_sizes() {
+                if size > limit && !is_reserved_metadata_key(key) && !selected.iter().any(|k| k == key) {
+                    selected.push(key.to_string());
+                }
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Ask which unreserved keys to remove
+    fn select_interactively(config: &ProjectConfig) -> Result<Vec<String>> {
+        if !std::io::stdin().is_terminal() {
+            return Err(ConfigError::validation_error(
+                "There is no terminal to choose keys from. Use --keys or --larger-than",
+            ));
+        }
+
+        let candidates: Vec<_> = config
+            .metadata_sizes()
+            .into_iter()
+            .filter(|(key, _)| !is_reserved_metadata_key(key))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let items: Vec<_> = candidates
+            .iter()
+            .map(|(key, size)| format!("{} ({})", key, format::size(*size as u64)))
+            .collect();
+        let chosen = MultiSelect::with_theme(crate::ui::theme().as_ref())
+            .with_prompt("Select the metadata keys to remove")
+            .items(&items)
+            .interact_opt()
+            .map_err(|e| ConfigError::io_error(format!("Failed to read user input: {}", e)))?
+            .ok_or_else(|| ConfigError::user_cancelled("Metadata selection was cancelled"))?;
+
+        Ok(chosen.into_iter().map(|index| candidates[index].0.to_string()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, RESERVED_METADATA_KEYS};
+    use tempfile::TempDir;
+
+    /// A configuration that automation has bloated with large metadata values
+    fn bloated_config() -> ProjectConfig {
+        let mut config = ProjectConfig::with_project_name(Agent::Claude, "demo");
+        config.set_metadata("initialized_by", "specforge-cli");
+        config.set_metadata("variables", serde_json::json!({ "team": "x".repeat(900) }));
+        for run in 0..20 {
+            config.set_metadata(format!("ci_run_{}", run), serde_json::json!(vec!["log line"; 200]));
+        }
+        config.set_metadata("owner", "platform-team");
+        config
+    }
+
+    fn command(dir: &std::path::Path, keys: &[&str], larger_than: Option<usize>) -> PruneMetadataCommand {
+        PruneMetadataCommand {
+            directory: dir.to_path_buf(),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+            larger_than,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_larger_than_skips_reserved_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let original = bloated_config();
+        FileOps::write_config_to_directory(&original, temp_dir.path()).unwrap();
+
+        command(temp_dir.path(), &[], Some(100)).execute().unwrap();
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        let keys: Vec<_> = config.metadata.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["created_at", "initialized_by", "owner", "variables"]);
+        for key in RESERVED_METADATA_KEYS {
+            assert_eq!(config.get_metadata(key), original.get_metadata(key), "{} was touched", key);
+        }
+    }
+
+    #[test]
+    fn test_prune_named_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        FileOps::write_config_to_directory(&bloated_config(), temp_dir.path()).unwrap();
+
+        command(temp_dir.path(), &["owner", "ci_run_3"], None).execute().unwrap();
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert!(config.get_metadata("owner").is_none());
+        assert!(config.get_metadata("ci_run_3").is_none());
+        assert!(config.get_metadata("ci_run_4").is_some());
+    }
+
+    #[test]
+    fn test_named_reserved_or_unknown_keys_are_rejected() {
+        let config = bloated_config();
+        let dir = std::path::Path::new(".");
+        assert!(command(dir, &["project_name"], None).select(&config).is_err());
+        assert!(command(dir, &["missing"], None).select(&config).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = FileOps::write_config_to_directory(&bloated_config(), temp_dir.path()).unwrap();
+        let before = std::fs::read_to_string(&config_path).unwrap();
+
+        let mut prune = command(temp_dir.path(), &[], Some(0));
+        prune.dry_run = true;
+        prune.execute().unwrap();
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), before);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::config::{ProjectConfig, TrackedFile};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, LOCK_FILE_NAME};
+use crate::output;
+use crate::prompt;
+use crate::status;
+use crate::templates::{self, TemplateSystem};
+use crate::ui::Status;
+use clap::Args;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PROJECT_NAME_PLACEHOLDER: &str = "{{project_name}}";
+
+/// Rename the project and update the deployed files that contain its name
+#[derive(Args)]
+pub struct RenameProjectCommand {
+    /// The new project name
+    pub new_name: String,
+
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Apply the changes without asking for confirmation
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+/// How the new name ends up in a deployed file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameMethod {
+    /// The file still matched its template, which was rendered again with the new name
+    Rendered,
+    /// The file was unmodified since deployment, but its template has changed since;
+    /// occurrences of the old name were replaced
+    Replaced,
+}
+
+/// Planned update of one deployed file
+#[derive(Debug, Clone)]
+pub struct RenamedFile {
+    /// File name relative to the project directory
+    pub name: String,
+    /// Current content of the file
+    pub previous: String,
+    /// Content with the new project name
+    pub content: String,
+    /// How the content was produced
+    pub method: RenameMethod,
+}
+
+/// Files a rename updates, and files it leaves alone because the user edited them
+#[derive(Debug, Default)]
+pub struct RenamePlan {
+    /// Files that will be rewritten
+    pub files: Vec<RenamedFile>,
+    /// Files containing the name that were modified since deployment
+    pub modified: Vec<String>,
+}
+
+impl RenameProjectCommand {
+    /// Execute the rename-project command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let mut config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let new_name = self.new_name.trim();
+        ProjectConfig::validate_project_name(new_name)?;
+
+        let old_name = Self::current_name(&config, &self.directory);
+        if old_name == new_name {
+            status!("{} Project is already named '{}'", Status::Ok, new_name);
+            return Ok(());
+        }
+
+        let plan = Self::plan(&config, &self.directory, new_name)?;
+        status!("{} Renaming project '{}' to '{}'", Status::Info, old_name, new_name);
+        for file in &plan.files {
+            if let Some(diff) = templates::unified_diff(&file.previous, &file.content, &file.name) {
+                templates::display_diff(&diff);
+            }
+        }
+
+        if !self.force && !plan.files.is_empty() {
+            if !prompt::is_interactive() {
+                return Err(ConfigError::validation_error(
+                    "Renaming the project rewrites deployed files and needs confirmation, but there is \
+                     no terminal to ask. Use --force to apply the rename",
+                ));
+            }
+            let prompt = format!("Update {} file(s) with the new project name?", plan.files.len());
+            if !FileOps::confirm_action(&prompt)? {
+                status!("{} Operation cancelled by user", Status::Error);
+                return Err(ConfigError::user_cancelled("Project rename cancelled"));
+            }
+        }
+
+        let original_config = fs::read(&config_path).map_err(|e| {
+            ConfigError::io_error(format!("Failed to read '{}': {}", config_path.display(), e))
+        })?;
+        let mut written = Vec::new();
+        if let Err(error) = Self::apply(&plan, &mut config, &config_path, &self.directory, new_name, &mut written) {
+            Self::rollback(&written, &config_path, &original_config);
+            return Err(error.add_context("project rename", "All changes were rolled back"));
+        }
+
+        Self::display_summary(&plan);
+        status!("{} Renamed project to '{}'", Status::Ok, new_name);
+
+        if output::is_json() {
+            output::print_json(Self::json_summary(&old_name, new_name, &plan));
+        }
+        Ok(())
+    }
+
+    /// The name currently substituted for `{{project_name}}`
+    fn current_name(config: &ProjectConfig, directory: &Path) -> String {
+        TemplateSystem::template_context(config, directory)
+            .remove("project_name")
+            .unwrap_or_default()
+    }
+
+    /// Work out the new content of every tracked file whose template uses the project name
+    pub fn plan(config: &ProjectConfig, directory: &Path, new_name: &str) -> Result<RenamePlan> {
+        let old_context = TemplateSystem::template_context(config, directory);
+        let old_name = old_context.get("project_name").cloned().unwrap_or_default();
+        let mut new_context = old_context.clone();
+        new_context.insert("project_name".to_string(), new_name.to_string());
+
+        let (resolved, _) = TemplateSystem::resolve_templates(&config.agents);
+        let mut plan = RenamePlan::default();
+        for template in resolved {
+            if !template.content.contains(PROJECT_NAME_PLACEHOLDER) {
+                continue;
+            }
+            let Some(tracked) = config.tracked_file(template.name) else {
+                continue;
+            };
+            let path = directory.join(template.name);
+            if !path.is_file() {
+                continue;
+            }
+
+            let bytes = fs::read(&path).map_err(|e| {
+                ConfigError::io_error(format!("Failed to read '{}': {}", path.display(), e))
+            })?;
+            let Ok(current) = String::from_utf8(bytes) else {
+                plan.modified.push(template.name.to_string());
+                continue;
+            };
+
+            let render = |context: &HashMap<String, String>| {
+                let (rendered, _) = templates::render_template(
+                    &template.content,
+                    &TemplateSystem::agent_context(context, &template.agent),
+                );
+                TemplateSystem::with_provenance(&template, &rendered, context)
+            };
+            let (content, method) = if current == render(&old_context) {
+                (render(&new_context), RenameMethod::Rendered)
+            } else if !old_name.is_empty() && FileOps::hash_tracked_content(&current) == tracked.sha256 {
+                (current.replace(&old_name, new_name), RenameMethod::Replaced)
+            } else {
+                plan.modified.push(template.name.to_string());
+                continue;
+            };
+
+            if content != current {
+                plan.files.push(RenamedFile {
+                    name: template.name.to_string(),
+                    previous: current,
+                    content,
+                    method,
+                });
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Write the planned files and the renamed configuration, recording each
+    /// written file so a failure can be rolled back
+    fn apply<'a>(
+        plan: &'a RenamePlan,
+        config: &mut ProjectConfig,
+        config_path: &Path,
+        directory: &Path,
+        new_name: &str,
+        written: &mut Vec<(PathBuf, &'a str)>,
+    ) -> Result<()> {
+        for file in &plan.files {
+            let path = directory.join(&file.name);
+            fs::write(&path, &file.content).map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&path),
+                _ => ConfigError::io_error(format!("Failed to write '{}': {}", path.display(), e)),
+            })?;
+            written.push((path, &file.previous));
+            config.track_file(TrackedFile::new(file.name.clone(), FileOps::hash_tracked_content(&file.content)));
+        }
+
+        config.project_name = Some(new_name.to_string());
+        FileOps::write_config(config, config_path).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Updating {}", config_path.display()),
+            )
+        })?;
+        FileOps::refresh_lock_file(config, config_path, Vec::new())
+            .map_err(|e| e.add_context("lock file writing", format!("Updating {}", LOCK_FILE_NAME)))?;
+        Ok(())
+    }
+
+    /// Restore the original content of written files and the configuration
+    fn rollback(written: &[(PathBuf, &str)], config_path: &Path, original_config: &[u8]) {
+        for (path, previous) in written {
+            if let Err(e) = fs::write(path, previous) {
+                status!("{} Could not restore {}: {}", Status::Warn, path.display(), e);
+            }
+        }
+        if let Err(e) = fs::write(config_path, original_config) {
+            status!("{} Could not restore {}: {}", Status::Warn, config_path.display(), e);
+        }
+    }
+
+    /// Print the files updated and the files left for the user
+    fn display_summary(plan: &RenamePlan) {
+        if plan.files.is_empty() && plan.modified.is_empty() {
+            return;
+        }
+        status!("📄 Files:");
+        for file in &plan.files {
+            let method = match file.method {
+                RenameMethod::Rendered => "re-rendered",
+                RenameMethod::Replaced => "name replaced",
+            };
+            status!("   ~ {} ({})", file.name, method);
+        }
+        for name in &plan.modified {
+            status!("   • {} (modified since deployment; update the name by hand)", name);
+        }
+        status!();
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(old_name: &str, new_name: &str, plan: &RenamePlan) -> serde_json::Value {
+        let updated: Vec<_> = plan.files.iter().map(|file| file.name.as_str()).collect();
+        serde_json::json!({
+            "old_name": old_name,
+            "new_name": new_name,
+            "updated": updated,
+            "modified": plan.modified,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::relative_path;
+    use crate::config::{Agent, Package};
+    use tempfile::TempDir;
+
+    /// Initialize a codeium project named "demo" and track its deployed files
+    fn setup_project(dir: &Path) -> ProjectConfig {
+        let mut config = ProjectConfig::new(Agent::Codeium);
+        config
+            .add_package(Package::new(Agent::Codeium.default_package_id(), "0.1.0").unwrap())
+            .unwrap();
+        config.project_name = Some("demo".to_string());
+
+        let context = TemplateSystem::template_context(&config, dir);
+        for path in TemplateSystem::deploy_templates(&[Agent::Codeium], dir, &context).unwrap() {
+            let name = relative_path(dir, &path);
+            config.track_file(TrackedFile::new(name, FileOps::hash_file(&path).unwrap()));
+        }
+        FileOps::write_config_to_directory(&config, dir).unwrap();
+        config
+    }
+
+    fn command(dir: &Path, new_name: &str) -> RenameProjectCommand {
+        RenameProjectCommand {
+            new_name: new_name.to_string(),
+            directory: dir.to_path_buf(),
+            force: true,
+        }
+    }
+
+    #[test]
+    fn test_plan_rerenders_unmodified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = setup_project(temp_dir.path());
+
+        let plan = RenameProjectCommand::plan(&config, temp_dir.path(), "atlas").unwrap();
+        assert!(plan.modified.is_empty());
+        let claude = plan.files.iter().find(|file| file.name == "CLAUDE.md").unwrap();
+        assert_eq!(claude.method, RenameMethod::Rendered);
+        assert!(claude.content.starts_with("# atlas: Codeium Instructions"));
+    }
+
+    #[test]
+    fn test_plan_replaces_name_when_template_changed() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = setup_project(temp_dir.path());
+
+        // Deployed by an older template, but untouched since
+        let old_content = "# demo\nWelcome to demo.\n";
+        fs::write(temp_dir.path().join("CLAUDE.md"), old_content).unwrap();
+        config.track_file(TrackedFile::new("CLAUDE.md", &FileOps::hash_content(old_content)));
+
+        let plan = RenameProjectCommand::plan(&config, temp_dir.path(), "atlas").unwrap();
+        let claude = plan.files.iter().find(|file| file.name == "CLAUDE.md").unwrap();
+        assert_eq!(claude.method, RenameMethod::Replaced);
+        assert_eq!(claude.content, "# atlas\nWelcome to atlas.\n");
+    }
+
+    #[test]
+    fn test_plan_leaves_modified_files_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = setup_project(temp_dir.path());
+        fs::write(temp_dir.path().join("CLAUDE.md"), "# demo, with my notes\n").unwrap();
+
+        let plan = RenameProjectCommand::plan(&config, temp_dir.path(), "atlas").unwrap();
+        assert_eq!(plan.modified, vec!["CLAUDE.md".to_string()]);
+        assert!(plan.files.iter().all(|file| file.name != "CLAUDE.md"));
+    }
+
+    #[test]
+    fn test_rename_project_updates_files_and_config() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path());
+
+        command(temp_dir.path(), "  atlas ").execute().unwrap();
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert_eq!(config.project_name(), Some("atlas"));
+        let claude_path = temp_dir.path().join("CLAUDE.md");
+        assert!(fs::read_to_string(&claude_path).unwrap().starts_with("# atlas:"));
+        assert_eq!(
+            config.tracked_file("CLAUDE.md").unwrap().sha256,
+            FileOps::hash_file(&claude_path).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rename_project_rejects_invalid_name() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path());
+        let before = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+
+        assert!(command(temp_dir.path(), "   ").execute().is_err());
+        assert!(command(temp_dir.path(), "bad\u{7}name").execute().is_err());
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert_eq!(config.project_name(), Some("demo"));
+        assert_eq!(fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap(), before);
+    }
+
+    #[test]
+    fn test_rollback_restores_files_and_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("CLAUDE.md");
+        let config_path = temp_dir.path().join(".specforge.json");
+        fs::write(&file, "# atlas\n").unwrap();
+        fs::write(&config_path, "{\"broken\": ").unwrap();
+
+        RenameProjectCommand::rollback(&[(file.clone(), "# demo\n")], &config_path, b"{}");
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "# demo\n");
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "{}");
+    }
+}
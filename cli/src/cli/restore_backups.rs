@@ -0,0 +1,125 @@
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, backup};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Put back the newest backup of each deployed file
+#[derive(Args)]
+pub struct RestoreBackupsCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Only list the backups, without restoring anything
+    #[arg(long)]
+    pub list: bool,
+}
+
+impl RestoreBackupsCommand {
+    /// Execute the restore-backups command
+    ///
+    /// The newest backup of every tracked file replaces the file and is
+    /// removed; older backups are left for the user to delete.
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let mut found = Vec::new();
+        for file in &config.files {
+            let path = self.directory.join(&file.path);
+            let backups = backup::find(&path)?;
+            if !backups.is_empty() {
+                found.push((path, backups));
+            }
+        }
+
+        if found.is_empty() {
+            status!("{} No backups of deployed files found", Status::Info);
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "restored": [], "backups": [] }));
+            }
+            return Ok(());
+        }
+
+        if self.list {
+            status!("📄 Backups of deployed files, oldest first:");
+            for (path, backups) in &found {
+                status!("   • {}", path.display());
+                for backup in backups {
+                    status!("     - {}", backup.display());
+                }
+            }
+            if output::is_json() {
+                let backups: Vec<_> = found
+                    .iter()
+                    .map(|(path, backups)| serde_json::json!({ "path": path, "backups": backups }))
+                    .collect();
+                output::print_json(serde_json::json!({ "restored": [], "backups": backups }));
+            }
+            return Ok(());
+        }
+
+        let mut restored = Vec::new();
+        for (path, backups) in &found {
+            let newest = &backups[backups.len() - 1];
+            fs::rename(newest, path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(path),
+                _ => ConfigError::from(e),
+            })?;
+            status!("↩️  Restored {} from {}", path.display(), newest.display());
+            if backups.len() > 1 {
+                status!("   {} older backup(s) kept", backups.len() - 1);
+            }
+            restored.push(serde_json::json!({ "path": path, "backup": newest }));
+        }
+        status!();
+        status!("{} Restored {} file(s); `specforge verify` now reports them as modified", Status::Ok, restored.len());
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "restored": restored, "backups": [] }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::relative_path;
+    use crate::config::{Agent, ProjectConfig, TrackedFile};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_restore_newest_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("CLAUDE.md");
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.track_file(TrackedFile::new(relative_path(temp_dir.path(), &path), "0".repeat(64)));
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        fs::write(&path, "first edits").unwrap();
+        let older = backup::create(&path).unwrap();
+        fs::write(&path, b"second edits\xff").unwrap();
+        let newest = backup::create(&path).unwrap();
+        fs::write(&path, "template").unwrap();
+
+        let list = RestoreBackupsCommand { directory: temp_dir.path().to_path_buf(), list: true };
+        list.execute().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "template");
+
+        let restore = RestoreBackupsCommand { directory: temp_dir.path().to_path_buf(), list: false };
+        restore.execute().unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"second edits\xff");
+        assert!(!newest.exists());
+        assert!(older.exists());
+    }
+}
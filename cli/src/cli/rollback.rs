@@ -0,0 +1,223 @@
+use crate::config::{ConfigDiff, format_agents};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, backup, format_timestamp};
+use crate::output;
+use crate::prompt;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Put back the previous configuration from its newest backup
+#[derive(Args)]
+pub struct RollbackCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub output_directory: PathBuf,
+
+    /// Roll back without asking for confirmation
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Backup to restore, counting from 1 for the newest as --list numbers them
+    #[arg(short, long, value_name = "N", conflicts_with = "list")]
+    pub backup: Option<usize>,
+
+    /// List the backups to roll back to, newest first, and exit
+    #[arg(short, long)]
+    pub list: bool,
+}
+
+impl RollbackCommand {
+    /// Execute the rollback command
+    ///
+    /// The configuration being replaced is backed up first, so running the
+    /// command again rolls the rollback back. Without --backup the newest
+    /// backup is restored, unless there is a terminal to choose one on.
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.output_directory);
+        let backups = FileOps::config_backups(&config_path)?;
+        if backups.is_empty() {
+            return Err(ConfigError::no_backups(&config_path));
+        }
+        if self.list {
+            return Self::list_backups(&backups);
+        }
+        let backup_path = self.select_backup(&backups)?.clone();
+
+        let previous = FileOps::read_config_backup(&backup_path, &config_path).map_err(|e| {
+            e.add_context("backup loading", format!("Reading {}", backup_path.display()))
+        })?;
+        let backed_up_at = format_timestamp(FileOps::get_file_info(&backup_path)?.modified_timestamp);
+        status!("📄 Backup: {}", backup_path.display());
+        status!("   Written: {}", backed_up_at);
+        status!("   Agent: {}", format_agents(&previous.agents));
+
+        // A configuration that no longer reads is exactly what a rollback fixes
+        let diff = match FileOps::read_config(&config_path) {
+            Ok(current) => Some(ConfigDiff::between(&current, &previous)),
+            Err(e) => {
+                status!("{} {} cannot be read, so its changes are not shown: {}", Status::Warn, config_path.display(), e);
+                None
+            }
+        };
+        match &diff {
+            Some(diff) if diff.is_empty() => status!("{} The backup matches the current configuration", Status::Info),
+            Some(diff) => {
+                status!("Rolling back changes:");
+                for entry in &diff.entries {
+                    status!("   • {}", entry);
+                }
+            }
+            None => {}
+        }
+
+        if !self.force {
+            let prompt = format!("Replace {} with the backup from {}?", config_path.display(), backed_up_at);
+            if !FileOps::confirm_action(&prompt)? {
+                status!("{} Operation cancelled by user", Status::Error);
+                return Err(ConfigError::user_cancelled("Rollback cancelled"));
+            }
+        }
+
+        let replaced = if config_path.is_file() { Some(backup::create(&config_path)?) } else { None };
+        // Renaming within a directory replaces the file in one step
+        fs::rename(&backup_path, &config_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&config_path),
+            _ => ConfigError::from(e),
+        })?;
+
+        status!("↩️  Rolled back {} to the backup from {}", config_path.display(), backed_up_at);
+        if let Some(replaced) = &replaced {
+            status!("   The replaced configuration is kept in {}", replaced.display());
+        }
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "config_path": config_path,
+                "backup": backup_path,
+                "replaced_backup": replaced,
+                "changes": diff.map(|diff| diff.entries),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Print the backups numbered as --backup takes them
+    fn list_backups(backups: &[PathBuf]) -> Result<()> {
+        let mut listed = Vec::with_capacity(backups.len());
+        for (index, backup) in backups.iter().enumerate() {
+            let written = format_timestamp(FileOps::get_file_info(backup)?.modified_timestamp);
+            status!("{:>3}. {}  {}", index + 1, written, backup.display());
+            listed.push(serde_json::json!({ "number": index + 1, "path": backup, "written": written }));
+        }
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "backups": listed }));
+        }
+        Ok(())
+    }
+
+    /// Backup picked with --backup, or on the terminal when there is a
+    /// choice and the command may ask; the newest otherwise
+    fn select_backup<'a>(&self, backups: &'a [PathBuf]) -> Result<&'a PathBuf> {
+        if let Some(number) = self.backup {
+            return number.checked_sub(1).and_then(|index| backups.get(index)).ok_or_else(|| {
+                ConfigError::validation_error(format!(
+                    "There is no backup {}; --backup takes 1 to {} (see --list)",
+                    number,
+                    backups.len()
+                ))
+            });
+        }
+        if self.force || backups.len() == 1 || !prompt::is_interactive() {
+            return Ok(&backups[0]);
+        }
+
+        let items = backups
+            .iter()
+            .map(|backup| {
+                let written = FileOps::get_file_info(backup).map(|info| format_timestamp(info.modified_timestamp))?;
+                Ok(format!("{}  {}", written, backup.display()))
+            })
+            .collect::<Result<Vec<String>>>()?;
+        match prompt::select("Which backup do you want to restore?", &items, 0)? {
+            Some(index) => Ok(&backups[index]),
+            None => {
+                status!("{} Operation cancelled by user", Status::Error);
+                Err(ConfigError::user_cancelled("Rollback cancelled"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, ProjectConfig};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rollback_swaps_in_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = FileOps::write_config_to_directory(&ProjectConfig::new(Agent::Claude), temp_dir.path()).unwrap();
+        let backup_path = temp_dir.path().join(".specforge.json.backup");
+        fs::copy(&config_path, &backup_path).unwrap();
+        FileOps::write_config(&ProjectConfig::new(Agent::Copilot), &config_path).unwrap();
+
+        let rollback = RollbackCommand {
+            output_directory: temp_dir.path().to_path_buf(),
+            force: true,
+            backup: None,
+            list: false,
+        };
+        rollback.execute().unwrap();
+        assert_eq!(FileOps::read_config(&config_path).unwrap().agents, [Agent::Claude]);
+        assert!(!backup_path.exists());
+
+        // The replaced configuration became the newest backup
+        rollback.execute().unwrap();
+        assert_eq!(FileOps::read_config(&config_path).unwrap().agents, [Agent::Copilot]);
+    }
+
+    #[test]
+    fn test_rollback_to_chosen_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = FileOps::write_config_to_directory(&ProjectConfig::new(Agent::Claude), temp_dir.path()).unwrap();
+        FileOps::write_config_with_backup(&ProjectConfig::new(Agent::Copilot), &config_path).unwrap();
+        FileOps::write_config_with_backup(&ProjectConfig::new(Agent::Codeium), &config_path).unwrap();
+        assert_eq!(FileOps::config_backups(&config_path).unwrap().len(), 2);
+
+        let mut rollback = RollbackCommand {
+            output_directory: temp_dir.path().to_path_buf(),
+            force: true,
+            backup: Some(3),
+            list: false,
+        };
+        let error = rollback.execute().unwrap_err();
+        assert!(error.to_string().contains("--backup takes 1 to 2"), "{}", error);
+
+        rollback.backup = Some(2);
+        rollback.execute().unwrap();
+        assert_eq!(FileOps::read_config(&config_path).unwrap().agents, [Agent::Claude]);
+    }
+
+    #[test]
+    fn test_rollback_without_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = FileOps::write_config_to_directory(&ProjectConfig::new(Agent::Claude), temp_dir.path()).unwrap();
+
+        let rollback = RollbackCommand {
+            output_directory: temp_dir.path().to_path_buf(),
+            force: true,
+            backup: None,
+            list: false,
+        };
+        let error = rollback.execute().unwrap_err();
+        assert!(matches!(error, ConfigError::NoBackups(_)));
+        assert_eq!(error.exit_code(), 66);
+        assert_eq!(error.code(), "no_backups");
+        let message = error.to_string();
+        assert!(message.starts_with(&format!("No backups of {} to roll back to.", config_path.display())), "{}", message);
+        assert!(message.contains("specforge config get max_backups"), "{}", message);
+    }
+}
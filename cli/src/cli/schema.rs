@@ -0,0 +1,99 @@
+use crate::config::config_schema;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, SCHEMA_FILE_NAME};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Print the JSON Schema of the .specforge.json configuration file
+#[derive(Args)]
+pub struct SchemaCommand {
+    /// Write the schema to specforge.schema.json instead of printing it
+    #[arg(short, long)]
+    pub write: bool,
+
+    /// Directory to write specforge.schema.json to
+    #[arg(short, long, default_value = ".", requires = "write")]
+    pub directory: PathBuf,
+
+    /// Write the schema to this file instead of printing it
+    #[arg(short, long, value_name = "PATH", conflicts_with = "write")]
+    pub output_file: Option<PathBuf>,
+}
+
+impl SchemaCommand {
+    /// Execute the schema command
+    pub fn execute(&self) -> Result<()> {
+        let schema = config_schema();
+        let schema_path = match &self.output_file {
+            Some(path) => path.clone(),
+            None if self.write => self.directory.join(SCHEMA_FILE_NAME),
+            None if output::is_json() => {
+                output::print_json(serde_json::json!({ "schema": schema }));
+                return Ok(());
+            }
+            None => {
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                return Ok(());
+            }
+        };
+
+        if let Some(parent) = schema_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            FileOps::ensure_directory_exists(parent)?;
+        }
+        fs::write(&schema_path, serde_json::to_string_pretty(&schema)? + "\n").map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&schema_path),
+            _ => ConfigError::from(e),
+        })?;
+
+        status!("{} Wrote {}", Status::Ok, schema_path.display());
+        if schema_path.file_name().is_some_and(|name| name == SCHEMA_FILE_NAME) {
+            status!("   Configuration files written next to it will reference it through \"$schema\"");
+        }
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "schema_path": schema_path }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = SchemaCommand {
+            write: true,
+            directory: temp_dir.path().join("schemas"),
+            output_file: None,
+        };
+        command.execute().unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(temp_dir.path().join("schemas").join(SCHEMA_FILE_NAME)).unwrap())
+                .unwrap();
+        assert_eq!(written, config_schema());
+    }
+
+    #[test]
+    fn test_write_schema_to_output_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_path = temp_dir.path().join(".vscode").join("specforge.json");
+        let command = SchemaCommand {
+            write: false,
+            directory: PathBuf::from("."),
+            output_file: Some(schema_path.clone()),
+        };
+        command.execute().unwrap();
+
+        let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&schema_path).unwrap()).unwrap();
+        assert_eq!(written, config_schema());
+        assert_eq!(written["$schema"], "http://json-schema.org/draft-07/schema#");
+    }
+}
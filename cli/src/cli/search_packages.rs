@@ -0,0 +1,112 @@
+use crate::error::Result;
+use crate::format;
+use crate::output;
+use crate::registry::{self, PackageSummary, SPECFORGE_REGISTRY_URL};
+use crate::status;
+use clap::Args;
+
+/// Search the package registry for template packages
+#[derive(Args)]
+pub struct SearchPackagesCommand {
+    /// Text to search for in package IDs and descriptions
+    #[arg(long)]
+    pub query: String,
+
+    /// Registry to search instead of the default registry
+    #[arg(long, default_value = SPECFORGE_REGISTRY_URL)]
+    pub registry_url: String,
+}
+
+impl SearchPackagesCommand {
+    /// Execute the search-packages command
+    pub fn execute(&self) -> Result<()> {
+        let packages = registry::search_packages(&self.registry_url, &self.query).map_err(|e| {
+            e.add_context(
+                "package search",
+                format!("Searching {} for '{}'", self.registry_url, self.query),
+            )
+        })?;
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "packages": packages }));
+            return Ok(());
+        }
+
+        if packages.is_empty() {
+            status!("No packages found matching '{}'", self.query);
+            return Ok(());
+        }
+        for line in format_table(&packages) {
+            status!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+/// Lay out packages as an aligned table with a header row
+fn format_table(packages: &[PackageSummary]) -> Vec<String> {
+    let header = ["ID", "VERSION", "DOWNLOADS", "DESCRIPTION"];
+    let rows: Vec<[String; 4]> = packages
+        .iter()
+        .map(|package| {
+            [
+                package.id.clone(),
+                package.latest_version.clone(),
+                format::count(package.downloads),
+                package.description.clone(),
+            ]
+        })
+        .collect();
+
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .chain(std::iter::once(header[column].len()))
+            .max()
+            .unwrap_or(0)
+    };
+    let (id_width, version_width, downloads_width) = (width(0), width(1), width(2));
+
+    std::iter::once(header.map(str::to_string))
+        .chain(rows)
+        .map(|[id, version, downloads, description]| {
+            format!(
+                "{:<id_width$}  {:<version_width$}  {:>downloads_width$}  {}",
+                id, version, downloads, description
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(id: &str, version: &str, downloads: u64, description: &str) -> PackageSummary {
+        PackageSummary {
+            id: id.to_string(),
+            description: description.to_string(),
+            latest_version: version.to_string(),
+            downloads,
+        }
+    }
+
+    #[test]
+    fn test_format_table_aligns_columns() {
+        let lines = format_table(&[
+            package("rust-api", "1.2.0", 1500, "REST APIs in Rust"),
+            package("go", "10.0.0", 7, ""),
+        ]);
+
+        assert_eq!(
+            lines,
+            vec![
+                "ID        VERSION  DOWNLOADS  DESCRIPTION",
+                "rust-api  1.2.0        1,500  REST APIs in Rust",
+                "go        10.0.0           7",
+            ]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::config::ProjectConfig;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::format;
+use crate::output;
+use crate::status;
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// Metadata keys shown on their own line instead of under "Metadata"
+const SUMMARY_METADATA_KEYS: &[&str] = &["project_name", "created_at", "updated_at"];
+
+/// Print the effective project configuration
+#[derive(Args)]
+pub struct ShowCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Print the validated configuration as JSON, as `--output json` does
+    #[arg(long, conflicts_with = "path")]
+    pub json: bool,
+
+    /// Only print the path of the configuration file
+    #[arg(long)]
+    pub path: bool,
+}
+
+impl ShowCommand {
+    /// Execute the show command
+    ///
+    /// The configuration is read as other commands read it, so the extended
+    /// configurations are merged in and it is validated.
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        if !config_path.exists() {
+            return Err(ConfigError::not_found(&config_path).add_context(
+                "project lookup",
+                format!(
+                    "{} is not a specforge project; run `specforge init` to create one",
+                    self.directory.display()
+                ),
+            ));
+        }
+
+        if self.path {
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "config_path": config_path }));
+            } else {
+                println!("{}", config_path.display());
+            }
+            return Ok(());
+        }
+
+        let config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context("configuration loading", format!("Reading {}", config_path.display()))
+        })?;
+
+        if self.json || output::is_json() {
+            output::print_json(serde_json::json!({ "config_path": config_path, "config": config }));
+            return Ok(());
+        }
+        for line in format_summary(&config_path, &config) {
+            status!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+/// Human-readable summary of a configuration, one line per entry
+fn format_summary(config_path: &Path, config: &ProjectConfig) -> Vec<String> {
+    let mut lines = vec![format!("Configuration: {}", config_path.display())];
+
+    let label = if config.agents.len() == 1 { "Agent" } else { "Agents" };
+    lines.push(format!("{}:", label));
+    for agent in &config.agents {
+        lines.push(format!("   • {} - {}", agent, agent.description()));
+    }
+
+    lines.push(format!("Project name: {}", config.project_name().unwrap_or("(not set)")));
+    if let Some(created_at) = config.created_at() {
+        lines.push(format!("Created: {}", created_at));
+    }
+    if let Some(updated_at) = config.get_metadata("updated_at").and_then(|value| value.as_str()) {
+        lines.push(format!("Updated: {}", updated_at));
+    }
+
+    let mut packages: Vec<_> = config.packages.iter().collect();
+    packages.sort_by(|a, b| format::listing_cmp(&a.id, &b.id));
+    lines.push(format!("Packages ({}):", packages.len()));
+    for package in packages {
+        match &package.url {
+            Some(url) => lines.push(format!("   • {} {} ({})", package.id, package.version, url)),
+            None => lines.push(format!("   • {} {}", package.id, package.version)),
+        }
+    }
+
+    let mut metadata: Vec<_> = config
+        .metadata
+        .iter()
+        .filter(|(key, _)| !SUMMARY_METADATA_KEYS.contains(&key.as_str()))
+        .collect();
+    if !metadata.is_empty() {
+        metadata.sort_by(|(a, _), (b, _)| format::listing_cmp(a, b));
+        lines.push("Metadata:".to_string());
+        for (key, value) in metadata {
+            lines.push(format!("   • {}: {}", key, metadata_value(value)));
+        }
+    }
+    lines
+}
+
+/// Metadata value as shown in the summary: strings without quotes, anything
+/// else as compact JSON
+fn metadata_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, Package};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_summary() {
+        let mut config = ProjectConfig::with_project_name(Agent::Claude, "demo");
+        config.set_metadata("created_at", "2025-01-01T00:00:00+00:00");
+        config.set_metadata("updated_at", "2025-02-01T00:00:00+00:00");
+        config.set_metadata("team", "payments");
+        config.set_metadata("provenance", false);
+        config
+            .add_package(Package::with_url("team-prompts", "https://example.com/p.tar.gz", "1.0.0").unwrap())
+            .unwrap();
+        config.add_package(Package::new("specforge-claude-templates", "0.1.0").unwrap()).unwrap();
+
+        let lines = format_summary(Path::new(".specforge.json"), &config);
+        assert_eq!(
+            lines,
+            [
+                "Configuration: .specforge.json".to_string(),
+                "Agent:".to_string(),
+                format!("   • claude - {}", Agent::Claude.description()),
+                "Project name: demo".to_string(),
+                "Created: 2025-01-01T00:00:00+00:00".to_string(),
+                "Updated: 2025-02-01T00:00:00+00:00".to_string(),
+                "Packages (2):".to_string(),
+                "   • specforge-claude-templates 0.1.0".to_string(),
+                "   • team-prompts 1.0.0 (https://example.com/p.tar.gz)".to_string(),
+                "Metadata:".to_string(),
+                "   • provenance: false".to_string(),
+                "   • team: payments".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_summary_without_name() {
+        let config = ProjectConfig::with_agents(vec![Agent::Claude, Agent::Copilot]);
+
+        let lines = format_summary(Path::new(".specforge.json"), &config);
+        assert_eq!(lines[1], "Agents:");
+        assert!(lines.contains(&"Project name: (not set)".to_string()));
+        assert!(lines.contains(&"Packages (0):".to_string()));
+        assert!(!lines.contains(&"Metadata:".to_string()));
+    }
+
+    #[test]
+    fn test_show_outside_a_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = ShowCommand {
+            directory: temp_dir.path().to_path_buf(),
+            json: false,
+            path: false,
+        };
+
+        let error = command.execute().unwrap_err();
+        assert_eq!(error.exit_code(), 2);
+        assert!(error.to_string().contains("is not a specforge project"), "{}", error);
+    }
+}
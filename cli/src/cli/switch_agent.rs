@@ -0,0 +1,309 @@
+use crate::cli::init::{AgentType, relative_path};
+use crate::config::{Agent, LockedPackage, Package, PackageVersion, ProjectConfig, TrackedFile, format_agents};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{ConflictPolicy, ConflictResolver, FileOps, LOCK_FILE_NAME};
+use crate::output;
+use crate::templates::TemplateSystem;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Move a project to a different AI agent
+#[derive(Args)]
+pub struct SwitchAgentCommand {
+    /// The agent to switch to
+    #[arg(value_enum)]
+    pub agent: AgentType,
+
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Delete the files deployed for the previous agents that the new agent does not provide
+    #[arg(long)]
+    pub remove_old_files: bool,
+
+    /// Skip the confirmation prompt and also delete old files modified since deployment
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+/// Files touched by an agent switch, relative to the project directory
+#[derive(Debug, Default, PartialEq)]
+pub struct SwitchSummary {
+    /// Template files deployed for the new agent
+    pub added: Vec<String>,
+    /// Files of the previous agents that were deleted
+    pub removed: Vec<String>,
+    /// Files of the previous agents that were left in place
+    pub kept: Vec<String>,
+}
+
+impl SwitchAgentCommand {
+    /// Execute the switch-agent command
+    pub fn execute(&self) -> Result<()> {
+        let config_path = FileOps::locate_config(&self.directory);
+        let mut config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        let agent = Agent::from(self.agent.clone());
+        if config.agents == [agent.clone()] {
+            status!("{} Project already uses {}", Status::Ok, agent);
+            return Ok(());
+        }
+        status!("{} Switching from {} to {}", Status::Info, format_agents(&config.agents), agent);
+
+        let old_files = Self::old_agent_files(&config, &agent);
+        let removable: Vec<_> = if self.remove_old_files {
+            old_files
+                .iter()
+                .filter(|file| self.should_remove(&self.directory, file))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if !removable.is_empty() && !self.force {
+            let prompt = format!(
+                "Delete {} file(s) deployed for {}?",
+                removable.len(),
+                format_agents(&config.agents)
+            );
+            if !FileOps::confirm_action(&prompt)? {
+                status!("{} Operation cancelled by user", Status::Error);
+                return Err(ConfigError::user_cancelled("Agent switch cancelled"));
+            }
+        }
+
+        let (templates, conflicts) = TemplateSystem::resolve_templates(std::slice::from_ref(&agent));
+        TemplateSystem::validate_target_paths(&templates, &self.directory)?;
+
+        Self::switch_config(&mut config, &agent)?;
+
+        let context = TemplateSystem::template_context(&config, &self.directory);
+        let issues = TemplateSystem::check_templates(&templates, &self.directory, &context);
+        TemplateSystem::report_template_issues(&issues, false)?;
+        // The new agent's templates replace files of the same name
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let deployed = TemplateSystem::deploy_resolved(
+            &templates,
+            &conflicts,
+            &self.directory,
+            &context,
+            &mut resolver,
+        )
+        .map_err(|e| {
+            e.add_context(
+                "template deployment",
+                format!("Deploying {} templates to {}", agent, self.directory.display()),
+            )
+        })?;
+
+        let mut summary = SwitchSummary::default();
+        for file in &deployed {
+            let relative = relative_path(&self.directory, &file.path);
+            config.track_file(
+                TrackedFile::new(relative.clone(), file.content_hash()?).with_mode(FileOps::file_mode(&file.path)),
+            );
+            summary.added.push(relative);
+        }
+
+        for file in old_files {
+            if removable.contains(&file) {
+                let path = self.directory.join(&file.path);
+                fs::remove_file(&path).map_err(|e| match e.kind() {
+                    std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&path),
+                    _ => ConfigError::from(e),
+                })?;
+                config.files.retain(|tracked| tracked.path != file.path);
+                summary.removed.push(file.path);
+            } else {
+                summary.kept.push(file.path);
+            }
+        }
+
+        FileOps::write_config(&config, &config_path).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Updating {}", config_path.display()),
+            )
+        })?;
+        // The new agent's templates are pinned in place of the old ones
+        let pins = config.get_package(agent.default_package_id()).map(LockedPackage::from_package);
+        FileOps::refresh_lock_file(&config, &config_path, pins.into_iter().collect())
+            .map_err(|e| e.add_context("lock file writing", format!("Updating {}", LOCK_FILE_NAME)))?;
+
+        Self::display_summary(&summary);
+        status!("{} Switched project to {}", Status::Ok, agent);
+
+        if output::is_json() {
+            output::print_json(Self::json_summary(&agent, &summary));
+        }
+        Ok(())
+    }
+
+    /// Replace the configured agents and their default packages with the new agent,
+    /// keeping metadata and all other packages
+    pub fn switch_config(config: &mut ProjectConfig, agent: &Agent) -> Result<()> {
+        for old in config.agents.clone() {
+            if &old != agent {
+                config.remove_package(old.default_package_id());
+            }
+        }
+        if config.get_package(agent.default_package_id()).is_none() {
+            config.add_package(Package::with_version(agent.default_package_id(), PackageVersion::current()))?;
+        }
+        config.agents = vec![agent.clone()];
+        config.validate()
+    }
+
+    /// Tracked files deployed for the previous agents that the new agent does not provide
+    fn old_agent_files(config: &ProjectConfig, agent: &Agent) -> Vec<TrackedFile> {
+        let new_files = TemplateSystem::list_template_files(agent);
+        let old_files: Vec<_> = config
+            .agents
+            .iter()
+            .flat_map(TemplateSystem::list_template_files)
+            .filter(|name| !new_files.contains(name))
+            .collect();
+
+        config
+            .files
+            .iter()
+            .filter(|file| old_files.contains(&file.path.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether an old file is deleted: it must exist, and be unmodified unless forced
+    fn should_remove(&self, directory: &Path, file: &TrackedFile) -> bool {
+        let path = directory.join(&file.path);
+        if !path.is_file() {
+            return false;
+        }
+        self.force || FileOps::hash_file(&path).is_ok_and(|sha256| sha256 == file.sha256)
+    }
+
+    /// Print the files added, removed and kept by the switch
+    fn display_summary(summary: &SwitchSummary) {
+        status!("📄 Files:");
+        for file in &summary.added {
+            status!("   + {}", file);
+        }
+        for file in &summary.removed {
+            status!("   - {}", file);
+        }
+        for file in &summary.kept {
+            status!("   • {} (kept)", file);
+        }
+        status!();
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(agent: &Agent, summary: &SwitchSummary) -> serde_json::Value {
+        serde_json::json!({
+            "agent": agent,
+            "added": summary.added,
+            "removed": summary.removed,
+            "kept": summary.kept,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Initialize a project for the given agent with a custom package and metadata
+    fn setup_project(dir: &Path, agent: Agent) {
+        let mut config = ProjectConfig::new(agent.clone());
+        config
+            .add_package(Package::new(agent.default_package_id(), "0.1.0").unwrap())
+            .unwrap();
+        config.add_package(Package::new("team-prompts", "2.0.0").unwrap()).unwrap();
+        config.project_name = Some("demo".to_string());
+
+        let context = TemplateSystem::template_context(&config, dir);
+        for path in TemplateSystem::deploy_templates(&[agent], dir, &context).unwrap() {
+            let name = relative_path(dir, &path);
+            config.track_file(TrackedFile::new(name, FileOps::hash_file(&path).unwrap()));
+        }
+        FileOps::write_config_to_directory(&config, dir).unwrap();
+    }
+
+    fn command(dir: &Path, agent: AgentType, remove_old_files: bool) -> SwitchAgentCommand {
+        SwitchAgentCommand {
+            agent,
+            directory: dir.to_path_buf(),
+            remove_old_files,
+            force: true,
+        }
+    }
+
+    #[test]
+    fn test_switch_config_preserves_custom_packages_and_metadata() {
+        let mut config = ProjectConfig::new(Agent::Copilot);
+        config
+            .add_package(Package::new(Agent::Copilot.default_package_id(), "0.1.0").unwrap())
+            .unwrap();
+        config.add_package(Package::new("team-prompts", "2.0.0").unwrap()).unwrap();
+        config.project_name = Some("demo".to_string());
+
+        SwitchAgentCommand::switch_config(&mut config, &Agent::Claude).unwrap();
+
+        assert_eq!(config.agents, vec![Agent::Claude]);
+        assert!(config.get_package(Agent::Copilot.default_package_id()).is_none());
+        assert!(config.get_package(Agent::Claude.default_package_id()).is_some());
+        assert!(config.get_package("team-prompts").is_some());
+        assert_eq!(config.project_name(), Some("demo"));
+    }
+
+    #[test]
+    fn test_switch_agent_removes_old_files() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), Agent::Codeium);
+
+        command(temp_dir.path(), AgentType::Claude, true).execute().unwrap();
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert_eq!(config.agents, vec![Agent::Claude]);
+        assert!(config.get_package("team-prompts").is_some());
+        assert!(!temp_dir.path().join("codeium.json").exists());
+        assert!(config.tracked_file("codeium.json").is_none());
+
+        // Shared file names are replaced by the new agent's version
+        assert!(temp_dir.path().join("README.md").exists());
+        let claude = config.tracked_file("CLAUDE.md").unwrap();
+        assert_eq!(
+            claude.sha256,
+            FileOps::hash_file(temp_dir.path().join("CLAUDE.md")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_switch_agent_keeps_old_files_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), Agent::Codeium);
+
+        command(temp_dir.path(), AgentType::Claude, false).execute().unwrap();
+
+        assert!(temp_dir.path().join("codeium.json").exists());
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert!(config.tracked_file("codeium.json").is_some());
+    }
+
+    #[test]
+    fn test_switch_agent_requires_config() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(command(temp_dir.path(), AgentType::Claude, false).execute().is_err());
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+    }
+}
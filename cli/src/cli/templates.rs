@@ -0,0 +1,120 @@
+use crate::config::{Agent, PackageVersion};
+use crate::error::Result;
+use crate::output;
+use crate::status;
+use crate::templates::changelog::{self, ChangelogEntry};
+use crate::ui::Status;
+use clap::{Args, Subcommand};
+
+/// Inspect the templates embedded in this release
+#[derive(Args)]
+pub struct TemplatesCommand {
+    #[command(subcommand)]
+    pub command: TemplatesSubcommand,
+}
+
+/// Available template subcommands
+#[derive(Subcommand)]
+pub enum TemplatesSubcommand {
+    /// List what changed in the embedded templates, release by release
+    Changelog(TemplatesChangelogCommand),
+}
+
+impl TemplatesCommand {
+    /// Execute the selected template subcommand
+    pub fn execute(&self) -> Result<()> {
+        match &self.command {
+            TemplatesSubcommand::Changelog(changelog_cmd) => changelog_cmd.execute(),
+        }
+    }
+}
+
+/// Print the changelog of the embedded templates
+#[derive(Args)]
+pub struct TemplatesChangelogCommand {
+    /// Only show changes to the templates of this agent; unknown names fail
+    /// as `init --agent` does
+    #[arg(short, long, value_name = "NAME")]
+    pub agent: Option<String>,
+
+    /// Print the entries as JSON, as `--output json` does
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl TemplatesChangelogCommand {
+    /// Execute the templates changelog command
+    ///
+    /// Lists the entries up to this release, newest first.
+    pub fn execute(&self) -> Result<()> {
+        let agent = self.agent.as_deref().map(str::parse::<Agent>).transpose()?;
+        let mut entries = changelog::between(agent.as_ref(), None, &PackageVersion::current());
+        entries.sort_by(|a, b| b.version.cmp(&a.version));
+
+        if self.json || output::is_json() {
+            output::print_json(serde_json::json!({ "entries": entries }));
+            return Ok(());
+        }
+        if entries.is_empty() {
+            status!("{} No template changes recorded", Status::Info);
+            return Ok(());
+        }
+        for line in format_entries(&entries) {
+            status!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+/// Lay out entries under a heading per release
+fn format_entries(entries: &[&ChangelogEntry]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut version = None;
+    for entry in entries {
+        if version != Some(&entry.version) {
+            if version.is_some() {
+                lines.push(String::new());
+            }
+            lines.push(entry.version.to_string());
+            version = Some(&entry.version);
+        }
+        lines.push(format!("  • {}: {} [{}]", entry.agent, entry.summary, entry.files.join(", ")));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ConfigError;
+
+    #[test]
+    fn test_format_entries_groups_by_version() {
+        let entry = |version: &str, agent| ChangelogEntry {
+            version: version.parse().unwrap(),
+            agent,
+            summary: "Shorter instructions".to_string(),
+            files: vec!["CLAUDE.md".to_string()],
+        };
+        let entries = [entry("0.2.0", Agent::Claude), entry("0.2.0", Agent::Copilot), entry("0.1.0", Agent::Claude)];
+
+        assert_eq!(
+            format_entries(&entries.iter().collect::<Vec<_>>()),
+            [
+                "0.2.0",
+                "  • claude: Shorter instructions [CLAUDE.md]",
+                "  • copilot: Shorter instructions [CLAUDE.md]",
+                "",
+                "0.1.0",
+                "  • claude: Shorter instructions [CLAUDE.md]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_agent_filter() {
+        let command = TemplatesChangelogCommand { agent: Some("gemini".to_string()), json: false };
+
+        assert!(matches!(command.execute(), Err(ConfigError::InvalidAgent(_))));
+    }
+}
@@ -0,0 +1,594 @@
+use crate::config::{Agent, ConditionContext, Package, PackageVersion, ProjectConfig, TrackedFile, format_agents};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{DEFAULT_MAX_HASH_SIZE, FileHash, FileOps, LOCK_FILE_NAME};
+use crate::templates::changelog::{self, ChangelogEntry};
+use crate::templates::diff::{self, DiffStyle};
+use crate::templates::{self, TemplateSystem};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Refresh deployed templates to the version embedded in this Specforge binary
+#[derive(Args)]
+pub struct UpgradeCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Show what would change without writing any files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Preview the local changes that the upgrade replaces as word diffs
+    /// instead of line diffs
+    #[arg(long)]
+    pub word_diff: bool,
+
+    /// Describe the template changes since this release instead of the
+    /// release the templates were deployed by
+    #[arg(long, value_name = "VERSION")]
+    pub since_version: Option<PackageVersion>,
+}
+
+/// What an upgrade does to a single template file
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileChange {
+    /// File does not exist yet and will be created
+    New,
+    /// File exists with different content and will be replaced
+    Updated,
+    /// File already matches the embedded template
+    Unchanged,
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileChange::New => write!(f, "new"),
+            FileChange::Updated => write!(f, "updated"),
+            FileChange::Unchanged => write!(f, "unchanged"),
+        }
+    }
+}
+
+/// Planned change for one template file
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    /// File name relative to the project directory
+    pub name: &'static str,
+    /// Template content rendered with the project's placeholder values
+    pub content: String,
+    /// Change that will be applied
+    pub change: FileChange,
+    /// Whether the user edited the file since it was deployed
+    pub user_modified: bool,
+}
+
+impl UpgradeCommand {
+    /// Execute the upgrade command
+    ///
+    /// The changelog entries of the releases in between describe what
+    /// changed; for agents without entries, the diff of each updated file is
+    /// shown instead.
+    pub fn execute(&self) -> Result<()> {
+        let current_version = PackageVersion::current();
+        let config_path = FileOps::locate_config(&self.directory);
+        let mut config = FileOps::read_config(&config_path).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading {}", config_path.display()),
+            )
+        })?;
+
+        // Packages whose condition does not hold are left alone
+        let context = ConditionContext::from_environment();
+        for package in config.inactive_packages(&context) {
+            if let Some(condition) = &package.when {
+                status!(
+                    "{} Skipping package {}: condition `{}` does not hold in this environment",
+                    Status::Info,
+                    package.id, condition
+                );
+            }
+        }
+
+        let agents = config.active_agents(&context);
+        if agents.is_empty() {
+            status!("{} No agent packages apply in this environment; nothing to upgrade", Status::Info);
+            if output::is_json() {
+                output::print_json(self.json_summary(&[], &[], &current_version, false));
+            }
+            return Ok(());
+        }
+
+        let agent_names = format_agents(&agents);
+        let outdated: Vec<_> = agents
+            .iter()
+            .filter_map(|agent| {
+                let installed = config
+                    .get_package(agent.default_package_id())
+                    .map(|p| p.version.clone());
+                (installed.as_ref() != Some(&current_version)).then(|| (agent.clone(), installed))
+            })
+            .collect();
+
+        // Replacing templates from a newer release with older ones would be a downgrade
+        if let Some((agent, Some(installed))) = outdated
+            .iter()
+            .find(|(_, installed)| installed.as_ref().is_some_and(|v| *v > current_version))
+        {
+            return Err(ConfigError::validation_error(format!(
+                "Templates for {} were deployed by specforge {}, which is newer than this release ({}). \
+                 Install specforge {} or later to upgrade them",
+                agent, installed, current_version, installed
+            )));
+        }
+
+        if outdated.is_empty() {
+            status!(
+                "{} Templates for {} are already at version {}",
+                Status::Ok,
+                agent_names, current_version
+            );
+            if output::is_json() {
+                output::print_json(self.json_summary(&[], &[], &current_version, false));
+            }
+            return Ok(());
+        }
+
+        status!(
+            "{} Upgrading {} templates to {}{}",
+            Status::Info,
+            agent_names,
+            current_version,
+            if self.dry_run { " (dry run)" } else { "" }
+        );
+        for (agent, installed) in &outdated {
+            status!(
+                "   • {}: {} → {}",
+                agent,
+                installed.as_ref().map_or("unknown version".to_string(), |v| v.to_string()),
+                current_version
+            );
+        }
+
+        let (entries, undocumented) = self.changelog(&outdated, &current_version);
+        Self::display_changelog(&entries);
+
+        let plan = Self::plan(&config, &agents, &self.directory)?;
+        Self::display_plan(&plan);
+        self.preview_changes(&plan, !undocumented.is_empty());
+
+        if self.dry_run {
+            status!("{} Dry run: no files were written", Status::Info);
+            if output::is_json() {
+                output::print_json(self.json_summary(&plan, &entries, &current_version, false));
+            }
+            return Ok(());
+        }
+
+        for file in &plan {
+            if file.change == FileChange::Unchanged {
+                continue;
+            }
+
+            if file.user_modified {
+                let backup = Self::backup_file(&self.directory.join(file.name))?;
+                status!("💾 Backed up {} to {}", file.name, backup.display());
+            }
+
+            templates::write_template_file(&file.content, &self.directory, file.name)?;
+        }
+
+        for file in &plan {
+            config.track_file(TrackedFile::new(
+                file.name.to_string(),
+                FileOps::hash_tracked_content(&file.content),
+            ));
+        }
+
+        for (agent, _) in &outdated {
+            let package_id = agent.default_package_id();
+            config.remove_package(package_id);
+            config.add_package(Package::with_version(package_id, current_version.clone()))?;
+        }
+        config.set_metadata("version", current_version.to_string());
+
+        FileOps::write_config(&config, &config_path).map_err(|e| {
+            e.add_context(
+                "configuration file writing",
+                format!("Updating {}", config_path.display()),
+            )
+        })?;
+        // Locked templates move to the version they were upgraded to
+        FileOps::refresh_lock_file(&config, &config_path, Vec::new())
+            .map_err(|e| e.add_context("lock file writing", format!("Updating {}", LOCK_FILE_NAME)))?;
+
+        status!("{} Upgraded {} templates to {}", Status::Ok, agent_names, current_version);
+        if output::is_json() {
+            output::print_json(self.json_summary(&plan, &entries, &current_version, true));
+        }
+        Ok(())
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(
+        &self,
+        plan: &[PlannedFile],
+        changelog: &[&ChangelogEntry],
+        version: &PackageVersion,
+        upgraded: bool,
+    ) -> serde_json::Value {
+        let files: Vec<_> = plan
+            .iter()
+            .map(|file| {
+                serde_json::json!({
+                    "name": file.name,
+                    "change": file.change.to_string(),
+                    "user_modified": file.user_modified,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "status": "success",
+            "upgraded": upgraded,
+            "dry_run": self.dry_run,
+            "version": version,
+            "files": files,
+            "changelog": changelog,
+        })
+    }
+
+    /// Compare the rendered templates of the given agents against the files in the project directory
+    pub fn plan(config: &ProjectConfig, agents: &[Agent], directory: &Path) -> Result<Vec<PlannedFile>> {
+        let (templates, _) = TemplateSystem::resolve_templates(agents);
+        TemplateSystem::validate_target_paths(&templates, directory)?;
+        let context = TemplateSystem::template_context(config, directory);
+        templates
+            .into_iter()
+            .map(|template| {
+                let name = template.name;
+                let rendered = templates::render_template_file(
+                    &template.content,
+                    name,
+                    &TemplateSystem::agent_context(&context, &template.agent),
+                );
+                let content = TemplateSystem::with_provenance(&template, &rendered, &context);
+                let path = directory.join(name);
+                if !path.is_file() {
+                    return Ok(PlannedFile {
+                        name,
+                        content,
+                        change: FileChange::New,
+                        user_modified: false,
+                    });
+                }
+
+                let current_hash = match FileOps::hash_file_with_limit(&path, DEFAULT_MAX_HASH_SIZE)? {
+                    FileHash::Hashed(sha256) => sha256,
+                    FileHash::TooLarge(size) => {
+                        status!(
+                            "{} {} is too large to verify ({} bytes); it will be backed up and replaced",
+                            Status::Warn,
+                            name, size
+                        );
+                        return Ok(PlannedFile {
+                            name,
+                            content,
+                            change: FileChange::Updated,
+                            user_modified: true,
+                        });
+                    }
+                };
+                // A file that only differs in its provenance line is updated, but
+                // does not count as modified by the user
+                let current = fs::read(&path).map_err(|e| {
+                    ConfigError::io_error(format!("Failed to read '{}': {}", path.display(), e))
+                })?;
+                let change = if current == content.as_bytes() {
+                    FileChange::Unchanged
+                } else {
+                    FileChange::Updated
+                };
+
+                // Files without a recorded hash are treated as modified so they are backed up
+                let user_modified = change == FileChange::Updated
+                    && current_hash != FileOps::hash_tracked_content(&content)
+                    && config
+                        .tracked_file(name)
+                        .is_none_or(|tracked| tracked.sha256 != current_hash);
+
+                Ok(PlannedFile {
+                    name,
+                    content,
+                    change,
+                    user_modified,
+                })
+            })
+            .collect()
+    }
+
+    /// Changelog entries between each outdated agent's version and `version`,
+    /// and the agents without any
+    ///
+    /// `--since-version` replaces the version each agent was deployed with.
+    fn changelog<'a>(
+        &self,
+        outdated: &'a [(Agent, Option<PackageVersion>)],
+        version: &PackageVersion,
+    ) -> (Vec<&'static ChangelogEntry>, Vec<&'a Agent>) {
+        let mut entries = Vec::new();
+        let mut undocumented = Vec::new();
+        for (agent, installed) in outdated {
+            let since = self.since_version.as_ref().or(installed.as_ref());
+            let agent_entries = changelog::between(Some(agent), since, version);
+            if agent_entries.is_empty() {
+                status!(
+                    "{} No changelog entries for {} since {}; the changed files are shown as diffs",
+                    Status::Info,
+                    agent,
+                    since.map_or("the first release".to_string(), |v| v.to_string())
+                );
+                undocumented.push(agent);
+            }
+            entries.extend(agent_entries);
+        }
+        (entries, undocumented)
+    }
+
+    /// Print what changed in the templates, release by release
+    fn display_changelog(entries: &[&ChangelogEntry]) {
+        if entries.is_empty() {
+            return;
+        }
+        status!("📝 What changed:");
+        for entry in entries {
+            status!("   • {} ({}): {} [{}]", entry.version, entry.agent, entry.summary, entry.files.join(", "));
+        }
+        status!();
+    }
+
+    /// Print the per-file summary of the upgrade
+    fn display_plan(plan: &[PlannedFile]) {
+        status!("📄 Template files:");
+        for file in plan {
+            if file.user_modified {
+                status!("   • {} - {} (modified locally, will be backed up)", file.name, file.change);
+            } else {
+                status!("   • {} - {}", file.name, file.change);
+            }
+        }
+        status!();
+    }
+
+    /// Show how each locally modified file differs from the template that
+    /// replaces it, so the edits can be carried over after the upgrade
+    ///
+    /// With `all_updated`, every file the upgrade replaces is shown, for
+    /// upgrades the changelog does not describe.
+    fn preview_changes(&self, plan: &[PlannedFile], all_updated: bool) {
+        let style = DiffStyle::from_word_diff(self.word_diff);
+        let shown = |file: &&PlannedFile| file.user_modified || (all_updated && file.change == FileChange::Updated);
+        for file in plan.iter().filter(shown) {
+            let path = self.directory.join(file.name);
+            // Files too large to hash are not read in full either
+            if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > DEFAULT_MAX_HASH_SIZE) {
+                continue;
+            }
+            if let Ok(current) = fs::read(&path) {
+                diff::preview(&String::from_utf8_lossy(&current), &file.content, file.name, style);
+            }
+        }
+    }
+
+    /// Copy a file to `<name>.bak` next to the original
+    fn backup_file(path: &Path) -> Result<PathBuf> {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".bak");
+        let backup = PathBuf::from(backup);
+
+        fs::copy(path, &backup).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&backup),
+            _ => ConfigError::from(e),
+        })?;
+
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::ConfigLayout;
+    use tempfile::TempDir;
+
+    /// Deploy the claude templates and record them at an older package version
+    fn setup_project(dir: &Path, version: &str) -> ProjectConfig {
+        let mut config = ProjectConfig::new(Agent::Claude);
+        let context = TemplateSystem::template_context(&config, dir);
+        let deployed = TemplateSystem::deploy_templates(&[Agent::Claude], dir, &context).unwrap();
+        config
+            .add_package(Package::new(Agent::Claude.default_package_id(), version).unwrap())
+            .unwrap();
+        for path in deployed {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            config.track_file(TrackedFile::new(name, FileOps::hash_file(&path).unwrap()));
+        }
+        FileOps::write_config_to_directory(&config, dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn test_upgrade_already_current_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), env!("CARGO_PKG_VERSION"));
+        let config_path = FileOps::get_config_path(temp_dir.path(), ConfigLayout::File);
+        let before = fs::read_to_string(&config_path).unwrap();
+
+        let cmd = UpgradeCommand {
+            directory: temp_dir.path().to_path_buf(),
+            dry_run: false,
+            word_diff: false,
+            since_version: None,
+        };
+        assert!(cmd.execute().is_ok());
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_upgrade_bumps_package_version() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), "0.0.1");
+
+        let cmd = UpgradeCommand {
+            directory: temp_dir.path().to_path_buf(),
+            dry_run: false,
+            word_diff: false,
+            since_version: None,
+        };
+        assert!(cmd.execute().is_ok());
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        let package = config.get_package(Agent::Claude.default_package_id()).unwrap();
+        assert_eq!(package.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_upgrade_backs_up_modified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), "0.0.1");
+        fs::write(temp_dir.path().join("CLAUDE.md"), "my edits").unwrap();
+
+        let cmd = UpgradeCommand {
+            directory: temp_dir.path().to_path_buf(),
+            dry_run: false,
+            word_diff: false,
+            since_version: None,
+        };
+        assert!(cmd.execute().is_ok());
+
+        let backup = fs::read_to_string(temp_dir.path().join("CLAUDE.md.bak")).unwrap();
+        assert_eq!(backup, "my edits");
+        let restored = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+        assert_ne!(restored, "my edits");
+        assert!(!temp_dir.path().join("README.md.bak").exists());
+    }
+
+    #[test]
+    fn test_changelog_between_versions() {
+        let current = PackageVersion::current();
+        let outdated = [(Agent::Claude, Some(PackageVersion::new(0, 0, 1))), (Agent::Copilot, None)];
+        let mut cmd = UpgradeCommand {
+            directory: PathBuf::from("."),
+            dry_run: true,
+            word_diff: false,
+            since_version: None,
+        };
+
+        let (entries, undocumented) = cmd.changelog(&outdated, &current);
+        assert!(entries.iter().any(|entry| entry.agent == Agent::Claude));
+        assert!(entries.iter().any(|entry| entry.agent == Agent::Copilot));
+        assert!(undocumented.is_empty());
+        let summary = cmd.json_summary(&[], &entries, &current, false);
+        assert_eq!(summary["changelog"][0]["agent"], "claude");
+
+        // Nothing changed since this release, so the diffs have to tell
+        cmd.since_version = Some(current.clone());
+        let (entries, undocumented) = cmd.changelog(&outdated, &current);
+        assert!(entries.is_empty());
+        assert_eq!(undocumented, [&Agent::Claude, &Agent::Copilot]);
+    }
+
+    #[test]
+    fn test_upgrade_dry_run_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), "0.0.1");
+        fs::remove_file(temp_dir.path().join("README.md")).unwrap();
+        let config_path = FileOps::get_config_path(temp_dir.path(), ConfigLayout::File);
+        let before = fs::read_to_string(&config_path).unwrap();
+
+        let cmd = UpgradeCommand {
+            directory: temp_dir.path().to_path_buf(),
+            dry_run: true,
+            word_diff: false,
+            since_version: None,
+        };
+        assert!(cmd.execute().is_ok());
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+        assert!(!temp_dir.path().join("README.md").exists());
+    }
+
+    #[test]
+    fn test_upgrade_skips_inactive_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = setup_project(temp_dir.path(), "0.0.1");
+        let package_id = Agent::Claude.default_package_id();
+        let package = config.remove_package(package_id).unwrap();
+        // PATH is set in any environment the tests run in
+        config
+            .add_package(package.with_condition("not(env_set(\"PATH\"))".parse().unwrap()))
+            .unwrap();
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        let cmd = UpgradeCommand {
+            directory: temp_dir.path().to_path_buf(),
+            dry_run: false,
+            word_diff: false,
+            since_version: None,
+        };
+        assert!(cmd.execute().is_ok());
+
+        let config = FileOps::read_config_from_directory(temp_dir.path(), None).unwrap();
+        assert_eq!(config.get_package(package_id).unwrap().version, "0.0.1");
+    }
+
+    #[test]
+    fn test_plan_classifies_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = setup_project(temp_dir.path(), "0.0.1");
+        fs::remove_file(temp_dir.path().join("README.md")).unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "my edits").unwrap();
+
+        let plan = UpgradeCommand::plan(&config, &config.agents, temp_dir.path()).unwrap();
+        let claude = plan.iter().find(|f| f.name == "CLAUDE.md").unwrap();
+        assert_eq!(claude.change, FileChange::Updated);
+        assert!(claude.user_modified);
+
+        let readme = plan.iter().find(|f| f.name == "README.md").unwrap();
+        assert_eq!(readme.change, FileChange::New);
+        assert!(!readme.user_modified);
+    }
+
+    #[test]
+    fn test_plan_unchanged_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = setup_project(temp_dir.path(), "0.0.1");
+
+        let plan = UpgradeCommand::plan(&config, &config.agents, temp_dir.path()).unwrap();
+        assert!(plan.iter().all(|f| f.change == FileChange::Unchanged));
+    }
+
+    #[test]
+    fn test_plan_compares_rendered_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = ProjectConfig::new(Agent::Codeium);
+        config.set_metadata("project_name", "demo");
+        let context = TemplateSystem::template_context(&config, temp_dir.path());
+        TemplateSystem::deploy_templates(&[Agent::Codeium], temp_dir.path(), &context).unwrap();
+
+        let plan = UpgradeCommand::plan(&config, &config.agents, temp_dir.path()).unwrap();
+        let claude = plan.iter().find(|f| f.name == "CLAUDE.md").unwrap();
+        assert!(claude.content.starts_with("# demo: Codeium Instructions"));
+        assert!(plan.iter().all(|f| f.change == FileChange::Unchanged));
+
+        // Renaming the project changes the rendered content
+        config.set_metadata("project_name", "renamed");
+        let plan = UpgradeCommand::plan(&config, &config.agents, temp_dir.path()).unwrap();
+        let claude = plan.iter().find(|f| f.name == "CLAUDE.md").unwrap();
+        assert_eq!(claude.change, FileChange::Updated);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::config::UserConfig;
+use crate::config::user::USER_CONFIG_KEYS;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Path of the user configuration, or an error when there is no configuration directory
+fn user_config_path() -> Result<PathBuf> {
+    UserConfig::path().ok_or_else(|| {
+        ConfigError::validation_error(format!(
+            "Cannot determine the user configuration directory; set {}",
+            crate::config::user::USER_CONFIG_DIR_ENV_VAR
+        ))
+    })
+}
+
+/// Where the effective value of a setting comes from
+fn source(key: &str, file: &UserConfig) -> Result<&'static str> {
+    if std::env::var(UserConfig::env_var(key)).is_ok_and(|value| !value.is_empty()) {
+        Ok("environment")
+    } else if file.get(key)?.is_some() {
+        Ok("user config")
+    } else {
+        Ok("unset")
+    }
+}
+
+/// Store a default in the user configuration
+#[derive(Args)]
+pub struct ConfigSetCommand {
+    /// Setting to change: default_agent, default_on_conflict, no_color or max_backups
+    pub key: String,
+
+    /// New value for the setting
+    pub value: String,
+}
+
+impl ConfigSetCommand {
+    /// Execute the config set command
+    pub fn execute(&self) -> Result<()> {
+        let path = user_config_path()?;
+        let mut config = FileOps::read_user_config(&path)?;
+        config.set(&self.key, &self.value)?;
+        FileOps::write_user_config(&config, &path).map_err(|e| {
+            e.add_context("user configuration writing", format!("Writing {}", path.display()))
+        })?;
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "key": self.key, "value": config.get(&self.key)? }));
+        } else {
+            status!("{} Set {} in {}", Status::Ok, self.key, path.display());
+        }
+        if std::env::var(UserConfig::env_var(&self.key)).is_ok_and(|value| !value.is_empty()) {
+            status!("{} {} is set and takes precedence", Status::Warn, UserConfig::env_var(&self.key));
+        }
+        Ok(())
+    }
+}
+
+/// Print a default from the user configuration
+#[derive(Args)]
+pub struct ConfigGetCommand {
+    /// Setting to print: default_agent, default_on_conflict, no_color or max_backups
+    pub key: String,
+}
+
+impl ConfigGetCommand {
+    /// Execute the config get command
+    ///
+    /// Prints the effective value, including environment overrides; prints
+    /// nothing for a setting that is not set.
+    pub fn execute(&self) -> Result<()> {
+        let file = FileOps::read_user_config(user_config_path()?)?;
+        let value = UserConfig::load()?.get(&self.key)?;
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "key": self.key, "value": value, "source": source(&self.key, &file)? }));
+        } else if let Some(value) = value {
+            println!("{}", value);
+        }
+        Ok(())
+    }
+}
+
+/// List the defaults in the user configuration
+#[derive(Args)]
+pub struct ConfigListCommand {}
+
+impl ConfigListCommand {
+    /// Execute the config list command
+    pub fn execute(&self) -> Result<()> {
+        let path = user_config_path()?;
+        let file = FileOps::read_user_config(&path)?;
+        let effective = UserConfig::load()?;
+
+        if output::is_json() {
+            let mut settings = serde_json::Map::new();
+            for key in USER_CONFIG_KEYS {
+                settings.insert(
+                    key.to_string(),
+                    serde_json::json!({ "value": effective.get(key)?, "source": source(key, &file)? }),
+                );
+            }
+            output::print_json(serde_json::json!({ "path": path, "settings": settings }));
+            return Ok(());
+        }
+
+        status!("{} User configuration: {}", Status::Info, path.display());
+        for key in USER_CONFIG_KEYS {
+            match effective.get(key)? {
+                Some(value) => status!("   {} = {} ({})", key, value, source(key, &file)?),
+                None => status!("   {} is not set", key),
+            }
+        }
+        Ok(())
+    }
+}
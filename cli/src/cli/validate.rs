@@ -0,0 +1,74 @@
+use crate::config::ValidationIssue;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::FileOps;
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Check that the project configuration file is valid
+#[derive(Args)]
+pub struct ValidateCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Look for the configuration in the directory and its parents, up to
+    /// the root of the repository
+    #[arg(long)]
+    pub auto_discover: bool,
+
+    /// Fail when the configuration has warnings, such as a package
+    /// downloaded over http
+    #[arg(long)]
+    pub deny_warnings: bool,
+}
+
+impl ValidateCommand {
+    /// Execute the validate command
+    ///
+    /// Reading the configuration validates it, so the problems surface as the
+    /// error of the read; all of them are listed at once. Warnings are listed
+    /// too, and fail the command with `--deny-warnings`.
+    pub fn execute(&self) -> Result<()> {
+        let config_path = if self.auto_discover {
+            FileOps::find_config(&self.directory).ok_or_else(|| {
+                ConfigError::not_found(&self.directory).add_context(
+                    "configuration discovery",
+                    format!(
+                        "No specforge configuration in {} or its parent directories",
+                        self.directory.display()
+                    ),
+                )
+            })?
+        } else {
+            FileOps::locate_config(&self.directory)
+        };
+        let add_context = |e: ConfigError| {
+            e.add_context(
+                "configuration validation",
+                format!("Validating {}", config_path.display()),
+            )
+        };
+        let (config, warnings) = FileOps::read_config_with_warnings(&config_path).map_err(add_context)?;
+        if self.deny_warnings {
+            config.validate_all().deny_warnings().into_result().map_err(add_context)?;
+        }
+
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "status": "valid", "valid": true, "config_path": config_path, "warnings": warnings }));
+        } else {
+            print_warnings(&warnings);
+            status!("{} {} is valid", Status::Ok, config_path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Print warnings about a configuration as status lines
+pub fn print_warnings(warnings: &[ValidationIssue]) {
+    for warning in warnings {
+        status!("{} {}", Status::Warn, warning);
+    }
+}
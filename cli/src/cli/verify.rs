@@ -0,0 +1,204 @@
+use crate::drift::{self, DriftReport, FileStatus};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, LOCK_FILE_NAME};
+use crate::format;
+use crate::output;
+use crate::status;
+use crate::templates::{TemplateIssue, TemplateSystem};
+use crate::ui::Status;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Check deployed template files against the checksums recorded at deploy time
+#[derive(Args)]
+pub struct VerifyCommand {
+    /// Project directory containing the .specforge.json file
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Give files whose content matches but whose mode drifted their recorded mode
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Also check the instructions files against each agent's size limits
+    #[arg(long)]
+    pub lint: bool,
+}
+
+impl VerifyCommand {
+    /// Execute the verify command
+    ///
+    /// Fails when any tracked file was modified, removed or lost its
+    /// recorded mode, or a package no longer matches its pin in the lock file. With `--fix`, modes are restored without rewriting the
+    /// files, so only content changes remain. With `--lint`, an instructions
+    /// file over the agent's hard size limit fails the command as well. In
+    /// JSON output mode the report is printed before the error, so CI jobs
+    /// get both.
+    pub fn execute(&self) -> Result<()> {
+        let report = drift::check(&self.directory).map_err(|e| {
+            e.add_context(
+                "configuration loading",
+                format!("Reading the configuration in {}", self.directory.display()),
+            )
+        })?;
+        let restored = if self.fix {
+            drift::restore_modes(&self.directory, &report)
+                .map_err(|e| e.add_context("mode restore", "Restoring the recorded file modes"))?
+        } else {
+            Vec::new()
+        };
+        let mismatches = Self::mismatches(&report, &restored);
+        let config_path = FileOps::locate_config(&self.directory);
+        let lock_mismatches = FileOps::read_config(&config_path)
+            .and_then(|config| FileOps::lock_mismatches(&config, &config_path))
+            .map_err(|e| e.add_context("lock file check", format!("Checking packages against {}", LOCK_FILE_NAME)))?;
+        let lint = if self.lint { self.lint()? } else { Vec::new() };
+
+        if output::is_json() {
+            let mut summary = Self::json_summary(&report, mismatches, &restored, &lock_mismatches);
+            if self.lint {
+                summary["lint"] = lint.iter().map(ToString::to_string).collect();
+            }
+            output::print_json(summary);
+        } else {
+            let mut files: Vec<_> = report.files.iter().collect();
+            files.sort_by(|a, b| format::listing_cmp(&a.path, &b.path));
+            for file in files {
+                match file.status {
+                    FileStatus::Clean => status!("{} {}", Status::Ok, file.path),
+                    FileStatus::ModeChanged if restored.contains(&file.path) => {
+                        status!("{} {} (mode restored)", Status::Fixed, file.path)
+                    }
+                    FileStatus::ModeChanged => status!("{} {} (mode changed)", Status::Error, file.path),
+                    FileStatus::Modified => status!("{} {} (modified)", Status::Error, file.path),
+                    FileStatus::Missing => status!("{} {} (missing)", Status::Error, file.path),
+                    FileStatus::Unverified => status!("{} {} (not verified)", Status::Warn, file.path),
+                }
+            }
+            for mismatch in &lock_mismatches {
+                status!("{} {}", Status::Error, mismatch);
+            }
+            for warning in &report.warnings {
+                status!("{} {}", Status::Warn, warning);
+            }
+        }
+
+        let lint_result = TemplateSystem::report_template_issues(&lint, false);
+
+        let mut errors = Vec::new();
+        if mismatches > 0 {
+            errors.push(ConfigError::verification_failed(mismatches));
+        }
+        if !lock_mismatches.is_empty() {
+            let lock_path = FileOps::lock_directory(&config_path).join(LOCK_FILE_NAME);
+            errors.push(ConfigError::lock_mismatch(lock_path, lock_mismatches));
+        }
+        if !errors.is_empty() {
+            return Err(ConfigError::multiple(errors));
+        }
+        lint_result?;
+        status!();
+        status!("🎉 All {} tracked files match their recorded checksums", report.files.len());
+        Ok(())
+    }
+
+    /// Size problems of the instructions files of the configured agents
+    fn lint(&self) -> Result<Vec<TemplateIssue>> {
+        let config = FileOps::read_config(FileOps::locate_config(&self.directory))?;
+        Ok(TemplateSystem::check_deployed_instructions(&self.directory, &config.agents))
+    }
+
+    /// Number of tracked files that were modified, removed or whose mode
+    /// drifted and was not restored
+    fn mismatches(report: &DriftReport, restored: &[String]) -> usize {
+        report
+            .files
+            .iter()
+            .filter(|file| match file.status {
+                FileStatus::Modified | FileStatus::Missing => true,
+                FileStatus::ModeChanged => !restored.contains(&file.path),
+                FileStatus::Clean | FileStatus::Unverified => false,
+            })
+            .count()
+    }
+
+    /// Machine-readable result printed to stdout in JSON output mode
+    fn json_summary(
+        report: &DriftReport,
+        mismatches: usize,
+        restored: &[String],
+        lock_mismatches: &[String],
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "verified": mismatches == 0 && lock_mismatches.is_empty(),
+            "mismatches": mismatches,
+            "files": report.files,
+            "mode_restored": restored,
+            "lock_mismatches": lock_mismatches,
+            "warnings": report.warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, LockFile, Package, ProjectConfig, TrackedFile};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_verify_detects_modified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = ProjectConfig::new(Agent::Claude);
+        for name in ["CLAUDE.md", "README.md"] {
+            fs::write(temp_dir.path().join(name), "original\n").unwrap();
+            config.track_file(TrackedFile::new(name.to_string(), FileOps::hash_content("original\n")));
+        }
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        let cmd = VerifyCommand {
+            directory: temp_dir.path().to_path_buf(),
+            fix: false,
+            lint: false,
+        };
+        assert!(cmd.execute().is_ok());
+
+        fs::write(temp_dir.path().join("CLAUDE.md"), "tampered\n").unwrap();
+        fs::remove_file(temp_dir.path().join("README.md")).unwrap();
+        let report = drift::check(temp_dir.path()).unwrap();
+        assert_eq!(VerifyCommand::mismatches(&report, &[]), 2);
+
+        let summary = VerifyCommand::json_summary(&report, 2, &[], &[]);
+        assert_eq!(summary["verified"], false);
+        assert_eq!(summary["files"][0]["status"], "modified");
+        assert_eq!(summary["files"][1]["status"], "missing");
+        assert!(matches!(cmd.execute(), Err(ConfigError::VerificationFailed(2))));
+    }
+
+    #[test]
+    fn test_verify_checks_lock_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.add_package(Package::new("team-prompts", "1.0.0").unwrap()).unwrap();
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+        FileOps::write_lock_file(&LockFile::from_config(&config), temp_dir.path()).unwrap();
+
+        let cmd = VerifyCommand {
+            directory: temp_dir.path().to_path_buf(),
+            fix: false,
+            lint: false,
+        };
+        assert!(cmd.execute().is_ok());
+
+        config.remove_package("team-prompts");
+        config.add_package(Package::new("team-prompts", "2.0.0").unwrap()).unwrap();
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+        match cmd.execute() {
+            Err(ConfigError::LockMismatch { mismatches, .. }) => {
+                assert_eq!(mismatches, ["'team-prompts' is configured at version 2.0.0 but locked to 1.0.0"]);
+            }
+            other => panic!("expected a lock mismatch, got {:?}", other),
+        }
+    }
+}
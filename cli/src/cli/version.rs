@@ -0,0 +1,47 @@
+use crate::config::{PackageVersion, VersionRequirement};
+use crate::error::{ConfigError, Result};
+use crate::output;
+use clap::Args;
+
+/// Print the specforge version, or check it against a requirement
+#[derive(Args)]
+pub struct VersionCommand {
+    /// Exit with status 1 unless this release satisfies the requirement,
+    /// such as ">=0.4" or "^1.2.0"
+    #[arg(long, value_name = "REQUIREMENT")]
+    pub check: Option<String>,
+}
+
+impl VersionCommand {
+    /// Execute the version command
+    ///
+    /// The check only looks at the running binary, so it works outside of a
+    /// project. A malformed requirement fails with
+    /// [`ConfigError::InvalidVersionRequirement`].
+    pub fn execute(&self) -> Result<()> {
+        let actual = PackageVersion::current();
+        let Some(requirement) = &self.check else {
+            if output::is_json() {
+                output::print_json(serde_json::json!({ "version": actual.to_string() }));
+            } else {
+                println!("specforge {}", actual);
+            }
+            return Ok(());
+        };
+
+        let requirement: VersionRequirement = requirement.parse()?;
+        let satisfied = requirement.matches(&actual);
+        if output::is_json() {
+            output::print_json(serde_json::json!({
+                "version": actual.to_string(),
+                "requirement": requirement.to_string(),
+                "satisfied": satisfied,
+            }));
+        }
+        if satisfied {
+            Ok(())
+        } else {
+            Err(ConfigError::version_check_failed(requirement.to_string(), actual.to_string()))
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::cli::init::AgentType;
+use crate::config::{Agent, WorkspaceConfig};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{FileOps, WORKSPACE_CONFIG_FILE_NAME};
+use crate::output;
+use crate::status;
+use crate::ui::Status;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+
+/// Manage the workspace configuration shared by the projects of a monorepo
+#[derive(Args)]
+pub struct WorkspaceCommand {
+    #[command(subcommand)]
+    pub command: WorkspaceSubcommand,
+}
+
+/// Available workspace subcommands
+#[derive(Subcommand)]
+pub enum WorkspaceSubcommand {
+    /// Write .specforge-workspace.json at the repository root
+    Init(WorkspaceInitCommand),
+}
+
+impl WorkspaceCommand {
+    /// Execute the selected workspace subcommand
+    pub fn execute(&self) -> Result<()> {
+        match &self.command {
+            WorkspaceSubcommand::Init(init_cmd) => init_cmd.execute(),
+        }
+    }
+}
+
+/// Create the workspace configuration of a monorepo
+#[derive(Args)]
+pub struct WorkspaceInitCommand {
+    /// Agent used by projects in the workspace that do not pick their own
+    #[arg(short, long, value_enum)]
+    pub agent: AgentType,
+
+    /// Repository root to write the workspace configuration to
+    #[arg(short, long, default_value = ".")]
+    pub directory: PathBuf,
+
+    /// Project directory relative to the root (repeat for several; all directories belong to the workspace when omitted)
+    #[arg(short, long = "member", value_name = "DIR")]
+    pub members: Vec<PathBuf>,
+
+    /// Replace an existing workspace configuration
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+impl WorkspaceInitCommand {
+    /// Execute the workspace init command
+    pub fn execute(&self) -> Result<()> {
+        let path = self.directory.join(WORKSPACE_CONFIG_FILE_NAME);
+        if path.exists() && !self.force {
+            return Err(ConfigError::file_exists(&path)
+                .add_context("workspace initialization", "Use --force to replace it"));
+        }
+
+        let mut config = WorkspaceConfig::new(Agent::from(self.agent.clone()));
+        config.members = self.members.clone();
+
+        FileOps::ensure_directory_exists(&self.directory)?;
+        let path = FileOps::write_workspace_config(&config, &self.directory)
+            .map_err(|e| e.add_context("workspace initialization", format!("Writing {}", path.display())))?;
+
+        status!("{} Wrote {}", Status::Ok, path.display());
+        status!("   `specforge init` below this directory uses {} unless --agent is given", config.default_agent);
+        if output::is_json() {
+            output::print_json(serde_json::json!({ "workspace_config": path, "default_agent": config.default_agent }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_workspace_init_writes_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = WorkspaceInitCommand {
+            agent: AgentType::Codeium,
+            directory: temp_dir.path().to_path_buf(),
+            members: vec![PathBuf::from("services/billing")],
+            force: false,
+        };
+        command.execute().unwrap();
+
+        let config = FileOps::read_workspace_config(temp_dir.path().join(WORKSPACE_CONFIG_FILE_NAME)).unwrap();
+        assert_eq!(config.default_agent, Agent::Codeium);
+        assert_eq!(config.members, vec![PathBuf::from("services/billing")]);
+
+        // An existing workspace configuration is only replaced with --force
+        assert!(command.execute().is_err());
+        let forced = WorkspaceInitCommand { force: true, ..command };
+        assert!(forced.execute().is_ok());
+    }
+}
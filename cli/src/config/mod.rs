@@ -1,3 +0,0 @@
-pub mod project;
-
-pub use project::*;
\ No newline at end of file
@@ -0,0 +1,315 @@
+//! Initialize a project from another Rust program, without the command line.
+//!
+//! ```no_run
+//! use specforge::config::Agent;
+//! use specforge::init::{self, InitOptions};
+//! use specforge::output::SilentReporter;
+//! use std::sync::Arc;
+//!
+//! let outcome = init::run(
+//!     InitOptions::new("services/billing")
+//!         .agent(Agent::Claude)
+//!         .project_name("billing")
+//!         .reporter(Arc::new(SilentReporter)),
+//! )?;
+//! println!("wrote {}", outcome.config_path.display());
+//! # Ok::<(), specforge::ConfigError>(())
+//! ```
+
+use crate::cli::init::{AgentType, InitCommand, parse_template_variable, validate_output_directory};
+use crate::config::{Agent, ProjectConfig};
+use crate::error::Result;
+use crate::file_ops::{ConfigFormat, ConflictPolicy, FileOps, FileWrite};
+use crate::output::{self, Reporter};
+use crate::prompt::{self, NoPrompter, Prompter, TerminalPrompter};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// What to initialize, built up with chained calls
+///
+/// Options left out behave like the `specforge init` flags they stand for.
+/// Without [`InitOptions::interactive`] nothing is asked: a missing agent
+/// and conflicting files fail instead of prompting.
+#[derive(Clone)]
+pub struct InitOptions {
+    agents: Vec<Agent>,
+    output_directory: PathBuf,
+    project_name: Option<String>,
+    force: bool,
+    on_conflict: Option<ConflictPolicy>,
+    backup: bool,
+    vars: Vec<(String, String)>,
+    interactive: bool,
+    reporter: Option<Arc<dyn Reporter>>,
+    prompter: Option<Arc<dyn Prompter>>,
+}
+
+impl InitOptions {
+    /// Options for initializing the project in `output_directory`
+    pub fn new(output_directory: impl Into<PathBuf>) -> Self {
+        Self {
+            agents: Vec::new(),
+            output_directory: output_directory.into(),
+            project_name: None,
+            force: false,
+            on_conflict: None,
+            backup: false,
+            vars: Vec::new(),
+            interactive: false,
+            reporter: None,
+            prompter: None,
+        }
+    }
+
+    /// Configure an agent; call again to configure several
+    pub fn agent(mut self, agent: Agent) -> Self {
+        self.agents.push(agent);
+        self
+    }
+
+    /// Set the project name
+    pub fn project_name(mut self, project_name: impl Into<String>) -> Self {
+        self.project_name = Some(project_name.into());
+        self
+    }
+
+    /// Overwrite existing files, like `--force`
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// What to do with existing files, like `--on-conflict`
+    pub fn on_conflict(mut self, policy: ConflictPolicy) -> Self {
+        self.on_conflict = Some(policy);
+        self
+    }
+
+    /// Back up files before overwriting them, like `--backup`
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// Set a template variable, like `--var KEY=VALUE`
+    pub fn var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.push((key.into(), value.into()));
+        self
+    }
+
+    /// Ask questions on the terminal, or with the prompter given to [`InitOptions::prompter`]
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Receive status lines instead of printing them
+    pub fn reporter(mut self, reporter: Arc<dyn Reporter>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Answer questions programmatically; implies [`InitOptions::interactive`]
+    pub fn prompter(mut self, prompter: Arc<dyn Prompter>) -> Self {
+        self.prompter = Some(prompter);
+        self.interactive = true;
+        self
+    }
+
+    /// The init command these options stand for
+    fn command(&self) -> Result<InitCommand> {
+        let vars = self
+            .vars
+            .iter()
+            .map(|(key, value)| parse_template_variable(&format!("{}={}", key, value)))
+            .collect::<Result<_>>()?;
+        Ok(InitCommand {
+            agent: self.agents.iter().cloned().map(AgentType::from).collect(),
+            output_directory: validate_output_directory(&self.output_directory.to_string_lossy())?,
+            project_name: self.project_name.clone(),
+            force: self.force,
+            on_conflict: self.on_conflict,
+            backup: self.backup,
+            word_diff: false,
+            config_format: ConfigFormat::Json,
+            use_directory: false,
+            vars,
+            template_dir: None,
+            strict_templates: false,
+            no_provenance: false,
+            update_gitignore: false,
+            gitignore_config: false,
+            package_url: None,
+            timeout: 30,
+            answers: None,
+            batch: None,
+            continue_on_error: false,
+        })
+    }
+}
+
+/// Result of initializing a project
+#[derive(Debug, Clone)]
+pub struct InitOutcome {
+    /// Configuration file that was written
+    pub config_path: PathBuf,
+    /// The configuration, with the deployed files it tracks
+    pub config: ProjectConfig,
+    /// Every template file, including the ones left unchanged or skipped
+    pub deployed_files: Vec<FileWrite>,
+    /// Whether .gitignore was updated
+    pub gitignore_updated: bool,
+}
+
+/// Initialize a project as `specforge init` does
+///
+/// When the user agrees to migrate a reforge configuration instead, the
+/// outcome describes the migrated configuration and no files are deployed.
+pub fn run(options: InitOptions) -> Result<InitOutcome> {
+    let prompter: Arc<dyn Prompter> = match (&options.prompter, options.interactive) {
+        (_, false) => Arc::new(NoPrompter),
+        (Some(prompter), true) => prompter.clone(),
+        (None, true) => Arc::new(TerminalPrompter),
+    };
+    let initialize = || {
+        let command = options.command()?;
+        match command.initialize()? {
+            Some(outcome) => Ok(outcome),
+            None => {
+                let config_path = FileOps::locate_config(&command.output_directory);
+                Ok(InitOutcome {
+                    config: FileOps::read_config(&config_path)?,
+                    config_path,
+                    deployed_files: Vec::new(),
+                    gitignore_updated: false,
+                })
+            }
+        }
+    };
+
+    prompt::with_prompter(prompter, || match options.reporter.clone() {
+        Some(reporter) => output::with_reporter(reporter, initialize),
+        None => initialize(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::WriteOutcome;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Reporter for Recorder {
+        fn status(&self, line: &str) {
+            self.0.lock().unwrap().push(line.to_string());
+        }
+    }
+
+    /// Picks the given agent and refuses to overwrite anything
+    struct Scripted(&'static str);
+
+    impl Prompter for Scripted {
+        fn is_interactive(&self) -> bool {
+            true
+        }
+
+        fn confirm(&self, _prompt: &str, _default: bool) -> Result<Option<bool>> {
+            Ok(Some(false))
+        }
+
+        fn select(&self, prompt: &str, items: &[String], _default: usize) -> Result<Option<usize>> {
+            if prompt.contains("Overwrite") {
+                return Ok(items.iter().position(|item| item == "No"));
+            }
+            Ok(items.iter().position(|item| item.starts_with(self.0)))
+        }
+    }
+
+    #[test]
+    fn test_run_reports_through_the_reporter() {
+        let temp_dir = TempDir::new().unwrap();
+        let recorder = Arc::new(Recorder::default());
+        let outcome = run(
+            InitOptions::new(temp_dir.path())
+                .agent(Agent::Copilot)
+                .project_name("demo")
+                .var("team", "payments")
+                .reporter(recorder.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.config_path, temp_dir.path().canonicalize().unwrap().join(".specforge.json"));
+        assert_eq!(outcome.config.project_name(), Some("demo"));
+        assert!(outcome.deployed_files.iter().all(|f| f.outcome == WriteOutcome::Created));
+        assert!(temp_dir.path().join("CLAUDE.md").exists());
+        let lines = recorder.0.lock().unwrap();
+        assert!(lines.iter().any(|line| line.contains("Selected agent: copilot")), "{:?}", lines);
+    }
+
+    #[test]
+    fn test_run_asks_the_prompter() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = InitOptions::new(temp_dir.path())
+            .prompter(Arc::new(Scripted("claude")))
+            .reporter(Arc::new(output::SilentReporter));
+        let outcome = run(options.clone()).unwrap();
+        assert_eq!(outcome.config.agents, vec![Agent::Claude]);
+
+        // The second run keeps the existing files, as the prompter answers "No"
+        let again = run(options.on_conflict(ConflictPolicy::Prompt)).unwrap();
+        assert!(again.deployed_files.iter().all(|f| f.outcome != WriteOutcome::Overwritten));
+    }
+
+    /// Picks claude and gives the project names in order
+    struct Naming(Mutex<Vec<&'static str>>);
+
+    impl Prompter for Naming {
+        fn is_interactive(&self) -> bool {
+            true
+        }
+
+        fn confirm(&self, _prompt: &str, _default: bool) -> Result<Option<bool>> {
+            Ok(Some(false))
+        }
+
+        fn select(&self, _prompt: &str, items: &[String], _default: usize) -> Result<Option<usize>> {
+            Ok(items.iter().position(|item| item.starts_with("claude")))
+        }
+
+        fn input(&self, _prompt: &str, _default: &str) -> Result<Option<String>> {
+            Ok(Some(self.0.lock().unwrap().remove(0).to_string()))
+        }
+    }
+
+    #[test]
+    fn test_run_asks_for_the_project_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let recorder = Arc::new(Recorder::default());
+        let prompter = Arc::new(Naming(Mutex::new(vec!["   ", "  demo ", "unused"])));
+        let options = InitOptions::new(temp_dir.path()).prompter(prompter.clone()).reporter(recorder.clone());
+        let outcome = run(options.clone()).unwrap();
+
+        // The blank answer is rejected and asked again
+        assert_eq!(outcome.config.project_name(), Some("demo"));
+        assert_eq!(*prompter.0.lock().unwrap(), ["unused"]);
+        let lines = recorder.0.lock().unwrap().clone();
+        assert!(lines.iter().any(|line| line.contains("project_name cannot be empty")), "{:?}", lines);
+
+        // --project-name is not asked again
+        let named = run(options.project_name("given").force(true)).unwrap();
+        assert_eq!(named.config.project_name(), Some("given"));
+        assert_eq!(*prompter.0.lock().unwrap(), ["unused"]);
+    }
+
+    #[test]
+    fn test_run_without_agent_does_not_prompt() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = run(InitOptions::new(temp_dir.path()).reporter(Arc::new(output::SilentReporter))).unwrap_err();
+        assert!(error.to_string().contains("--agent"), "{}", error);
+        assert!(!temp_dir.path().join(".specforge.json").exists());
+    }
+}
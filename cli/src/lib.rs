@@ -1,8 +1,10 @@
-pub mod config;
 pub mod cli;
-pub mod error;
-pub mod file_ops;
-pub mod templates;
+pub mod init;
 
-pub use error::{ConfigError, Result};
-pub use file_ops::FileOps;
\ No newline at end of file
+pub use specforge_core::{config, drift, error, file_ops, format, output, progress, prompt, status, templates, ui};
+#[cfg(feature = "remote")]
+pub use specforge_core::registry;
+#[cfg(feature = "test-util")]
+pub use specforge_core::testing;
+
+pub use specforge_core::{Agent, ConfigError, FileOps, ProjectConfig, Result};
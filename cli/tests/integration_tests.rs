@@ -9,13 +9,13 @@ fn test_full_init_flow_copilot() {
     let temp_dir = TempDir::new().unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "copilot", "--output-directory"])
+    cmd.args(["init", "--agent", "copilot", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("✅ Successfully created Specforge configuration"))
+        .stdout(predicate::str::contains("[ok] Successfully created Specforge configuration"))
         .stdout(predicate::str::contains("📄 Deployed 2 template files"))
         .stdout(predicate::str::contains("Selected agent: copilot"));
 
@@ -43,13 +43,13 @@ fn test_full_init_flow_claude() {
     let temp_dir = TempDir::new().unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "claude", "--output-directory"])
+    cmd.args(["init", "--agent", "claude", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("✅ Successfully created Specforge configuration"))
+        .stdout(predicate::str::contains("[ok] Successfully created Specforge configuration"))
         .stdout(predicate::str::contains("📄 Deployed 2 template files"))
         .stdout(predicate::str::contains("Selected agent: claude"));
 
@@ -71,19 +71,55 @@ fn test_full_init_flow_claude() {
     assert!(readme_content.contains("Claude Code Configuration"));
 }
 
+/// Test full init flow for codeium agent
+#[test]
+fn test_full_init_flow_codeium() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("[ok] Successfully created Specforge configuration"))
+        .stdout(predicate::str::contains("📄 Deployed 2 template files"))
+        .stdout(predicate::str::contains("Selected agent: codeium"));
+
+    // Verify files were created
+    assert!(temp_dir.path().join(".specforge.json").exists());
+    assert!(temp_dir.path().join("CLAUDE.md").exists());
+    assert!(temp_dir.path().join("codeium.json").exists());
+    assert!(!temp_dir.path().join("README.md").exists());
+
+    // Verify config content
+    let config_content = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config_content.contains("\"agent\": \"codeium\""));
+    assert!(config_content.contains("specforge-codeium-templates"));
+
+    // Verify template content
+    let claude_content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    assert!(claude_content.contains("Codeium"));
+
+    let codeium_content = fs::read_to_string(temp_dir.path().join("codeium.json")).unwrap();
+    let codeium_json: serde_json::Value = serde_json::from_str(&codeium_content).unwrap();
+    assert!(codeium_json.get("workspace").is_some());
+}
+
 /// Test invalid agent input handling
 #[test]
 fn test_invalid_agent_input() {
     let temp_dir = TempDir::new().unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "invalid-agent", "--output-directory"])
+    cmd.args(["init", "--agent", "invalid-agent", "--output-directory"])
         .arg(temp_dir.path());
 
     cmd.assert()
         .failure()
         .stderr(predicate::str::contains("invalid value 'invalid-agent'"))
-        .stderr(predicate::str::contains("possible values: copilot, claude"));
+        .stderr(predicate::str::contains("possible values: copilot, claude, codeium"));
 
     // Verify no files were created
     assert!(!temp_dir.path().join(".specforge.json").exists());
@@ -97,7 +133,7 @@ fn test_init_with_project_name() {
     let temp_dir = TempDir::new().unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&[
+    cmd.args([
         "init",
         "--agent", "copilot",
         "--project-name", "my-test-project",
@@ -108,7 +144,7 @@ fn test_init_with_project_name() {
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("✅ Successfully created Specforge configuration"));
+        .stdout(predicate::str::contains("[ok] Successfully created Specforge configuration"));
 
     // Verify config contains project name
     let config_content = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
@@ -121,7 +157,7 @@ fn test_file_creation_feedback() {
     let temp_dir = TempDir::new().unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "claude", "--output-directory"])
+    cmd.args(["init", "--agent", "claude", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
@@ -139,7 +175,7 @@ fn test_timing_requirements() {
     let start_time = std::time::Instant::now();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "copilot", "--output-directory"])
+    cmd.args(["init", "--agent", "copilot", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
@@ -163,7 +199,7 @@ fn test_init_in_existing_directory_with_files() {
     fs::create_dir(temp_dir.path().join("existing_dir")).unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "claude", "--output-directory"])
+    cmd.args(["init", "--agent", "claude", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
@@ -195,7 +231,7 @@ fn test_help_includes_init_command() {
 #[test]
 fn test_init_command_help() {
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--help"]);
+    cmd.args(["init", "--help"]);
 
     cmd.assert()
         .success()
@@ -213,7 +249,7 @@ fn test_overwrite_protection() {
 
     // Create initial configuration
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "copilot", "--output-directory"])
+    cmd.args(["init", "--agent", "copilot", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
     cmd.assert().success();
@@ -221,7 +257,7 @@ fn test_overwrite_protection() {
     // Try to init again without force flag (this would normally prompt in interactive mode)
     // For testing purposes, we test with force to ensure files are overwritten
     let mut cmd2 = Command::cargo_bin("specforge").unwrap();
-    cmd2.args(&["init", "--agent", "claude", "--output-directory"])
+    cmd2.args(["init", "--agent", "claude", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
@@ -238,7 +274,7 @@ fn test_template_deployment_file_structure() {
     let temp_dir = TempDir::new().unwrap();
 
     let mut cmd = Command::cargo_bin("specforge").unwrap();
-    cmd.args(&["init", "--agent", "copilot", "--output-directory"])
+    cmd.args(["init", "--agent", "copilot", "--output-directory"])
         .arg(temp_dir.path())
         .arg("--force");
 
@@ -268,4 +304,2341 @@ fn test_template_deployment_file_structure() {
             file
         );
     }
-}
\ No newline at end of file
+}
+/// Test that clean removes the files recorded during init
+#[test]
+fn test_clean_removes_deployed_files() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    // Verify deployed files were recorded in the config
+    let config_content = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&config_content).unwrap();
+    let files = json.get("files").unwrap().as_array().unwrap();
+    assert_eq!(files.len(), 2);
+
+    // Modify one of the files; --force removes it anyway
+    fs::write(temp_dir.path().join("README.md"), "my own notes").unwrap();
+
+    let mut clean = Command::cargo_bin("specforge").unwrap();
+    clean.args(["clean", "--directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    clean.assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 2 deployed file(s)"));
+
+    assert!(!temp_dir.path().join("CLAUDE.md").exists());
+    assert!(!temp_dir.path().join("README.md").exists());
+    assert!(temp_dir.path().join(".specforge.json").exists());
+
+    let config_content = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(!config_content.contains("\"files\""));
+}
+
+/// Test that deinit removes everything init created, even without tracked files
+#[test]
+fn test_deinit_removes_specforge() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    // Older configurations do not record the deployed files
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    json.as_object_mut().unwrap().remove("files");
+    fs::write(&config_path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+
+    let mut deinit = Command::cargo_bin("specforge").unwrap();
+    deinit.args(["deinit", "--directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    deinit.assert()
+        .success()
+        .stdout(predicate::str::contains("CLAUDE.md - remove"));
+
+    assert!(!config_path.exists());
+    assert!(!temp_dir.path().join("CLAUDE.md").exists());
+    assert!(!temp_dir.path().join("README.md").exists());
+
+    // A second run finds nothing to remove
+    let mut again = Command::cargo_bin("specforge").unwrap();
+    again.args(["deinit", "--directory"]).arg(temp_dir.path());
+    again.assert()
+        .success()
+        .stdout(predicate::str::contains("nothing to remove"));
+}
+
+/// Test that init inherits the agent from a workspace configuration further up
+#[test]
+fn test_init_inherits_workspace_agent() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut workspace = Command::cargo_bin("specforge").unwrap();
+    workspace.args(["workspace", "init", "--agent", "codeium", "--directory"])
+        .arg(temp_dir.path());
+    workspace.assert().success();
+    assert!(temp_dir.path().join(".specforge-workspace.json").exists());
+
+    let project = temp_dir.path().join("services").join("billing");
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--output-directory"])
+        .arg(&project)
+        .arg("--force");
+    init.assert()
+        .success()
+        .stdout(predicate::str::contains("from the workspace configuration"));
+
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(project.join(".specforge.json")).unwrap()).unwrap();
+    assert_eq!(config["agent"], "codeium");
+}
+
+/// Test that upgrade refreshes templates from an older package version
+#[test]
+fn test_upgrade_from_older_version() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "copilot", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    // Pretend the project was initialized by an older release
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    json["packages"][0]["version"] = serde_json::Value::from("0.0.1");
+    fs::write(&config_path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+    fs::write(temp_dir.path().join("CLAUDE.md"), "local changes").unwrap();
+
+    let mut dry_run = Command::cargo_bin("specforge").unwrap();
+    dry_run.args(["upgrade", "--dry-run", "--directory"])
+        .arg(temp_dir.path());
+    dry_run.assert()
+        .success()
+        .stdout(predicate::str::contains("CLAUDE.md - updated"))
+        .stdout(predicate::str::contains("README.md - unchanged"))
+        .stdout(predicate::str::contains("no files were written"));
+    assert!(!temp_dir.path().join("CLAUDE.md.bak").exists());
+
+    let mut upgrade = Command::cargo_bin("specforge").unwrap();
+    upgrade.args(["upgrade", "--directory"])
+        .arg(temp_dir.path());
+    upgrade.assert()
+        .success()
+        .stdout(predicate::str::contains("Upgraded copilot templates"));
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("CLAUDE.md.bak")).unwrap(),
+        "local changes"
+    );
+    let config_content = fs::read_to_string(&config_path).unwrap();
+    assert!(!config_content.contains("0.0.1"));
+
+    // A second upgrade has nothing to do
+    let mut again = Command::cargo_bin("specforge").unwrap();
+    again.args(["upgrade", "--directory"])
+        .arg(temp_dir.path());
+    again.assert()
+        .success()
+        .stdout(predicate::str::contains("already at version"));
+}
+
+/// Test init with several agents deploys all their templates
+#[test]
+fn test_init_with_multiple_agents() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "claude", "--agent", "codeium", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Selected agents: claude, codeium"))
+        .stdout(predicate::str::contains("Skipping CLAUDE.md from codeium: already provided by claude"))
+        .stdout(predicate::str::contains("📄 Deployed 3 template files"));
+
+    assert!(temp_dir.path().join("CLAUDE.md").exists());
+    assert!(temp_dir.path().join("README.md").exists());
+    assert!(temp_dir.path().join("codeium.json").exists());
+
+    let config_content = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&config_content).unwrap();
+    assert_eq!(json["agent"], serde_json::json!(["claude", "codeium"]));
+    assert_eq!(json["packages"].as_array().unwrap().len(), 2);
+
+    // The primary agent's instructions win the CLAUDE.md conflict
+    let claude_content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    assert!(claude_content.contains("Claude Code"));
+}
+
+#[test]
+fn test_doctor_reports_problems() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "copilot", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    // Copilot is not recommended in .vscode/extensions.json yet
+    let mut doctor = Command::cargo_bin("specforge").unwrap();
+    doctor.args(["doctor", "--directory"]).arg(temp_dir.path());
+    doctor.assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[ok] Configuration file"))
+        .stdout(predicate::str::contains("[error] Agent copilot"))
+        .stderr(predicate::str::contains("1 diagnostic check(s) failed"));
+
+    fs::create_dir(temp_dir.path().join(".vscode")).unwrap();
+    fs::write(
+        temp_dir.path().join(".vscode").join("extensions.json"),
+        r#"{ "recommendations": ["github.copilot"] }"#,
+    )
+    .unwrap();
+
+    let mut doctor = Command::cargo_bin("specforge").unwrap();
+    doctor.args(["doctor", "--directory"]).arg(temp_dir.path());
+    doctor.assert()
+        .success()
+        .stdout(predicate::str::contains("[ok] Template CLAUDE.md"))
+        .stdout(predicate::str::contains("checks passed"));
+}
+
+#[test]
+fn test_init_rejects_overlong_path_component() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("d".repeat(300));
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(&output_dir);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Path is too long"))
+        .stderr(predicate::str::contains("is 300"));
+
+    assert!(fs::read_dir(temp_dir.path()).unwrap().next().is_none());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_init_with_toml_config() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--config-format", "toml", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert()
+        .success()
+        .stdout(predicate::str::contains(".specforge.toml"));
+
+    assert!(!temp_dir.path().join(".specforge.json").exists());
+    let content = fs::read_to_string(temp_dir.path().join(".specforge.toml")).unwrap();
+    assert!(content.contains("agent = \"claude\""));
+    assert!(content.contains("[[packages]]"));
+
+    // Other commands find the TOML file on their own
+    let mut clean = Command::cargo_bin("specforge").unwrap();
+    clean.args(["clean", "--force", "--directory"]).arg(temp_dir.path());
+    clean.assert().success();
+    assert!(!temp_dir.path().join("CLAUDE.md").exists());
+    assert!(temp_dir.path().join(".specforge.toml").exists());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_init_with_yaml_config() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--config-format", "yaml", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert()
+        .success()
+        .stdout(predicate::str::contains(".specforge.yaml"));
+
+    let yaml_path = temp_dir.path().join(".specforge.yaml");
+    let content = fs::read_to_string(&yaml_path).unwrap();
+    assert!(content.contains("agent: claude"), "{}", content);
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert().success();
+
+    // Syntax errors point at the line in the YAML file
+    fs::write(&yaml_path, format!("{}\n  - : [", content)).unwrap();
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert()
+        .failure()
+        .stderr(predicate::str::contains("YAML syntax error at line"));
+}
+
+#[test]
+fn test_init_interpolates_template_variables() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--project-name", "demo", "--var", "team=platform", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    cmd.assert().success();
+
+    let content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    assert!(content.starts_with("# demo: Codeium Instructions"));
+    assert!(content.contains("Generated by specforge for codeium on "));
+    assert!(!content.contains("{{"));
+
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"team\": \"platform\""));
+
+    // Variable names must be usable as placeholders
+    let mut check = Command::cargo_bin("specforge").unwrap();
+    check.args(["init", "--agent", "codeium", "--var", "bad name=x", "--output-directory"])
+        .arg(temp_dir.path());
+    check.assert().failure();
+}
+
+#[test]
+fn test_init_with_custom_template_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = TempDir::new().unwrap();
+    fs::write(template_dir.path().join("CLAUDE.md"), "# {{project_name}} house rules\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--project-name", "demo", "--template-dir"])
+        .arg(template_dir.path())
+        .arg("--output-directory")
+        .arg(temp_dir.path())
+        .arg("--force");
+    cmd.assert().success();
+
+    let content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    // The provenance line records the hash of the custom template
+    let source = specforge::FileOps::hash_content("# {{project_name}} house rules\n");
+    assert_eq!(
+        content,
+        format!(
+            "# demo house rules\n<!-- specforge: package=specforge-codeium-templates version={} source=sha256:{} -->\n",
+            env!("CARGO_PKG_VERSION"),
+            source
+        )
+    );
+    // Not present in the custom directory, so the built-in template is used
+    assert!(temp_dir.path().join("codeium.json").exists());
+
+    let mut missing = Command::cargo_bin("specforge").unwrap();
+    missing.args(["init", "--agent", "codeium", "--template-dir"])
+        .arg(temp_dir.path().join("no-such-dir"))
+        .arg("--output-directory")
+        .arg(temp_dir.path());
+    missing.assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a directory"));
+}
+
+#[test]
+fn test_init_strict_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let template_dir = TempDir::new().unwrap();
+    fs::write(
+        template_dir.path().join("CLAUDE.md"),
+        "# {{projct_name}}\n\nSee [the guide](docs/guide.md).\n",
+    )
+    .unwrap();
+
+    let mut strict = Command::cargo_bin("specforge").unwrap();
+    strict.args(["init", "--agent", "codeium", "--strict-templates", "--template-dir"])
+        .arg(template_dir.path())
+        .arg("--output-directory")
+        .arg(temp_dir.path());
+    strict.assert()
+        .failure()
+        .code(22)
+        .stdout(predicate::str::contains("CLAUDE.md:1: unresolved placeholder {{projct_name}}"))
+        .stdout(predicate::str::contains("CLAUDE.md:3: link to 'docs/guide.md'"));
+    assert!(!temp_dir.path().join(".specforge.json").exists());
+    assert!(!temp_dir.path().join("CLAUDE.md").exists());
+
+    // Without the flag the same problems are only warnings
+    let mut lenient = Command::cargo_bin("specforge").unwrap();
+    lenient.args(["init", "--agent", "codeium", "--template-dir"])
+        .arg(template_dir.path())
+        .arg("--output-directory")
+        .arg(temp_dir.path());
+    lenient.assert()
+        .success()
+        .stdout(predicate::str::contains("unresolved placeholder {{projct_name}}"));
+    assert!(temp_dir.path().join("CLAUDE.md").exists());
+}
+
+#[test]
+fn test_init_on_conflict_skip_keeps_existing_files() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("CLAUDE.md"), "# my own notes\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--on-conflict", "skip", "--output-directory"])
+        .arg(temp_dir.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1 skipped"));
+
+    let content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    assert_eq!(content, "# my own notes\n");
+    assert!(temp_dir.path().join("codeium.json").exists());
+
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap()).unwrap();
+    let tracked: Vec<_> = config["files"].as_array().unwrap().iter().map(|f| f["path"].as_str().unwrap()).collect();
+    assert_eq!(tracked, vec!["codeium.json"]);
+
+    let mut prompt = Command::cargo_bin("specforge").unwrap();
+    prompt.args(["init", "--agent", "codeium", "--output-directory"])
+        .arg(temp_dir.path());
+    prompt.assert()
+        .failure()
+        .stderr(predicate::str::contains("--on-conflict"));
+}
+
+#[test]
+fn test_init_force_shows_diff_of_overwritten_files() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("CLAUDE.md"), "# my own notes\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--project-name", "demo", "--force", "--output-directory"])
+        .arg(temp_dir.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("--- a/CLAUDE.md"))
+        .stdout(predicate::str::contains("-# my own notes"))
+        .stdout(predicate::str::contains("+# demo: Codeium Instructions"));
+}
+
+#[test]
+fn test_init_word_diff_marks_changed_words() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("CLAUDE.md"), "# demo: Windsurf Instructions\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--project-name", "demo", "--force", "--word-diff", "--output-directory"])
+        .arg(temp_dir.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("--- a/CLAUDE.md"))
+        .stdout(predicate::str::contains("# demo: [-Windsurf-]{+Codeium+} Instructions"));
+}
+
+#[test]
+fn test_init_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["--output", "json", "init", "--agent", "claude", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    let output = cmd.assert().success().get_output().clone();
+
+    // Status lines go to stderr so stdout holds nothing but the result
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["status"], "success");
+    assert_eq!(result["agent"], "claude");
+    assert_eq!(result["project_name"], "demo");
+    assert!(result["config_path"].as_str().unwrap().ends_with(".specforge.json"));
+    let deployed: Vec<&str> = result["deployed_files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f.as_str().unwrap())
+        .collect();
+    assert!(deployed.contains(&"CLAUDE.md"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Successfully created"));
+}
+
+#[test]
+fn test_error_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["clean", "--output", "json", "--directory"])
+        .arg(temp_dir.path());
+    let output = cmd.assert().failure().code(22).get_output().clone();
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(error["code"], "validation_error");
+    assert_eq!(error["exit_code"], 22);
+    assert!(error["message"].as_str().unwrap().contains("does not exist"));
+}
+
+#[test]
+fn test_validate_reports_every_problem() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["agent"] = serde_json::json!(["claude", "claude"]);
+    config["metadata"]["created_at"] = serde_json::json!("yesterday");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert()
+        .failure()
+        .code(22)
+        .stderr(predicate::str::contains("  • agent[1]: Duplicate agent: 'claude'"))
+        .stderr(predicate::str::contains("  • metadata.created_at: Invalid created_at timestamp format"));
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["--output", "json", "validate", "--directory"]).arg(temp_dir.path());
+    let output = validate.assert().failure().code(22).get_output().clone();
+    let error: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(error["code"], "multiple_validation_errors");
+    assert_eq!(error["issues"][1]["path"], "metadata.created_at");
+    assert_eq!(error["issues"][1]["severity"], "error");
+}
+
+#[test]
+fn test_validate_warnings_and_deny_warnings() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["packages"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({ "id": "team-prompts", "version": "1.0.0", "url": "http://example.com/p.tar.gz" }));
+    config["metadata"]["createdAt"] = serde_json::json!("2025-01-01T00:00:00Z");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert()
+        .success()
+        .stdout(predicate::str::contains("[warn] packages[1].url: Package 'team-prompts' is downloaded over http"))
+        .stdout(predicate::str::contains("[warn] metadata.createdAt: Unknown metadata key 'createdAt'"))
+        .stdout(predicate::str::contains("is valid"));
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["--output", "json", "validate", "--directory"]).arg(temp_dir.path());
+    let output = validate.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["warnings"][0]["path"], "packages[1].url");
+    assert_eq!(result["warnings"][0]["severity"], "warning");
+
+    let mut strict = Command::cargo_bin("specforge").unwrap();
+    strict.args(["validate", "--deny-warnings", "--directory"]).arg(temp_dir.path());
+    strict.assert()
+        .failure()
+        .code(22)
+        .stderr(predicate::str::contains("  • metadata.createdAt: Unknown metadata key 'createdAt'"));
+}
+
+#[test]
+fn test_rollback_restores_upgraded_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    let mut missing = Command::cargo_bin("specforge").unwrap();
+    missing.args(["rollback", "--force", "--output-directory"]).arg(temp_dir.path());
+    missing.assert()
+        .failure()
+        .code(66)
+        .stderr(predicate::str::contains("No backups of"));
+
+    // Reading a configuration with an older schema upgrades it and keeps a backup
+    let config_path = temp_dir.path().join(".specforge.json");
+    let legacy = r#"{"agent": "copilot", "packages": [], "metadata": {"created_at": "2025-01-01T00:00:00Z"}}"#;
+    fs::write(&config_path, legacy).unwrap();
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert().success().stdout(predicate::str::contains("the original is kept in"));
+
+    let mut rollback = Command::cargo_bin("specforge").unwrap();
+    rollback.args(["rollback", "--force", "--output-directory"]).arg(temp_dir.path());
+    rollback.assert()
+        .success()
+        .stdout(predicate::str::contains("Agent: copilot"))
+        .stdout(predicate::str::contains("Rolled back"));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), legacy);
+}
+
+#[test]
+fn test_failed_init_leaves_directory_untouched() {
+    let temp_dir = TempDir::new().unwrap();
+    // A directory where the second Claude template goes makes its deployment fail
+    fs::create_dir(temp_dir.path().join("README.md")).unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "claude", "--force", "--output-directory"]).arg(temp_dir.path());
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Undid the changes"));
+
+    let entries: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(entries, ["README.md"]);
+}
+
+#[test]
+fn test_diff_summarizes_config_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    for (name, agent) in [("before", "copilot"), ("after", "claude")] {
+        let mut init = Command::cargo_bin("specforge").unwrap();
+        init.args(["init", "--agent", agent, "--project-name", name, "--output-directory"])
+            .arg(temp_dir.path().join(name));
+        init.assert().success();
+    }
+    let before = temp_dir.path().join("before").join(".specforge.json");
+    let after = temp_dir.path().join("after").join(".specforge.json");
+
+    let mut diff = Command::cargo_bin("specforge").unwrap();
+    diff.arg("diff").arg(&before).arg(&after);
+    diff.assert()
+        .success()
+        .stdout(predicate::str::contains("Agent changed: copilot → claude"))
+        .stdout(predicate::str::contains("Project name changed: 'before' → 'after'"))
+        .stdout(predicate::str::contains("Package added: specforge-claude-templates@"))
+        .stdout(predicate::str::contains("Package removed: specforge-copilot-templates@"));
+
+    let mut diff = Command::cargo_bin("specforge").unwrap();
+    diff.args(["--output-format", "json", "diff"]).arg(&before).arg(&before);
+    let output = diff.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result, serde_json::json!({ "status": "success", "entries": [] }));
+}
+
+#[test]
+fn test_output_format_json_for_every_result() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut help = Command::cargo_bin("specforge").unwrap();
+    help.arg("--help").assert().success().stdout(predicate::str::contains("--output-format"));
+
+    let json = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args(["--output-format", "json"]).args(args).arg(temp_dir.path());
+        let output = cmd.output().unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<serde_json::Value> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        (output.status.code(), lines)
+    };
+
+    let (code, lines) = json(&["validate", "--directory"]);
+    assert_eq!(code, Some(22));
+    assert_eq!(lines[0]["status"], "invalid");
+    assert_eq!(lines[0]["exit_code"], 22);
+    assert!(lines[0]["error"].as_str().unwrap().contains("does not exist"));
+
+    let (code, _) = json(&["init", "--agent", "claude", "--output-directory"]);
+    assert_eq!(code, Some(0));
+    let (code, lines) = json(&["validate", "--directory"]);
+    assert_eq!((code, &lines[0]["status"]), (Some(0), &serde_json::json!("valid")));
+
+    let (code, lines) = json(&["doctor", "--directory"]);
+    assert_eq!(code, Some(0), "{:?}", lines);
+    assert_eq!(lines[0]["status"], "success");
+    assert!(lines[0]["checks"].as_array().unwrap().iter().all(|check| check["passed"] == true));
+
+    let (code, lines) = json(&["upgrade", "--directory"]);
+    assert_eq!((code, &lines[0]["upgraded"]), (Some(0), &serde_json::json!(false)));
+
+    let (code, lines) = json(&["clean", "--force", "--directory"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0]["removed"].as_array().unwrap().contains(&serde_json::json!("CLAUDE.md")));
+}
+
+/// Run specforge with `--output-format json` and return the single object it printed
+fn json_result(config_dir: &std::path::Path, args: &[&str]) -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.env("SPECFORGE_CONFIG_DIR", config_dir).args(["--output-format", "json"]).args(args);
+    let output = cmd.assert().success().get_output().clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "{:?}: {}", args, stdout);
+    serde_json::from_str(&stdout).unwrap()
+}
+
+#[test]
+fn test_output_format_json_envelope_for_every_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("user-config");
+    let root = temp_dir.path().join("repo");
+    let project = root.join("app");
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::create_dir_all(&project).unwrap();
+    let legacy = temp_dir.path().join("legacy");
+    fs::create_dir(&legacy).unwrap();
+    fs::write(
+        legacy.join(".reforge.json"),
+        r#"{"agent": "copilot", "packages": [], "metadata": {"created_at": "2025-03-01T12:00:00Z"}}"#,
+    )
+    .unwrap();
+    // Reading a configuration with an older schema keeps a backup to roll back to
+    let upgraded = temp_dir.path().join("upgraded");
+    fs::create_dir(&upgraded).unwrap();
+    fs::write(
+        upgraded.join(".specforge.json"),
+        r#"{"agent": "copilot", "packages": [], "metadata": {"created_at": "2025-01-01T00:00:00Z"}}"#,
+    )
+    .unwrap();
+    let merge = temp_dir.path().join("merge");
+    fs::create_dir(&merge).unwrap();
+    for side in ["base", "ours", "theirs"] {
+        fs::write(merge.join(side), r#"{"version": 1, "packages": []}"#).unwrap();
+    }
+    let path = |path: &std::path::Path| path.to_str().unwrap().to_string();
+    let (root, project, legacy, upgraded, merge) =
+        (path(&root), path(&project), path(&legacy), path(&upgraded), path(&merge));
+    let config_path = format!("{}/.specforge.json", project);
+    let [base, ours, theirs] = ["base", "ours", "theirs"].map(|side| format!("{}/{}", merge, side));
+
+    let commands: Vec<Vec<&str>> = vec![
+        vec!["init", "--agent", "claude", "--project-name", "demo", "--output-directory", &project],
+        vec!["validate", "--directory", &project],
+        vec!["doctor", "--directory", &project],
+        vec!["fmt", "--directory", &project],
+        vec!["verify", "--directory", &project],
+        vec!["env", "--directory", &project],
+        vec!["env", "--shell", "sh", "--directory", &project],
+        vec!["show", "--directory", &project],
+        vec!["show", "--path", "--directory", &project],
+        vec!["diff", &config_path, &config_path],
+        vec!["preview-templates", "--agent", "claude"],
+        vec!["templates", "changelog", "--agent", "claude"],
+        vec!["list-agents"],
+        vec!["list-packages", "--directory", &project],
+        vec!["list-packages", "specforge-claude-templates", "--directory", &project],
+        vec!["completions", "bash"],
+        vec!["completions", "bash", "--output-directory", &project],
+        vec!["config", "set", "max_backups", "3"],
+        vec!["config", "get", "max_backups"],
+        vec!["config", "list"],
+        vec!["config", "rename-project", "renamed", "--force", "--directory", &project],
+        vec!["config", "prune-metadata", "--larger-than", "100000", "--dry-run", "--directory", &project],
+        vec!["workspace", "init", "--agent", "claude", "--directory", &root],
+        vec!["lock", "--directory", &project],
+        vec!["unlock", "--package", "specforge-claude-templates", "--directory", &project],
+        vec!["unlock", "--directory", &project],
+        vec!["schema"],
+        vec!["schema", "--write", "--directory", &project],
+        vec!["install-hooks", "--output-directory", &project],
+        vec!["install-hooks", "--output-directory", &project, "--attributes"],
+        vec!["export", "--format", "github-actions", "--output-directory", &project],
+        vec!["version"],
+        vec!["version", "--check", ">=0.1.0"],
+        vec!["upgrade", "--directory", &project],
+        vec!["switch-agent", "codeium", "--force", "--directory", &project],
+        vec!["restore-backups", "--directory", &project],
+        vec!["validate", "--directory", &upgraded],
+        vec!["rollback", "--list", "--output-directory", &upgraded],
+        vec!["rollback", "--force", "--output-directory", &upgraded],
+        vec!["clean", "--force", "--directory", &project],
+        vec!["deinit", "--force", "--directory", &project],
+        vec!["migrate", "--directory", &legacy],
+        vec!["generate-example"],
+        vec!["merge-driver", &base, &ours, &theirs],
+    ];
+
+    #[cfg(feature = "remote")]
+    let (registry_url, package_url, server) = {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let registry_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0u8; 1024]).unwrap();
+            stream.write_all(b"HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n[]").unwrap();
+        });
+        let package_url = write_package_archive(temp_dir.path(), "prompts.tar", &[("prompts/review.md", "Review\n")]);
+        (registry_url, package_url, server)
+    };
+    #[cfg(feature = "remote")]
+    let commands = [
+        commands,
+        vec![
+            vec!["search-packages", "--query", "rust", "--registry-url", &registry_url],
+            vec!["install", &package_url, "--package-version", "1.0.0", "--directory", &upgraded],
+        ],
+    ]
+    .concat();
+    for args in commands {
+        let result = json_result(&config_dir, &args);
+        assert!(result.is_object(), "{:?}: {}", args, result);
+        assert_eq!(result["status"], if args[0] == "validate" { "valid" } else { "success" }, "{:?}", args);
+    }
+    #[cfg(feature = "remote")]
+    server.join().unwrap();
+}
+
+#[test]
+fn test_env_evaluates_package_conditions() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    json["packages"][0]["when"] = serde_json::Value::from("not(env_set(\"CI\"))");
+    fs::write(&config_path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+
+    let mut local = Command::cargo_bin("specforge").unwrap();
+    local.args(["env", "--directory"])
+        .arg(temp_dir.path())
+        .env_remove("CI");
+    local.assert()
+        .success()
+        .stdout(predicate::str::contains("when not(env_set(\"CI\")) → true"));
+
+    let mut ci = Command::cargo_bin("specforge").unwrap();
+    ci.args(["--output", "json", "env", "--directory"])
+        .arg(temp_dir.path())
+        .env("CI", "true");
+    let output = ci.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["packages"][0]["active"], false);
+}
+
+#[test]
+fn test_invalid_package_condition_points_at_token() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    json["packages"][0]["when"] = serde_json::Value::from("env(é");
+    fs::write(&config_path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+
+    for command in ["validate", "env"] {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args([command, "--directory"]).arg(temp_dir.path());
+        cmd.assert()
+            .failure()
+            .code(22)
+            .stderr(predicate::str::contains("unexpected character 'é'"))
+            .stderr(predicate::str::contains("  env(é\n      ^"))
+            .stderr(predicate::str::contains("corrupted").not());
+    }
+}
+
+#[test]
+fn test_env_shell_exports() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--project-name", "It's mine", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+    // A second config format makes env print a warning, which must stay off stdout
+    fs::write(temp_dir.path().join(".specforge.toml"), "").unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["env", "--shell", "sh", "--directory"]).arg(temp_dir.path());
+    let output = cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Found both"))
+        .get_output()
+        .clone();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|line| line.starts_with("export SPECFORGE_")));
+    assert!(lines.contains(&"export SPECFORGE_AGENT='claude'"));
+    assert!(lines.contains(&"export SPECFORGE_PROJECT_NAME='It'\\''s mine'"));
+}
+
+#[test]
+fn test_env_applies_config_overlay() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+    fs::write(
+        temp_dir.path().join(".specforge.ci.json"),
+        r#"{"agent": "copilot", "metadata": {"project_name": "demo-ci"}}"#,
+    )
+    .unwrap();
+
+    let mut flag = Command::cargo_bin("specforge").unwrap();
+    flag.args(["env", "--shell", "sh", "--env", "ci", "--directory"]).arg(temp_dir.path());
+    flag.assert()
+        .success()
+        .stdout(predicate::str::contains("export SPECFORGE_AGENT='copilot'"))
+        .stdout(predicate::str::contains("export SPECFORGE_PROJECT_NAME='demo-ci'"));
+
+    let mut variable = Command::cargo_bin("specforge").unwrap();
+    variable.args(["--output", "json", "env", "--directory"])
+        .arg(temp_dir.path())
+        .env("SPECFORGE_ENV", "ci");
+    let output = variable.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["env"], "ci");
+
+    let mut missing = Command::cargo_bin("specforge").unwrap();
+    missing.args(["env", "--env", "staging", "--directory"]).arg(temp_dir.path());
+    missing.assert()
+        .failure()
+        .stderr(predicate::str::contains("No configuration for environment 'staging'"));
+
+    // The base configuration is left untouched
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"demo\""));
+}
+
+#[test]
+fn test_completions_for_each_shell() {
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args(["completions", shell]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_empty().not())
+            .stdout(predicate::str::contains("specforge"));
+    }
+
+    // Agent values are offered after `init --agent`
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["completions", "bash"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("copilot claude codeium"));
+}
+
+#[test]
+fn test_preview_templates_writes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["preview-templates", "--agent", "codeium"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("--- CLAUDE.md ---\n"))
+        .stdout(predicate::str::contains("--- codeium.json ---"));
+
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+    let mut missing_agent = Command::cargo_bin("specforge").unwrap();
+    missing_agent.arg("preview-templates");
+    missing_agent.assert().failure();
+}
+
+#[test]
+fn test_switch_agent() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "codeium", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let mut switch = Command::cargo_bin("specforge").unwrap();
+    switch.args(["switch-agent", "claude", "--remove-old-files", "--force", "--directory"])
+        .arg(temp_dir.path());
+    switch.assert()
+        .success()
+        .stdout(predicate::str::contains("+ README.md"))
+        .stdout(predicate::str::contains("- codeium.json"));
+
+    assert!(!temp_dir.path().join("codeium.json").exists());
+    let config: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(config["agent"], "claude");
+    assert_eq!(config["packages"][0]["id"], "specforge-claude-templates");
+    assert_eq!(config["project_name"], "demo");
+
+    // Files deployed by the switch are tracked with their mode
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(temp_dir.path().join("README.md")).unwrap().permissions().mode() & 0o7777;
+        let tracked = config["files"].as_array().unwrap().iter().find(|f| f["path"] == "README.md").unwrap();
+        assert_eq!(tracked["mode"], format!("{:o}", mode));
+    }
+
+    // The lock file follows the switch
+    let lock: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join("specforge.lock")).unwrap()).unwrap();
+    let locked: Vec<_> = lock["packages"].as_array().unwrap().iter().map(|p| p["id"].clone()).collect();
+    assert_eq!(locked, ["specforge-claude-templates"]);
+}
+
+#[cfg(feature = "dev-tools")]
+#[test]
+fn test_dist_is_reproducible() {
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+
+    for out_dir in [first.path(), second.path()] {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args(["dist", "--out-dir"]).arg(out_dir);
+        cmd.assert().success();
+    }
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(first.path().join("manifest.json")).unwrap()).unwrap();
+    let mut paths: Vec<_> = manifest["artifacts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|artifact| artifact["path"].as_str().unwrap().to_string())
+        .collect();
+    assert!(paths.contains(&"man/specforge-init.1".to_string()));
+    assert!(paths.contains(&"completions/specforge.bash".to_string()));
+    assert!(paths.contains(&"exit-codes.json".to_string()));
+
+    paths.push("manifest.json".to_string());
+    for path in paths {
+        let a = fs::read(first.path().join(&path)).unwrap();
+        let b = fs::read(second.path().join(&path)).unwrap();
+        assert_eq!(a, b, "{} differs between runs", path);
+    }
+}
+
+#[test]
+fn test_show_configuration() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut outside = Command::cargo_bin("specforge").unwrap();
+    outside.args(["show", "--directory"]).arg(temp_dir.path());
+    outside.assert().failure().code(2).stderr(predicate::str::contains("is not a specforge project"));
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path());
+    init.assert().success();
+
+    let mut show = Command::cargo_bin("specforge").unwrap();
+    show.args(["show", "--directory"]).arg(temp_dir.path());
+    show.assert()
+        .success()
+        .stdout(predicate::str::contains("Project name: demo"))
+        .stdout(predicate::str::contains("   • specforge-claude-templates"));
+
+    let mut path = Command::cargo_bin("specforge").unwrap();
+    path.args(["show", "--path", "--directory"]).arg(temp_dir.path());
+    let output = path.assert().success().get_output().clone();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim_end(),
+        temp_dir.path().join(".specforge.json").display().to_string()
+    );
+
+    let mut json = Command::cargo_bin("specforge").unwrap();
+    json.args(["show", "--json", "--directory"]).arg(temp_dir.path());
+    let output = json.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["status"], "success");
+    let config = specforge::config::ProjectConfig::from_json_string(&result["config"].to_string()).unwrap();
+    assert_eq!(config.project_name(), Some("demo"));
+}
+
+#[test]
+fn test_working_directory_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    fs::create_dir(&templates).unwrap();
+    fs::write(templates.join("CLAUDE.md"), "# {{project_name}} from custom templates\n").unwrap();
+
+    // Relative paths resolve against -C, not the working directory of the process
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.current_dir(std::env::temp_dir())
+        .arg("-C")
+        .arg(temp_dir.path())
+        .args(["init", "--agent", "claude", "--project-name", "demo"])
+        .args(["--output-directory", "app", "--template-dir", "templates"]);
+    init.assert().success();
+    let claude = fs::read_to_string(temp_dir.path().join("app/CLAUDE.md")).unwrap();
+    assert!(claude.starts_with("# demo from custom templates"), "{}", claude);
+
+    let mut env = Command::cargo_bin("specforge").unwrap();
+    env.arg("-C").arg(temp_dir.path().join("app")).arg("env");
+    env.assert().success().stdout(predicate::str::contains(
+        temp_dir.path().canonicalize().unwrap().join("app").join(".specforge.json").display().to_string(),
+    ));
+
+    let mut missing = Command::cargo_bin("specforge").unwrap();
+    missing.arg("-C").arg(temp_dir.path().join("missing")).arg("validate");
+    missing.assert().failure().code(2).stderr(predicate::str::contains("missing"));
+}
+
+#[test]
+fn test_config_rename_project() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "codeium", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let mut rename = Command::cargo_bin("specforge").unwrap();
+    rename.args(["config", "rename-project", "atlas", "--force", "--directory"])
+        .arg(temp_dir.path());
+    rename.assert()
+        .success()
+        .stdout(predicate::str::contains("-# demo: Codeium Instructions"))
+        .stdout(predicate::str::contains("+# atlas: Codeium Instructions"));
+
+    let content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    assert!(content.starts_with("# atlas: Codeium Instructions"));
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"project_name\": \"atlas\""));
+
+    // Without --force there is no terminal to confirm the rewrite
+    let mut unconfirmed = Command::cargo_bin("specforge").unwrap();
+    unconfirmed.args(["config", "rename-project", "orion", "--directory"])
+        .arg(temp_dir.path());
+    unconfirmed.assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+}
+
+#[test]
+fn test_init_writes_lock_file() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let lock: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join("specforge.lock")).unwrap()).unwrap();
+    assert_eq!(lock["packages"][0]["id"], "specforge-claude-templates");
+    assert!(lock["packages"][0]["checksum"].as_str().unwrap().starts_with("sha256:"));
+
+    let mut unlock = Command::cargo_bin("specforge").unwrap();
+    unlock.args(["unlock", "--directory"]).arg(temp_dir.path());
+    unlock.assert().success();
+    assert!(!temp_dir.path().join("specforge.lock").exists());
+
+    let mut lock_cmd = Command::cargo_bin("specforge").unwrap();
+    lock_cmd.args(["lock", "--directory"]).arg(temp_dir.path());
+    lock_cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("specforge-claude-templates"));
+    assert!(temp_dir.path().join("specforge.lock").exists());
+}
+
+#[test]
+fn test_init_and_verify_honour_lock_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let lock_path = temp_dir.path().join("specforge.lock");
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let mut verify = Command::cargo_bin("specforge").unwrap();
+    verify.args(["verify", "--directory"]).arg(temp_dir.path());
+    verify.assert().success();
+
+    // Templates that differ from the pinned ones are refused
+    let mut lock: serde_json::Value = serde_json::from_str(&fs::read_to_string(&lock_path).unwrap()).unwrap();
+    lock["packages"][0]["checksum"] = format!("sha256:{}", "0".repeat(64)).into();
+    fs::write(&lock_path, lock.to_string()).unwrap();
+
+    let mut verify = Command::cargo_bin("specforge").unwrap();
+    verify.args(["verify", "--directory"]).arg(temp_dir.path());
+    verify.assert()
+        .code(65)
+        .stdout(predicate::str::contains("[error] 'specforge-claude-templates' has templates"))
+        .stderr(predicate::str::contains("specforge lock"));
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert()
+        .code(65)
+        .stderr(predicate::str::contains("do not match their pins"));
+
+    // Pinning again accepts the templates of this release
+    let mut lock_cmd = Command::cargo_bin("specforge").unwrap();
+    lock_cmd.args(["lock", "--directory"]).arg(temp_dir.path());
+    lock_cmd.assert().success();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+}
+
+#[test]
+fn test_migrate_legacy_reforge_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let legacy = r#"{
+  "agent": "copilot",
+  "packages": [
+    {"id": "reforge-copilot-templates", "url": null, "version": "0.1.0"},
+    {"id": "team-prompts", "url": "https://example.com/prompts", "version": "1.2.0"}
+  ],
+  "metadata": {"created_at": "2025-03-01T12:00:00Z", "project_name": "legacy-app"}
+}"#;
+    fs::write(temp_dir.path().join(".reforge.json"), legacy).unwrap();
+
+    // init points at the legacy file instead of silently ignoring it
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "copilot", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert()
+        .success()
+        .stdout(predicate::str::contains("specforge migrate"));
+    fs::remove_file(temp_dir.path().join(".specforge.json")).unwrap();
+
+    let mut migrate = Command::cargo_bin("specforge").unwrap();
+    migrate.args(["migrate", "--keep-legacy", "--directory"]).arg(temp_dir.path());
+    migrate.assert().success();
+
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap()).unwrap();
+    assert_eq!(config["agent"], "copilot");
+    assert_eq!(config["packages"][0]["id"], "specforge-copilot-templates");
+    assert_eq!(config["packages"][1]["id"], "team-prompts");
+    assert_eq!(config["project_name"], "legacy-app");
+    assert_eq!(config["metadata"]["created_at"], "2025-03-01T12:00:00Z");
+    assert_eq!(config["metadata"]["migrated_from"], ".reforge.json");
+    assert!(temp_dir.path().join(".reforge.json").exists());
+
+    // A second run would overwrite the new configuration
+    let mut again = Command::cargo_bin("specforge").unwrap();
+    again.args(["migrate", "--delete-legacy", "--directory"]).arg(temp_dir.path());
+    again.assert().failure();
+
+    let mut forced = Command::cargo_bin("specforge").unwrap();
+    forced.args(["migrate", "--delete-legacy", "--force", "--directory"]).arg(temp_dir.path());
+    forced.assert().success();
+    assert!(!temp_dir.path().join(".reforge.json").exists());
+}
+
+#[test]
+fn test_migrate_corrupt_legacy_config() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join(".reforge.json"), "{\"agent\": \"copilot\",").unwrap();
+
+    let mut migrate = Command::cargo_bin("specforge").unwrap();
+    migrate.args(["migrate", "--directory"]).arg(temp_dir.path());
+    migrate.assert()
+        .failure()
+        .stderr(predicate::str::contains("corrupted or invalid"))
+        .stderr(predicate::str::contains("Recovery options"));
+    assert!(!temp_dir.path().join(".specforge.json").exists());
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn test_search_packages_against_local_registry() {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let registry_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 1024];
+        let _ = stream.read(&mut request).unwrap();
+        stream
+            .write_all(
+                b"HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n\
+                  [{\"id\": \"rust-api\", \"description\": \"REST APIs in Rust\", \"latest_version\": \"1.2.0\", \"downloads\": 42}]",
+            )
+            .unwrap();
+    });
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["search-packages", "--query", "rust", "--registry-url", &registry_url]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("ID"))
+        .stdout(predicate::str::contains("rust-api  1.2.0"))
+        .stdout(predicate::str::contains("REST APIs in Rust"));
+    server.join().unwrap();
+
+    // Nothing listens on the old port anymore
+    let mut unreachable = Command::cargo_bin("specforge").unwrap();
+    unreachable.args(["search-packages", "--query", "rust", "--registry-url", &registry_url]);
+    unreachable.assert()
+        .failure()
+        .code(69)
+        .stderr(predicate::str::contains("Failed to reach registry"));
+
+    // A registry URL that cannot work is not worth retrying
+    let mut unsupported = Command::cargo_bin("specforge").unwrap();
+    unsupported.args(["search-packages", "--query", "rust", "--registry-url", "ftp://registry.example.com"]);
+    unsupported.assert()
+        .failure()
+        .code(22)
+        .stderr(predicate::str::contains("registry URLs start with http:// or https://"))
+        .stderr(predicate::str::contains("temporary").not());
+}
+
+/// The drift check is a library API; it must agree with what init deploys
+#[test]
+fn test_drift_check_library_api() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    cmd.assert().success();
+
+    let report = specforge::drift::check(temp_dir.path()).unwrap();
+    assert!(report.is_clean(), "freshly initialized project has drift: {:?}", report);
+    assert!(!report.files.is_empty());
+
+    fs::write(temp_dir.path().join("CLAUDE.md"), "edited by hand").unwrap();
+    let report = specforge::drift::check(temp_dir.path()).unwrap();
+    assert!(!report.is_clean());
+    let claude = report.files.iter().find(|file| file.path == "CLAUDE.md").unwrap();
+    assert_eq!(claude.status, specforge::drift::FileStatus::Modified);
+}
+
+/// A configuration written by init must pass the schema printed by `schema`
+#[test]
+fn test_init_config_matches_schema() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut schema_cmd = Command::cargo_bin("specforge").unwrap();
+    schema_cmd.args(["schema", "--write", "--directory"]).arg(temp_dir.path());
+    schema_cmd.assert().success();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let schema: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join("specforge.schema.json")).unwrap()).unwrap();
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap()).unwrap();
+    assert_eq!(config["$schema"], "specforge.schema.json");
+
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let errors: Vec<_> = validator.iter_errors(&config).map(|e| e.to_string()).collect();
+    assert!(errors.is_empty(), "init wrote a configuration the schema rejects: {:?}", errors);
+
+    // Without --write the schema goes to stdout
+    let mut print = Command::cargo_bin("specforge").unwrap();
+    print.arg("schema");
+    let printed = print.assert().success().get_output().stdout.clone();
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&printed).unwrap(), schema);
+}
+
+/// A configuration bloated by automation is flagged and can be pruned
+#[test]
+fn test_prune_bloated_metadata() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    for run in 0..40 {
+        config["metadata"][format!("ci_run_{}", run)] = serde_json::json!(vec!["x".repeat(100); 20]);
+    }
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    // Sizes are human-readable unless --raw is given
+    let mut dry_run = Command::cargo_bin("specforge").unwrap();
+    dry_run.args(["config", "prune-metadata", "--larger-than", "1000", "--dry-run", "--directory"])
+        .arg(temp_dir.path());
+    dry_run.assert().success().stdout(predicate::str::contains("ci_run_0 - 2.0 KiB\n"));
+    let mut raw = Command::cargo_bin("specforge").unwrap();
+    raw.args(["--raw", "config", "prune-metadata", "--larger-than", "1000", "--dry-run", "--directory"])
+        .arg(temp_dir.path());
+    raw.assert().success().stdout(predicate::str::is_match(r"ci_run_0 - \d+\n").unwrap());
+
+    let mut prune = Command::cargo_bin("specforge").unwrap();
+    prune.args(["config", "prune-metadata", "--larger-than", "1000", "--directory"])
+        .arg(temp_dir.path());
+    prune.assert()
+        .success()
+        .stdout(predicate::str::contains("run `specforge config prune-metadata` to shrink it"))
+        .stdout(predicate::str::contains("[ok] Removed 40 key(s)"));
+
+    let config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert!(config["metadata"].get("ci_run_0").is_none());
+    assert_eq!(config["project_name"], "demo");
+}
+
+/// Write a tar archive of (path, content) pairs and return its file:// URL
+#[cfg(feature = "remote")]
+fn write_package_archive(dir: &std::path::Path, name: &str, files: &[(&str, &str)]) -> String {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_entry_type(tar::EntryType::Regular);
+        let entry_name = &mut header.as_old_mut().name;
+        entry_name[..path.len()].copy_from_slice(path.as_bytes());
+        header.set_cksum();
+        builder.append(&header, content.as_bytes()).unwrap();
+    }
+    let archive_path = dir.join(name);
+    fs::write(&archive_path, builder.into_inner().unwrap()).unwrap();
+    format!("file://{}", archive_path.display())
+}
+
+#[test]
+#[cfg(feature = "remote")]
+fn test_install_package_from_file_url() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_dir = TempDir::new().unwrap();
+    let url = write_package_archive(
+        archive_dir.path(),
+        "team-prompts.tar",
+        &[("docs/prompts/review.md", "Review {{project_name}}\n")],
+    );
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--project-name", "demo", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let mut install = Command::cargo_bin("specforge").unwrap();
+    install.args(["--output", "json", "install", &url, "--package-version", "2.1.0", "--directory"])
+        .arg(temp_dir.path());
+    let output = install.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["id"], "team-prompts");
+    assert_eq!(result["files"], serde_json::json!(["docs/prompts/review.md"]));
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("docs/prompts/review.md")).unwrap(),
+        "Review demo\n"
+    );
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap()).unwrap();
+    let package = config["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["id"] == "team-prompts")
+        .unwrap();
+    assert_eq!(package["url"], url.as_str());
+    assert_eq!(package["version"], "2.1.0");
+    assert!(config["files"].as_array().unwrap().iter().any(|f| f["path"] == "docs/prompts/review.md"));
+}
+
+#[test]
+#[cfg(feature = "remote")]
+fn test_install_honours_lock_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_dir = TempDir::new().unwrap();
+    let url = write_package_archive(archive_dir.path(), "team-prompts.tar", &[("REVIEW.md", "Review\n")]);
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let mut install = Command::cargo_bin("specforge").unwrap();
+    install.args(["install", &url, "--directory"]).arg(temp_dir.path());
+    install.assert().success();
+
+    // The package is pinned to the archive it was installed from
+    let lock: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join("specforge.lock")).unwrap()).unwrap();
+    let pinned = lock["packages"].as_array().unwrap().iter().find(|p| p["id"] == "team-prompts").unwrap();
+    assert_eq!(pinned["resolved_version"], "1.0.0");
+    assert!(pinned["checksum"].as_str().unwrap().starts_with("sha256:"));
+
+    write_package_archive(archive_dir.path(), "team-prompts.tar", &[("REVIEW.md", "Changed\n")]);
+    let mut reinstall = Command::cargo_bin("specforge").unwrap();
+    reinstall.args(["install", &url, "--on-conflict", "overwrite", "--directory"]).arg(temp_dir.path());
+    reinstall.assert()
+        .failure()
+        .stderr(predicate::str::contains("Checksum mismatch"));
+    assert_eq!(fs::read_to_string(temp_dir.path().join("REVIEW.md")).unwrap(), "Review\n");
+
+    let mut other_version = Command::cargo_bin("specforge").unwrap();
+    other_version.args(["install", &url, "--package-version", "2.0.0", "--directory"]).arg(temp_dir.path());
+    other_version.assert()
+        .code(65)
+        .stderr(predicate::str::contains("'team-prompts' is configured at version 2.0.0 but locked to 1.0.0"));
+}
+
+#[test]
+#[cfg(feature = "remote")]
+fn test_init_with_package_url() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_dir = TempDir::new().unwrap();
+    let url = write_package_archive(archive_dir.path(), "extra.tar", &[("EXTRA.md", "extra\n")]);
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "codeium", "--package-url", &url, "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    cmd.assert().success();
+
+    assert!(temp_dir.path().join("EXTRA.md").exists());
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"id\": \"extra\""));
+    assert!(config.contains("\"path\": \"EXTRA.md\""));
+}
+
+#[test]
+#[cfg(feature = "remote")]
+fn test_install_rejects_unsafe_archives_and_unreachable_servers() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+    let config_before = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+
+    let url = write_package_archive(
+        archive_dir.path(),
+        "evil.tar",
+        &[("ok.md", "fine\n"), ("../escape.md", "gotcha\n")],
+    );
+    let mut install = Command::cargo_bin("specforge").unwrap();
+    install.args(["install", &url, "--directory"]).arg(temp_dir.path());
+    install.assert()
+        .failure()
+        .stderr(predicate::str::contains("points outside the project directory"));
+    assert!(!temp_dir.path().join("ok.md").exists());
+    assert!(!temp_dir.path().parent().unwrap().join("escape.md").exists());
+
+    // Nothing listens on a port that was just released
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let unreachable = format!("http://{}/p.tar.gz", listener.local_addr().unwrap());
+    drop(listener);
+    let mut offline = Command::cargo_bin("specforge").unwrap();
+    offline.args(["install", &unreachable, "--timeout", "5", "--directory"]).arg(temp_dir.path());
+    offline.assert()
+        .failure()
+        .code(69)
+        .stderr(predicate::str::contains("may be temporary"));
+
+    assert_eq!(fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap(), config_before);
+}
+
+#[test]
+fn test_verify_detects_tampered_templates() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--force");
+    init.assert().success();
+
+    let mut clean = Command::cargo_bin("specforge").unwrap();
+    clean.args(["verify", "--directory"]).arg(temp_dir.path());
+    clean.assert()
+        .success()
+        .stdout(predicate::str::contains("[ok] CLAUDE.md"));
+
+    fs::write(temp_dir.path().join("CLAUDE.md"), "# Edited by hand\n").unwrap();
+
+    let mut tampered = Command::cargo_bin("specforge").unwrap();
+    tampered.args(["verify", "--directory"]).arg(temp_dir.path());
+    tampered.assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[error] CLAUDE.md (modified)"))
+        .stderr(predicate::str::contains("1 deployed file(s) do not match"));
+
+    let mut json = Command::cargo_bin("specforge").unwrap();
+    json.args(["--output", "json", "verify", "--directory"]).arg(temp_dir.path());
+    let output = json.assert().failure().code(1).get_output().clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    assert_eq!(report["verified"], false);
+    assert_eq!(report["mismatches"], 1);
+    let error: serde_json::Value = serde_json::from_str(stdout.lines().nth(1).unwrap()).unwrap();
+    assert_eq!(error["code"], "verification_failed");
+}
+
+#[test]
+fn test_templates_changelog() {
+    let mut json = Command::cargo_bin("specforge").unwrap();
+    json.args(["templates", "changelog", "--agent", "claude", "--json"]);
+    let output = json.assert().success().get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = result["entries"].as_array().unwrap();
+    assert!(!entries.is_empty());
+    assert!(entries.iter().all(|entry| entry["agent"] == "claude"));
+
+    let mut unknown = Command::cargo_bin("specforge").unwrap();
+    unknown.args(["templates", "changelog", "--agent", "gemini"]);
+    unknown.assert().failure();
+}
+
+#[test]
+fn test_verify_lint_checks_instructions_size() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "copilot", "--force", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    // The edit also fails the checksum check; --lint adds why the file is a problem
+    fs::write(temp_dir.path().join("CLAUDE.md"), "x".repeat(40_000)).unwrap();
+    let mut lint = Command::cargo_bin("specforge").unwrap();
+    lint.args(["verify", "--lint", "--directory"]).arg(temp_dir.path());
+    lint.assert()
+        .failure()
+        .stdout(predicate::str::contains("[error] CLAUDE.md: 40000 bytes is more than copilot reads at all"))
+        .stdout(predicate::str::contains("path-scoped instruction files"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_verify_fix_restores_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("CLAUDE.md");
+    let init = || {
+        let mut init = Command::cargo_bin("specforge").unwrap();
+        init.args(["init", "--agent", "claude", "--output-directory"])
+            .arg(temp_dir.path())
+            .arg("--force");
+        init
+    };
+    init().assert().success();
+
+    // Record the file as an executable script that has since lost its execute bits
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    let tracked = config["files"].as_array_mut().unwrap().iter_mut().find(|f| f["path"] == "CLAUDE.md").unwrap();
+    assert!(tracked["mode"].is_string());
+    tracked["mode"] = serde_json::json!("755");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+    let content = fs::read(&script).unwrap();
+    let modified = fs::metadata(&script).unwrap().modified().unwrap();
+
+    let mut verify = Command::cargo_bin("specforge").unwrap();
+    verify.args(["verify", "--directory"]).arg(temp_dir.path());
+    verify.assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[error] CLAUDE.md (mode changed)"));
+
+    let mut fix = Command::cargo_bin("specforge").unwrap();
+    fix.args(["verify", "--fix", "--directory"]).arg(temp_dir.path());
+    fix.assert()
+        .success()
+        .stdout(predicate::str::contains("[fixed] CLAUDE.md (mode restored)"));
+
+    let metadata = fs::metadata(&script).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o755);
+    assert_eq!(metadata.modified().unwrap(), modified);
+    assert_eq!(fs::read(&script).unwrap(), content);
+
+    // Redeploying brings the mode back as well, without rewriting the content
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+    init().assert().success().stdout(predicate::str::contains("CLAUDE.md (mode restored)"));
+    assert_eq!(fs::metadata(&script).unwrap().permissions().mode() & 0o777, 0o755);
+    assert_eq!(fs::metadata(&script).unwrap().modified().unwrap(), modified);
+}
+
+/// Contents of every file below a directory, keyed by relative path
+fn snapshot_files(dir: &std::path::Path) -> std::collections::BTreeMap<String, Vec<u8>> {
+    let mut files = std::collections::BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                let relative = path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/");
+                files.insert(relative, fs::read(&path).unwrap());
+            }
+        }
+    }
+    files
+}
+
+#[test]
+fn test_init_from_answers_file_is_idempotent() {
+    let answers_dir = TempDir::new().unwrap();
+    let answers = answers_dir.path().join("answers.json");
+    fs::write(
+        &answers,
+        r#"{
+            "agent": ["claude", "copilot"],
+            "project_name": "answered",
+            "on_conflict": "overwrite",
+            "variables": {"team": "platform"}
+        }"#,
+    )
+    .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut first = Command::cargo_bin("specforge").unwrap();
+    first.args(["init", "--output-directory"]).arg(temp_dir.path()).arg("--answers").arg(&answers);
+    first.assert().success();
+    let after_first = snapshot_files(temp_dir.path());
+
+    let mut second = Command::cargo_bin("specforge").unwrap();
+    second.args(["init", "--output-directory"]).arg(temp_dir.path()).arg("--answers").arg(&answers);
+    second.assert()
+        .success()
+        .stdout(predicate::str::contains("0 created, 0 overwritten"));
+    assert_eq!(snapshot_files(temp_dir.path()), after_first);
+
+    // The answers file gives the same project as the equivalent flags
+    let flags_dir = TempDir::new().unwrap();
+    let mut flags = Command::cargo_bin("specforge").unwrap();
+    flags.args(["init", "--agent", "claude", "copilot", "--project-name", "answered"])
+        .args(["--on-conflict", "overwrite", "--var", "team=platform", "--output-directory"])
+        .arg(flags_dir.path());
+    flags.assert().success();
+
+    let read_config = |dir: &std::path::Path| {
+        let mut config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join(".specforge.json")).unwrap()).unwrap();
+        config["metadata"].as_object_mut().unwrap().remove("created_at");
+        config
+    };
+    assert_eq!(read_config(temp_dir.path()), read_config(flags_dir.path()));
+}
+
+#[test]
+fn test_init_flags_override_answers_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let answers = temp_dir.path().join("answers.json");
+    fs::write(&answers, r#"{"agent": "copilot", "project_name": "answered", "groups": []}"#).unwrap();
+
+    let mut cmd = Command::cargo_bin("specforge").unwrap();
+    cmd.args(["init", "--agent", "claude", "--output-directory"])
+        .arg(temp_dir.path())
+        .arg("--answers")
+        .arg(&answers);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("--agent overrides 'agent'"))
+        .stdout(predicate::str::contains("Ignoring unknown key 'groups'"));
+
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"agent\": \"claude\""));
+    assert!(config.contains("\"project_name\": \"answered\""));
+}
+
+#[test]
+fn test_install_hooks_and_validate() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut outside = Command::cargo_bin("specforge").unwrap();
+    outside.args(["install-hooks", "--output-directory"]).arg(temp_dir.path());
+    outside.assert()
+        .code(2)
+        .stderr(predicate::str::contains("not inside a Git repository"))
+        .stderr(predicate::str::contains("git init"));
+
+    fs::create_dir(temp_dir.path().join(".git")).unwrap();
+    let project = temp_dir.path().join("app");
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(&project);
+    init.assert().success();
+
+    let mut hooks = Command::cargo_bin("specforge").unwrap();
+    hooks.args(["install-hooks", "--output-directory"]).arg(&project);
+    hooks.assert()
+        .success()
+        .stdout(predicate::str::contains("Installed pre-commit hook"));
+    let hook = fs::read_to_string(temp_dir.path().join(".git/hooks/pre-commit")).unwrap();
+    assert!(hook.contains("validate --directory 'app'"));
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(&project);
+    validate.assert().success().stdout(predicate::str::contains("is valid"));
+
+    fs::write(project.join(".specforge.json"), "{ not json").unwrap();
+    let mut invalid = Command::cargo_bin("specforge").unwrap();
+    invalid.args(["validate", "--directory"]).arg(&project);
+    invalid.assert().failure();
+}
+
+#[test]
+fn test_deinit_removes_hooks_and_other_specforge_files() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join(".git")).unwrap();
+    fs::write(temp_dir.path().join(".gitignore"), "target/\n").unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--update-gitignore", "--output-directory"])
+        .arg(temp_dir.path());
+    init.assert().success();
+    let mut hooks = Command::cargo_bin("specforge").unwrap();
+    hooks.args(["install-hooks", "--attributes", "--output-directory"]).arg(temp_dir.path());
+    hooks.assert().success();
+    let mut export = Command::cargo_bin("specforge").unwrap();
+    export.args(["export", "--format", "github-actions", "--output-directory"]).arg(temp_dir.path());
+    export.assert().success();
+    fs::write(temp_dir.path().join("CLAUDE.md.specforge-backup-20260101T000000Z"), "mine").unwrap();
+    let hook = temp_dir.path().join(".git/hooks/pre-commit");
+    assert!(hook.exists());
+
+    let mut deinit = Command::cargo_bin("specforge").unwrap();
+    deinit.args(["deinit", "--force", "--directory"]).arg(temp_dir.path());
+    deinit.assert()
+        .success()
+        .stdout(predicate::str::contains("pre-commit - remove"))
+        .stdout(predicate::str::contains("specforge-validate.yml - remove"));
+
+    assert!(!hook.exists());
+    assert!(temp_dir.path().join(".git/hooks").is_dir());
+    assert!(!temp_dir.path().join(".github").exists());
+    assert!(!temp_dir.path().join("CLAUDE.md.specforge-backup-20260101T000000Z").exists());
+    assert!(!temp_dir.path().join(".gitattributes").exists());
+    assert_eq!(fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap(), "target/\n");
+}
+
+#[test]
+fn test_install_hooks_attributes_and_merge_driver() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join(".git")).unwrap();
+    fs::write(temp_dir.path().join(".git/config"), "[core]\n\tbare = false\n").unwrap();
+
+    let mut hooks = Command::cargo_bin("specforge").unwrap();
+    hooks.args(["install-hooks", "--attributes", "--output-directory"]).arg(temp_dir.path());
+    hooks.assert()
+        .success()
+        .stdout(predicate::str::contains("Registered the specforge-json merge driver"));
+    let attributes = fs::read_to_string(temp_dir.path().join(".gitattributes")).unwrap();
+    assert!(attributes.contains(".specforge.json text eol=lf merge=specforge-json\n"));
+    let git_config = fs::read_to_string(temp_dir.path().join(".git/config")).unwrap();
+    assert!(git_config.contains("driver = specforge merge-driver %O %A %B"));
+
+    let base = r#"{"schema_version": 3, "agent": "claude", "packages": [{"id": "shared", "version": "1.0.0"}], "files": [], "metadata": {"created_at": "2026-01-01T00:00:00+00:00"}}"#;
+    let ours = r#"{"schema_version": 3, "agent": "claude", "packages": [{"id": "shared", "version": "1.0.0"}, {"id": "ours", "version": "1.0.0"}], "files": [], "metadata": {"created_at": "2026-01-01T00:00:00+00:00"}}"#;
+    let theirs = r#"{"schema_version": 3, "agent": "claude", "packages": [{"id": "shared", "version": "1.0.0"}, {"id": "theirs", "version": "2.0.0"}], "files": [], "metadata": {"created_at": "2026-01-01T00:00:00+00:00"}}"#;
+    for (name, content) in [("base", base), ("ours", ours), ("theirs", theirs)] {
+        fs::write(temp_dir.path().join(name), content).unwrap();
+    }
+    let mut merge = Command::cargo_bin("specforge").unwrap();
+    merge.args(["merge-driver", "base", "ours", "theirs"]).current_dir(temp_dir.path());
+    merge.assert().success();
+
+    let merged = fs::read_to_string(temp_dir.path().join("ours")).unwrap();
+    let ids: Vec<&str> = ["\"shared\"", "\"ours\"", "\"theirs\""].into_iter().filter(|id| merged.contains(id)).collect();
+    assert_eq!(ids.len(), 3, "{}", merged);
+}
+
+#[test]
+fn test_validate_auto_discover() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join(".git")).unwrap();
+    let nested = temp_dir.path().join("src").join("module");
+    fs::create_dir_all(&nested).unwrap();
+
+    let mut missing = Command::cargo_bin("specforge").unwrap();
+    missing.args(["validate", "--auto-discover"]).current_dir(&nested);
+    missing.assert()
+        .code(2)
+        .stderr(predicate::str::contains("or its parent directories"));
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--auto-discover"]).current_dir(&nested);
+    validate.assert()
+        .success()
+        .stdout(predicate::str::contains(".specforge.json is valid"));
+}
+
+#[test]
+fn test_verbosity_flags() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut quiet = Command::cargo_bin("specforge").unwrap();
+    quiet.args(["init", "--agent", "claude", "-q", "--output-directory"]).arg(temp_dir.path());
+    quiet.assert().success().stdout("").stderr("");
+
+    let mut debug = Command::cargo_bin("specforge").unwrap();
+    debug.args(["verify", "-vv", "--output", "json", "--directory"]).arg(temp_dir.path());
+    let output = debug.assert().success().stderr(predicate::str::contains("DEBUG")).get_output().clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["verified"], true);
+
+    // SPECFORGE_DEBUG is an alias for -vv
+    let mut env_debug = Command::cargo_bin("specforge").unwrap();
+    env_debug.env("SPECFORGE_DEBUG", "1").args(["verify", "--directory"]).arg(temp_dir.path());
+    env_debug.assert().success().stderr(predicate::str::contains("hashing file"));
+
+    let mut conflicting = Command::cargo_bin("specforge").unwrap();
+    conflicting.args(["verify", "-q", "-v"]);
+    conflicting.assert().failure();
+}
+
+#[test]
+fn test_no_color_and_no_emoji() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut no_emoji = Command::cargo_bin("specforge").unwrap();
+    // Output that is not a terminal is plain, unless colors are forced
+    no_emoji
+        .env("CLICOLOR_FORCE", "1")
+        .args(["init", "--agent", "claude", "--no-emoji", "--output-directory"])
+        .arg(temp_dir.path());
+    no_emoji
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\n[ok] Successfully created Specforge configuration"))
+        .stdout(predicate::str::contains("\nDeployed 2 template files"))
+        .stdout(predicate::str::contains("✅").not());
+
+    // CLICOLOR_FORCE colors diagnostics even when stderr is not a terminal
+    let mut colored = Command::cargo_bin("specforge").unwrap();
+    colored.env("CLICOLOR_FORCE", "1").args(["verify", "-v", "--directory"]).arg(temp_dir.path());
+    colored.assert().success().stderr(predicate::str::contains("\x1b["));
+
+    let mut no_color_env = Command::cargo_bin("specforge").unwrap();
+    no_color_env
+        .env("CLICOLOR_FORCE", "1")
+        .env("NO_COLOR", "1")
+        .args(["verify", "-v", "--directory"])
+        .arg(temp_dir.path());
+    no_color_env.assert().success().stderr(predicate::str::contains("\x1b[").not());
+
+    let mut no_color_flag = Command::cargo_bin("specforge").unwrap();
+    no_color_flag.env("CLICOLOR_FORCE", "1").args(["verify", "-v", "--no-color", "--directory"]).arg(temp_dir.path());
+    no_color_flag.assert().success().stderr(predicate::str::contains("\x1b[").not());
+
+    // Diffs of overwritten files are colored too, unless colors are off
+    fs::write(temp_dir.path().join("CLAUDE.md"), "# My own notes\n").unwrap();
+    let mut no_color_diff = Command::cargo_bin("specforge").unwrap();
+    no_color_diff
+        .env("CLICOLOR_FORCE", "1")
+        .args(["init", "--agent", "claude", "--force", "--no-color", "--output-directory"])
+        .arg(temp_dir.path());
+    let output = no_color_diff
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\n[ok] Successfully created Specforge configuration"))
+        .stdout(predicate::str::contains("\n[info] Selected agent: claude"))
+        .get_output()
+        .clone();
+    assert!(!output.stdout.contains(&0x1b) && !output.stderr.contains(&0x1b));
+}
+
+#[test]
+fn test_init_updates_gitignore() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join(".gitignore"), "target/").unwrap();
+
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args(["init", "--agent", "claude", "--force", "--update-gitignore", "--gitignore-config"])
+            .arg("--output-directory")
+            .arg(temp_dir.path());
+        cmd.assert().success();
+    }
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap(),
+        "target/\n*.json.backup*\n.specforge.json\n"
+    );
+
+    let mut without = Command::cargo_bin("specforge").unwrap();
+    without.args(["init", "--agent", "claude", "--gitignore-config", "--output-directory"]).arg(temp_dir.path());
+    without.assert().failure().stderr(predicate::str::contains("--update-gitignore"));
+}
+
+#[test]
+fn test_export_github_actions_workflow() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "copilot", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    let mut export = Command::cargo_bin("specforge").unwrap();
+    export.args(["export", "--format", "github-actions", "--output-directory"]).arg(temp_dir.path());
+    export.assert().success().stdout(predicate::str::contains("specforge-validate.yml"));
+
+    let workflow = fs::read_to_string(temp_dir.path().join(".github/workflows/specforge-validate.yml")).unwrap();
+    assert!(workflow.contains("Validate Specforge configuration (copilot)"));
+    assert!(workflow.contains("run: specforge validate"));
+
+    let mut again = Command::cargo_bin("specforge").unwrap();
+    again.args(["export", "--format", "github-actions", "--output-directory"]).arg(temp_dir.path());
+    again.assert().success().stdout(predicate::str::contains("already up to date"));
+}
+
+#[test]
+fn test_user_config_defaults() {
+    let config_dir = TempDir::new().unwrap();
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut set = Command::cargo_bin("specforge").unwrap();
+    set.env("SPECFORGE_CONFIG_DIR", config_dir.path())
+        .args(["config", "set", "default_agent", "codeium"]);
+    set.assert().success();
+    let mut invalid = Command::cargo_bin("specforge").unwrap();
+    invalid.env("SPECFORGE_CONFIG_DIR", config_dir.path())
+        .args(["config", "set", "default_agent", "vim"]);
+    invalid.assert().failure();
+
+    let mut get = Command::cargo_bin("specforge").unwrap();
+    get.env("SPECFORGE_CONFIG_DIR", config_dir.path())
+        .args(["config", "get", "default_agent"]);
+    get.assert().success().stdout("codeium\n");
+
+    let mut list = Command::cargo_bin("specforge").unwrap();
+    list.env("SPECFORGE_CONFIG_DIR", config_dir.path())
+        .env("SPECFORGE_DEFAULT_AGENT", "copilot")
+        .args(["config", "list"]);
+    list.assert()
+        .success()
+        .stdout(predicate::str::contains("default_agent = copilot (environment)"))
+        .stdout(predicate::str::contains("no_color is not set"));
+
+    // The user default replaces the prompt, and --agent wins over it
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.env("SPECFORGE_CONFIG_DIR", config_dir.path())
+        .env_remove("SPECFORGE_DEFAULT_AGENT")
+        .args(["init", "--output-directory"])
+        .arg(temp_dir.path());
+    init.assert()
+        .success()
+        .stdout(predicate::str::contains("Using default agent codeium"));
+    assert!(temp_dir.path().join("codeium.json").exists());
+
+    let mut flagged = Command::cargo_bin("specforge").unwrap();
+    flagged.env("SPECFORGE_CONFIG_DIR", config_dir.path())
+        .env("SPECFORGE_DEFAULT_AGENT", "copilot")
+        .args(["init", "--agent", "claude", "--force", "--output-directory"])
+        .arg(temp_dir.path());
+    flagged.assert().success();
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"agent\": \"claude\""));
+}
+
+#[test]
+fn test_provenance_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let claude_path = temp_dir.path().join("CLAUDE.md");
+    let verify = || {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args(["verify", "--directory"]).arg(temp_dir.path());
+        cmd.assert()
+    };
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+
+    let deployed = fs::read_to_string(&claude_path).unwrap();
+    let footer = deployed.lines().last().unwrap().to_string();
+    assert!(footer.starts_with("<!-- specforge: package=specforge-claude-templates version="));
+    assert!(footer.contains(" source=sha256:"));
+    // README.md is opted out in the template registry
+    let readme = fs::read_to_string(temp_dir.path().join("README.md")).unwrap();
+    assert!(!readme.contains("<!-- specforge:"));
+    verify().success();
+
+    // Rewriting the provenance line is not a modification, and upgrade restores it
+    fs::write(&claude_path, deployed.replace(&footer, &footer.replace("version=", "version=0.0.0-"))).unwrap();
+    verify().success();
+
+    let config_path = temp_dir.path().join(".specforge.json");
+    let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["packages"][0]["version"] = serde_json::json!("0.0.1");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    let mut upgrade = Command::cargo_bin("specforge").unwrap();
+    upgrade.args(["upgrade", "--directory"]).arg(temp_dir.path());
+    upgrade.assert()
+        .success()
+        .stdout(predicate::str::contains("CLAUDE.md - updated"))
+        .stdout(predicate::str::contains("will be backed up").not());
+    assert_eq!(fs::read_to_string(&claude_path).unwrap(), deployed);
+    verify().success();
+
+    // Deleting the provenance line by hand is a modification of that line only
+    let without_footer = deployed.replace(&format!("{}\n", footer), "");
+    fs::write(&claude_path, &without_footer).unwrap();
+    verify()
+        .failure()
+        .stdout(predicate::str::contains("[error] CLAUDE.md (modified)"));
+    let diff = specforge::templates::unified_diff(&without_footer, &deployed, "CLAUDE.md").unwrap();
+    let changed: Vec<_> = diff
+        .lines()
+        .filter(|line| (line.starts_with('+') || line.starts_with('-')) && !line.starts_with("+++") && !line.starts_with("---"))
+        .collect();
+    assert_eq!(changed, vec![format!("+{}", footer)]);
+}
+
+#[test]
+fn test_init_without_provenance() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "codeium", "--no-provenance", "--output-directory"])
+        .arg(temp_dir.path());
+    init.assert().success();
+
+    let claude = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+    assert!(!claude.contains("<!-- specforge:"));
+    let codeium = fs::read_to_string(temp_dir.path().join("codeium.json")).unwrap();
+    assert!(!codeium.contains("_specforge"));
+    let config = fs::read_to_string(temp_dir.path().join(".specforge.json")).unwrap();
+    assert!(config.contains("\"provenance\": false"));
+
+    let mut with_provenance = Command::cargo_bin("specforge").unwrap();
+    with_provenance.args(["init", "--agent", "codeium", "--force", "--output-directory"])
+        .arg(temp_dir.path());
+    with_provenance.assert().success();
+    let codeium: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join("codeium.json")).unwrap()).unwrap();
+    assert_eq!(codeium["_specforge"]["package"], "specforge-codeium-templates");
+}
+
+/// A batch initializes each project on its own and reports the ones that failed
+#[cfg(unix)]
+#[test]
+fn test_init_batch_with_read_only_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let read_only = temp_dir.path().join("two");
+    fs::create_dir(&read_only).unwrap();
+    let mut permissions = fs::metadata(&read_only).unwrap().permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&read_only, permissions).unwrap();
+
+    // Privileged users can write to read-only directories, so there is nothing to check
+    if fs::write(read_only.join("probe"), "").is_ok() {
+        return;
+    }
+
+    let batch_path = temp_dir.path().join("projects.csv");
+    fs::write(&batch_path, "# name,directory\nProject One,one\nProject Two,two\nProject Three,three\n").unwrap();
+
+    let mut stop = Command::cargo_bin("specforge").unwrap();
+    stop.args(["init", "--agent", "claude", "--on-conflict", "overwrite", "--batch"])
+        .arg(&batch_path);
+    stop.assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("1 initialized, 1 failed, 1 not attempted"))
+        .stderr(predicate::str::contains("1 of 3 project(s) could not be initialized"));
+    assert!(temp_dir.path().join("one").join(".specforge.json").exists());
+    assert!(!temp_dir.path().join("three").exists());
+
+    let mut json = Command::cargo_bin("specforge").unwrap();
+    json.args(["--output", "json", "init", "--agent", "claude", "--on-conflict", "overwrite"])
+        .args(["--continue-on-error", "--batch"])
+        .arg(&batch_path);
+    let output = json.assert().failure().code(1).get_output().clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    let statuses: Vec<&str> = report["projects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|project| project["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["initialized", "failed", "initialized"]);
+    let error: serde_json::Value = serde_json::from_str(stdout.lines().nth(1).unwrap()).unwrap();
+    assert_eq!(error["code"], "batch_failed");
+
+    // Each project got its own configuration and lock file
+    for (dir, name) in [("one", "Project One"), ("three", "Project Three")] {
+        let config = fs::read_to_string(temp_dir.path().join(dir).join(".specforge.json")).unwrap();
+        assert!(config.contains(name));
+        assert!(temp_dir.path().join(dir).join("specforge.lock").exists());
+    }
+    assert!(!read_only.join(".specforge.json").exists());
+}
+
+/// Files overwritten by `init --backup` are copied first and can be restored
+#[test]
+fn test_init_backup_and_restore() {
+    let temp_dir = TempDir::new().unwrap();
+    let init = || {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.args(["init", "--agent", "claude", "--on-conflict", "overwrite", "--backup", "--output-directory"])
+            .arg(temp_dir.path());
+        cmd
+    };
+    init().assert().success();
+
+    let claude_path = temp_dir.path().join("CLAUDE.md");
+    let edited = b"# Local edits\r\nwith \xc3\xa9 and no trailing newline".to_vec();
+    fs::write(&claude_path, &edited).unwrap();
+    init().assert()
+        .success()
+        .stdout(predicate::str::contains("💾 Backed up"))
+        .stdout(predicate::str::contains("previous version in"));
+
+    let backups: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().contains("CLAUDE.md.specforge-backup-"))
+        .collect();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(fs::read(&backups[0]).unwrap(), edited);
+    assert_ne!(fs::read(&claude_path).unwrap(), edited);
+
+    let mut restore = Command::cargo_bin("specforge").unwrap();
+    restore.args(["restore-backups", "--directory"]).arg(temp_dir.path());
+    restore.assert().success().stdout(predicate::str::contains("Restored 1 file(s)"));
+    assert_eq!(fs::read(&claude_path).unwrap(), edited);
+    assert!(!backups[0].exists());
+}
+
+/// Set `min_specforge_version` in an initialized project's configuration
+fn require_specforge_version(dir: &std::path::Path, version: &str) {
+    let config_path = dir.join(".specforge.json");
+    let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["min_specforge_version"] = version.into();
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+}
+
+/// A project requiring a newer release stops every command before it writes
+#[test]
+fn test_min_specforge_version_newer_than_installed() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+    require_specforge_version(temp_dir.path(), "99.0.0");
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert()
+        .failure()
+        .code(22)
+        .stderr(predicate::str::contains("requires specforge 99.0.0 or newer"))
+        .stderr(predicate::str::contains(format!("specforge {} is installed", env!("CARGO_PKG_VERSION"))));
+
+    let claude_path = temp_dir.path().join("CLAUDE.md");
+    fs::write(&claude_path, "# Local edits").unwrap();
+    let mut reinit = Command::cargo_bin("specforge").unwrap();
+    reinit.args(["init", "--agent", "claude", "--on-conflict", "overwrite", "--output-directory"])
+        .arg(temp_dir.path());
+    reinit.assert().failure().code(22);
+    assert_eq!(fs::read_to_string(&claude_path).unwrap(), "# Local edits");
+
+    let mut ignored = Command::cargo_bin("specforge").unwrap();
+    ignored.args(["validate", "--ignore-version-requirement", "--directory"]).arg(temp_dir.path());
+    ignored.assert().success();
+}
+
+/// A project requiring an older release runs as usual
+#[test]
+fn test_min_specforge_version_older_than_installed() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+    require_specforge_version(temp_dir.path(), "0.0.1");
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert().success();
+}
+
+/// A minimum version that is not semver is reported instead of ignored
+#[test]
+fn test_min_specforge_version_malformed() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut init = Command::cargo_bin("specforge").unwrap();
+    init.args(["init", "--agent", "claude", "--output-directory"]).arg(temp_dir.path());
+    init.assert().success();
+    require_specforge_version(temp_dir.path(), "1.x");
+
+    let mut validate = Command::cargo_bin("specforge").unwrap();
+    validate.args(["validate", "--directory"]).arg(temp_dir.path());
+    validate.assert()
+        .failure()
+        .code(22)
+        .stderr(predicate::str::contains("min_specforge_version"));
+}
+
+/// `version --check` compares the running binary with a requirement outside of any project
+#[test]
+fn test_version_check() {
+    let temp_dir = TempDir::new().unwrap();
+    let check = |requirement: &str| {
+        let mut cmd = Command::cargo_bin("specforge").unwrap();
+        cmd.current_dir(temp_dir.path()).args(["version", "--check", requirement]);
+        cmd
+    };
+
+    check(&format!(">={}", env!("CARGO_PKG_VERSION"))).assert().success();
+    check(">=99.0")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("does not satisfy the version requirement '>=99.0'"));
+    check(">=one").assert().failure().code(22);
+    for malformed in ["", ">=1.0, <"] {
+        check(malformed)
+            .assert()
+            .failure()
+            .code(22)
+            .stderr(predicate::str::contains("Invalid version requirement"))
+            .stderr(predicate::str::contains("specforge init").not());
+    }
+
+    let mut version = Command::cargo_bin("specforge").unwrap();
+    version.arg("version");
+    version.assert()
+        .success()
+        .stdout(format!("specforge {}\n", env!("CARGO_PKG_VERSION")));
+}
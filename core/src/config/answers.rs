@@ -0,0 +1,169 @@
+use crate::config::Agent;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{ConfigFormat, ConflictPolicy};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Keys recognized in an answers file
+pub const ANSWER_KEYS: &[&str] = &[
+    "agent",
+    "project_name",
+    "on_conflict",
+    "config_format",
+    "variables",
+    "template_dir",
+    "strict_templates",
+];
+
+/// Answers to the questions `specforge init` would otherwise take from flags or prompts
+///
+/// Read from a JSON or TOML file so that a project can be initialized the
+/// same way on every run, e.g. in CI or from a setup script.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InitAnswers {
+    /// Agents to configure; a single agent may be given as a bare string
+    #[serde(default, deserialize_with = "crate::config::project::agents_format::deserialize")]
+    pub agent: Vec<Agent>,
+    /// Project name recorded in the configuration metadata
+    pub project_name: Option<String>,
+    /// What to do with existing configuration and template files
+    pub on_conflict: Option<ConflictPolicy>,
+    /// File format of the configuration file to write
+    pub config_format: Option<ConfigFormat>,
+    /// Template variables substituted for `{{KEY}}` placeholders
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// Directory with custom templates, relative to the answers file
+    pub template_dir: Option<PathBuf>,
+    /// Fail instead of warning on unresolved placeholders and broken links
+    pub strict_templates: Option<bool>,
+}
+
+impl InitAnswers {
+    /// Read an answers file, in TOML or YAML when its extension says so and in JSON otherwise
+    ///
+    /// Unknown keys are dropped with a warning instead of failing, so answers
+    /// files written for newer releases still work. A relative `template_dir`
+    /// is resolved against the directory of the answers file.
+    pub fn read(path: &Path) -> Result<(Self, Vec<String>)> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            ConfigError::io_error(format!("Failed to read answers file '{}': {}", path.display(), e))
+        })?;
+        let value: serde_json::Value = match ConfigFormat::from_path(path) {
+            ConfigFormat::Json => serde_json::from_str(&content).map_err(|e| invalid(path, e))?,
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => toml::from_str(&content).map_err(|e| invalid(path, e))?,
+            #[cfg(not(feature = "toml"))]
+            ConfigFormat::Toml => {
+                return Err(ConfigError::validation_error(
+                    "TOML answers files require specforge to be built with the 'toml' feature",
+                ));
+            }
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(&content).map_err(|e| invalid(path, e))?,
+            #[cfg(not(feature = "yaml"))]
+            ConfigFormat::Yaml => {
+                return Err(ConfigError::validation_error(
+                    "YAML answers files require specforge to be built with the 'yaml' feature",
+                ));
+            }
+        };
+        let serde_json::Value::Object(mut map) = value else {
+            return Err(invalid(path, "expected a table of answers"));
+        };
+
+        let mut warnings = Vec::new();
+        map.retain(|key, _| {
+            let known = ANSWER_KEYS.contains(&key.as_str());
+            if !known {
+                warnings.push(format!("Ignoring unknown key '{}' in {}", key, path.display()));
+            }
+            known
+        });
+
+        let mut answers: Self =
+            serde_json::from_value(serde_json::Value::Object(map)).map_err(|e| invalid(path, e))?;
+        if let Some(template_dir) = &answers.template_dir
+            && template_dir.is_relative()
+        {
+            let base = path.parent().unwrap_or(Path::new("."));
+            answers.template_dir = Some(base.join(template_dir));
+        }
+        Ok((answers, warnings))
+    }
+}
+
+/// Error for an answers file that cannot be parsed
+fn invalid(path: &Path, error: impl std::fmt::Display) -> ConfigError {
+    ConfigError::validation_error(format!("Invalid answers file {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_json_answers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("answers.json");
+        fs::write(
+            &path,
+            r#"{
+                "agent": ["claude", "copilot"],
+                "project_name": "demo",
+                "on_conflict": "overwrite",
+                "variables": {"team": "platform"},
+                "template_dir": "templates",
+                "excludes": ["README.md"]
+            }"#,
+        )
+        .unwrap();
+
+        let (answers, warnings) = InitAnswers::read(&path).unwrap();
+        assert_eq!(answers.agent, vec![Agent::Claude, Agent::Copilot]);
+        assert_eq!(answers.project_name.as_deref(), Some("demo"));
+        assert_eq!(answers.on_conflict, Some(ConflictPolicy::Overwrite));
+        assert_eq!(answers.config_format, None);
+        assert_eq!(answers.variables["team"], "platform");
+        assert_eq!(answers.template_dir, Some(temp_dir.path().join("templates")));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'excludes'"));
+    }
+
+    #[test]
+    fn test_read_invalid_answers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("answers.json");
+
+        fs::write(&path, r#"{"agent": "vim"}"#).unwrap();
+        let error = InitAnswers::read(&path).unwrap_err();
+        assert!(error.to_string().contains("Invalid answers file"));
+
+        fs::write(&path, r#"["claude"]"#).unwrap();
+        assert!(InitAnswers::read(&path).is_err());
+        assert!(InitAnswers::read(&temp_dir.path().join("missing.json")).is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_read_toml_answers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("answers.toml");
+        fs::write(
+            &path,
+            "agent = \"codeium\"\nconfig_format = \"toml\"\nstrict_templates = true\n\n[variables]\nteam = \"web\"\n",
+        )
+        .unwrap();
+
+        let (answers, warnings) = InitAnswers::read(&path).unwrap();
+        assert_eq!(answers.agent, vec![Agent::Codeium]);
+        assert_eq!(answers.config_format, Some(ConfigFormat::Toml));
+        assert_eq!(answers.strict_templates, Some(true));
+        assert_eq!(answers.variables["team"], "web");
+        assert!(warnings.is_empty());
+    }
+}
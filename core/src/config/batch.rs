@@ -0,0 +1,139 @@
+use crate::error::{ConfigError, Result};
+use serde::Deserialize;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A project to initialize as part of `specforge init --batch`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEntry {
+    /// Project name recorded in the configuration metadata
+    pub name: String,
+    /// Directory to initialize, relative to the batch file; defaults to the name
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+}
+
+impl BatchEntry {
+    /// Read the projects listed in a batch file
+    ///
+    /// A file with a `.json` extension holds an array of `{"name", "directory"}`
+    /// objects. Any other file lists one project per line as `name,directory`,
+    /// skipping blank lines and lines starting with `#`. Relative directories
+    /// are resolved against the directory of the batch file, and every entry
+    /// gets a directory of its own.
+    pub fn read_all(path: &Path) -> Result<Vec<BatchEntry>> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            ConfigError::io_error(format!("Failed to read batch file '{}': {}", path.display(), e))
+        })?;
+
+        let is_json = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let mut entries: Vec<BatchEntry> = if is_json {
+            serde_json::from_str(&content).map_err(|e| invalid(path, e))?
+        } else {
+            parse_lines(&content, path)?
+        };
+        if entries.is_empty() {
+            return Err(invalid(path, "no projects listed"));
+        }
+
+        let base = path.parent().unwrap_or(Path::new("."));
+        for entry in &mut entries {
+            entry.name = entry.name.trim().to_string();
+            if entry.name.is_empty() {
+                return Err(invalid(path, "project names cannot be empty"));
+            }
+            let directory = entry.directory.take().unwrap_or_else(|| PathBuf::from(&entry.name));
+            entry.directory = Some(base.join(directory));
+        }
+
+        for (index, entry) in entries.iter().enumerate() {
+            if entries[..index].iter().any(|other| other.directory == entry.directory) {
+                return Err(invalid(
+                    path,
+                    format!("'{}' is listed more than once", entry.directory().display()),
+                ));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Directory to initialize
+    pub fn directory(&self) -> &Path {
+        self.directory.as_deref().unwrap_or(Path::new(&self.name))
+    }
+}
+
+/// Parse the `name,directory` lines of a batch file
+fn parse_lines(content: &str, path: &Path) -> Result<Vec<BatchEntry>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split(',').map(str::trim).collect::<Vec<_>>().as_slice() {
+            [name] => Ok(BatchEntry { name: name.to_string(), directory: None }),
+            [name, directory] if !directory.is_empty() => Ok(BatchEntry {
+                name: name.to_string(),
+                directory: Some(PathBuf::from(directory)),
+            }),
+            _ => Err(invalid(path, format!("expected 'name,directory' but found '{}'", line))),
+        })
+        .collect()
+}
+
+/// Error for a batch file that cannot be parsed
+fn invalid(path: &Path, error: impl std::fmt::Display) -> ConfigError {
+    ConfigError::validation_error(format!("Invalid batch file {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_line_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("projects.csv");
+        fs::write(&path, "# name,directory\nbilling, services/billing\n\nsearch\n").unwrap();
+
+        let entries = BatchEntry::read_all(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "billing");
+        assert_eq!(entries[0].directory(), temp_dir.path().join("services/billing"));
+        assert_eq!(entries[1].name, "search");
+        assert_eq!(entries[1].directory(), temp_dir.path().join("search"));
+    }
+
+    #[test]
+    fn test_read_json_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("projects.json");
+        fs::write(&path, r#"[{"name": "billing", "directory": "/srv/billing"}, {"name": "search"}]"#).unwrap();
+
+        let entries = BatchEntry::read_all(&path).unwrap();
+        assert_eq!(entries[0].directory(), Path::new("/srv/billing"));
+        assert_eq!(entries[1].directory(), temp_dir.path().join("search"));
+    }
+
+    #[test]
+    fn test_read_invalid_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("projects.txt");
+
+        fs::write(&path, "a,b,c\n").unwrap();
+        assert!(BatchEntry::read_all(&path).unwrap_err().to_string().contains("Invalid batch file"));
+
+        fs::write(&path, "# nothing yet\n").unwrap();
+        assert!(BatchEntry::read_all(&path).is_err());
+
+        fs::write(&path, "billing\nbilling-v2,billing\n").unwrap();
+        assert!(BatchEntry::read_all(&path).unwrap_err().to_string().contains("more than once"));
+
+        assert!(BatchEntry::read_all(&temp_dir.path().join("missing.txt")).is_err());
+    }
+}
@@ -0,0 +1,520 @@
+//! Conditions that decide whether a package applies in the current environment.
+//!
+//! The condition language is intentionally small:
+//!
+//! ```text
+//! env_set("CI")                     environment variable is set
+//! os("windows")                     operating system or family (unix, windows)
+//! profile("prod")                   SPECFORGE_PROFILE equals the given name
+//! not(<condition>)                  negation
+//! <condition> && <condition>        both hold (binds tighter than ||)
+//! <condition> || <condition>        either holds
+//! (<condition>)                     grouping
+//! ```
+
+use crate::error::{ConfigError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Environment variable that selects the active profile
+pub const PROFILE_ENV_VAR: &str = "SPECFORGE_PROFILE";
+
+/// A parsed package condition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// The environment variable is set
+    EnvSet(String),
+    /// The operating system or OS family matches
+    Os(String),
+    /// The active profile matches
+    Profile(String),
+    /// The inner condition does not hold
+    Not(Box<Condition>),
+    /// All conditions hold
+    All(Vec<Condition>),
+    /// At least one condition holds
+    Any(Vec<Condition>),
+}
+
+/// Facts about the environment that conditions are evaluated against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConditionContext {
+    /// Operating system name, e.g. "linux" or "windows"
+    pub os: String,
+    /// Operating system family, e.g. "unix" or "windows"
+    pub os_family: String,
+    /// Active profile, if any
+    pub profile: Option<String>,
+    /// Names of the environment variables that are set
+    pub env_vars: BTreeSet<String>,
+}
+
+impl ConditionContext {
+    /// Capture the current process environment
+    pub fn from_environment() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            os_family: std::env::consts::FAMILY.to_string(),
+            profile: std::env::var(PROFILE_ENV_VAR).ok().filter(|p| !p.is_empty()),
+            env_vars: std::env::vars_os()
+                .map(|(name, _)| name.to_string_lossy().into_owned())
+                .collect(),
+        }
+    }
+}
+
+impl Condition {
+    /// Evaluate the condition against the given environment
+    pub fn evaluate(&self, context: &ConditionContext) -> bool {
+        match self {
+            Condition::EnvSet(name) => context.env_vars.contains(name),
+            Condition::Os(name) => {
+                name.eq_ignore_ascii_case(&context.os) || name.eq_ignore_ascii_case(&context.os_family)
+            }
+            Condition::Profile(name) => context.profile.as_deref() == Some(name.as_str()),
+            Condition::Not(inner) => !inner.evaluate(context),
+            Condition::All(conditions) => conditions.iter().all(|c| c.evaluate(context)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.evaluate(context)),
+        }
+    }
+}
+
+/// Write a string literal, escaping quotes and backslashes
+fn write_literal(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    write!(f, "\"")
+}
+
+/// Write the operands of && or ||, grouping nested operators in parentheses
+fn write_operands(f: &mut fmt::Formatter<'_>, conditions: &[Condition], operator: &str) -> fmt::Result {
+    for (index, condition) in conditions.iter().enumerate() {
+        if index > 0 {
+            write!(f, " {} ", operator)?;
+        }
+        match condition {
+            Condition::All(_) | Condition::Any(_) => write!(f, "({})", condition)?,
+            _ => write!(f, "{}", condition)?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::EnvSet(name) => {
+                write!(f, "env_set(")?;
+                write_literal(f, name)?;
+                write!(f, ")")
+            }
+            Condition::Os(name) => {
+                write!(f, "os(")?;
+                write_literal(f, name)?;
+                write!(f, ")")
+            }
+            Condition::Profile(name) => {
+                write!(f, "profile(")?;
+                write_literal(f, name)?;
+                write!(f, ")")
+            }
+            Condition::Not(inner) => write!(f, "not({})", inner),
+            Condition::All(conditions) => write_operands(f, conditions, "&&"),
+            Condition::Any(conditions) => write_operands(f, conditions, "||"),
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Parser::new(s)?.parse()
+    }
+}
+
+impl Serialize for Condition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        source.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Lexical token with its byte range in the source
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    LParen,
+    RParen,
+    And,
+    Or,
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(name) => format!("'{}'", name),
+            Token::Str(value) => format!("string \"{}\"", value),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::And => "'&&'".to_string(),
+            Token::Or => "'||'".to_string(),
+            Token::End => "end of condition".to_string(),
+        }
+    }
+}
+
+/// Build an error that underlines the offending part of the condition
+fn condition_error(source: &str, start: usize, end: usize, message: &str) -> ConfigError {
+    let column = source[..start].chars().count();
+    let width = source[start..end].chars().count().max(1);
+    ConfigError::invalid_package(format!(
+        "Invalid condition at column {}: {}\n  {}\n  {}{}",
+        column + 1,
+        message,
+        source,
+        " ".repeat(column),
+        "^".repeat(width)
+    ))
+}
+
+/// Split the source into tokens with their byte ranges
+fn tokenize(source: &str) -> Result<Vec<(Token, usize, usize)>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '&' | '|' => match chars.next_if(|&(_, next)| next == c) {
+                Some(_) if c == '&' => Token::And,
+                Some(_) => Token::Or,
+                None => {
+                    return Err(condition_error(
+                        source,
+                        start,
+                        start + 1,
+                        &format!("expected '{}{}'", c, c),
+                    ));
+                }
+            },
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) => value.push(escaped),
+                            None => {
+                                return Err(condition_error(source, start, source.len(), "unterminated string"));
+                            }
+                        },
+                        Some((_, other)) => value.push(other),
+                        None => {
+                            return Err(condition_error(source, start, source.len(), "unterminated string"));
+                        }
+                    }
+                }
+                Token::Str(value)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some((_, next)) = chars.next_if(|&(_, n)| n.is_ascii_alphanumeric() || n == '_') {
+                    name.push(next);
+                }
+                Token::Ident(name)
+            }
+            other => {
+                return Err(condition_error(
+                    source,
+                    start,
+                    start + other.len_utf8(),
+                    &format!("unexpected character '{}'", other),
+                ));
+            }
+        };
+
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push((token, start, end));
+    }
+
+    tokens.push((Token::End, source.len(), source.len()));
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the token list
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, usize, usize)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Result<Self> {
+        Ok(Self {
+            source,
+            tokens: tokenize(source)?,
+            position: 0,
+        })
+    }
+
+    fn parse(mut self) -> Result<Condition> {
+        if self.peek() == &Token::End {
+            return Err(condition_error(self.source, 0, self.source.len(), "condition is empty"));
+        }
+
+        let condition = self.parse_or()?;
+        match self.peek() {
+            Token::End => Ok(condition),
+            other => {
+                let message = format!("unexpected {} after condition", other.describe());
+                Err(self.error_here(&message))
+            }
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn advance(&mut self) -> (Token, usize, usize) {
+        let token = self.tokens[self.position].clone();
+        if token.0 != Token::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error_here(&self, message: &str) -> ConfigError {
+        let (_, start, end) = &self.tokens[self.position];
+        condition_error(self.source, *start, *end, message)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        if self.peek() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            let message = format!("expected {}, found {}", expected.describe(), self.peek().describe());
+            Err(self.error_here(&message))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Condition> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek() == &Token::Or {
+            self.advance();
+            operands.push(self.parse_and()?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Condition::Any(operands) })
+    }
+
+    fn parse_and(&mut self) -> Result<Condition> {
+        let mut operands = vec![self.parse_primary()?];
+        while self.peek() == &Token::And {
+            self.advance();
+            operands.push(self.parse_primary()?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Condition::All(operands) })
+    }
+
+    fn parse_primary(&mut self) -> Result<Condition> {
+        match self.peek().clone() {
+            Token::LParen => {
+                self.advance();
+                let inner = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Token::Ident(name) => {
+                let (_, start, end) = self.advance();
+                self.expect(Token::LParen)?;
+                let condition = match name.as_str() {
+                    "not" => Condition::Not(Box::new(self.parse_or()?)),
+                    "env_set" => Condition::EnvSet(self.parse_string()?),
+                    "os" => Condition::Os(self.parse_string()?),
+                    "profile" => Condition::Profile(self.parse_string()?),
+                    _ => {
+                        return Err(condition_error(
+                            self.source,
+                            start,
+                            end,
+                            &format!(
+                                "unknown function '{}' (expected env_set, os, profile, or not)",
+                                name
+                            ),
+                        ));
+                    }
+                };
+                self.expect(Token::RParen)?;
+                Ok(condition)
+            }
+            other => {
+                let message = format!("expected a condition, found {}", other.describe());
+                Err(self.error_here(&message))
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String> {
+        match self.peek().clone() {
+            Token::Str(value) if !value.is_empty() => {
+                self.advance();
+                Ok(value)
+            }
+            Token::Str(_) => Err(self.error_here("argument cannot be empty")),
+            other => {
+                let message = format!("expected a string argument, found {}", other.describe());
+                Err(self.error_here(&message))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Condition {
+        source.parse().unwrap_or_else(|e| panic!("{}: {}", source, e))
+    }
+
+    fn error(source: &str) -> String {
+        source.parse::<Condition>().unwrap_err().to_string()
+    }
+
+    fn context() -> ConditionContext {
+        ConditionContext {
+            os: "linux".to_string(),
+            os_family: "unix".to_string(),
+            profile: Some("dev".to_string()),
+            env_vars: ["CI".to_string()].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_eq!(parse(r#"env_set("CI")"#), Condition::EnvSet("CI".to_string()));
+        assert_eq!(parse(r#" os( "windows" ) "#), Condition::Os("windows".to_string()));
+        assert_eq!(parse(r#"profile("prod")"#), Condition::Profile("prod".to_string()));
+        assert_eq!(
+            parse(r#"not(env_set("CI"))"#),
+            Condition::Not(Box::new(Condition::EnvSet("CI".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        let condition = parse(r#"os("linux") || os("macos") && env_set("CI")"#);
+        assert_eq!(
+            condition,
+            Condition::Any(vec![
+                Condition::Os("linux".to_string()),
+                Condition::All(vec![
+                    Condition::Os("macos".to_string()),
+                    Condition::EnvSet("CI".to_string()),
+                ]),
+            ])
+        );
+
+        let grouped = parse(r#"(os("linux") || os("macos")) && env_set("CI")"#);
+        assert!(matches!(grouped, Condition::All(_)));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let context = context();
+        assert!(parse(r#"env_set("CI")"#).evaluate(&context));
+        assert!(!parse(r#"env_set("HOME_LAB")"#).evaluate(&context));
+        assert!(parse(r#"os("linux")"#).evaluate(&context));
+        assert!(parse(r#"os("UNIX")"#).evaluate(&context));
+        assert!(!parse(r#"os("windows")"#).evaluate(&context));
+        assert!(parse(r#"profile("dev")"#).evaluate(&context));
+        assert!(!parse(r#"profile("prod")"#).evaluate(&context));
+        assert!(!parse(r#"not(env_set("CI"))"#).evaluate(&context));
+
+        // Precedence changes the outcome: true || (false && false) vs (true || false) && false
+        assert!(parse(r#"os("linux") || os("windows") && profile("prod")"#).evaluate(&context));
+        assert!(!parse(r#"(os("linux") || os("windows")) && profile("prod")"#).evaluate(&context));
+
+        assert!(!parse(r#"profile("dev")"#).evaluate(&ConditionContext::default()));
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for source in [
+            r#"env_set("CI")"#,
+            r#"not(os("windows"))"#,
+            r#"os("linux") || os("macos") && env_set("CI")"#,
+            r#"(os("linux") || os("macos")) && not(profile("prod") || env_set("CI"))"#,
+            r#"env_set("A") && (env_set("B") && env_set("C"))"#,
+            r#"profile("say \"hi\" \\ bye")"#,
+        ] {
+            let condition = parse(source);
+            assert_eq!(parse(&condition.to_string()), condition, "{}", source);
+        }
+
+        assert_eq!(
+            parse(r#"(os("linux")||os("macos"))&&env_set("CI")"#).to_string(),
+            r#"(os("linux") || os("macos")) && env_set("CI")"#
+        );
+    }
+
+    #[test]
+    fn test_unknown_function_points_at_name() {
+        let message = error(r#"env_set("CI") && branch("main")"#);
+        assert!(message.contains("column 18"), "{}", message);
+        assert!(message.contains("unknown function 'branch'"));
+        assert!(message.contains("\n                   ^^^^^^"), "{}", message);
+    }
+
+    #[test]
+    fn test_syntax_errors_point_at_token() {
+        let message = error(r#"env_set(CI)"#);
+        assert!(message.contains("column 9"));
+        assert!(message.contains("expected a string argument, found 'CI'"));
+
+        let message = error(r#"os("linux") & os("macos")"#);
+        assert!(message.contains("column 13"));
+        assert!(message.contains("expected '&&'"));
+
+        let message = error(r#"not(os("linux")"#);
+        assert!(message.contains("expected ')', found end of condition"));
+
+        let message = error(r#"os("linux") os("macos")"#);
+        assert!(message.contains("unexpected 'os' after condition"));
+
+        assert!(error(r#"os("linux"#).contains("unterminated string"));
+        assert!(error(r#"profile("")"#).contains("argument cannot be empty"));
+        assert!(error("   ").contains("condition is empty"));
+        assert!(error("os(\"linux\") || $").contains("unexpected character '$'"));
+    }
+
+    #[test]
+    fn test_serde_as_string() {
+        let condition = parse(r#"not(env_set("CI"))"#);
+        let json = serde_json::to_string(&condition).unwrap();
+        assert_eq!(json, r#""not(env_set(\"CI\"))""#);
+        assert_eq!(serde_json::from_str::<Condition>(&json).unwrap(), condition);
+
+        let error = serde_json::from_str::<Condition>(r#""nope()""#).unwrap_err();
+        assert!(error.to_string().contains("unknown function 'nope'"));
+    }
+}
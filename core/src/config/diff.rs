@@ -0,0 +1,276 @@
+use crate::config::{Agent, Package, ProjectConfig, format_agents};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// One difference between two configurations
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum DiffEntry {
+    /// The configured agents differ
+    AgentChanged { from: Vec<Agent>, to: Vec<Agent> },
+    /// The top-level project name differs
+    ProjectNameChanged { from: Option<String>, to: Option<String> },
+    /// The schema version differs
+    SchemaVersionChanged { from: u32, to: u32 },
+    /// A package is only in the new configuration
+    PackageAdded { id: String, version: String },
+    /// A package is only in the old configuration
+    PackageRemoved { id: String, version: String },
+    /// A field of a package in both configurations differs
+    PackageChanged {
+        id: String,
+        field: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// A metadata entry is only in the new configuration
+    MetadataAdded { key: String, value: serde_json::Value },
+    /// A metadata entry is only in the old configuration
+    MetadataRemoved { key: String, value: serde_json::Value },
+    /// A metadata entry in both configurations differs
+    MetadataChanged {
+        key: String,
+        from: serde_json::Value,
+        to: serde_json::Value,
+    },
+    /// A deployed file is only tracked by the new configuration
+    FileAdded { path: String },
+    /// A deployed file is only tracked by the old configuration
+    FileRemoved { path: String },
+    /// A deployed file is tracked by both configurations with a different hash
+    FileChanged { path: String },
+}
+
+impl fmt::Display for DiffEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffEntry::AgentChanged { from, to } => {
+                let label = if from.len() == 1 && to.len() == 1 { "Agent" } else { "Agents" };
+                write!(f, "{} changed: {} → {}", label, format_agents(from), format_agents(to))
+            }
+            DiffEntry::ProjectNameChanged { from, to } => write!(
+                f,
+                "Project name changed: {} → {}",
+                quoted_or_unset(from.as_deref()),
+                quoted_or_unset(to.as_deref())
+            ),
+            DiffEntry::SchemaVersionChanged { from, to } => {
+                write!(f, "Schema version changed: {} → {}", from, to)
+            }
+            DiffEntry::PackageAdded { id, version } => write!(f, "Package added: {}@{}", id, version),
+            DiffEntry::PackageRemoved { id, version } => write!(f, "Package removed: {}@{}", id, version),
+            DiffEntry::PackageChanged { id, field, from, to } => write!(
+                f,
+                "Package '{}' {} changed: {} → {}",
+                id,
+                field,
+                quoted_or_unset(from.as_deref()),
+                quoted_or_unset(to.as_deref())
+            ),
+            DiffEntry::MetadataAdded { key, value } => {
+                write!(f, "Metadata key '{}' added: {}", key, metadata_value(value))
+            }
+            DiffEntry::MetadataRemoved { key, value } => {
+                write!(f, "Metadata key '{}' removed: {}", key, metadata_value(value))
+            }
+            DiffEntry::MetadataChanged { key, from, to } => write!(
+                f,
+                "Metadata key '{}' changed: {} → {}",
+                key,
+                metadata_value(from),
+                metadata_value(to)
+            ),
+            DiffEntry::FileAdded { path } => write!(f, "Tracked file added: {}", path),
+            DiffEntry::FileRemoved { path } => write!(f, "Tracked file removed: {}", path),
+            DiffEntry::FileChanged { path } => write!(f, "Tracked file changed: {}", path),
+        }
+    }
+}
+
+/// Structural differences between two configurations, for reviewing a change
+/// to a configuration file
+///
+/// Entries come in a fixed order: agents, project name, schema version,
+/// packages, metadata and tracked files. Packages and tracked files keep the
+/// order of the configuration they come from, and metadata keys are sorted.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct ConfigDiff {
+    pub entries: Vec<DiffEntry>,
+}
+
+impl ConfigDiff {
+    /// Compare an old configuration with a new one
+    pub fn between(old: &ProjectConfig, new: &ProjectConfig) -> Self {
+        let mut entries = Vec::new();
+
+        if old.agents != new.agents {
+            entries.push(DiffEntry::AgentChanged {
+                from: old.agents.clone(),
+                to: new.agents.clone(),
+            });
+        }
+        if old.project_name() != new.project_name() {
+            entries.push(DiffEntry::ProjectNameChanged {
+                from: old.project_name().map(str::to_string),
+                to: new.project_name().map(str::to_string),
+            });
+        }
+        if old.schema_version != new.schema_version {
+            entries.push(DiffEntry::SchemaVersionChanged {
+                from: old.schema_version,
+                to: new.schema_version,
+            });
+        }
+
+        for package in &new.packages {
+            match old.get_package(&package.id) {
+                Some(previous) => entries.extend(package_changes(previous, package)),
+                None => entries.push(DiffEntry::PackageAdded {
+                    id: package.id.clone(),
+                    version: package.version.to_string(),
+                }),
+            }
+        }
+        for package in old.packages.iter().filter(|p| new.get_package(&p.id).is_none()) {
+            entries.push(DiffEntry::PackageRemoved {
+                id: package.id.clone(),
+                version: package.version.to_string(),
+            });
+        }
+
+        let keys: BTreeSet<&String> = old.metadata.keys().chain(new.metadata.keys()).collect();
+        for key in keys {
+            match (old.metadata.get(key), new.metadata.get(key)) {
+                (None, Some(value)) => entries.push(DiffEntry::MetadataAdded {
+                    key: key.clone(),
+                    value: value.clone(),
+                }),
+                (Some(value), None) => entries.push(DiffEntry::MetadataRemoved {
+                    key: key.clone(),
+                    value: value.clone(),
+                }),
+                (Some(from), Some(to)) if from != to => entries.push(DiffEntry::MetadataChanged {
+                    key: key.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                _ => {}
+            }
+        }
+
+        for file in &new.files {
+            match old.tracked_file(&file.path) {
+                None => entries.push(DiffEntry::FileAdded { path: file.path.clone() }),
+                Some(previous) if previous.sha256 != file.sha256 => {
+                    entries.push(DiffEntry::FileChanged { path: file.path.clone() })
+                }
+                Some(_) => {}
+            }
+        }
+        for file in old.files.iter().filter(|f| new.tracked_file(&f.path).is_none()) {
+            entries.push(DiffEntry::FileRemoved { path: file.path.clone() });
+        }
+
+        Self { entries }
+    }
+
+    /// Whether the configurations are the same
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Changed fields of a package that both configurations list
+fn package_changes(old: &Package, new: &Package) -> Vec<DiffEntry> {
+    let join = |dependencies: &[String]| (!dependencies.is_empty()).then(|| dependencies.join(", "));
+    let fields = [
+        ("version", Some(old.version.to_string()), Some(new.version.to_string())),
+        ("url", old.url.clone(), new.url.clone()),
+        ("checksum", old.checksum.clone(), new.checksum.clone()),
+        ("when", old.when.as_ref().map(|c| c.to_string()), new.when.as_ref().map(|c| c.to_string())),
+        ("dependencies", join(&old.dependencies), join(&new.dependencies)),
+        ("description", old.description.clone(), new.description.clone()),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| DiffEntry::PackageChanged {
+            id: new.id.clone(),
+            field: field.to_string(),
+            from,
+            to,
+        })
+        .collect()
+}
+
+fn quoted_or_unset(value: Option<&str>) -> String {
+    value.map_or_else(|| "(not set)".to_string(), |value| format!("'{}'", value))
+}
+
+/// Metadata value as shown in the summary: strings in quotes, anything else
+/// as compact JSON
+fn metadata_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => format!("'{}'", text),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TrackedFile;
+
+    #[test]
+    fn test_between_identical() {
+        let config = ProjectConfig::with_project_name(Agent::Claude, "demo");
+        assert!(ConfigDiff::between(&config, &config).is_empty());
+    }
+
+    #[test]
+    fn test_between_lists_changes() {
+        let mut old = ProjectConfig::with_project_name(Agent::Copilot, "foo");
+        old.add_package(Package::new("old-pkg", "1.0.0").unwrap()).unwrap();
+        old.add_package(Package::new("shared", "1.0.0").unwrap()).unwrap();
+        old.set_metadata("team", "payments");
+        old.track_file(TrackedFile::new("CLAUDE.md", &"a".repeat(64)));
+
+        let mut new = old.clone();
+        new.agents = vec![Agent::Claude];
+        new.project_name = Some("bar".to_string());
+        new.remove_package("old-pkg");
+        new.remove_package("shared");
+        new.add_package(Package::new("shared", "1.1.0").unwrap()).unwrap();
+        new.add_package(Package::new("my-pkg", "2.0.0").unwrap()).unwrap();
+        new.set_metadata("team", "platform");
+        new.track_file(TrackedFile::new("CLAUDE.md", &"b".repeat(64)));
+
+        let lines: Vec<String> = ConfigDiff::between(&old, &new).entries.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "Agent changed: copilot → claude",
+                "Project name changed: 'foo' → 'bar'",
+                "Package 'shared' version changed: '1.0.0' → '1.1.0'",
+                "Package added: my-pkg@2.0.0",
+                "Package removed: old-pkg@1.0.0",
+                "Metadata key 'team' changed: 'payments' → 'platform'",
+                "Tracked file changed: CLAUDE.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serializes_entries_with_change_type() {
+        let old = ProjectConfig::new(Agent::Claude);
+        let mut new = old.clone();
+        new.add_package(Package::new("my-pkg", "2.0.0").unwrap()).unwrap();
+
+        let json = serde_json::to_value(ConfigDiff::between(&old, &new)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "entries": [{ "change": "package_added", "id": "my-pkg", "version": "2.0.0" }] })
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use crate::config::{Agent, Package, ProjectConfig};
+use crate::error::{ConfigError, Result};
+use crate::templates::TemplateSystem;
+use serde::{Deserialize, Serialize};
+
+/// Exact versions and checksums of the packages a project uses, kept next to
+/// the configuration file like `Cargo.lock`
+///
+/// Commands that change packages on purpose, such as `upgrade` or
+/// `install`, move the pins along with the configuration; `init`, `install`
+/// and `verify` refuse packages that no longer match their pin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockFile {
+    /// Format version of the lock file
+    pub version: u32,
+    /// Locked packages, sorted by ID
+    pub packages: Vec<LockedPackage>,
+}
+
+/// A package pinned to the version and content it resolved to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// Package ID as listed in the configuration
+    pub id: String,
+    /// Version the package resolved to
+    pub resolved_version: String,
+    /// Digest of the package content in `<algorithm>:<hex>` form, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Where the package was fetched from; absent for templates built into specforge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+}
+
+impl LockedPackage {
+    /// Lock a package at its configured version
+    ///
+    /// Template packages built into specforge are locked to the checksum of
+    /// the embedded templates; other packages keep their configured checksum.
+    pub fn from_package(package: &Package) -> Self {
+        let built_in = Agent::all()
+            .into_iter()
+            .find(|agent| agent.default_package_id() == package.id);
+        let checksum = match (&package.url, built_in) {
+            (None, Some(agent)) => Some(TemplateSystem::package_checksum(&agent)),
+            _ => package.checksum.clone(),
+        };
+        Self {
+            id: package.id.clone(),
+            resolved_version: package.version.to_string(),
+            checksum,
+            source_url: package.url.clone(),
+        }
+    }
+}
+
+impl LockFile {
+    /// Current lock file format version
+    pub const CURRENT_VERSION: u32 = 1;
+
+    /// Lock every package in the configuration at its configured version
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        let mut packages: Vec<_> = config.packages.iter().map(LockedPackage::from_package).collect();
+        packages.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Self {
+            version: Self::CURRENT_VERSION,
+            packages,
+        }
+    }
+
+    /// Get a locked package by its ID
+    pub fn get(&self, id: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|package| package.id == id)
+    }
+
+    /// Why a configured package does not match its pin, or `None` when it
+    /// matches or is not locked
+    ///
+    /// A package without a configured checksum only has its version and URL
+    /// compared; its content is checked against the pin when it is downloaded.
+    pub fn package_mismatch(&self, package: &Package) -> Option<String> {
+        let locked = self.get(&package.id)?;
+        let expected = LockedPackage::from_package(package);
+        if expected.resolved_version != locked.resolved_version {
+            return Some(format!(
+                "'{}' is configured at version {} but locked to {}",
+                package.id, expected.resolved_version, locked.resolved_version
+            ));
+        }
+        if expected.source_url != locked.source_url {
+            return Some(format!(
+                "'{}' is configured from {} but locked to {}",
+                package.id,
+                expected.source_url.as_deref().unwrap_or("the built-in templates"),
+                locked.source_url.as_deref().unwrap_or("the built-in templates")
+            ));
+        }
+        match (&expected.checksum, &locked.checksum) {
+            (Some(actual), Some(pinned)) if actual != pinned && package.url.is_none() => Some(format!(
+                "'{}' has templates in this specforge release that differ from the locked ones",
+                package.id
+            )),
+            (Some(actual), Some(pinned)) if actual != pinned => Some(format!(
+                "'{}' is configured with checksum {} but locked to {}",
+                package.id, actual, pinned
+            )),
+            _ => None,
+        }
+    }
+
+    /// The package with the checksum it is locked to filled in, so that a
+    /// download is verified against the pin
+    pub fn pinned(&self, package: &Package) -> Package {
+        match (&package.checksum, self.get(&package.id).and_then(|locked| locked.checksum.as_ref())) {
+            (None, Some(checksum)) if package.url.is_some() => package.clone().with_checksum(checksum.clone()),
+            _ => package.clone(),
+        }
+    }
+
+    /// Every configured package that does not match its pin, see
+    /// [`LockFile::package_mismatch`]
+    pub fn mismatches(&self, config: &ProjectConfig) -> Vec<String> {
+        config.packages.iter().filter_map(|package| self.package_mismatch(package)).collect()
+    }
+
+    /// Move the pins along with a configuration that was changed on purpose
+    ///
+    /// Packages that are no longer configured are dropped, and a locked
+    /// package whose configured version, URL or checksum changed is pinned
+    /// again as configured; the others keep their pin. Packages that are not
+    /// locked stay unlocked, except for `pins`, which are added or replace
+    /// their entry, e.g. for templates that were just upgraded.
+    pub fn refresh(&mut self, config: &ProjectConfig, pins: Vec<LockedPackage>) {
+        let previous = std::mem::take(&mut self.packages);
+        for locked in previous {
+            let Some(package) = config.packages.iter().find(|package| package.id == locked.id) else {
+                continue;
+            };
+            let changed = package.version.to_string() != locked.resolved_version
+                || package.url != locked.source_url
+                || package.checksum.as_ref().is_some_and(|checksum| Some(checksum) != locked.checksum.as_ref());
+            self.packages.push(if changed { LockedPackage::from_package(package) } else { locked });
+        }
+        for pin in pins {
+            self.remove(&pin.id);
+            self.packages.push(pin);
+        }
+        self.packages.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    /// Remove a locked package, returning whether it was present
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.packages.len();
+        self.packages.retain(|package| package.id != id);
+        self.packages.len() != before
+    }
+
+    /// Serialize to pretty-printed JSON
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// Parse a lock file, rejecting versions newer than this release understands
+    pub fn from_json_string(content: &str) -> Result<Self> {
+        let lock: Self = serde_json::from_str(content)?;
+        if lock.version == 0 || lock.version > Self::CURRENT_VERSION {
+            return Err(ConfigError::validation_error(format!(
+                "Lock file version {} is not supported by this version of specforge (expected {})",
+                lock.version,
+                Self::CURRENT_VERSION
+            )));
+        }
+        Ok(lock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ProjectConfig {
+        let mut config = ProjectConfig::new(Agent::Codeium);
+        config
+            .add_package(Package::new(Agent::Codeium.default_package_id(), "0.1.0").unwrap())
+            .unwrap();
+        config
+            .add_package(
+                Package::with_url("team-prompts", "https://example.com/prompts.tar.gz", "2.0.0").unwrap()
+                    .with_checksum(format!("sha256:{}", "a".repeat(64))),
+            )
+            .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_from_config() {
+        let lock = LockFile::from_config(&config());
+        assert_eq!(lock.version, LockFile::CURRENT_VERSION);
+        let ids: Vec<_> = lock.packages.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["specforge-codeium-templates", "team-prompts"]);
+
+        let built_in = lock.get("specforge-codeium-templates").unwrap();
+        assert_eq!(built_in.resolved_version, "0.1.0");
+        assert_eq!(built_in.source_url, None);
+        assert_eq!(
+            built_in.checksum.as_deref(),
+            Some(TemplateSystem::package_checksum(&Agent::Codeium).as_str())
+        );
+
+        let remote = lock.get("team-prompts").unwrap();
+        assert_eq!(remote.source_url.as_deref(), Some("https://example.com/prompts.tar.gz"));
+        assert_eq!(remote.checksum, Some(format!("sha256:{}", "a".repeat(64))));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let lock = LockFile::from_config(&config());
+        let json = lock.to_json_string().unwrap();
+        assert_eq!(LockFile::from_json_string(&json).unwrap(), lock);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_version() {
+        let result = LockFile::from_json_string(r#"{"version": 2, "packages": []}"#);
+        assert!(result.unwrap_err().to_string().contains("Lock file version 2"));
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let config = config();
+        let mut lock = LockFile::from_config(&config);
+        assert!(lock.mismatches(&config).is_empty());
+
+        lock.packages[0].checksum = Some(format!("sha256:{}", "0".repeat(64)));
+        lock.packages[1].resolved_version = "1.0.0".to_string();
+        assert_eq!(
+            lock.mismatches(&config),
+            [
+                "'specforge-codeium-templates' has templates in this specforge release that differ from the locked ones",
+                "'team-prompts' is configured at version 2.0.0 but locked to 1.0.0",
+            ]
+        );
+
+        // Unlocked packages have nothing to match
+        lock.remove("team-prompts");
+        assert_eq!(lock.mismatches(&config).len(), 1);
+    }
+
+    #[test]
+    fn test_pinned() {
+        let config = config();
+        let mut lock = LockFile::from_config(&config);
+        let mut remote = config.packages[1].clone();
+        assert_eq!(lock.pinned(&remote), remote);
+
+        remote.checksum = None;
+        assert_eq!(lock.pinned(&remote).checksum, config.packages[1].checksum);
+
+        // Built-in templates have no archive to verify
+        assert_eq!(lock.pinned(&config.packages[0]).checksum, None);
+
+        lock.remove("team-prompts");
+        assert_eq!(lock.pinned(&remote).checksum, None);
+    }
+
+    #[test]
+    fn test_refresh_follows_configuration() {
+        let mut config = config();
+        let mut lock = LockFile::from_config(&config);
+        lock.remove("specforge-codeium-templates");
+        let archive = format!("sha256:{}", "b".repeat(64));
+        lock.packages[0].checksum = Some(archive.clone());
+        config.packages[1].checksum = None;
+        config.add_package(Package::new("new", "1.0.0").unwrap()).unwrap();
+
+        // Unchanged packages keep their pin, unlocked ones stay unlocked
+        lock.refresh(&config, Vec::new());
+        let ids: Vec<_> = lock.packages.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["team-prompts"]);
+        assert_eq!(lock.get("team-prompts").unwrap().checksum, Some(archive));
+
+        config.packages[1].version = "2.1.0".parse().unwrap();
+        lock.refresh(&config, Vec::new());
+        assert_eq!(lock.get("team-prompts").unwrap().resolved_version, "2.1.0");
+        assert_eq!(lock.get("team-prompts").unwrap().checksum, None);
+
+        config.remove_package("team-prompts");
+        let pin = LockedPackage::from_package(config.get_package("new").unwrap());
+        lock.refresh(&config, vec![pin]);
+        let ids: Vec<_> = lock.packages.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["new"]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut lock = LockFile::from_config(&config());
+        assert!(lock.remove("team-prompts"));
+        assert!(!lock.remove("team-prompts"));
+        assert_eq!(lock.packages.len(), 1);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::config::{ProjectConfig, migration};
+use crate::error::{ConfigError, Result};
+use serde_json::{Map, Value};
+
+/// Arrays merged entry by entry, with the field identifying an entry
+const KEYED_ARRAYS: &[(&str, &str)] = &[("packages", "id"), ("files", "path")];
+
+/// Fields that cannot be resolved by preferring our side when both sides
+/// changed them differently
+///
+/// The agents decide which templates are deployed, so picking one side
+/// would silently drop the other side's files.
+const EXCLUSIVE_FIELDS: &[&str] = &["agent"];
+
+/// Merge the changes two branches made to a JSON document, as a Git merge
+/// driver does
+///
+/// Changes made on one side only are taken over. Packages and tracked files
+/// are matched on their ID and path, so entries added on both sides are all
+/// kept; objects such as `metadata` are merged key by key. When both sides
+/// changed a value differently, ours wins, except for the agents, where
+/// differing changes are a conflict. A `base` that is not an object, as when
+/// both sides added the file, counts as empty.
+pub fn merge_documents(base: &Value, ours: &Value, theirs: &Value) -> Result<Value> {
+    let (Some(ours), Some(theirs)) = (ours.as_object(), theirs.as_object()) else {
+        return Err(ConfigError::validation_error("Only JSON objects can be merged"));
+    };
+    let empty = Map::new();
+    let base = base.as_object().unwrap_or(&empty);
+
+    let mut merged = Map::new();
+    for key in union_keys(ours, theirs) {
+        let (base_value, our_value, their_value) = (base.get(key), ours.get(key), theirs.get(key));
+        if EXCLUSIVE_FIELDS.contains(&key.as_str())
+            && our_value != base_value
+            && their_value != base_value
+            && our_value != their_value
+        {
+            return Err(ConfigError::validation_error(format!(
+                "Both sides changed '{}': ours is {}, theirs is {}",
+                key,
+                our_value.map_or_else(|| "unset".to_string(), Value::to_string),
+                their_value.map_or_else(|| "unset".to_string(), Value::to_string)
+            )));
+        }
+
+        let value = match KEYED_ARRAYS.iter().find(|(field, _)| field == key) {
+            Some((_, id_field)) => merge_keyed(base_value, our_value, their_value, id_field),
+            None => merge_values(base_value, our_value, their_value),
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value);
+        }
+    }
+    Ok(Value::Object(merged))
+}
+
+/// Merge the changes two branches made to a configuration document
+///
+/// All three documents are migrated to the current schema version first, so
+/// a branch that upgraded the file merges with one that did not. The result
+/// must be a valid configuration.
+pub fn merge_configs(base: &Value, ours: &Value, theirs: &Value) -> Result<ProjectConfig> {
+    let migrated = |value: &Value| -> Result<Value> {
+        let mut value = value.clone();
+        migration::migrate_to_current(&mut value)?;
+        migration::promote_project_name(&mut value)?;
+        Ok(value)
+    };
+    let base = if base.is_object() { migrated(base)? } else { Value::Null };
+    let merged = merge_documents(&base, &migrated(ours)?, &migrated(theirs)?)?;
+    ProjectConfig::from_json_value(merged)
+}
+
+/// Keys of `ours` followed by the keys only `theirs` has
+fn union_keys<'a>(ours: &'a Map<String, Value>, theirs: &'a Map<String, Value>) -> Vec<&'a String> {
+    ours.keys()
+        .chain(theirs.keys().filter(|key| !ours.contains_key(*key)))
+        .collect()
+}
+
+/// Three-way merge of one value; `None` means the value is absent
+fn merge_values(base: Option<&Value>, ours: Option<&Value>, theirs: Option<&Value>) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+    match (ours, theirs) {
+        (Some(Value::Object(ours)), Some(Value::Object(theirs))) => {
+            let empty = Map::new();
+            let base = base.and_then(Value::as_object).unwrap_or(&empty);
+            let merged = union_keys(ours, theirs)
+                .into_iter()
+                .filter_map(|key| {
+                    merge_values(base.get(key), ours.get(key), theirs.get(key)).map(|value| (key.clone(), value))
+                })
+                .collect();
+            Some(Value::Object(merged))
+        }
+        // An entry removed on one side and edited on the other keeps the edit
+        _ => ours.or(theirs).cloned(),
+    }
+}
+
+/// Three-way merge of an array whose entries are identified by `id_field`
+///
+/// Entries keep our order, followed by the entries only theirs added. Arrays
+/// holding entries without a string ID are merged as plain values.
+fn merge_keyed(base: Option<&Value>, ours: Option<&Value>, theirs: Option<&Value>, id_field: &str) -> Option<Value> {
+    let entries = |value: Option<&Value>| -> Option<Vec<(String, Value)>> {
+        match value {
+            None => Some(Vec::new()),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| Some((item.get(id_field)?.as_str()?.to_string(), item.clone())))
+                .collect(),
+            Some(_) => None,
+        }
+    };
+    let (Some(base_entries), Some(our_entries), Some(their_entries)) = (entries(base), entries(ours), entries(theirs))
+    else {
+        return merge_values(base, ours, theirs);
+    };
+    if ours.is_none() && theirs.is_none() {
+        return None;
+    }
+
+    let mut ids: Vec<&String> = our_entries.iter().map(|(id, _)| id).collect();
+    ids.extend(their_entries.iter().map(|(id, _)| id).filter(|id| find(&our_entries, id).is_none()));
+
+    let merged = ids
+        .into_iter()
+        .filter_map(|id| merge_values(find(&base_entries, id), find(&our_entries, id), find(&their_entries, id)))
+        .collect();
+    Some(Value::Array(merged))
+}
+
+/// Entry of a keyed array with the given ID
+fn find<'a>(entries: &'a [(String, Value)], id: &str) -> Option<&'a Value> {
+    entries.iter().find(|(key, _)| key == id).map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Agent, Package};
+    use serde_json::json;
+
+    fn document(config: &ProjectConfig) -> Value {
+        serde_json::to_value(config).unwrap()
+    }
+
+    #[test]
+    fn test_merge_keeps_packages_added_on_both_sides() {
+        let mut base = ProjectConfig::with_project_name(Agent::Claude, "demo");
+        base.add_package(Package::new("shared", "1.0.0").unwrap()).unwrap();
+        base.add_package(Package::new("retired", "1.0.0").unwrap()).unwrap();
+        let mut ours = base.clone();
+        ours.add_package(Package::new("ours-pkg", "1.0.0").unwrap()).unwrap();
+        ours.remove_package("retired");
+        let mut theirs = base.clone();
+        theirs.add_package(Package::new("theirs-pkg", "2.0.0").unwrap()).unwrap();
+        theirs.remove_package("shared");
+        theirs.add_package(Package::new("shared", "1.1.0").unwrap()).unwrap();
+
+        let merged = merge_configs(&document(&base), &document(&ours), &document(&theirs)).unwrap();
+        let packages: Vec<String> = merged.packages.iter().map(|p| format!("{}@{}", p.id, p.version)).collect();
+        assert_eq!(packages, ["shared@1.1.0", "ours-pkg@1.0.0", "theirs-pkg@2.0.0"]);
+    }
+
+    #[test]
+    fn test_merge_rejects_conflicting_agent_changes() {
+        let base = ProjectConfig::new(Agent::Claude);
+        let mut ours = base.clone();
+        ours.agents = vec![Agent::Copilot];
+        let mut theirs = base.clone();
+        theirs.agents = vec![Agent::Codeium];
+
+        let error = merge_configs(&document(&base), &document(&ours), &document(&theirs)).unwrap_err();
+        assert!(error.to_string().contains("Both sides changed 'agent'"), "{}", error);
+
+        // The same change on both sides, or a change on one side, merges
+        let merged = merge_configs(&document(&base), &document(&ours), &document(&ours)).unwrap();
+        assert_eq!(merged.agents, [Agent::Copilot]);
+        let merged = merge_configs(&document(&base), &document(&base), &document(&theirs)).unwrap();
+        assert_eq!(merged.agents, [Agent::Codeium]);
+    }
+
+    #[test]
+    fn test_merge_metadata_key_by_key() {
+        let mut base = ProjectConfig::new(Agent::Claude);
+        base.set_metadata("team", "payments");
+        base.set_metadata("owner", "alice");
+        let mut ours = base.clone();
+        ours.set_metadata("team", "platform");
+        ours.set_metadata("updated_at", "2026-01-02T00:00:00+00:00");
+        let mut theirs = base.clone();
+        theirs.set_metadata("team", "billing");
+        theirs.set_metadata("updated_at", "2026-01-03T00:00:00+00:00");
+        theirs.set_metadata("ticket", "OPS-12");
+        theirs.metadata.remove("owner");
+
+        let merged = merge_configs(&document(&base), &document(&ours), &document(&theirs)).unwrap();
+        assert_eq!(merged.get_metadata("team"), Some(&json!("platform")));
+        assert_eq!(merged.get_metadata("updated_at"), Some(&json!("2026-01-02T00:00:00+00:00")));
+        assert_eq!(merged.get_metadata("ticket"), Some(&json!("OPS-12")));
+        assert_eq!(merged.get_metadata("owner"), None);
+    }
+
+    #[test]
+    fn test_merge_lock_documents_without_base() {
+        let ours = json!({ "version": 1, "packages": [{ "id": "a", "resolved_version": "1.0.0" }] });
+        let theirs = json!({ "version": 1, "packages": [{ "id": "b", "resolved_version": "2.0.0" }] });
+
+        let merged = merge_documents(&Value::Null, &ours, &theirs).unwrap();
+        assert_eq!(
+            merged["packages"],
+            json!([{ "id": "a", "resolved_version": "1.0.0" }, { "id": "b", "resolved_version": "2.0.0" }])
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use crate::config::{Agent, ProjectConfig};
+use crate::error::{ConfigError, Result};
+use serde_json::Value;
+
+/// Schema version assumed for configuration files without a `schema_version` field
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Metadata key recording which file a configuration was migrated from
+pub const MIGRATED_FROM_METADATA_KEY: &str = "migrated_from";
+
+/// Upgrades a raw configuration document by exactly one schema version
+pub trait Migration {
+    /// Schema version this migration upgrades from
+    fn applies_to_version(&self) -> u32;
+
+    /// Rewrite the document in place to the next schema version
+    fn migrate(&self, value: &mut Value) -> Result<()>;
+}
+
+/// Version 2 records deployed files in a `files` list
+///
+/// Configuration files written before deployed files were tracked have no
+/// `files` entry, so an empty list is added.
+pub struct V1ToV2Migration;
+
+impl Migration for V1ToV2Migration {
+    fn applies_to_version(&self) -> u32 {
+        1
+    }
+
+    fn migrate(&self, value: &mut Value) -> Result<()> {
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| ConfigError::validation_error("Configuration must be a JSON object"))?;
+
+        match object.get("files") {
+            None | Some(Value::Null) => {
+                object.insert("files".to_string(), Value::Array(Vec::new()));
+                Ok(())
+            }
+            Some(Value::Array(_)) => Ok(()),
+            Some(_) => Err(ConfigError::validation_error("'files' must be an array")),
+        }
+    }
+}
+
+/// Version 3 keeps the project name in a top-level `project_name` field
+///
+/// Earlier versions stored it as the `project_name` metadata entry. When
+/// both are present the top-level field wins and the entry is dropped.
+pub struct V2ToV3Migration;
+
+impl Migration for V2ToV3Migration {
+    fn applies_to_version(&self) -> u32 {
+        2
+    }
+
+    fn migrate(&self, value: &mut Value) -> Result<()> {
+        promote_project_name(value).map(|_| ())
+    }
+}
+
+/// Move a `project_name` metadata entry to the top-level field, returning
+/// whether there was one
+///
+/// Documents at the current version can still hold the entry when they were
+/// edited by hand or by older tooling; reading it is deprecated.
+pub fn promote_project_name(value: &mut Value) -> Result<bool> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| ConfigError::validation_error("Configuration must be a JSON object"))?;
+
+    let Some(name) = object
+        .get_mut("metadata")
+        .and_then(Value::as_object_mut)
+        .and_then(|metadata| metadata.remove("project_name"))
+    else {
+        return Ok(false);
+    };
+    if !name.is_string() {
+        return Err(ConfigError::validation_error("metadata.project_name must be a string"));
+    }
+    object.entry("project_name").or_insert(name);
+    Ok(true)
+}
+
+/// All known migrations
+pub fn migrations() -> Vec<Box<dyn Migration>> {
+    vec![Box::new(V1ToV2Migration), Box::new(V2ToV3Migration)]
+}
+
+/// Read the schema version of a raw configuration document
+pub fn schema_version(value: &Value) -> Result<u32> {
+    match value.get("schema_version") {
+        None => Ok(LEGACY_SCHEMA_VERSION),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                ConfigError::validation_error("schema_version must be a positive integer")
+            }),
+    }
+}
+
+/// Apply migrations until the document is at the current schema version
+///
+/// Returns the schema version the document had before migrating. Documents
+/// at or above the current version are left untouched.
+pub fn migrate_to_current(value: &mut Value) -> Result<u32> {
+    migrate_with(value, &migrations(), ProjectConfig::CURRENT_SCHEMA_VERSION)
+}
+
+/// Apply the given migrations until the document reaches the target version
+fn migrate_with(value: &mut Value, migrations: &[Box<dyn Migration>], target: u32) -> Result<u32> {
+    let original = schema_version(value)?;
+    let mut version = original;
+
+    while version < target {
+        let migration = migrations
+            .iter()
+            .find(|m| m.applies_to_version() == version)
+            .ok_or_else(|| {
+                ConfigError::migration_required(
+                    version,
+                    target,
+                    format!("No migration available from schema version {}", version),
+                )
+            })?;
+
+        migration
+            .migrate(value)
+            .map_err(|e| ConfigError::migration_required(version, target, e.to_string()))?;
+
+        version += 1;
+        if let Some(object) = value.as_object_mut() {
+            object.insert("schema_version".to_string(), Value::from(version));
+        }
+    }
+
+    Ok(original)
+}
+
+/// Rename the template packages of a configuration written by reforge, the
+/// former name of specforge, to their specforge IDs
+///
+/// When both IDs are present the reforge package is dropped. Returns the
+/// number of packages renamed or dropped.
+pub fn rename_reforge_packages(config: &mut ProjectConfig) -> usize {
+    let mut changed = 0;
+    for agent in Agent::all() {
+        let legacy_id = format!("reforge-{}-templates", agent);
+        if config.get_package(&legacy_id).is_none() {
+            continue;
+        }
+        changed += 1;
+        if config.get_package(agent.default_package_id()).is_some() {
+            config.remove_package(&legacy_id);
+        } else if let Some(package) = config.packages.iter_mut().find(|p| p.id == legacy_id) {
+            package.id = agent.default_package_id().to_string();
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_version_defaults_to_legacy() {
+        assert_eq!(schema_version(&json!({})).unwrap(), 1);
+        assert_eq!(schema_version(&json!({"schema_version": 3})).unwrap(), 3);
+        assert!(schema_version(&json!({"schema_version": "2"})).is_err());
+        assert!(schema_version(&json!({"schema_version": -1})).is_err());
+    }
+
+    #[test]
+    fn test_v1_to_v2_adds_files() {
+        let mut value = json!({"agent": "claude", "packages": [], "metadata": {}});
+        V1ToV2Migration.migrate(&mut value).unwrap();
+        assert_eq!(value["files"], json!([]));
+
+        let mut value = json!({"files": [{"path": "CLAUDE.md", "sha256": "ab"}]});
+        V1ToV2Migration.migrate(&mut value).unwrap();
+        assert_eq!(value["files"][0]["path"], "CLAUDE.md");
+
+        assert!(V1ToV2Migration.migrate(&mut json!({"files": "CLAUDE.md"})).is_err());
+    }
+
+    #[test]
+    fn test_v2_to_v3_moves_project_name() {
+        let mut value = json!({"metadata": {"created_at": "2025-01-01T00:00:00Z", "project_name": "demo"}});
+        V2ToV3Migration.migrate(&mut value).unwrap();
+        assert_eq!(value["project_name"], "demo");
+        assert_eq!(value["metadata"], json!({"created_at": "2025-01-01T00:00:00Z"}));
+
+        // A top-level name set by hand wins over the metadata entry
+        let mut value = json!({"project_name": "kept", "metadata": {"project_name": "dropped"}});
+        V2ToV3Migration.migrate(&mut value).unwrap();
+        assert_eq!(value, json!({"project_name": "kept", "metadata": {}}));
+
+        let mut value = json!({"metadata": {}});
+        V2ToV3Migration.migrate(&mut value).unwrap();
+        assert_eq!(value, json!({"metadata": {}}));
+
+        assert!(V2ToV3Migration.migrate(&mut json!({"metadata": {"project_name": 42}})).is_err());
+    }
+
+    #[test]
+    fn test_migrate_to_current() {
+        let mut value = json!({"agent": "claude", "packages": [], "metadata": {}});
+        assert_eq!(migrate_to_current(&mut value).unwrap(), 1);
+        assert_eq!(value["schema_version"], ProjectConfig::CURRENT_SCHEMA_VERSION);
+        assert_eq!(value["files"], json!([]));
+
+        // Already current documents are left untouched
+        let before = value.clone();
+        migrate_to_current(&mut value).unwrap();
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn test_rename_reforge_packages() {
+        use crate::config::Package;
+
+        let mut config = ProjectConfig::with_agents(vec![Agent::Copilot, Agent::Claude]);
+        config.add_package(Package::new("reforge-copilot-templates", "0.1.0").unwrap()).unwrap();
+        config.add_package(Package::new("reforge-claude-templates", "0.1.0").unwrap()).unwrap();
+        config.add_package(Package::new("specforge-claude-templates", "0.2.0").unwrap()).unwrap();
+        config.add_package(Package::new("team-prompts", "1.0.0").unwrap()).unwrap();
+
+        assert_eq!(rename_reforge_packages(&mut config), 2);
+        let ids: Vec<_> = config.packages.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["specforge-copilot-templates", "specforge-claude-templates", "team-prompts"]
+        );
+        assert_eq!(config.get_package("specforge-claude-templates").unwrap().version, "0.2.0");
+
+        assert_eq!(rename_reforge_packages(&mut config), 0);
+    }
+
+    #[test]
+    fn test_missing_migration_is_reported() {
+        let mut value = json!({"schema_version": 1});
+        let error = migrate_with(&mut value, &migrations(), 4).unwrap_err();
+        assert!(matches!(error, ConfigError::MigrationRequired { from: 3, to: 4, .. }));
+    }
+
+    #[test]
+    fn test_failed_migration_is_reported() {
+        let mut value = json!({"files": 42});
+        let error = migrate_to_current(&mut value).unwrap_err();
+        assert!(matches!(error, ConfigError::MigrationRequired { from: 1, .. }));
+        assert!(error.to_string().contains("'files' must be an array"));
+    }
+}
@@ -0,0 +1,27 @@
+pub mod answers;
+pub mod batch;
+pub mod condition;
+pub mod diff;
+pub mod lock;
+pub mod merge;
+pub mod migration;
+pub mod project;
+pub mod schema;
+pub mod user;
+pub mod validation;
+pub mod version;
+pub mod workspace;
+
+pub use answers::InitAnswers;
+pub use batch::BatchEntry;
+pub use condition::{Condition, ConditionContext};
+pub use diff::{ConfigDiff, DiffEntry};
+pub use lock::{LockFile, LockedPackage};
+pub use merge::{merge_configs, merge_documents};
+pub use migration::{Migration, V1ToV2Migration};
+pub use project::*;
+pub use schema::config_schema;
+pub use user::UserConfig;
+pub use validation::{Severity, ValidationIssue, ValidationReport};
+pub use version::{PackageVersion, VersionRequirement};
+pub use workspace::WorkspaceConfig;
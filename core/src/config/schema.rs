@@ -0,0 +1,306 @@
+//! JSON Schema for the configuration file, for editors and CI validation.
+//!
+//! The schema is maintained by hand next to the validation in
+//! [`ProjectConfig::validate`] and mirrors the constraints enforced there.
+//! Keep the two in sync when changing either.
+
+use crate::config::{Agent, ProjectConfig};
+use serde_json::{Value, json};
+
+/// Canonical location of the schema, where releases publish it
+pub const SCHEMA_ID: &str = "https://github.com/wmeints/specforge/releases/latest/download/specforge.schema.json";
+
+/// Characters `char::is_control` rejects in metadata keys and project names
+const NO_CONTROL_CHARACTERS: &str = "^[^\\u0000-\\u001F\\u007F-\\u009F]*$";
+
+/// JSON Schema (draft 7) describing `.specforge.json`
+///
+/// Draft 7 is the newest draft that editors such as VS Code and the JetBrains
+/// IDEs fully support.
+pub fn config_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "$id": SCHEMA_ID,
+        "title": "specforge configuration",
+        "description": "Project configuration written by specforge init",
+        "type": "object",
+        "if": { "not": { "required": ["extends"] } },
+        "then": { "required": ["agent", "packages", "metadata"] },
+        "additionalProperties": false,
+        "properties": {
+            "$schema": {
+                "description": "Reference to this schema, for editors",
+                "type": "string"
+            },
+            "schema_version": {
+                "description": "Version of the configuration file format; 1 when absent",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": ProjectConfig::CURRENT_SCHEMA_VERSION
+            },
+            "extends": {
+                "description": "Configuration to inherit from, relative to this file",
+                "type": "string",
+                "minLength": 1
+            },
+            "min_specforge_version": {
+                "description": "Oldest specforge release that may work with this configuration",
+                "type": "string",
+                "pattern": "^\\s*(0|[1-9][0-9]*)(\\.(0|[1-9][0-9]*)){2}(-(0|[1-9][0-9]*|[0-9]*[A-Za-z-][0-9A-Za-z-]*)(\\.(0|[1-9][0-9]*|[0-9]*[A-Za-z-][0-9A-Za-z-]*))*)?(\\+[0-9A-Za-z-]+(\\.[0-9A-Za-z-]+)*)?\\s*$"
+            },
+            "project_name": {
+                "description": "Name of the project",
+                "type": "string",
+                "maxLength": 200,
+                "allOf": [
+                    { "pattern": "\\S" },
+                    { "pattern": NO_CONTROL_CHARACTERS }
+                ]
+            },
+            "agent": {
+                "description": "The AI agent, or agents in order of precedence",
+                "oneOf": [
+                    { "$ref": "#/definitions/agent" },
+                    {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/agent" },
+                        "minItems": 1,
+                        "uniqueItems": true
+                    }
+                ]
+            },
+            "packages": {
+                "description": "Template packages deployed in this project",
+                "type": "array",
+                "items": { "$ref": "#/definitions/package" },
+                "maxItems": 100
+            },
+            "metadata": { "$ref": "#/definitions/metadata" },
+            "files": {
+                "description": "Files deployed by specforge",
+                "type": "array",
+                "items": { "$ref": "#/definitions/trackedFile" }
+            }
+        },
+        "definitions": {
+            "agent": {
+                "description": "A supported AI agent",
+                "enum": Agent::all_names()
+            },
+            "package": {
+                "description": "A package of prompt templates",
+                "type": "object",
+                "required": ["id", "version"],
+                "additionalProperties": false,
+                "properties": {
+                    "id": {
+                        "description": "Unique identifier for the package",
+                        "type": "string",
+                        "maxLength": 100,
+                        "pattern": "^\\S+$"
+                    },
+                    "url": {
+                        "description": "Optional URL where the package can be downloaded",
+                        "type": ["string", "null"],
+                        "maxLength": 500,
+                        "pattern": "^\\s*(https?://[^\\s/?#]+|file://(localhost)?/)\\S*\\s*$"
+                    },
+                    "version": {
+                        "description": "Version of the package (semantic versioning), e.g. 1.0.0",
+                        "type": "string",
+                        "pattern": "^\\s*(0|[1-9][0-9]*)(\\.(0|[1-9][0-9]*)){2}(-(0|[1-9][0-9]*|[0-9]*[A-Za-z-][0-9A-Za-z-]*)(\\.(0|[1-9][0-9]*|[0-9]*[A-Za-z-][0-9A-Za-z-]*))*)?(\\+[0-9A-Za-z-]+(\\.[0-9A-Za-z-]+)*)?\\s*$"
+                    },
+                    "when": {
+                        "description": "Condition under which the package applies, e.g. env(CI); always applies when absent",
+                        "type": "string"
+                    },
+                    "checksum": {
+                        "description": "Expected digest of the package content, e.g. sha256:<hex>",
+                        "type": "string",
+                        "pattern": "^(sha256:[0-9a-f]{64}|sha512:[0-9a-f]{128})$"
+                    },
+                    "dependencies": {
+                        "description": "IDs of packages this package builds on, which must also be configured",
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "maxLength": 100,
+                            "pattern": "^\\S+$"
+                        },
+                        "uniqueItems": true
+                    },
+                    "description": {
+                        "description": "Note on what the package is for, shown in listings",
+                        "type": "string",
+                        "maxLength": 500,
+                        "pattern": NO_CONTROL_CHARACTERS
+                    }
+                }
+            },
+            "metadata": {
+                "description": "Additional project metadata",
+                "type": "object",
+                "required": ["created_at"],
+                "maxProperties": 50,
+                "propertyNames": {
+                    "maxLength": 100,
+                    "allOf": [
+                        { "pattern": "\\S" },
+                        { "pattern": NO_CONTROL_CHARACTERS }
+                    ]
+                },
+                "properties": {
+                    "created_at": {
+                        "description": "When the project was initialized, in RFC 3339 format",
+                        "type": "string",
+                        "format": "date-time"
+                    },
+                    "project_name": {
+                        "description": "Name of the project before schema version 3, which moved it to the top level",
+                        "type": "string",
+                        "maxLength": 200,
+                        "allOf": [
+                            { "pattern": "\\S" },
+                            { "pattern": NO_CONTROL_CHARACTERS }
+                        ]
+                    }
+                },
+                "additionalProperties": {
+                    "maxLength": 1000
+                }
+            },
+            "trackedFile": {
+                "description": "A file deployed by specforge",
+                "type": "object",
+                "required": ["path", "sha256"],
+                "additionalProperties": false,
+                "properties": {
+                    "path": {
+                        "description": "Path relative to the project directory, with forward slashes",
+                        "type": "string",
+                        "pattern": "^[^/\\\\]",
+                        "not": { "pattern": "(^|[/\\\\])\\.\\.([/\\\\]|$)" }
+                    },
+                    "sha256": {
+                        "description": "SHA-256 hash of the file content as it was deployed",
+                        "type": "string",
+                        "pattern": "^[0-9a-f]{64}$"
+                    },
+                    "mode": {
+                        "description": "Unix permission bits of the file as it was deployed, in octal",
+                        "type": "string",
+                        "pattern": "^[0-7]{1,4}$"
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Package, TrackedFile};
+
+    fn validator() -> jsonschema::Validator {
+        jsonschema::validator_for(&config_schema()).unwrap()
+    }
+
+    #[test]
+    fn test_valid_configs_pass() {
+        let mut config = ProjectConfig::with_project_name(Agent::Claude, "my-project");
+        config
+            .add_package(Package::new(Agent::Claude.default_package_id(), "0.1.0").unwrap())
+            .unwrap();
+        config
+            .add_package(
+                Package::with_url("team-prompts", "https://example.com/p.tar.gz", "1.0.0-beta.1+build5").unwrap()
+                    .with_checksum(format!("sha512:{}", "0".repeat(128)))
+                    .with_dependencies([Agent::Claude.default_package_id()])
+                    .with_description("Prompts for the compliance review, ünïcödé included"),
+            )
+            .unwrap();
+        config.track_file(TrackedFile::new("docs/CLAUDE.md".to_string(), "a".repeat(64)));
+
+        let validator = validator();
+        let document: Value = serde_json::from_str(&config.to_json_string().unwrap()).unwrap();
+        assert!(validator.is_valid(&document), "{:?}", validator.iter_errors(&document).collect::<Vec<_>>());
+
+        config.agents.push(Agent::Copilot);
+        let document: Value = serde_json::from_str(&config.to_json_string().unwrap()).unwrap();
+        assert!(validator.is_valid(&document));
+    }
+
+    #[test]
+    fn test_invalid_configs_fail() {
+        let validator = validator();
+        let valid = json!({
+            "schema_version": 2,
+            "agent": "claude",
+            "packages": [{"id": "p", "url": null, "version": "1.0.0"}],
+            "metadata": {"created_at": "2025-01-01T00:00:00Z"}
+        });
+        assert!(validator.is_valid(&valid));
+
+        let invalid = |path: &str, value: Value| {
+            let mut document = valid.clone();
+            *document.pointer_mut(path).unwrap() = value;
+            document
+        };
+        let too_many_keys: serde_json::Map<String, Value> =
+            (0..51).map(|i| (format!("key{}", i), json!("x"))).collect();
+
+        for document in [
+            invalid("/agent", json!("vim")),
+            invalid("/agent", json!([])),
+            invalid("/schema_version", json!(ProjectConfig::CURRENT_SCHEMA_VERSION + 1)),
+            invalid("/packages/0/version", json!("1.0")),
+            invalid("/packages/0/version", json!("01.0.0")),
+            invalid("/packages/0/version", json!("1.2.3.4")),
+            invalid("/packages/0/id", json!("has space")),
+            invalid("/packages/0/url", json!("ftp://example.com")),
+            invalid("/packages/0/url", json!("https:// /foo")),
+            invalid("/packages/0/url", json!("file://example.com/templates.tar.gz")),
+            invalid("/metadata", json!({})),
+            invalid("/metadata", Value::Object(too_many_keys)),
+            invalid("/metadata", json!({"created_at": "2025-01-01T00:00:00Z", "x".repeat(101): 1})),
+            invalid("/metadata", json!({"created_at": "2025-01-01T00:00:00Z", "project_name": " "})),
+            json!({"agent": "claude", "packages": [], "metadata": {"created_at": "now"}, "files": [{"path": "../x", "sha256": "a".repeat(64)}]}),
+            json!({"agent": "claude", "packages": [], "metadata": {"created_at": "now"}, "agnet": "claude"}),
+            json!({"packages": [], "metadata": {"created_at": "now"}}),
+            json!({"extends": ""}),
+            json!({"agent": "claude", "packages": [{"id": "p", "version": "1.0.0", "description": "x".repeat(501)}], "metadata": {"created_at": "now"}}),
+            json!({"agent": "claude", "packages": [{"id": "p", "version": "1.0.0", "description": "two\nlines"}], "metadata": {"created_at": "now"}}),
+        ] {
+            assert!(!validator.is_valid(&document), "{} should be rejected", document);
+        }
+    }
+
+    #[test]
+    fn test_every_property_is_described() {
+        fn check(schema: &Value, path: &str) {
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, property) in properties {
+                    let described = property.get("description").is_some()
+                        || property.get("$ref").is_some();
+                    assert!(described, "{}/{} has no description", path, name);
+                    check(property, &format!("{}/{}", path, name));
+                }
+            }
+        }
+
+        let schema = config_schema();
+        assert_eq!(schema["$id"], SCHEMA_ID);
+        check(&schema, "");
+        for (name, definition) in schema["definitions"].as_object().unwrap() {
+            check(definition, name);
+        }
+    }
+
+    #[test]
+    fn test_extending_config_may_omit_inherited_fields() {
+        let validator = validator();
+        assert!(validator.is_valid(&json!({"extends": "../.specforge.json"})));
+        assert!(validator.is_valid(&json!({"extends": "../.specforge.json", "agent": "copilot"})));
+    }
+}
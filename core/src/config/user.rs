@@ -0,0 +1,233 @@
+use crate::config::Agent;
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{ConflictPolicy, FileOps};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Environment variable pointing at the directory holding the user configuration
+pub const USER_CONFIG_DIR_ENV_VAR: &str = "SPECFORGE_CONFIG_DIR";
+
+/// File name of the user configuration
+pub const USER_CONFIG_FILE_NAME: &str = "config.json";
+
+/// Settings that can be stored in the user configuration
+pub const USER_CONFIG_KEYS: &[&str] = &["default_agent", "default_on_conflict", "no_color", "max_backups"];
+
+/// Configuration backups kept when max_backups is not set
+pub const DEFAULT_MAX_BACKUPS: usize = 5;
+
+/// Per-user defaults that apply to every project
+///
+/// Stored in `config.json` in the platform configuration directory, e.g.
+/// `~/.config/specforge` on Linux. Every setting can be overridden with an
+/// environment variable named after it, e.g. `SPECFORGE_DEFAULT_AGENT`, and
+/// command line flags override both.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// Agent used by `init` when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_agent: Option<Agent>,
+    /// Conflict policy used when neither --force nor --on-conflict is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_on_conflict: Option<ConflictPolicy>,
+    /// Disable colored output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_color: Option<bool>,
+    /// Number of configuration backups to keep
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_backups: Option<usize>,
+}
+
+impl UserConfig {
+    /// Directory holding the user configuration, if one can be determined
+    pub fn directory() -> Option<PathBuf> {
+        match std::env::var_os(USER_CONFIG_DIR_ENV_VAR) {
+            Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+            _ => dirs::config_dir().map(|dir| dir.join("specforge")),
+        }
+    }
+
+    /// Path of the user configuration file, if one can be determined
+    pub fn path() -> Option<PathBuf> {
+        Self::directory().map(|dir| dir.join(USER_CONFIG_FILE_NAME))
+    }
+
+    /// Environment variable that overrides a setting
+    pub fn env_var(key: &str) -> String {
+        format!("SPECFORGE_{}", key.to_uppercase())
+    }
+
+    /// Read the user configuration and apply the environment overrides
+    ///
+    /// A missing file gives the defaults.
+    pub fn load() -> Result<Self> {
+        let mut config = match Self::path() {
+            Some(path) => FileOps::read_user_config(&path)?,
+            None => Self::default(),
+        };
+        config.apply_env_overrides(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Replace settings with the values of their environment variables
+    ///
+    /// `lookup` returns the value of an environment variable. Returns the keys
+    /// that were overridden.
+    pub fn apply_env_overrides(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Vec<&'static str>> {
+        let mut overridden = Vec::new();
+        for key in USER_CONFIG_KEYS {
+            let name = Self::env_var(key);
+            if let Some(value) = lookup(&name).filter(|value| !value.is_empty()) {
+                self.set(key, &value)
+                    .map_err(|e| ConfigError::validation_error(format!("Invalid {}: {}", name, e)))?;
+                overridden.push(*key);
+            }
+        }
+        Ok(overridden)
+    }
+
+    /// Number of configuration backups to keep, [`DEFAULT_MAX_BACKUPS`] unless set
+    pub fn max_backups(&self) -> usize {
+        self.max_backups.unwrap_or(DEFAULT_MAX_BACKUPS)
+    }
+
+    /// Value of a setting as text, or `None` when it is not set
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(match key {
+            "default_agent" => self.default_agent.as_ref().map(Agent::to_string),
+            "default_on_conflict" => self
+                .default_on_conflict
+                .and_then(|policy| policy.to_possible_value())
+                .map(|value| value.get_name().to_string()),
+            "no_color" => self.no_color.map(|value| value.to_string()),
+            "max_backups" => self.max_backups.map(|value| value.to_string()),
+            _ => return Err(unknown_key(key)),
+        })
+    }
+
+    /// Change a setting, validating the value
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "default_agent" => self.default_agent = Some(value.parse()?),
+            "default_on_conflict" => {
+                let policy = ConflictPolicy::from_str(value, true).map_err(|_| {
+                    ConfigError::validation_error(format!(
+                        "Invalid conflict policy '{}': expected overwrite, skip or prompt",
+                        value
+                    ))
+                })?;
+                self.default_on_conflict = Some(policy);
+            }
+            "no_color" => {
+                let enabled = match value.to_lowercase().as_str() {
+                    "true" | "1" | "yes" | "on" => true,
+                    "false" | "0" | "no" | "off" => false,
+                    _ => {
+                        return Err(ConfigError::validation_error(format!(
+                            "Invalid value '{}' for no_color: expected true or false",
+                            value
+                        )));
+                    }
+                };
+                self.no_color = Some(enabled);
+            }
+            "max_backups" => {
+                let count = value.parse().map_err(|_| {
+                    ConfigError::validation_error(format!(
+                        "Invalid value '{}' for max_backups: expected a number of backups",
+                        value
+                    ))
+                })?;
+                self.max_backups = Some(count);
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+}
+
+/// Error for a setting the user configuration does not have
+fn unknown_key(key: &str) -> ConfigError {
+    ConfigError::validation_error(format!(
+        "Unknown setting '{}'; expected one of: {}",
+        key,
+        USER_CONFIG_KEYS.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_set_and_get() {
+        let mut config = UserConfig::default();
+        assert_eq!(config.get("default_agent").unwrap(), None);
+
+        config.set("default_agent", "Claude").unwrap();
+        config.set("default_on_conflict", "skip").unwrap();
+        config.set("no_color", "yes").unwrap();
+        config.set("max_backups", "3").unwrap();
+        assert_eq!(config.default_agent, Some(Agent::Claude));
+        assert_eq!(config.get("default_agent").unwrap().as_deref(), Some("claude"));
+        assert_eq!(config.get("default_on_conflict").unwrap().as_deref(), Some("skip"));
+        assert_eq!(config.get("no_color").unwrap().as_deref(), Some("true"));
+        assert_eq!(config.get("max_backups").unwrap().as_deref(), Some("3"));
+        assert_eq!(config.max_backups(), 3);
+
+        assert!(config.set("default_agent", "vim").is_err());
+        assert!(config.set("default_on_conflict", "maybe").is_err());
+        assert!(config.set("no_color", "sometimes").is_err());
+        assert!(config.set("max_backups", "-1").is_err());
+        assert!(config.set("theme", "dark").is_err());
+        assert!(config.get("theme").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut config = UserConfig {
+            default_agent: Some(Agent::Claude),
+            ..UserConfig::default()
+        };
+        let env = HashMap::from([
+            ("SPECFORGE_DEFAULT_AGENT".to_string(), "copilot".to_string()),
+            ("SPECFORGE_NO_COLOR".to_string(), String::new()),
+        ]);
+
+        let overridden = config.apply_env_overrides(|name| env.get(name).cloned()).unwrap();
+        assert_eq!(overridden, vec!["default_agent"]);
+        assert_eq!(config.default_agent, Some(Agent::Copilot));
+        assert_eq!(config.no_color, None);
+
+        let error = config
+            .apply_env_overrides(|name| (name == "SPECFORGE_DEFAULT_ON_CONFLICT").then(|| "later".to_string()))
+            .unwrap_err();
+        assert!(error.to_string().contains("SPECFORGE_DEFAULT_ON_CONFLICT"));
+    }
+
+    #[test]
+    fn test_read_and_write_user_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("specforge").join(USER_CONFIG_FILE_NAME);
+        assert_eq!(FileOps::read_user_config(&path).unwrap(), UserConfig::default());
+
+        let mut config = UserConfig::default();
+        config.set("default_on_conflict", "overwrite").unwrap();
+        FileOps::write_user_config(&config, &path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\n  \"default_on_conflict\": \"overwrite\"\n}\n"
+        );
+        assert_eq!(FileOps::read_user_config(&path).unwrap(), config);
+
+        std::fs::write(&path, r#"{"theme": "dark"}"#).unwrap();
+        assert!(FileOps::read_user_config(&path).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::error::{ConfigError, Result};
+use serde::Serialize;
+use std::fmt;
+
+/// How serious a validation issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The configuration cannot be used
+    Error,
+    /// The configuration works, but probably not as intended
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// One problem found in a configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Field the issue concerns, e.g. `packages[1]` or `metadata.created_at`
+    pub path: String,
+    /// What is wrong, on one line
+    pub message: String,
+    /// How serious the issue is
+    pub severity: Severity,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Every issue found by [`ProjectConfig::validate_all`](super::ProjectConfig::validate_all)
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+    /// Errors as the checks returned them, in the order of their issues
+    errors: Vec<ConfigError>,
+}
+
+impl ValidationReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the error of a failed check under the field it concerns
+    pub fn check(&mut self, path: impl Into<String>, result: Result<()>) {
+        if let Err(error) = result {
+            self.add_error(path, error);
+        }
+    }
+
+    /// Record an error under the field it concerns
+    pub fn add_error(&mut self, path: impl Into<String>, error: ConfigError) {
+        let message = summary(&error);
+        self.add_error_as(path, message, error);
+    }
+
+    /// Record an error with its own message for the issue list, for errors
+    /// that carry context the path already gives
+    pub(crate) fn add_error_as(&mut self, path: impl Into<String>, message: String, error: ConfigError) {
+        self.issues.push(ValidationIssue {
+            path: path.into(),
+            message,
+            severity: Severity::Error,
+        });
+        self.errors.push(error);
+    }
+
+    /// Record a warning under the field it concerns
+    pub fn add_warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            path: path.into(),
+            message: message.into(),
+            severity: Severity::Warning,
+        });
+    }
+
+    /// All issues, in the order they were found
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// Issues that make the configuration unusable
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Issues that do not fail the configuration but should be shown
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Treat every warning as an error, as `--deny-warnings` does
+    pub fn deny_warnings(mut self) -> Self {
+        for issue in &mut self.issues {
+            if issue.severity == Severity::Warning {
+                issue.severity = Severity::Error;
+                self.errors.push(ConfigError::validation_error(format!("{}: {}", issue.path, issue.message)));
+            }
+        }
+        self
+    }
+
+    /// Whether any issue is an error
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Fail when the report holds errors
+    ///
+    /// A single error is returned as its check reported it, so it keeps its
+    /// own exit code; several are combined into
+    /// [`ConfigError::MultipleValidationErrors`].
+    pub fn into_result(mut self) -> Result<()> {
+        match self.errors.len() {
+            0 => Ok(()),
+            1 => Err(self.errors.remove(0)),
+            _ => Err(ConfigError::multiple_validation_errors(
+                self.issues.into_iter().filter(|issue| issue.severity == Severity::Error).collect(),
+            )),
+        }
+    }
+}
+
+/// First line of an error's message, without the advice that follows it
+pub(crate) fn summary(error: &ConfigError) -> String {
+    let message = match error {
+        ConfigError::ValidationError(message) | ConfigError::InvalidPackage(message) => message.clone(),
+        ConfigError::MissingRequiredField(field) => format!("Required field '{}' is missing", field),
+        other => other.to_string(),
+    };
+    message.lines().next().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_result_keeps_a_single_error() {
+        let mut report = ValidationReport::new();
+        report.add_warning("metadata.team", "looks unused");
+        assert!(!report.has_errors());
+
+        report.check("metadata.created_at", Err(ConfigError::missing_required_field("created_at")));
+        assert_eq!(report.issues()[1].message, "Required field 'created_at' is missing");
+        let error = report.into_result().unwrap_err();
+        assert_eq!(error.code(), "missing_required_field");
+    }
+
+    #[test]
+    fn test_into_result_combines_errors() {
+        let mut report = ValidationReport::new();
+        report.check("agents", Err(ConfigError::validation_error("At least one agent must be configured")));
+        report.add_warning("metadata.team", "looks unused");
+        report.check("packages[0]", Err(ConfigError::invalid_package("Package ID cannot be empty")));
+
+        let error = report.into_result().unwrap_err();
+        assert_eq!(error.exit_code(), 22);
+        let ConfigError::MultipleValidationErrors(issues) = &error else {
+            panic!("expected several validation errors, got {:?}", error);
+        };
+        assert_eq!(issues.len(), 2);
+        assert!(error.to_string().contains("  • packages[0]: Package ID cannot be empty"), "{}", error);
+    }
+
+    #[test]
+    fn test_deny_warnings() {
+        let mut report = ValidationReport::new();
+        report.add_warning("packages[0].url", "uses http");
+        assert_eq!(report.warnings().count(), 1);
+        assert!(report.into_result().is_ok());
+
+        let mut report = ValidationReport::new();
+        report.add_warning("packages[0].url", "uses http");
+        let report = report.deny_warnings();
+        assert_eq!(report.warnings().count(), 0);
+        let error = report.into_result().unwrap_err();
+        assert!(error.to_string().contains("packages[0].url: uses http"), "{}", error);
+    }
+}
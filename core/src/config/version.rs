@@ -0,0 +1,533 @@
+use crate::error::{ConfigError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A semantic version (`major.minor.patch[-pre-release][+build]`)
+///
+/// Serialized as a plain string. Versions are ordered by semver precedence,
+/// with build metadata only used to break ties so the ordering stays
+/// consistent with equality.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackageVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot-separated pre-release identifiers; empty for a release
+    pub pre: Vec<String>,
+    /// Dot-separated build metadata identifiers
+    pub build: Vec<String>,
+}
+
+impl PackageVersion {
+    /// Create a release version without pre-release or build metadata
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+
+    /// Version of this specforge release, which is also the version of its bundled templates
+    pub fn current() -> Self {
+        env!("CARGO_PKG_VERSION")
+            .parse()
+            .expect("CARGO_PKG_VERSION is a valid semantic version")
+    }
+
+    /// Whether this is a pre-release such as `1.0.0-beta.1`
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Parse the numeric major, minor or patch component at `position`
+    fn parse_component(version: &str, part: &str, position: usize) -> Result<u64> {
+        if part.is_empty() {
+            return Err(ConfigError::invalid_package(format!(
+                "Version '{}' has empty version component at position {}",
+                version, position
+            )));
+        }
+
+        if !part.chars().all(|c| c.is_ascii_digit()) {
+            let component = ["major", "minor", "patch"][position];
+            return Err(ConfigError::invalid_package(format!(
+                "Version '{}' has invalid {} component '{}' (must be numeric)",
+                version, component, part
+            )));
+        }
+
+        // Check for leading zeros (not allowed in semantic versioning)
+        if part.len() > 1 && part.starts_with('0') {
+            return Err(ConfigError::invalid_package(format!(
+                "Version '{}' component '{}' cannot have leading zeros",
+                version, part
+            )));
+        }
+
+        part.parse().map_err(|_| {
+            ConfigError::invalid_package(format!(
+                "Version '{}' component '{}' is too large",
+                version, part
+            ))
+        })
+    }
+
+    /// Split pre-release or build metadata into identifiers of `[0-9A-Za-z-]`
+    ///
+    /// Numeric pre-release identifiers cannot have leading zeros; build
+    /// metadata can.
+    fn parse_identifiers(version: &str, section: &str, kind: &str) -> Result<Vec<String>> {
+        section
+            .split('.')
+            .map(|identifier| {
+                if identifier.is_empty()
+                    || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                {
+                    return Err(ConfigError::invalid_package(format!(
+                        "Version '{}' has invalid {} identifier '{}'",
+                        version, kind, identifier
+                    )));
+                }
+                let leading_zero = identifier.len() > 1 && identifier.starts_with('0');
+                if kind == "pre-release" && leading_zero && is_numeric(identifier) {
+                    return Err(ConfigError::invalid_package(format!(
+                        "Version '{}' pre-release identifier '{}' cannot have leading zeros",
+                        version, identifier
+                    )));
+                }
+                Ok(identifier.to_string())
+            })
+            .collect()
+    }
+}
+
+impl FromStr for PackageVersion {
+    type Err = ConfigError;
+
+    fn from_str(version: &str) -> Result<Self> {
+        let trimmed = version.trim();
+        if trimmed.is_empty() {
+            return Err(ConfigError::invalid_package("Package version cannot be empty"));
+        }
+
+        // Basic format check - should start with digits
+        if !trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(ConfigError::invalid_package(format!(
+                "Version '{}' must start with a number (e.g., '1.0.0')",
+                version
+            )));
+        }
+
+        let (main_part, build) = match trimmed.split_once('+') {
+            Some((_, "")) => {
+                return Err(ConfigError::invalid_package(format!(
+                    "Version '{}' has empty build metadata",
+                    version
+                )));
+            }
+            Some((main_part, build)) => (main_part, Self::parse_identifiers(version, build, "build")?),
+            None => (trimmed, Vec::new()),
+        };
+        let (core, pre) = match main_part.split_once('-') {
+            Some((_, "")) => {
+                return Err(ConfigError::invalid_package(format!(
+                    "Version '{}' has empty pre-release identifier",
+                    version
+                )));
+            }
+            Some((core, pre)) => (core, Self::parse_identifiers(version, pre, "pre-release")?),
+            None => (main_part, Vec::new()),
+        };
+
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() < 3 {
+            return Err(ConfigError::invalid_package(format!(
+                "Version '{}' should have at least major.minor.patch format (e.g., '1.0.0')",
+                version
+            )));
+        }
+        if parts.len() > 3 {
+            return Err(ConfigError::invalid_package(format!(
+                "Version '{}' has more than major.minor.patch components (e.g., '1.0.0')",
+                version
+            )));
+        }
+
+        Ok(Self {
+            major: Self::parse_component(version, parts[0], 0)?,
+            minor: Self::parse_component(version, parts[1], 1)?,
+            patch: Self::parse_component(version, parts[2], 2)?,
+            pre,
+            build,
+        })
+    }
+}
+
+impl fmt::Display for PackageVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether an identifier consists of digits only
+fn is_numeric(identifier: &str) -> bool {
+    identifier.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Compare identifiers: numeric ones numerically and below alphanumeric ones
+///
+/// Identifiers that differ as text never compare equal, so the ordering
+/// stays consistent with equality; numeric build metadata with leading
+/// zeros sorts by its length first.
+fn compare_identifiers(a: &str, b: &str) -> Ordering {
+    match (is_numeric(a), is_numeric(b)) {
+        (true, true) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.cmp(b),
+    }
+}
+
+/// Compare identifier lists; a longer list wins when one is a prefix of the other
+fn compare_identifier_lists(a: &[String], b: &[String]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| compare_identifiers(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+impl Ord for PackageVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A release ranks above its pre-releases
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                _ => compare_identifier_lists(&self.pre, &other.pre),
+            })
+            .then_with(|| compare_identifier_lists(&self.build, &other.build))
+    }
+}
+
+impl PartialOrd for PackageVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<str> for PackageVersion {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<PackageVersion>().is_ok_and(|version| version == *self)
+    }
+}
+
+impl PartialEq<&str> for PackageVersion {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Serialize for PackageVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PackageVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let version = String::deserialize(deserializer)?;
+        version.parse().map_err(|e: ConfigError| match e {
+            ConfigError::InvalidPackage(msg) => serde::de::Error::custom(msg),
+            other => serde::de::Error::custom(other),
+        })
+    }
+}
+
+/// A version requirement such as `>=1.2, <2` or `^0.4`
+///
+/// Comparators are separated by commas and must all hold. Operators are
+/// `=`, `>`, `>=`, `<`, `<=`, `~` and `^`, which is also used when none is
+/// given; versions may leave out the minor and patch components, and `*`
+/// matches every version. Parsed as Cargo does for its dependencies, except
+/// that pre-releases are not treated specially.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRequirement {
+    text: String,
+    bounds: Vec<(Bound, Bound)>,
+}
+
+/// One end of the range allowed by a comparator
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Bound {
+    Unbounded,
+    Inclusive(PackageVersion),
+    Exclusive(PackageVersion),
+}
+
+impl VersionRequirement {
+    /// Whether a version satisfies every comparator
+    pub fn matches(&self, version: &PackageVersion) -> bool {
+        self.bounds.iter().all(|(lower, upper)| {
+            let above = match lower {
+                Bound::Unbounded => true,
+                Bound::Inclusive(bound) => version >= bound,
+                Bound::Exclusive(bound) => version > bound,
+            };
+            let below = match upper {
+                Bound::Unbounded => true,
+                Bound::Inclusive(bound) => version <= bound,
+                Bound::Exclusive(bound) => version < bound,
+            };
+            above && below
+        })
+    }
+
+    /// Allowed range of one comparator
+    fn parse_comparator(requirement: &str, comparator: &str) -> Result<(Bound, Bound)> {
+        let invalid = |detail: &str| ConfigError::invalid_version_requirement(requirement.trim(), detail);
+
+        let (op, version) = ["~", "^", ">=", "<=", ">", "<", "="]
+            .iter()
+            .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest.trim())))
+            .unwrap_or(("^", comparator));
+        if version == "*" && op == "^" {
+            return Ok((Bound::Unbounded, Bound::Unbounded));
+        }
+        if version.is_empty() {
+            return Err(invalid(&format!("'{}' is missing a version", comparator)));
+        }
+
+        // Leave out components as Cargo allows, e.g. `>=1.2`
+        let given = version.split(['-', '+']).next().unwrap_or_default().split('.').count();
+        let full = match given {
+            1 => format!("{}.0.0", version),
+            2 => format!("{}.0", version),
+            _ => version.to_string(),
+        };
+        if given < 3 && version.contains(['-', '+']) {
+            return Err(invalid(&format!("'{}' needs major.minor.patch for a pre-release", comparator)));
+        }
+        let v: PackageVersion = full.parse().map_err(|e: ConfigError| match e {
+            ConfigError::InvalidPackage(msg) => invalid(&msg),
+            other => other,
+        })?;
+
+        let (major, minor, patch) = (v.major, v.minor, v.patch);
+        let bump = |given: usize| match given {
+            1 => PackageVersion::new(major + 1, 0, 0),
+            2 => PackageVersion::new(major, minor + 1, 0),
+            _ => PackageVersion::new(major, minor, patch + 1),
+        };
+        let range = match op {
+            "=" if given == 3 => (Bound::Inclusive(v.clone()), Bound::Inclusive(v)),
+            "=" => (Bound::Inclusive(v), Bound::Exclusive(bump(given))),
+            ">" if given == 3 => (Bound::Exclusive(v), Bound::Unbounded),
+            ">" => (Bound::Inclusive(bump(given)), Bound::Unbounded),
+            ">=" => (Bound::Inclusive(v), Bound::Unbounded),
+            "<" => (Bound::Unbounded, Bound::Exclusive(v)),
+            "<=" if given == 3 => (Bound::Unbounded, Bound::Inclusive(v)),
+            "<=" => (Bound::Unbounded, Bound::Exclusive(bump(given))),
+            "~" => {
+                let upper = bump(given.min(2));
+                (Bound::Inclusive(v), Bound::Exclusive(upper))
+            }
+            _ => {
+                // Caret: the leftmost non-zero component may not change
+                let upper = if major > 0 || given == 1 {
+                    bump(1)
+                } else if minor > 0 || given == 2 {
+                    bump(2)
+                } else {
+                    bump(3)
+                };
+                (Bound::Inclusive(v), Bound::Exclusive(upper))
+            }
+        };
+        Ok(range)
+    }
+}
+
+impl FromStr for VersionRequirement {
+    type Err = ConfigError;
+
+    fn from_str(requirement: &str) -> Result<Self> {
+        let text = requirement.trim();
+        if text.is_empty() {
+            return Err(ConfigError::invalid_version_requirement(text, "the requirement is empty"));
+        }
+        let bounds = text
+            .split(',')
+            .map(|comparator| Self::parse_comparator(requirement, comparator.trim()))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            text: text.to_string(),
+            bounds,
+        })
+    }
+}
+
+impl fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> PackageVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_valid_versions() {
+        let parsed = version("1.2.3-beta.1+build.2");
+        assert_eq!((parsed.major, parsed.minor, parsed.patch), (1, 2, 3));
+        assert_eq!(parsed.pre, vec!["beta", "1"]);
+        assert_eq!(parsed.build, vec!["build", "2"]);
+        assert!(parsed.is_prerelease());
+
+        for valid in ["0.1.0", "10.20.30", "1.2.3-alpha", "1.0.0+build", "1.2.3-0a", "1.0.0+0123", " 1.0.0 "] {
+            assert!(valid.parse::<PackageVersion>().is_ok(), "'{}' should be valid", valid);
+        }
+        assert_eq!(version(" 1.0.0 ").to_string(), "1.0.0");
+    }
+
+    #[test]
+    fn test_parse_errors_keep_friendly_messages() {
+        let cases = [
+            ("", "Package version cannot be empty"),
+            ("v1.0.0", "must start with a number"),
+            ("1.2.3-", "has empty pre-release identifier"),
+            ("1.2.3+", "has empty build metadata"),
+            ("1.2", "should have at least major.minor.patch format"),
+            ("1.2.3.4", "has more than major.minor.patch components"),
+            ("1..0", "has empty version component at position 1"),
+            ("1.0.x", "has invalid patch component 'x'"),
+            ("01.0.0", "cannot have leading zeros"),
+            ("0.1.0-01", "pre-release identifier '01' cannot have leading zeros"),
+            ("1.0.0-be_ta", "has invalid pre-release identifier 'be_ta'"),
+            ("1.0.0-a..b", "has invalid pre-release identifier ''"),
+            ("1.0.0+b@d", "has invalid build identifier 'b@d'"),
+        ];
+        for (input, message) in cases {
+            let error = input.parse::<PackageVersion>().unwrap_err();
+            assert!(matches!(error, ConfigError::InvalidPackage(_)), "{:?}", error);
+            assert!(error.to_string().contains(message), "'{}': {}", input, error);
+        }
+    }
+
+    #[test]
+    fn test_ordering_follows_semver_precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(version("1.0.0+a").cmp(&version("1.0.0+a")), Ordering::Equal);
+        assert_ne!(version("1.0.0+a"), version("1.0.0+b"));
+    }
+
+    #[test]
+    fn test_serializes_as_string() {
+        let parsed = version("1.2.3-rc.1+sha.5114f85");
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert_eq!(json, "\"1.2.3-rc.1+sha.5114f85\"");
+        assert_eq!(serde_json::from_str::<PackageVersion>(&json).unwrap(), parsed);
+
+        let error = serde_json::from_str::<PackageVersion>("\"1.0\"").unwrap_err();
+        assert!(error.to_string().contains("should have at least major.minor.patch format"));
+    }
+
+    #[test]
+    fn test_current_version() {
+        assert_eq!(PackageVersion::current(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_version_requirements() {
+        let cases = [
+            (">=0.1.0", "0.1.0", true),
+            (">=0.2", "0.1.9", false),
+            (">1.2", "1.2.9", false),
+            (">1.2", "1.3.0", true),
+            (">1.2.3", "1.2.3", false),
+            ("<2", "1.99.0", true),
+            ("<=1.2", "1.2.7", true),
+            ("<=1.2.3", "1.2.4", false),
+            ("=1.2", "1.2.5", true),
+            ("=1.2.3", "1.2.4", false),
+            ("1.2.3", "1.9.0", true),
+            ("^1.2.3", "2.0.0", false),
+            ("^0.2.3", "0.2.9", true),
+            ("^0.2.3", "0.3.0", false),
+            ("^0.0.3", "0.0.4", false),
+            ("^0", "0.9.0", true),
+            ("~1.2.3", "1.2.9", true),
+            ("~1.2.3", "1.3.0", false),
+            ("~1", "1.9.0", true),
+            (">= 1.0, < 1.5", "1.4.9", true),
+            (">= 1.0, < 1.5", "1.5.0", false),
+            ("*", "12.0.0", true),
+        ];
+        for (requirement, candidate, expected) in cases {
+            let parsed: VersionRequirement = requirement.parse().unwrap();
+            assert_eq!(parsed.matches(&version(candidate)), expected, "{} {}", requirement, candidate);
+        }
+        assert_eq!(" >=1.2 ".parse::<VersionRequirement>().unwrap().to_string(), ">=1.2");
+    }
+
+    #[test]
+    fn test_malformed_version_requirements() {
+        for requirement in ["", ">=", "1.x", "^01.0", ">=1.0,", "1.2-beta", "=>1.0"] {
+            let error = requirement.parse::<VersionRequirement>().unwrap_err();
+            assert!(matches!(error, ConfigError::InvalidVersionRequirement { .. }), "'{}': {:?}", requirement, error);
+            assert!(!error.to_string().contains("configuration"), "{}", error);
+        }
+    }
+
+    mod ordering {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Versions from a few components, so that equal versions come up often
+        fn arb_close_version() -> impl Strategy<Value = PackageVersion> {
+            "[01]\\.[01]\\.[01](-(0|1|01|a|a1|1a)(\\.(0|1|a)){0,1})?(\\+(0|1|01|b)){0,1}"
+                .prop_filter_map("valid version", |version| version.parse().ok())
+        }
+
+        proptest! {
+            #[test]
+            fn ordering_agrees_with_equality(a in arb_close_version(), b in arb_close_version()) {
+                prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b, "{} and {}", a, b);
+                prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            }
+        }
+    }
+}
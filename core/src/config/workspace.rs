@@ -0,0 +1,166 @@
+use crate::config::{Agent, Package, normalize_stored_path};
+use crate::error::{ConfigError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Settings shared by the projects of a monorepo, kept in
+/// `.specforge-workspace.json` at the repository root
+///
+/// `specforge init` in a directory below the root uses the workspace agent
+/// when no `--agent` is given and adds the shared packages to the project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    /// Agent used by projects in the workspace that do not pick their own
+    pub default_agent: Agent,
+    /// Project directories relative to the workspace root; every directory
+    /// below the root belongs to the workspace when empty
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_members",
+        deserialize_with = "deserialize_members"
+    )]
+    pub members: Vec<PathBuf>,
+    /// Packages added to every project in the workspace
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_packages: Vec<Package>,
+}
+
+impl WorkspaceConfig {
+    /// Create a workspace configuration for an agent, without members or shared packages
+    pub fn new(default_agent: Agent) -> Self {
+        Self {
+            default_agent,
+            members: Vec::new(),
+            shared_packages: Vec::new(),
+        }
+    }
+
+    /// Validate the members and shared packages
+    pub fn validate(&self) -> Result<()> {
+        for member in &self.members {
+            if member.as_os_str().is_empty()
+                || member.is_absolute()
+                || member.components().any(|c| matches!(c, Component::ParentDir))
+            {
+                return Err(ConfigError::validation_error(format!(
+                    "Workspace member '{}' must be a directory below the workspace root",
+                    member.display()
+                )));
+            }
+        }
+
+        for (index, package) in self.shared_packages.iter().enumerate() {
+            package.validate()?;
+            if self.shared_packages[..index].iter().any(|other| other.id == package.id) {
+                return Err(ConfigError::invalid_package(format!(
+                    "Shared package '{}' is listed more than once",
+                    package.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a project directory belongs to the workspace rooted at `root`
+    ///
+    /// Paths are compared after resolving symlinks where they exist.
+    pub fn includes(&self, root: &Path, project_dir: &Path) -> bool {
+        let project_dir = resolve(project_dir);
+        let root = resolve(root);
+        if !project_dir.starts_with(&root) {
+            return false;
+        }
+        self.members.is_empty() || self.members.iter().any(|member| resolve(&root.join(member)) == project_dir)
+    }
+
+    /// Serialize to pretty-printed JSON
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// Parse and validate a workspace configuration
+    pub fn from_json_string(content: &str) -> Result<Self> {
+        let config: Self = serde_json::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Read member directories written with either separator
+fn deserialize_members<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<PathBuf>, D::Error> {
+    let members = Vec::<String>::deserialize(deserializer)?;
+    Ok(members.iter().map(|member| PathBuf::from(normalize_stored_path(member))).collect())
+}
+
+/// Write member directories with forward slashes
+fn serialize_members<S: Serializer>(members: &[PathBuf], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(members.iter().map(|member| normalize_stored_path(&member.to_string_lossy())))
+}
+
+/// Canonical form of a path, or the path itself when it does not exist yet
+fn resolve(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut config = WorkspaceConfig::new(Agent::Claude);
+        config.members.push(PathBuf::from("services/billing"));
+        config
+            .shared_packages
+            .push(Package::with_url("team-prompts", "https://example.com/prompts.tar.gz", "1.0.0").unwrap());
+
+        let json = config.to_json_string().unwrap();
+        assert!(json.contains("\"default_agent\": \"claude\""));
+        assert_eq!(WorkspaceConfig::from_json_string(&json).unwrap(), config);
+
+        let windows = WorkspaceConfig::from_json_string(
+            r#"{"default_agent": "claude", "members": ["services\\billing"], "shared_packages": [{"id": "team-prompts", "url": "https://example.com/prompts.tar.gz", "version": "1.0.0"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(windows, config);
+
+        let minimal = WorkspaceConfig::from_json_string(r#"{"default_agent": "copilot"}"#).unwrap();
+        assert_eq!(minimal, WorkspaceConfig::new(Agent::Copilot));
+    }
+
+    #[test]
+    fn test_validate_rejects_members_outside_the_root() {
+        for member in ["../other", "/srv/billing", ""] {
+            let mut config = WorkspaceConfig::new(Agent::Claude);
+            config.members.push(PathBuf::from(member));
+            assert!(config.validate().is_err(), "{member}");
+        }
+
+        let mut config = WorkspaceConfig::new(Agent::Claude);
+        let package = Package::new("team-prompts", "1.0.0").unwrap();
+        config.shared_packages = vec![package.clone(), package];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_includes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let billing = root.join("services").join("billing");
+        fs::create_dir_all(&billing).unwrap();
+
+        let open = WorkspaceConfig::new(Agent::Claude);
+        assert!(open.includes(root, &billing));
+        assert!(open.includes(root, &root.join("not-created-yet")));
+        assert!(!open.includes(&billing, root));
+
+        let mut listed = WorkspaceConfig::new(Agent::Claude);
+        listed.members.push(PathBuf::from("services/billing"));
+        assert!(listed.includes(root, &billing));
+        assert!(!listed.includes(root, &root.join("services").join("search")));
+    }
+}
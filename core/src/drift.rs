@@ -0,0 +1,336 @@
+//! Drift detection between a project and the state specforge recorded for it.
+//!
+//! [`check`] compares every file tracked in the configuration against the
+//! hash recorded when it was deployed, and every built-in template package
+//! against the templates bundled with this release. It only reads from the
+//! project, so CI jobs can call it in-process instead of running the CLI:
+//!
+//! ```
+//! use specforge_core::config::{Agent, Package, ProjectConfig, TrackedFile};
+//! use specforge_core::{FileOps, drift};
+//! use std::fs;
+//!
+//! let project = tempfile::tempdir()?;
+//! fs::write(project.path().join("CLAUDE.md"), "# Instructions\n")?;
+//!
+//! let mut config = ProjectConfig::new(Agent::Claude);
+//! config.add_package(Package::new(Agent::Claude.default_package_id(), drift::BUNDLED_VERSION)?)?;
+//! config.track_file(TrackedFile::new("CLAUDE.md".to_string(), FileOps::hash_content("# Instructions\n")));
+//! FileOps::write_config_to_directory(&config, project.path())?;
+//!
+//! assert!(drift::check(project.path())?.is_clean());
+//!
+//! fs::write(project.path().join("CLAUDE.md"), "# Edited by hand\n")?;
+//! let report = drift::check(project.path())?;
+//! assert!(!report.is_clean());
+//! assert_eq!(report.files[0].status, drift::FileStatus::Modified);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::config::{Agent, PackageVersion, ProjectConfig};
+use crate::error::Result;
+use crate::file_ops::{DEFAULT_MAX_HASH_SIZE, FileHash, FileOps};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Version of the templates bundled with this release
+pub const BUNDLED_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// State of a tracked file compared to the hash recorded when it was deployed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    /// Content matches the recorded hash
+    Clean,
+    /// Content differs from the recorded hash
+    Modified,
+    /// Content matches, but the execute bits differ from the recorded mode
+    ModeChanged,
+    /// File no longer exists
+    Missing,
+    /// File is too large to hash, so it could not be compared
+    Unverified,
+}
+
+/// Drift of a single tracked file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDrift {
+    /// Path relative to the project directory
+    pub path: String,
+    /// How the file compares to the recorded hash
+    pub status: FileStatus,
+}
+
+/// Version of a built-in template package compared to this release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageDrift {
+    /// Package ID as listed in the configuration
+    pub id: String,
+    /// Version recorded in the configuration
+    pub configured_version: PackageVersion,
+    /// Version of the templates bundled with this release
+    pub bundled_version: PackageVersion,
+}
+
+impl PackageDrift {
+    /// Whether the configured version matches the bundled templates
+    pub fn is_current(&self) -> bool {
+        self.configured_version == self.bundled_version
+    }
+}
+
+/// Result of a drift check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Every tracked file, in the order listed in the configuration
+    pub files: Vec<FileDrift>,
+    /// Every built-in template package in the configuration
+    pub packages: Vec<PackageDrift>,
+    /// Problems that do not count as drift but may hide it
+    pub warnings: Vec<String>,
+}
+
+impl DriftReport {
+    /// Whether every tracked file is unchanged and every package is current
+    ///
+    /// Warnings do not make a report unclean.
+    pub fn is_clean(&self) -> bool {
+        self.files.iter().all(|file| file.status == FileStatus::Clean)
+            && self.packages.iter().all(PackageDrift::is_current)
+    }
+}
+
+/// Check the project in `project_root` for drift
+///
+/// Fails when the configuration cannot be found or read; problems with
+/// individual files are reported in the returned [`DriftReport`].
+pub fn check(project_root: &Path) -> Result<DriftReport> {
+    let config_path = FileOps::locate_config(project_root);
+    let config = FileOps::read_config(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or(project_root);
+    Ok(report(&config, config_dir))
+}
+
+/// Give every file whose mode drifted the mode recorded for it, returning
+/// their paths
+///
+/// Only the mode is changed, so the content and modification time of the
+/// files stay as they are.
+pub fn restore_modes(project_root: &Path, report: &DriftReport) -> Result<Vec<String>> {
+    let config_path = FileOps::locate_config(project_root);
+    let config = FileOps::read_config(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or(project_root);
+
+    let mut restored = Vec::new();
+    for file in report.files.iter().filter(|file| file.status == FileStatus::ModeChanged) {
+        let Some(mode) = config.tracked_file(&file.path).and_then(|tracked| tracked.mode) else {
+            continue;
+        };
+        if FileOps::set_file_mode(config_dir.join(&file.path), mode)? {
+            restored.push(file.path.clone());
+        }
+    }
+    Ok(restored)
+}
+
+/// Status of a file whose content is clean, comparing its mode to the recorded one
+///
+/// Only the execute bits count, since the others depend on the umask of
+/// whoever checked the project out. Files without a recorded mode, and
+/// platforms without modes, are clean.
+fn mode_status(path: &Path, recorded: Option<u32>) -> FileStatus {
+    match (recorded, FileOps::file_mode(path)) {
+        (Some(recorded), Some(actual)) if (recorded ^ actual) & 0o111 != 0 => FileStatus::ModeChanged,
+        _ => FileStatus::Clean,
+    }
+}
+
+/// Build the drift report for a configuration whose files live in `config_dir`
+fn report(config: &ProjectConfig, config_dir: &Path) -> DriftReport {
+    let mut warnings = Vec::new();
+
+    let files = config
+        .files
+        .iter()
+        .map(|tracked| {
+            let path = config_dir.join(&tracked.path);
+            let status = if !path.is_file() {
+                FileStatus::Missing
+            } else {
+                match FileOps::hash_file_with_limit(&path, DEFAULT_MAX_HASH_SIZE) {
+                    Ok(FileHash::Hashed(sha256)) if sha256 == tracked.sha256 => mode_status(&path, tracked.mode),
+                    Ok(FileHash::Hashed(_)) => FileStatus::Modified,
+                    Ok(FileHash::TooLarge(size)) => {
+                        warnings.push(format!(
+                            "{} is too large to verify ({} bytes)",
+                            tracked.path, size
+                        ));
+                        FileStatus::Unverified
+                    }
+                    Err(e) => {
+                        warnings.push(format!("{} could not be read: {}", tracked.path, e));
+                        FileStatus::Unverified
+                    }
+                }
+            };
+            FileDrift {
+                path: tracked.path.clone(),
+                status,
+            }
+        })
+        .collect();
+
+    let packages = config
+        .packages
+        .iter()
+        .filter(|package| {
+            package.url.is_none()
+                && Agent::all()
+                    .iter()
+                    .any(|agent| agent.default_package_id() == package.id)
+        })
+        .map(|package| PackageDrift {
+            id: package.id.clone(),
+            configured_version: package.version.clone(),
+            bundled_version: PackageVersion::current(),
+        })
+        .collect();
+
+    if config.files.is_empty() {
+        warnings.push(
+            "No deployed files are tracked in the configuration; re-run specforge init to record them"
+                .to_string(),
+        );
+    }
+
+    DriftReport {
+        files,
+        packages,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Package, TrackedFile};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_project(dir: &Path, version: &str) {
+        fs::write(dir.join("CLAUDE.md"), "instructions").unwrap();
+        fs::write(dir.join("README.md"), "readme").unwrap();
+
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config
+            .add_package(Package::new(Agent::Claude.default_package_id(), version).unwrap())
+            .unwrap();
+        config
+            .add_package(Package::with_url("team-prompts", "https://example.com/p.tar.gz", "2.0.0").unwrap())
+            .unwrap();
+        for (name, content) in [("CLAUDE.md", "instructions"), ("README.md", "readme")] {
+            config.track_file(TrackedFile::new(name.to_string(), FileOps::hash_content(content)));
+        }
+        FileOps::write_config_to_directory(&config, dir).unwrap();
+    }
+
+    #[test]
+    fn test_clean_project() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), BUNDLED_VERSION);
+
+        let report = check(temp_dir.path()).unwrap();
+        assert!(report.is_clean());
+        assert!(report.warnings.is_empty());
+        assert_eq!(report.files.len(), 2);
+        // Packages with a URL are not built into specforge
+        assert_eq!(report.packages.len(), 1);
+        assert!(report.packages[0].is_current());
+    }
+
+    #[test]
+    fn test_modified_and_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), BUNDLED_VERSION);
+        fs::write(temp_dir.path().join("CLAUDE.md"), "edited").unwrap();
+        fs::remove_file(temp_dir.path().join("README.md")).unwrap();
+
+        let report = check(temp_dir.path()).unwrap();
+        assert!(!report.is_clean());
+        assert_eq!(report.files[0].status, FileStatus::Modified);
+        assert_eq!(report.files[1].status, FileStatus::Missing);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mode_changed_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("hook.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut config = ProjectConfig::new(Agent::Claude);
+        config.track_file(
+            TrackedFile::new("hook.sh".to_string(), FileOps::hash_content("#!/bin/sh\n")).with_mode(Some(0o755)),
+        );
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+        assert!(check(temp_dir.path()).unwrap().is_clean());
+
+        // Group write permission depends on the umask, so it is not drift
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o775)).unwrap();
+        assert!(check(temp_dir.path()).unwrap().is_clean());
+
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        let report = check(temp_dir.path()).unwrap();
+        assert_eq!(report.files[0].status, FileStatus::ModeChanged);
+        assert!(!report.is_clean());
+
+        assert_eq!(restore_modes(temp_dir.path(), &report).unwrap(), vec!["hook.sh"]);
+        assert_eq!(FileOps::file_mode(&script), Some(0o755));
+        assert!(check(temp_dir.path()).unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_outdated_package() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_project(temp_dir.path(), "0.0.1");
+
+        let report = check(temp_dir.path()).unwrap();
+        assert!(!report.is_clean());
+        assert_eq!(report.packages[0].configured_version, "0.0.1");
+        assert_eq!(report.packages[0].bundled_version, BUNDLED_VERSION);
+    }
+
+    #[test]
+    fn test_untracked_project_warns() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ProjectConfig::new(Agent::Claude);
+        FileOps::write_config_to_directory(&config, temp_dir.path()).unwrap();
+
+        let report = check(temp_dir.path()).unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_config() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(check(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let report = DriftReport {
+            files: vec![FileDrift {
+                path: "CLAUDE.md".to_string(),
+                status: FileStatus::Unverified,
+            }],
+            packages: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["files"][0]["status"], "unverified");
+        assert_eq!(serde_json::from_value::<DriftReport>(json).unwrap(), report);
+    }
+}
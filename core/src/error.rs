@@ -1,3 +1,4 @@
+use crate::config::ValidationIssue;
 use std::fmt;
 use std::path::PathBuf;
 
@@ -7,6 +8,10 @@ pub enum ConfigError {
     IoError(std::io::Error),
     /// JSON serialization/deserialization failed
     JsonError(serde_json::Error),
+    /// TOML serialization/deserialization failed
+    TomlError(String),
+    /// YAML serialization/deserialization failed
+    YamlError(String),
     /// Configuration validation failed
     ValidationError(String),
     /// Invalid agent specified
@@ -19,6 +24,14 @@ pub enum ConfigError {
     DirectoryCreationFailed(PathBuf, std::io::Error),
     /// Configuration file is corrupted or invalid
     CorruptedConfig(PathBuf),
+    /// Configuration file is not valid in its format, e.g. TOML or YAML
+    ParseError {
+        path: PathBuf,
+        format: String,
+        message: String,
+        /// Line and column of the error, both starting at 1, when known
+        location: Option<(usize, usize)>,
+    },
     /// Required field missing from configuration
     MissingRequiredField(String),
     /// Invalid package configuration
@@ -37,6 +50,40 @@ pub enum ConfigError {
     DiskSpaceError(PathBuf),
     /// File or directory not found
     NotFound(PathBuf),
+    /// One or more diagnostic checks failed
+    DiagnosticsFailed(usize),
+    /// Path or one of its components exceeds the platform length limit
+    PathTooLong(PathBuf, String),
+    /// Configuration file uses an older schema that could not be migrated
+    MigrationRequired {
+        from: u32,
+        to: u32,
+        reason: String,
+    },
+    /// Configuration files extend each other in a cycle
+    CircularExtends(Vec<PathBuf>),
+    /// Deployed files no longer match the checksums recorded for them
+    VerificationFailed(usize),
+    /// Some projects of a batch could not be initialized
+    BatchFailed { failed: usize, total: usize },
+    /// Several independent operations failed, e.g. writing files in parallel
+    Multiple(Vec<ConfigError>),
+    /// The configuration has several problems, all found in one pass
+    MultipleValidationErrors(Vec<ValidationIssue>),
+    /// The project requires a newer specforge release than the one running
+    SpecforgeTooOld { required: String, actual: String },
+    /// The configuration uses a schema version newer than this release supports
+    SchemaTooNew { version: u32, supported: u32 },
+    /// The running specforge release does not satisfy a version requirement
+    VersionCheckFailed { requirement: String, actual: String },
+    /// A version requirement such as `>=0.4` could not be parsed
+    InvalidVersionRequirement { requirement: String, reason: String },
+    /// Configured packages no longer match the pins in the lock file
+    LockMismatch { path: PathBuf, mismatches: Vec<String> },
+    /// The configuration has no backups to roll back to
+    NoBackups(PathBuf),
+    /// The command needs a Git repository, and the directory is not inside one
+    GitRepositoryRequired { path: PathBuf, reason: String },
 }
 
 impl fmt::Display for ConfigError {
@@ -53,11 +100,17 @@ impl fmt::Display for ConfigError {
                     err.line(),
                     err.column())
             }
+            ConfigError::TomlError(msg) => {
+                write!(f, "Failed to process TOML configuration: {}\n\nEnsure the .specforge.toml file contains valid TOML syntax.\nNote: TOML has no null value, so metadata entries set to null cannot be written.", msg)
+            }
+            ConfigError::YamlError(msg) => {
+                write!(f, "Failed to process YAML configuration: {}\n\nEnsure the .specforge.yaml file contains valid YAML syntax.", msg)
+            }
             ConfigError::ValidationError(msg) => {
                 write!(f, "Configuration validation failed: {}\n\nPlease check your configuration file format and ensure all required fields are present.\nFor reference, run 'specforge init' to see the expected format.", msg)
             }
             ConfigError::InvalidAgent(agent) => {
-                write!(f, "Invalid agent '{}' specified.\n\nSupported agents are:\n  • 'copilot' - GitHub Copilot integration\n  • 'claude' - Anthropic Claude integration\n\nExamples:\n  specforge init --agent copilot\n  specforge init --agent claude", agent)
+                write!(f, "Invalid agent '{}' specified.\n\nSupported agents are:\n  • 'copilot' - GitHub Copilot integration\n  • 'claude' - Anthropic Claude integration\n  • 'codeium' - Codeium integration\n  • 'continue' - Continue integration\n  • 'amazonq' - Amazon Q Developer integration\n\nExamples:\n  specforge init --agent copilot\n  specforge init --agent claude\n  specforge init --agent codeium\n  specforge init --agent continue\n  specforge init --agent amazonq", agent)
             }
             ConfigError::FileExists(path) => {
                 write!(f, "Configuration file already exists at: {}\n\nOptions:\n  • Use 'specforge init --force' to overwrite\n  • Choose a different directory with '--output-directory <path>'\n  • Remove the existing file manually: rm {}",
@@ -73,14 +126,21 @@ impl fmt::Display for ConfigError {
                     path.display(), err)
             }
             ConfigError::CorruptedConfig(path) => {
-                write!(f, "Configuration file is corrupted or invalid: {}\n\nRecovery options:\n  1. Backup the current file: cp {} {}.backup\n  2. Delete the corrupted file: rm {}\n  3. Recreate with: reforge init\n  4. Restore from backup if needed",
+                write!(f, "Configuration file is corrupted or invalid: {}\n\nRecovery options:\n  1. Backup the current file: cp {} {}.backup\n  2. Delete the corrupted file: rm {}\n  3. Recreate with: specforge init\n  4. Restore from backup if needed",
                     path.display(), path.display(), path.display(), path.display())
             }
+            ConfigError::ParseError { path, format, message, location } => {
+                write!(f, "Configuration file is corrupted or invalid: {}\n\n{} syntax error", path.display(), format)?;
+                if let Some((line, column)) = location {
+                    write!(f, " at line {}, column {}", line, column)?;
+                }
+                write!(f, ": {}\n\nRecovery options:\n  1. Fix the error in the file and run the command again\n  2. Restore the file from version control or a backup\n  3. Recreate with: specforge init --force", message)
+            }
             ConfigError::MissingRequiredField(field) => {
                 write!(f, "Required field '{}' is missing from configuration.\n\nQuick fix:\n  1. Backup current config: cp .specforge.json .specforge.json.backup\n  2. Recreate config: specforge init\n  3. Merge custom settings from backup if needed", field)
             }
             ConfigError::InvalidPackage(msg) => {
-                write!(f, "Invalid package configuration: {}\n\nPackage requirements:\n  • ID must be non-empty and contain no whitespace\n  • Version must follow semantic versioning (e.g., '1.0.0')\n  • URL (if provided) must start with 'http://' or 'https://'\n\nCheck the packages array in your .specforge.json file.", msg)
+                write!(f, "Invalid package configuration: {}\n\nPackage requirements:\n  • ID must be non-empty and contain no whitespace\n  • Version must follow semantic versioning (e.g., '1.0.0')\n  • URL (if provided) must be an http:// or https:// URL with a host, or a local file:// URL\n  • Dependencies must be listed in the configuration, without cycles\n\nCheck the packages array in your .specforge.json file.", msg)
             }
             ConfigError::UserCancelled(msg) => {
                 write!(f, "Operation cancelled: {}\n\nYou can restart the operation at any time.", msg)
@@ -99,6 +159,73 @@ impl fmt::Display for ConfigError {
                 write!(f, "File or directory not found: {}\n\nVerification:\n  • Check if the path exists: ls -la {}\n  • Verify correct spelling and case sensitivity\n  • Ensure you're in the correct working directory\n  • Check if the file was moved or deleted",
                     path.display(), path.display())
             }
+            ConfigError::DiagnosticsFailed(count) => {
+                write!(f, "{} diagnostic check(s) failed.\n\nReview the failed entries above for details on how to resolve each problem.", count)
+            }
+            ConfigError::PathTooLong(path, detail) => {
+                write!(f, "Path is too long: {}\n\n{}\n\nSuggestions:\n  • Shorten the directory or file name mentioned above\n  • Choose an output directory closer to the filesystem root\n  • On Windows, enable long path support (LongPathsEnabled) or use a shorter path",
+                    path.display(), detail)
+            }
+            ConfigError::MigrationRequired { from, to, reason } => {
+                write!(f, "Configuration schema version {} could not be migrated to version {}: {}\n\nRecovery options:\n  1. Backup the current file: cp .specforge.json .specforge.json.backup\n  2. Recreate config: specforge init --force\n  3. Merge custom settings from backup if needed",
+                    from, to, reason)
+            }
+            ConfigError::CircularExtends(chain) => {
+                let chain: Vec<_> = chain.iter().map(|path| path.display().to_string()).collect();
+                write!(f, "Configuration files extend each other in a cycle: {}\n\nRemove the 'extends' field from one of these files so the chain ends in a configuration that does not extend another.",
+                    chain.join(" -> "))
+            }
+            ConfigError::VerificationFailed(count) => {
+                write!(f, "{} deployed file(s) do not match the checksum recorded when they were deployed.\n\nNext steps:\n  • Review the failed entries above to see which files changed\n  • Restore the templates with: specforge upgrade\n  • Or keep your changes and record them with: specforge init --force", count)
+            }
+            ConfigError::BatchFailed { failed, total } => {
+                write!(f, "{} of {} project(s) could not be initialized.\n\nReview the failed entries above, fix the problems and run the batch again; projects that were initialized are left as they are.", failed, total)
+            }
+            ConfigError::Multiple(errors) => {
+                write!(f, "{} operations failed:", errors.len())?;
+                for (index, error) in errors.iter().enumerate() {
+                    let message = error.to_string();
+                    write!(f, "\n\n{}. {}", index + 1, message.lines().next().unwrap_or_default())?;
+                }
+                Ok(())
+            }
+            ConfigError::MultipleValidationErrors(issues) => {
+                write!(f, "Configuration validation failed with {} problems:", issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  • {}", issue)?;
+                }
+                write!(f, "\n\nFix all of them and run the command again.")
+            }
+            ConfigError::SpecforgeTooOld { required, actual } => {
+                write!(f, "This project requires specforge {} or newer, but specforge {} is installed.\n\nUpgrade specforge, or pass --ignore-version-requirement to run this command anyway.",
+                    required, actual)
+            }
+            ConfigError::SchemaTooNew { version, supported } => {
+                write!(f, "The configuration uses schema version {}, but this release of specforge supports up to version {}.\n\nPlease upgrade specforge to work with this project.",
+                    version, supported)
+            }
+            ConfigError::VersionCheckFailed { requirement, actual } => {
+                write!(f, "specforge {} does not satisfy the version requirement '{}'", actual, requirement)
+            }
+            ConfigError::InvalidVersionRequirement { requirement, reason } => {
+                write!(f, "Invalid version requirement '{}': {}\n\nA requirement is one or more comparisons separated by commas, such as '>=0.4', '^1.2.0' or '>=1.0, <2.0'.",
+                    requirement, reason)
+            }
+            ConfigError::LockMismatch { path, mismatches } => {
+                write!(f, "{} package(s) do not match their pins in {}:", mismatches.len(), path.display())?;
+                for mismatch in mismatches {
+                    write!(f, "\n  • {}", mismatch)?;
+                }
+                write!(f, "\n\nNext steps:\n  • Pin the configured packages again with: specforge lock\n  • Or stop pinning a package with: specforge unlock --package <ID>")
+            }
+            ConfigError::NoBackups(path) => {
+                write!(f, "No backups of {} to roll back to.\n\nSpecforge backs up the configuration each time it rewrites it, so there is nothing to roll back to until it has changed.\n\nNext steps:\n  • Check that backups are kept with: specforge config get max_backups\n  • Restore the configuration from version control instead, e.g. git checkout -- {}",
+                    path.display(), path.display())
+            }
+            ConfigError::GitRepositoryRequired { path, reason } => {
+                write!(f, "{}\n\nNext steps:\n  • Create a Git repository with: git init {}\n  • Or run the command inside an existing repository",
+                    reason, path.display())
+            }
         }
     }
 }
@@ -181,6 +308,16 @@ impl From<serde_json::Error> for ConfigError {
 }
 
 impl ConfigError {
+    /// Create a TOML error with message
+    pub fn toml_error<S: Into<String>>(msg: S) -> Self {
+        ConfigError::TomlError(msg.into())
+    }
+
+    /// Create a YAML error with message
+    pub fn yaml_error<S: Into<String>>(msg: S) -> Self {
+        ConfigError::YamlError(msg.into())
+    }
+
     /// Create a permission denied error with path context
     pub fn permission_denied<P: Into<PathBuf>>(path: P) -> Self {
         ConfigError::PermissionDenied(path.into())
@@ -201,6 +338,21 @@ impl ConfigError {
         ConfigError::CorruptedConfig(path.into())
     }
 
+    /// Create an error for a configuration file that does not parse in its format
+    pub fn parse_error<P, F, M>(path: P, format: F, message: M, location: Option<(usize, usize)>) -> Self
+    where
+        P: Into<PathBuf>,
+        F: Into<String>,
+        M: Into<String>,
+    {
+        ConfigError::ParseError {
+            path: path.into(),
+            format: format.into(),
+            message: message.into(),
+            location,
+        }
+    }
+
     /// Create a missing required field error
     pub fn missing_required_field<S: Into<String>>(field: S) -> Self {
         ConfigError::MissingRequiredField(field.into())
@@ -228,7 +380,7 @@ impl ConfigError {
 
     /// Create an I/O error with message
     pub fn io_error<S: Into<String>>(msg: S) -> Self {
-        ConfigError::IoError(std::io::Error::new(std::io::ErrorKind::Other, msg.into()))
+        ConfigError::IoError(std::io::Error::other(msg.into()))
     }
 
     /// Create a contextual error with operation details
@@ -259,6 +411,108 @@ impl ConfigError {
         ConfigError::NotFound(path.into())
     }
 
+    /// Create a diagnostics failed error
+    pub fn diagnostics_failed(count: usize) -> Self {
+        ConfigError::DiagnosticsFailed(count)
+    }
+
+    /// Create a path too long error
+    pub fn path_too_long<P: Into<PathBuf>, S: Into<String>>(path: P, detail: S) -> Self {
+        ConfigError::PathTooLong(path.into(), detail.into())
+    }
+
+    /// Create a migration required error
+    pub fn migration_required<S: Into<String>>(from: u32, to: u32, reason: S) -> Self {
+        ConfigError::MigrationRequired {
+            from,
+            to,
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a circular extends error from the chain of files, ending with the repeated one
+    pub fn circular_extends(chain: Vec<PathBuf>) -> Self {
+        ConfigError::CircularExtends(chain)
+    }
+
+    /// Create a verification failed error
+    pub fn verification_failed(count: usize) -> Self {
+        ConfigError::VerificationFailed(count)
+    }
+
+    /// Create a batch failed error
+    pub fn batch_failed(failed: usize, total: usize) -> Self {
+        ConfigError::BatchFailed { failed, total }
+    }
+
+    /// Create an error for a project that requires a newer specforge release
+    pub fn specforge_too_old<S1: Into<String>, S2: Into<String>>(required: S1, actual: S2) -> Self {
+        ConfigError::SpecforgeTooOld {
+            required: required.into(),
+            actual: actual.into(),
+        }
+    }
+
+    /// Create an error for a configuration written for a newer specforge release
+    pub fn schema_too_new(version: u32, supported: u32) -> Self {
+        ConfigError::SchemaTooNew { version, supported }
+    }
+
+    /// Create an error for a failed `specforge version --check`
+    pub fn version_check_failed<S1: Into<String>, S2: Into<String>>(requirement: S1, actual: S2) -> Self {
+        ConfigError::VersionCheckFailed {
+            requirement: requirement.into(),
+            actual: actual.into(),
+        }
+    }
+
+    /// Create an error for a version requirement that does not parse
+    pub fn invalid_version_requirement<S1: Into<String>, S2: Into<String>>(requirement: S1, reason: S2) -> Self {
+        ConfigError::InvalidVersionRequirement {
+            requirement: requirement.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Create an error for packages that no longer match the lock file at `path`
+    pub fn lock_mismatch<P: Into<PathBuf>>(path: P, mismatches: Vec<String>) -> Self {
+        ConfigError::LockMismatch {
+            path: path.into(),
+            mismatches,
+        }
+    }
+
+    /// Create an error for a configuration without backups to roll back to
+    pub fn no_backups<P: Into<PathBuf>>(config_path: P) -> Self {
+        ConfigError::NoBackups(config_path.into())
+    }
+
+    /// Create an error for a command that needs the Git repository of `path`
+    pub fn git_repository_required<P: Into<PathBuf>, S: Into<String>>(path: P, reason: S) -> Self {
+        ConfigError::GitRepositoryRequired {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Combine the errors of independent operations, keeping a single error as it is
+    ///
+    /// # Panics
+    ///
+    /// Panics when `errors` is empty.
+    pub fn multiple(mut errors: Vec<ConfigError>) -> Self {
+        assert!(!errors.is_empty(), "ConfigError::multiple needs at least one error");
+        if errors.len() == 1 {
+            return errors.remove(0);
+        }
+        ConfigError::Multiple(errors)
+    }
+
+    /// Create an error listing every problem found in a configuration
+    pub fn multiple_validation_errors(issues: Vec<ValidationIssue>) -> Self {
+        ConfigError::MultipleValidationErrors(issues)
+    }
+
     /// Add context to an existing error
     pub fn add_context<S1: Into<String>, S2: Into<String>>(
         self,
@@ -298,38 +552,167 @@ impl ConfigError {
             ConfigError::DirectoryCreationFailed(_, _) => 73, // Can't create
             ConfigError::IoError(_) => 74,           // IO error
             ConfigError::JsonError(_) => 65,         // Data format error
+            ConfigError::TomlError(_) => 65,         // Data format error
+            ConfigError::YamlError(_) => 65,         // Data format error
+            ConfigError::ParseError { .. } => 65,    // Data format error
             ConfigError::UserCancelled(_) => 1,      // User cancelled operation
             ConfigError::ContextualError { cause, .. } => cause.exit_code(),
             ConfigError::NetworkError(_) => 69,      // Service unavailable
             ConfigError::DiskSpaceError(_) => 28,    // No space left on device
             ConfigError::NotFound(_) => 2,           // No such file or directory
+            ConfigError::DiagnosticsFailed(_) => 1,  // Checks failed
+            ConfigError::PathTooLong(_, _) => 36,    // File name too long
+            ConfigError::MigrationRequired { .. } => 65, // Data format error
+            ConfigError::CircularExtends(_) => 22,   // Invalid argument
+            ConfigError::VerificationFailed(_) => 1, // Checks failed
+            ConfigError::BatchFailed { .. } => 1,    // Checks failed
+            ConfigError::Multiple(errors) => errors.first().map_or(1, ConfigError::exit_code),
+            ConfigError::MultipleValidationErrors(_) => 22, // Invalid argument
+            ConfigError::SpecforgeTooOld { .. } => 22, // Invalid argument
+            ConfigError::SchemaTooNew { .. } => 78,  // Configuration error
+            ConfigError::VersionCheckFailed { .. } => 1, // Checks failed
+            ConfigError::InvalidVersionRequirement { .. } => 22, // Invalid argument
+            ConfigError::LockMismatch { .. } => 65,  // Data format error
+            ConfigError::NoBackups(_) => 66,         // Cannot open input
+            ConfigError::GitRepositoryRequired { .. } => 2, // No such file or directory
         }
     }
 
+    /// Every error code with the exit code it produces, in declaration order
+    pub fn exit_code_table() -> Vec<(&'static str, i32)> {
+        let json_error = serde_json::from_str::<serde_json::Value>("").unwrap_err();
+        let io_error = || std::io::Error::other("");
+        [
+            ConfigError::IoError(io_error()),
+            ConfigError::JsonError(json_error),
+            ConfigError::toml_error(""),
+            ConfigError::yaml_error(""),
+            ConfigError::validation_error(""),
+            ConfigError::invalid_agent(""),
+            ConfigError::file_exists(""),
+            ConfigError::permission_denied(""),
+            ConfigError::directory_creation_failed("", io_error()),
+            ConfigError::corrupted_config(""),
+            ConfigError::parse_error("", "", "", None),
+            ConfigError::missing_required_field(""),
+            ConfigError::invalid_package(""),
+            ConfigError::user_cancelled(""),
+            ConfigError::network_error(""),
+            ConfigError::disk_space_error(""),
+            ConfigError::not_found(""),
+            ConfigError::diagnostics_failed(0),
+            ConfigError::path_too_long("", ""),
+            ConfigError::migration_required(0, 0, ""),
+            ConfigError::circular_extends(Vec::new()),
+            ConfigError::verification_failed(0),
+            ConfigError::batch_failed(0, 0),
+            ConfigError::Multiple(Vec::new()),
+            ConfigError::multiple_validation_errors(Vec::new()),
+            ConfigError::specforge_too_old("", ""),
+            ConfigError::schema_too_new(0, 0),
+            ConfigError::version_check_failed("", ""),
+            ConfigError::invalid_version_requirement("", ""),
+            ConfigError::lock_mismatch("", Vec::new()),
+            ConfigError::no_backups(""),
+            ConfigError::git_repository_required("", ""),
+        ]
+        .iter()
+        .map(|error| (error.code(), error.exit_code()))
+        .collect()
+    }
+
+    /// Stable machine-readable identifier for this error type
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConfigError::IoError(_) => "io_error",
+            ConfigError::JsonError(_) => "json_error",
+            ConfigError::TomlError(_) => "toml_error",
+            ConfigError::YamlError(_) => "yaml_error",
+            ConfigError::ValidationError(_) => "validation_error",
+            ConfigError::InvalidAgent(_) => "invalid_agent",
+            ConfigError::FileExists(_) => "file_exists",
+            ConfigError::PermissionDenied(_) => "permission_denied",
+            ConfigError::DirectoryCreationFailed(_, _) => "directory_creation_failed",
+            ConfigError::CorruptedConfig(_) => "corrupted_config",
+            ConfigError::ParseError { .. } => "parse_error",
+            ConfigError::MissingRequiredField(_) => "missing_required_field",
+            ConfigError::InvalidPackage(_) => "invalid_package",
+            ConfigError::UserCancelled(_) => "user_cancelled",
+            ConfigError::ContextualError { cause, .. } => cause.code(),
+            ConfigError::NetworkError(_) => "network_error",
+            ConfigError::DiskSpaceError(_) => "disk_space_error",
+            ConfigError::NotFound(_) => "not_found",
+            ConfigError::DiagnosticsFailed(_) => "diagnostics_failed",
+            ConfigError::PathTooLong(_, _) => "path_too_long",
+            ConfigError::MigrationRequired { .. } => "migration_required",
+            ConfigError::CircularExtends(_) => "circular_extends",
+            ConfigError::VerificationFailed(_) => "verification_failed",
+            ConfigError::BatchFailed { .. } => "batch_failed",
+            ConfigError::Multiple(_) => "multiple_errors",
+            ConfigError::MultipleValidationErrors(_) => "multiple_validation_errors",
+            ConfigError::SpecforgeTooOld { .. } => "specforge_too_old",
+            ConfigError::SchemaTooNew { .. } => "schema_too_new",
+            ConfigError::VersionCheckFailed { .. } => "version_check_failed",
+            ConfigError::InvalidVersionRequirement { .. } => "invalid_version_requirement",
+            ConfigError::LockMismatch { .. } => "lock_mismatch",
+            ConfigError::NoBackups(_) => "no_backups",
+            ConfigError::GitRepositoryRequired { .. } => "git_repository_required",
+        }
+    }
+
+    /// Problems listed by a [`ConfigError::MultipleValidationErrors`], also
+    /// when it is wrapped in context
+    pub fn validation_issues(&self) -> Option<&[ValidationIssue]> {
+        match self {
+            ConfigError::MultipleValidationErrors(issues) => Some(issues),
+            ConfigError::ContextualError { cause, .. } => cause.validation_issues(),
+            _ => None,
+        }
+    }
+
+    /// Serialize this error for JSON output mode
+    ///
+    /// `error` repeats `message` under the name scripts look for next to
+    /// `status`. Configurations with several problems list them under `issues`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let message = self.to_string();
+        let mut json = serde_json::json!({
+            "status": "error",
+            "code": self.code(),
+            "message": message,
+            "error": message,
+            "exit_code": self.exit_code(),
+        });
+        if let Some(issues) = self.validation_issues() {
+            json["issues"] = serde_json::json!(issues);
+        }
+        json
+    }
+
     /// Log this error appropriately without exposing sensitive information
     pub fn log_securely(&self) {
         match self {
             ConfigError::PermissionDenied(path) => {
-                eprintln!("DEBUG: Permission denied for path (length: {} chars)", path.as_os_str().len());
+                tracing::debug!("Permission denied for path (length: {} chars)", path.as_os_str().len());
             }
             ConfigError::DirectoryCreationFailed(path, err) => {
-                eprintln!("DEBUG: Directory creation failed - OS error: {:?}, path length: {}",
+                tracing::debug!("Directory creation failed - OS error: {:?}, path length: {}",
                     err.kind(), path.as_os_str().len());
             }
             ConfigError::IoError(err) => {
-                eprintln!("DEBUG: IO error - kind: {:?}, OS error: {:?}",
+                tracing::debug!("IO error - kind: {:?}, OS error: {:?}",
                     err.kind(), err.raw_os_error());
             }
             ConfigError::JsonError(err) => {
-                eprintln!("DEBUG: JSON parsing error at line {}, column {}",
+                tracing::debug!("JSON parsing error at line {}, column {}",
                     err.line(), err.column());
             }
             ConfigError::ContextualError { operation, cause, .. } => {
-                eprintln!("DEBUG: Error in operation '{}', underlying cause:", operation);
+                tracing::debug!("Error in operation '{}', underlying cause:", operation);
                 cause.log_securely();
             }
             _ => {
-                eprintln!("DEBUG: Error occurred: {}", std::any::type_name::<Self>());
+                tracing::debug!("Error occurred: {}", std::any::type_name::<Self>());
             }
         }
     }
@@ -440,6 +823,50 @@ mod tests {
         assert_eq!(ConfigError::disk_space_error("/test").exit_code(), 28);
         assert_eq!(ConfigError::network_error("test").exit_code(), 69);
         assert_eq!(ConfigError::user_cancelled("test").exit_code(), 1);
+        assert_eq!(ConfigError::diagnostics_failed(2).exit_code(), 1);
+        assert_eq!(ConfigError::verification_failed(1).exit_code(), 1);
+        assert_eq!(ConfigError::batch_failed(1, 3).exit_code(), 1);
+
+        let multiple = ConfigError::multiple(vec![
+            ConfigError::permission_denied("/a"),
+            ConfigError::not_found("/b"),
+        ]);
+        assert_eq!(multiple.exit_code(), 13);
+        assert_eq!(multiple.code(), "multiple_errors");
+        let message = multiple.to_string();
+        assert!(message.starts_with("2 operations failed:"), "{}", message);
+        assert!(message.contains("\n\n2. "), "{}", message);
+        assert_eq!(ConfigError::multiple(vec![ConfigError::not_found("/b")]).code(), "not_found");
+        assert_eq!(ConfigError::path_too_long("/test", "too long").exit_code(), 36);
+        assert_eq!(ConfigError::migration_required(1, 2, "test").exit_code(), 65);
+        assert_eq!(ConfigError::toml_error("test").exit_code(), 65);
+    }
+
+    #[test]
+    fn test_exit_code_table() {
+        let table = ConfigError::exit_code_table();
+        assert_eq!(table.first(), Some(&("io_error", 74)));
+        assert!(table.contains(&("permission_denied", 13)));
+        assert!(table.contains(&("migration_required", 65)));
+
+        let mut codes: Vec<_> = table.iter().map(|(code, _)| *code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), table.len());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let error = ConfigError::invalid_agent("gpt")
+            .add_context("agent selection", "Determining which AI agent to configure");
+        let json = error.to_json();
+
+        assert_eq!(json["status"], "error");
+        assert_eq!(json["code"], "invalid_agent");
+        assert_eq!(json["exit_code"], 22);
+        assert_eq!(json["error"], json["message"]);
+        assert!(json["message"].as_str().unwrap().contains("Invalid agent 'gpt'"));
+        assert!(json.get("issues").is_none());
     }
 
     #[test]
@@ -0,0 +1,126 @@
+//! Backups of template files taken before they are overwritten.
+//!
+//! A backup is a byte-for-byte copy named `<name>.specforge-backup-<timestamp>`
+//! next to the original. Backups are never overwritten: when a backup with the
+//! same timestamp exists, a counter is appended. Specforge does not remove
+//! backups by itself; `specforge restore-backups` puts the newest one back.
+
+use crate::error::{ConfigError, Result};
+use crate::format;
+use chrono::Utc;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Text between the file name and the timestamp of a backup
+pub const BACKUP_MARKER: &str = ".specforge-backup-";
+
+/// Ignore pattern matching every template backup
+pub const BACKUP_FILE_PATTERN: &str = "*.specforge-backup-*";
+
+/// Copy a file to a new backup next to it, returning the backup path
+pub fn create(path: &Path) -> Result<PathBuf> {
+    let content = fs::read(path).map_err(|e| io_error(path, e))?;
+    let mut base = path.as_os_str().to_owned();
+    base.push(BACKUP_MARKER);
+    base.push(Utc::now().format("%Y%m%dT%H%M%SZ").to_string());
+
+    for attempt in 0.. {
+        let mut backup = base.clone();
+        if attempt > 0 {
+            backup.push(format!("-{}", attempt));
+        }
+        let backup = PathBuf::from(backup);
+        // create_new keeps an earlier backup from ever being replaced
+        match OpenOptions::new().write(true).create_new(true).open(&backup) {
+            Ok(mut file) => {
+                file.write_all(&content).map_err(|e| io_error(&backup, e))?;
+                tracing::debug!(path = %backup.display(), "backed up file");
+                return Ok(backup);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(io_error(&backup, e)),
+        }
+    }
+    unreachable!("backup attempts are unbounded")
+}
+
+/// Backups of a file, oldest first
+pub fn find(path: &Path) -> Result<Vec<PathBuf>> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Ok(Vec::new());
+    };
+    let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+    let prefix = format!("{}{}", name.to_string_lossy(), BACKUP_MARKER);
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(dir, e)),
+    };
+    let mut backups: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|file_name| file_name.starts_with(&prefix))
+        .collect();
+    // Counters are compared as numbers, so `-10` sorts after `-9`
+    backups.sort_by(|a, b| format::natural_cmp(a, b));
+    Ok(backups.into_iter().map(|file_name| dir.join(file_name)).collect())
+}
+
+/// Error for a file that could not be backed up or restored
+fn io_error(path: &Path, error: std::io::Error) -> ConfigError {
+    match error.kind() {
+        ErrorKind::PermissionDenied => ConfigError::permission_denied(path),
+        _ => ConfigError::io_error(format!("Failed to back up '{}': {}", path.display(), error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_copies_bytes_and_never_overwrites() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("CLAUDE.md");
+        let original = b"# Notes\r\n\xff\xfe binary tail".to_vec();
+        fs::write(&path, &original).unwrap();
+
+        let first = create(&path).unwrap();
+        fs::write(&path, "second version").unwrap();
+        let second = create(&path).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(fs::read(&first).unwrap(), original);
+        assert_eq!(fs::read(&second).unwrap(), b"second version");
+        assert!(first.to_string_lossy().contains(".specforge-backup-"));
+        assert_eq!(find(&path).unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_find_orders_counters_numerically() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("README.md");
+        for suffix in ["20260101T000000Z-10", "20260101T000000Z", "20260101T000000Z-9"] {
+            fs::write(temp_dir.path().join(format!("README.md{}{}", BACKUP_MARKER, suffix)), "").unwrap();
+        }
+        fs::write(temp_dir.path().join("CLAUDE.md.specforge-backup-20260101T000000Z"), "").unwrap();
+
+        let names: Vec<String> = find(&path)
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "README.md.specforge-backup-20260101T000000Z",
+                "README.md.specforge-backup-20260101T000000Z-9",
+                "README.md.specforge-backup-20260101T000000Z-10",
+            ]
+        );
+        assert!(find(&temp_dir.path().join("missing").join("x.md")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! The block of a project's `.gitattributes` that specforge manages.
+//!
+//! The block sits between two marker comments. Writing it again replaces the
+//! lines between the markers and leaves everything around them alone, so
+//! running the same update twice leaves the file unchanged. A file using
+//! CRLF line endings keeps using them. Removing the block deletes the file
+//! when nothing else is left in it.
+
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{LOCK_FILE_NAME, ProjectPaths};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Name of the Git attributes file in a project directory
+pub const GITATTRIBUTES_FILE_NAME: &str = ".gitattributes";
+
+/// Name of the merge driver the managed block assigns to specforge files
+pub const MERGE_DRIVER_NAME: &str = "specforge-json";
+
+const BEGIN_MARKER: &str = "# BEGIN specforge (managed by `specforge install-hooks --attributes`)";
+const END_MARKER: &str = "# END specforge";
+
+/// Attribute lines for the configuration and lock file: LF line endings on
+/// every platform and the structural merge driver
+pub fn managed_lines() -> Vec<String> {
+    [ProjectPaths::config_file_name(), LOCK_FILE_NAME]
+        .iter()
+        .map(|file| format!("{} text eol=lf merge={}", file, MERGE_DRIVER_NAME))
+        .collect()
+}
+
+/// Write the managed block with `lines` to the attributes file at `path`,
+/// creating the file when it does not exist
+///
+/// Returns whether the file changed.
+pub fn write_managed_block<S: AsRef<str>>(path: &Path, lines: &[S]) -> Result<bool> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(io_error(path, e)),
+    };
+
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut block = String::new();
+    for line in std::iter::once(BEGIN_MARKER)
+        .chain(lines.iter().map(AsRef::as_ref))
+        .chain(std::iter::once(END_MARKER))
+    {
+        block.push_str(line);
+        block.push_str(newline);
+    }
+
+    let updated = match block_range(&content) {
+        Some((start, end)) => format!("{}{}{}", &content[..start], block, &content[end..]),
+        None => {
+            let mut updated = content.clone();
+            if !updated.is_empty() && !updated.ends_with('\n') {
+                updated.push_str(newline);
+            }
+            updated.push_str(&block);
+            updated
+        }
+    };
+    if updated == content {
+        return Ok(false);
+    }
+    fs::write(path, updated).map_err(|e| io_error(path, e))?;
+    Ok(true)
+}
+
+/// Whether the attributes file at `path` holds the managed block
+pub fn has_managed_block(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| block_range(&content).is_some())
+}
+
+/// Remove the managed block from the attributes file at `path`
+///
+/// Returns whether the file changed.
+pub fn remove_managed_block(path: &Path) -> Result<bool> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(io_error(path, e)),
+    };
+    let Some((start, end)) = block_range(&content) else {
+        return Ok(false);
+    };
+
+    let updated = format!("{}{}", &content[..start], &content[end..]);
+    if updated.trim().is_empty() {
+        fs::remove_file(path).map_err(|e| io_error(path, e))?;
+    } else {
+        fs::write(path, updated).map_err(|e| io_error(path, e))?;
+    }
+    Ok(true)
+}
+
+/// Byte range of the managed block, from the start of its first marker to
+/// the end of the line holding its last one
+fn block_range(content: &str) -> Option<(usize, usize)> {
+    let start = content.find(BEGIN_MARKER)?;
+    let end_marker = start + content[start..].find(END_MARKER)?;
+    let end = content[end_marker..]
+        .find('\n')
+        .map_or(content.len(), |offset| end_marker + offset + 1);
+    Some((start, end))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> ConfigError {
+    match error.kind() {
+        ErrorKind::PermissionDenied => ConfigError::permission_denied(path),
+        _ => ConfigError::io_error(format!("Failed to update '{}': {}", path.display(), error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_managed_block_replaces_previous_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITATTRIBUTES_FILE_NAME);
+        fs::write(&path, "*.png binary\r\n").unwrap();
+
+        assert!(write_managed_block(&path, &["old.json text"]).unwrap());
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "*.md text\r\n").unwrap();
+        assert!(write_managed_block(&path, &managed_lines()).unwrap());
+        assert!(!write_managed_block(&path, &managed_lines()).unwrap());
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!(
+                "*.png binary\r\n{}\r\n.specforge.json text eol=lf merge=specforge-json\r\n\
+                 specforge.lock text eol=lf merge=specforge-json\r\n{}\r\n*.md text\r\n",
+                BEGIN_MARKER, END_MARKER
+            )
+        );
+    }
+
+    #[test]
+    fn test_remove_managed_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITATTRIBUTES_FILE_NAME);
+        fs::write(&path, "*.png binary\n").unwrap();
+        write_managed_block(&path, &managed_lines()).unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "*.md text\n").unwrap();
+
+        assert!(has_managed_block(&path));
+        assert!(remove_managed_block(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "*.png binary\n*.md text\n");
+        assert!(!remove_managed_block(&path).unwrap());
+
+        fs::write(&path, "").unwrap();
+        write_managed_block(&path, &managed_lines()).unwrap();
+        assert!(remove_managed_block(&path).unwrap());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,190 @@
+//! Idempotent updates of a project's `.gitignore`.
+//!
+//! Entries are appended only when no existing line matches them, so running
+//! the same update twice leaves the file unchanged. A file without a trailing
+//! newline gets one before the new entries, and a file using CRLF line endings
+//! keeps using them. Removing entries keeps every other line as it is.
+
+use crate::error::{ConfigError, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Name of the Git ignore file in a project directory
+pub const GITIGNORE_FILE_NAME: &str = ".gitignore";
+
+/// Pattern matching the backups written next to a JSON configuration file
+pub const BACKUP_PATTERN: &str = "*.json.backup*";
+
+/// Append the entries missing from the ignore file at `path`, creating the
+/// file when it does not exist
+///
+/// Returns the entries that were added; an empty list means the file was
+/// not touched.
+pub fn append_entries<S: AsRef<str>>(path: &Path, entries: &[S]) -> Result<Vec<String>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(io_error(path, e)),
+    };
+
+    let mut added: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.as_ref().trim();
+        if entry.is_empty() || contains_entry(&content, entry) || added.iter().any(|a| a == entry) {
+            continue;
+        }
+        added.push(entry.to_string());
+    }
+    if added.is_empty() {
+        return Ok(added);
+    }
+
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut updated = content;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    for entry in &added {
+        updated.push_str(entry);
+        updated.push_str(newline);
+    }
+    fs::write(path, updated).map_err(|e| io_error(path, e))?;
+    Ok(added)
+}
+
+/// The entries that have a line of their own in the ignore file at `path`
+pub fn present_entries<S: AsRef<str>>(path: &Path, entries: &[S]) -> Result<Vec<String>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(path, e)),
+    };
+    let mut present: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.as_ref().trim();
+        if !entry.is_empty() && contains_entry(&content, entry) && !present.iter().any(|p| p == entry) {
+            present.push(entry.to_string());
+        }
+    }
+    Ok(present)
+}
+
+/// Remove the lines matching `entries` from the ignore file at `path`,
+/// deleting the file when no other lines are left
+///
+/// Returns the entries that were removed; an empty list means the file was
+/// not touched.
+pub fn remove_entries<S: AsRef<str>>(path: &Path, entries: &[S]) -> Result<Vec<String>> {
+    let removed = present_entries(path, entries)?;
+    if removed.is_empty() {
+        return Ok(removed);
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+    let updated: String = content
+        .split_inclusive('\n')
+        .filter(|line| !removed.iter().any(|entry| line.trim() == entry))
+        .collect();
+    if updated.trim().is_empty() {
+        fs::remove_file(path).map_err(|e| io_error(path, e))?;
+    } else {
+        fs::write(path, updated).map_err(|e| io_error(path, e))?;
+    }
+    Ok(removed)
+}
+
+/// Whether a line of the ignore file already matches `entry`, ignoring
+/// surrounding whitespace and line endings
+fn contains_entry(content: &str, entry: &str) -> bool {
+    content.lines().any(|line| line.trim() == entry)
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> ConfigError {
+    match error.kind() {
+        ErrorKind::PermissionDenied => ConfigError::permission_denied(path),
+        _ => ConfigError::io_error(format!("Failed to update '{}': {}", path.display(), error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_creates_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITIGNORE_FILE_NAME);
+
+        let added = append_entries(&path, &[BACKUP_PATTERN, ".specforge.json"]).unwrap();
+        assert_eq!(added, [BACKUP_PATTERN, ".specforge.json"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "*.json.backup*\n.specforge.json\n");
+
+        assert!(append_entries(&path, &[BACKUP_PATTERN]).unwrap().is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "*.json.backup*\n.specforge.json\n");
+    }
+
+    #[test]
+    fn test_append_skips_existing_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITIGNORE_FILE_NAME);
+
+        fs::write(&path, "target/\n  *.json.backup*  \n").unwrap();
+        assert!(append_entries(&path, &[BACKUP_PATTERN, BACKUP_PATTERN]).unwrap().is_empty());
+
+        // No trailing newline: the entry goes on a line of its own
+        fs::write(&path, "target/").unwrap();
+        append_entries(&path, &[BACKUP_PATTERN, BACKUP_PATTERN]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "target/\n*.json.backup*\n");
+    }
+
+    #[test]
+    fn test_append_preserves_crlf() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITIGNORE_FILE_NAME);
+
+        fs::write(&path, "target/\r\nnode_modules/").unwrap();
+        append_entries(&path, &[BACKUP_PATTERN]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "target/\r\nnode_modules/\r\n*.json.backup*\r\n");
+        assert!(append_entries(&path, &[BACKUP_PATTERN]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_entries_keeps_other_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITIGNORE_FILE_NAME);
+
+        fs::write(&path, "target/\r\n*.json.backup*\r\nnode_modules/").unwrap();
+        assert_eq!(remove_entries(&path, &[BACKUP_PATTERN, ".specforge.json"]).unwrap(), [BACKUP_PATTERN]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "target/\r\nnode_modules/");
+        assert!(remove_entries(&path, &[BACKUP_PATTERN]).unwrap().is_empty());
+
+        // A file holding only specforge entries is removed with them
+        fs::write(&path, "*.json.backup*\n.specforge.json\n").unwrap();
+        remove_entries(&path, &[BACKUP_PATTERN, ".specforge.json"]).unwrap();
+        assert!(!path.exists());
+        assert!(remove_entries(&path, &[BACKUP_PATTERN]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_append_to_read_only_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(GITIGNORE_FILE_NAME);
+        fs::write(&path, "target/\n").unwrap();
+        let mut permissions = fs::metadata(&path).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&path, permissions).unwrap();
+
+        // Privileged users can write read-only files, so there is nothing to check
+        if fs::OpenOptions::new().append(true).open(&path).is_ok() {
+            return;
+        }
+        match append_entries(&path, &[BACKUP_PATTERN]) {
+            Err(ConfigError::PermissionDenied(denied)) => assert_eq!(denied, path),
+            other => panic!("expected PermissionDenied, got {:?}", other),
+        }
+        // A read-only file that already has the entries is not written at all
+        assert!(append_entries(&path, &["target/"]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Undoing a set of file writes that failed partway.
+//!
+//! A [`WriteJournal`] records the content of every file an operation may
+//! write before the first write happens. Dropping the journal without
+//! committing it, as an early `?` return does, puts every recorded file back
+//! as it was: files that did not exist are removed, overwritten files get
+//! their original content back, and directories created for them are removed
+//! again when empty.
+
+use crate::error::{ConfigError, Result};
+use crate::status;
+use crate::ui::Status;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Original state of the files an operation writes, restored unless the
+/// operation commits
+#[derive(Debug, Default)]
+#[must_use = "dropping the journal immediately rolls back nothing it records later"]
+pub struct WriteJournal {
+    /// Recorded files with their original content, `None` when they did not exist
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    /// Files the operation created on top of the recorded ones, such as backups
+    created: Vec<PathBuf>,
+    /// Directories that did not exist when a file in them was recorded
+    directories: Vec<PathBuf>,
+    committed: bool,
+}
+
+impl WriteJournal {
+    /// Create an empty journal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current content of a file before it may be written
+    ///
+    /// Recording a file twice keeps its first state.
+    pub fn record(&mut self, path: &Path) -> Result<()> {
+        if self.files.iter().any(|(recorded, _)| recorded == path) {
+            return Ok(());
+        }
+        let original = match fs::read(path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            // A directory in the way, or a file in place of a parent directory,
+            // makes the write fail; there is nothing to restore
+            Err(e) if e.kind() == ErrorKind::NotADirectory || path.is_dir() => return Ok(()),
+            Err(e) => {
+                return Err(ConfigError::io_error(format!("Failed to read '{}': {}", path.display(), e)));
+            }
+        };
+
+        for directory in path.ancestors().skip(1) {
+            if directory.as_os_str().is_empty() || directory.exists() {
+                break;
+            }
+            if !self.directories.iter().any(|recorded| recorded == directory) {
+                self.directories.push(directory.to_path_buf());
+            }
+        }
+        self.files.push((path.to_path_buf(), original));
+        Ok(())
+    }
+
+    /// Record a file the operation created that was not recorded beforehand,
+    /// such as a backup, so a rollback removes it
+    pub fn track_created(&mut self, path: &Path) {
+        self.created.push(path.to_path_buf());
+    }
+
+    /// Keep every change; the journal no longer rolls anything back
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    /// Put every recorded file back as it was, returning how many changed
+    ///
+    /// Files that cannot be restored are reported and skipped, so as much as
+    /// possible is undone.
+    fn rollback(&mut self) -> usize {
+        let mut restored = 0;
+        for path in self.created.drain(..).rev() {
+            if fs::remove_file(&path).is_ok() {
+                restored += 1;
+            }
+        }
+        for (path, original) in self.files.drain(..).rev() {
+            let outcome = match (&original, fs::read(&path)) {
+                (Some(original), Ok(current)) if *original == current => continue,
+                (None, Err(e)) if e.kind() == ErrorKind::NotFound => continue,
+                (None, _) if path.is_dir() => continue,
+                (Some(original), _) => fs::write(&path, original),
+                (None, _) => fs::remove_file(&path),
+            };
+            match outcome {
+                Ok(()) => restored += 1,
+                Err(e) => status!("{} Could not restore {}: {}", Status::Warn, path.display(), e),
+            }
+        }
+        // Deepest first, and only the ones left empty
+        self.directories.sort_by_key(|directory| std::cmp::Reverse(directory.components().count()));
+        for directory in self.directories.drain(..) {
+            let _ = fs::remove_dir(&directory);
+        }
+        restored
+    }
+}
+
+impl Drop for WriteJournal {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        let restored = self.rollback();
+        if restored > 0 {
+            status!("↩️  Undid the changes to {} file(s)", restored);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_drop_restores_recorded_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("existing.md");
+        let created = temp_dir.path().join("docs/nested/created.md");
+        let backup = temp_dir.path().join("existing.md.bak");
+        fs::write(&existing, "original").unwrap();
+
+        {
+            let mut journal = WriteJournal::new();
+            journal.record(&existing).unwrap();
+            journal.record(&created).unwrap();
+            fs::write(&existing, "changed").unwrap();
+            fs::create_dir_all(created.parent().unwrap()).unwrap();
+            fs::write(&created, "new").unwrap();
+            fs::write(&backup, "original").unwrap();
+            journal.track_created(&backup);
+        }
+
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "original");
+        assert!(!backup.exists());
+        assert!(!temp_dir.path().join("docs").exists());
+    }
+
+    #[test]
+    fn test_commit_keeps_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("created.md");
+
+        let mut journal = WriteJournal::new();
+        journal.record(&path).unwrap();
+        fs::write(&path, "new").unwrap();
+        journal.commit();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    }
+}
@@ -0,0 +1,114 @@
+//! Names and locations of the files Specforge keeps in a project.
+//!
+//! Library code should ask [`ProjectPaths`] for file names instead of using
+//! string constants, so a rename only has to happen here.
+
+use std::path::{Path, PathBuf};
+
+/// Where a project keeps its configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigLayout {
+    /// A single `.specforge.json` (or `.specforge.toml`) in the project directory
+    #[default]
+    File,
+    /// `.specforge/config.json`, with custom templates in `.specforge/templates`
+    Directory,
+}
+
+/// Files and directories Specforge keeps in a project directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectPaths {
+    root: PathBuf,
+}
+
+impl ProjectPaths {
+    /// Paths for the project in `root`
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Name of the JSON configuration file, `.specforge.json`
+    pub const fn config_file_name() -> &'static str {
+        ".specforge.json"
+    }
+
+    /// Name of the configuration file written by reforge, the former name of
+    /// specforge, `.reforge.json`
+    pub const fn legacy_config_file_name() -> &'static str {
+        ".reforge.json"
+    }
+
+    /// Name of the directory holding state Specforge keeps for a project, `.specforge`
+    pub const fn state_dir_name() -> &'static str {
+        ".specforge"
+    }
+
+    /// Name of the configuration file inside the state directory, `config.json`
+    pub const fn directory_config_file_name() -> &'static str {
+        "config.json"
+    }
+
+    /// Name of the custom template directory inside the state directory, `templates`
+    pub const fn templates_dir_name() -> &'static str {
+        "templates"
+    }
+
+    /// Project directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the JSON configuration file
+    pub fn config_file(&self) -> PathBuf {
+        self.root.join(Self::config_file_name())
+    }
+
+    /// Path of a reforge configuration file
+    pub fn legacy_config_file(&self) -> PathBuf {
+        self.root.join(Self::legacy_config_file_name())
+    }
+
+    /// Path of the state directory
+    pub fn state_dir(&self) -> PathBuf {
+        self.root.join(Self::state_dir_name())
+    }
+
+    /// Path of the configuration file in the directory layout, `.specforge/config.json`
+    pub fn directory_config_file(&self) -> PathBuf {
+        self.state_dir().join(Self::directory_config_file_name())
+    }
+
+    /// Path of the custom template directory, `.specforge/templates`
+    pub fn templates_dir(&self) -> PathBuf {
+        self.state_dir().join(Self::templates_dir_name())
+    }
+
+    /// Path of the JSON configuration file in the given layout
+    pub fn config_file_in(&self, layout: ConfigLayout) -> PathBuf {
+        match layout {
+            ConfigLayout::File => self.config_file(),
+            ConfigLayout::Directory => self.directory_config_file(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_paths() {
+        let paths = ProjectPaths::new("project");
+        assert_eq!(paths.root(), Path::new("project"));
+        assert_eq!(paths.config_file(), Path::new("project").join(".specforge.json"));
+        assert_eq!(paths.legacy_config_file(), Path::new("project").join(".reforge.json"));
+        assert_eq!(paths.state_dir(), Path::new("project").join(".specforge"));
+        assert_eq!(
+            paths.directory_config_file(),
+            Path::new("project").join(".specforge").join("config.json")
+        );
+        assert_eq!(paths.templates_dir(), Path::new("project").join(".specforge").join("templates"));
+        assert_eq!(paths.config_file_in(ConfigLayout::File), paths.config_file());
+        assert_eq!(paths.config_file_in(ConfigLayout::Directory), paths.directory_config_file());
+    }
+}
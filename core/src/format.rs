@@ -0,0 +1,234 @@
+//! Formatting of listings for people reading them.
+//!
+//! Names are sorted in natural order (case-insensitive, with digit runs
+//! compared as numbers), sizes are shown in KiB or MiB and counts get
+//! thousands separators. Long text is wrapped to the column it is shown in. With raw output selected (`--raw`) all of it is
+//! turned off: names are sorted by their bytes and numbers are printed as
+//! plain integers, so scripts get stable output.
+
+use crate::output;
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Compare two names in natural order
+///
+/// Letters compare case-insensitively and runs of ASCII digits compare by
+/// their numeric value, so `file2` sorts before `file10` and `alpha` before
+/// `Zeta`. Names that only differ in case or leading zeros fall back to
+/// byte order, so the ordering is total.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut left, mut right) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let ordering = match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                compare_numbers(&digit_run(&mut left), &digit_run(&mut right))
+            }
+            (Some(l), Some(r)) => {
+                left.next();
+                right.next();
+                l.to_lowercase().cmp(r.to_lowercase())
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Compare two names the way listings are sorted: naturally, or by their
+/// bytes in raw output mode
+pub fn listing_cmp(a: &str, b: &str) -> Ordering {
+    if output::is_raw() { a.cmp(b) } else { natural_cmp(a, b) }
+}
+
+/// Take the run of ASCII digits at the front of `chars`
+fn digit_run(chars: &mut Peekable<Chars>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        run.push(c);
+    }
+    run
+}
+
+/// Compare digit runs by value without parsing, so long runs cannot overflow
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Size in B, KiB or MiB with one decimal, e.g. `1.5 KiB`
+pub fn human_size(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * KIB;
+    match bytes {
+        b if b < KIB => format!("{} B", b),
+        b if b < MIB => format!("{:.1} KiB", b as f64 / KIB as f64),
+        b => format!("{:.1} MiB", b as f64 / MIB as f64),
+    }
+}
+
+/// Integer with a comma between groups of three digits, e.g. `12,345`
+pub fn thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Size for a listing: human-readable, or a byte count in raw output mode
+pub fn size(bytes: u64) -> String {
+    if output::is_raw() { bytes.to_string() } else { human_size(bytes) }
+}
+
+/// Count for a listing: with thousands separators, or plain in raw output mode
+pub fn count(n: u64) -> String {
+    if output::is_raw() { n.to_string() } else { thousands(n) }
+}
+
+/// Width of `text` in terminal columns; wide characters such as CJK take two
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// `text` followed by spaces up to `width` columns
+pub fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(display_width(text))))
+}
+
+/// Break `text` into lines of at most `width` columns
+///
+/// Lines break between words; words wider than a line are split between
+/// characters. A character wider than the line still gets a line of its own.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in text.split_whitespace() {
+        let word_width = display_width(word);
+        if line_width > 0 && line_width + 1 + word_width <= width {
+            line.push(' ');
+            line.push_str(word);
+            line_width += 1 + word_width;
+            continue;
+        }
+        if line_width > 0 {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if word_width <= width {
+            line.push_str(word);
+            line_width = word_width;
+            continue;
+        }
+        for c in word.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if line_width > 0 && line_width + char_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            line.push(c);
+            line_width += char_width;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        names.sort_by(|a, b| natural_cmp(a, b));
+        names
+    }
+
+    #[test]
+    fn test_natural_cmp_ignores_case() {
+        assert_eq!(
+            sorted(&["Zeta-templates", "alpha-templates", "Beta", "beta"]),
+            ["alpha-templates", "Beta", "beta", "Zeta-templates"]
+        );
+        assert_eq!(natural_cmp("README.md", "readme.md"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_natural_cmp_numeric_runs() {
+        assert_eq!(
+            sorted(&["file10", "file2", "file1", "file02", "file", "file1a", "file1b2", "file1b10"]),
+            ["file", "file1", "file1a", "file1b2", "file1b10", "file02", "file2", "file10"]
+        );
+        assert_eq!(natural_cmp("v1.10.0", "v1.9.3"), Ordering::Greater);
+        assert_eq!(natural_cmp("a99999999999999999999999", "a100000000000000000000000"), Ordering::Less);
+        assert_eq!(natural_cmp("x7", "x07"), Ordering::Greater);
+        assert_eq!(natural_cmp("x07", "x07"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_natural_cmp_unicode() {
+        assert_eq!(sorted(&["Éclair", "éclair", "zebra", "Ärger"]), ["zebra", "Ärger", "Éclair", "éclair"]);
+        assert_eq!(natural_cmp("ÄRGER", "ärger"), Ordering::Less);
+        assert_eq!(sorted(&["日本2", "日本10", "日本1"]), ["日本1", "日本2", "日本10"]);
+        // Non-ASCII digits are not treated as numbers
+        assert_eq!(natural_cmp("٣", "١٠"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0 B");
+        assert_eq!(human_size(1023), "1023 B");
+        assert_eq!(human_size(1024), "1.0 KiB");
+        assert_eq!(human_size(1536), "1.5 KiB");
+        assert_eq!(human_size(10 * 1024 * 1024), "10.0 MiB");
+        assert_eq!(human_size(u64::MAX), format!("{:.1} MiB", u64::MAX as f64 / 1048576.0));
+    }
+
+    #[test]
+    fn test_thousands() {
+        assert_eq!(thousands(0), "0");
+        assert_eq!(thousands(999), "999");
+        assert_eq!(thousands(1000), "1,000");
+        assert_eq!(thousands(1234567), "1,234,567");
+        assert_eq!(thousands(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn test_wrap_between_words() {
+        assert_eq!(wrap("Prompts for the compliance review", 15), ["Prompts for the", "compliance", "review"]);
+        assert_eq!(wrap("  spaced   out  ", 20), ["spaced out"]);
+        assert_eq!(wrap("", 10), [""]);
+    }
+
+    #[test]
+    fn test_wrap_long_unbroken_words() {
+        assert_eq!(wrap("https://example.com/prompts", 10), ["https://ex", "ample.com/", "prompts"]);
+        assert_eq!(wrap("see aaaaaaaaaaaa", 5), ["see", "aaaaa", "aaaaa", "aa"]);
+    }
+
+    #[test]
+    fn test_wrap_unicode_widths() {
+        // CJK characters take two columns, combining marks none
+        assert_eq!(display_width("漢字"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(wrap("漢字漢字漢", 4), ["漢字", "漢字", "漢"]);
+        assert_eq!(wrap("漢字漢", 3), ["漢", "字", "漢"]);
+        assert_eq!(wrap("ünïcödé wörds", 7), ["ünïcödé", "wörds"]);
+        // Wider than the line on its own
+        assert_eq!(wrap("漢", 1), ["漢"]);
+        assert_eq!(pad("漢", 4), "漢  ");
+        assert_eq!(pad("toolong", 3), "toolong");
+    }
+}
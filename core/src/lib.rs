@@ -0,0 +1,18 @@
+pub mod config;
+pub mod drift;
+pub mod error;
+pub mod file_ops;
+pub mod format;
+pub mod output;
+pub mod progress;
+pub mod prompt;
+#[cfg(feature = "remote")]
+pub mod registry;
+pub mod templates;
+pub mod ui;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+
+pub use config::{Agent, ProjectConfig};
+pub use error::{ConfigError, Result};
+pub use file_ops::FileOps;
@@ -0,0 +1,413 @@
+//! Output mode shared by all commands.
+//!
+//! In JSON mode stdout is reserved for the machine-readable result, so
+//! human-readable status lines printed with [`status!`](crate::status) go to
+//! stderr instead. Commands whose stdout is consumed by another program, such
+//! as `env --shell`, reserve stdout the same way.
+//!
+//! Status lines are silenced at [`Verbosity::Quiet`]. Diagnostics below them,
+//! such as the files being read and written, are `tracing` events that the
+//! CLI shows on stderr at higher verbosity levels.
+//!
+//! Programs that embed specforge can receive status lines themselves by
+//! running commands inside [`with_reporter`].
+//!
+//! The [`OutputStyle`] decides whether output is colored and whether status
+//! lines keep their emoji prefix.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// How command results are written to stdout
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A single JSON object
+    Json,
+}
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+static RAW_OUTPUT: AtomicBool = AtomicBool::new(false);
+static EMOJI_OUTPUT: AtomicBool = AtomicBool::new(true);
+static COLOR_OUTPUT: AtomicBool = AtomicBool::new(true);
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// How much is printed besides command results and errors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only command results and errors (`-q`)
+    Quiet,
+    /// Status lines
+    #[default]
+    Normal,
+    /// Status lines and informational diagnostics (`-v`)
+    Verbose,
+    /// Everything, including debug diagnostics for file operations (`-vv`)
+    Debug,
+}
+
+impl Verbosity {
+    /// Verbosity selected by the number of `-v` flags and `-q`
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+}
+
+/// Select the output mode for the rest of the process
+pub fn set_output_mode(mode: OutputMode) {
+    JSON_OUTPUT.store(mode == OutputMode::Json, Ordering::Relaxed);
+}
+
+/// Currently selected output mode
+pub fn output_mode() -> OutputMode {
+    if JSON_OUTPUT.load(Ordering::Relaxed) {
+        OutputMode::Json
+    } else {
+        OutputMode::Text
+    }
+}
+
+/// Whether results are written as JSON
+pub fn is_json() -> bool {
+    output_mode() == OutputMode::Json
+}
+
+/// Wrap a command result in the object every command prints in JSON mode
+///
+/// The envelope has a `status`, `"success"` unless the result sets its own
+/// such as `"valid"`, next to the fields of the result. Results that are not
+/// objects go under `result`. Errors are printed in the same shape with
+/// `"status": "error"`, see [`ConfigError::to_json`](crate::error::ConfigError::to_json).
+pub fn json_envelope(result: serde_json::Value) -> serde_json::Value {
+    let mut envelope = match result {
+        serde_json::Value::Object(fields) => fields,
+        other => serde_json::Map::from_iter([("result".to_string(), other)]),
+    };
+    envelope.entry("status").or_insert_with(|| "success".into());
+    serde_json::Value::Object(envelope)
+}
+
+/// Print a command result on stdout in its [`json_envelope`]
+pub fn print_json(result: serde_json::Value) {
+    println!("{}", json_envelope(result));
+}
+
+/// Send status lines to stderr for the rest of the process
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+/// Whether stdout only receives the command result
+pub fn stdout_reserved() -> bool {
+    is_json() || STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// Select the verbosity for the rest of the process
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Currently selected verbosity
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Debug,
+    }
+}
+
+/// Whether status lines are silenced
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// Turn off natural sorting and number formatting in listings for the rest
+/// of the process (see [`format`](crate::format))
+pub fn set_raw(raw: bool) {
+    RAW_OUTPUT.store(raw, Ordering::Relaxed);
+}
+
+/// Whether listings are printed without formatting, for scripts
+pub fn is_raw() -> bool {
+    RAW_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Environment variable that turns off colors when set to a non-empty value,
+/// see <https://no-color.org>
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// How output is decorated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutputStyle {
+    /// Whether output may be colored; terminals that don't support colors
+    /// never get them
+    pub color: bool,
+    /// Whether status lines keep their emoji prefix
+    pub emoji: bool,
+}
+
+impl Default for OutputStyle {
+    fn default() -> Self {
+        Self { color: true, emoji: true }
+    }
+}
+
+impl OutputStyle {
+    /// Style selected by `--no-color`, `--no-emoji` and `NO_COLOR`
+    pub fn from_flags(no_color: bool, no_emoji: bool) -> Self {
+        Self::from_flags_and_env(no_color, no_emoji, std::env::var_os(NO_COLOR_ENV_VAR))
+    }
+
+    fn from_flags_and_env(no_color: bool, no_emoji: bool, no_color_env: Option<std::ffi::OsString>) -> Self {
+        let no_color_env = no_color_env.is_some_and(|value| !value.is_empty());
+        Self {
+            color: !no_color && !no_color_env,
+            emoji: !no_emoji,
+        }
+    }
+}
+
+/// Select the output style for the rest of the process
+pub fn set_style(style: OutputStyle) {
+    if !style.color {
+        set_colors_enabled(false);
+    }
+    COLOR_OUTPUT.store(style.color, Ordering::Relaxed);
+    EMOJI_OUTPUT.store(style.emoji, Ordering::Relaxed);
+}
+
+/// Currently selected output style
+pub fn style() -> OutputStyle {
+    OutputStyle {
+        color: console::colors_enabled() || console::colors_enabled_stderr(),
+        emoji: EMOJI_OUTPUT.load(Ordering::Relaxed),
+    }
+}
+
+/// Drop the emoji in front of a status line, keeping its indentation
+fn strip_emoji(line: &str) -> String {
+    let text = line.trim_start_matches(' ');
+    let indent = &line[..line.len() - text.len()];
+    let rest = text.trim_start_matches(|c: char| !c.is_ascii() && !c.is_alphanumeric());
+    if rest.len() == text.len() {
+        return line.to_string();
+    }
+    format!("{}{}", indent, rest.trim_start_matches(' '))
+}
+
+/// Decorate a status line for a destination with the given style
+///
+/// Without colors or without emoji the status emoji become tags such as
+/// `[ok]` (see [`ui::plain_status`](crate::ui::plain_status)); without emoji
+/// any other emoji prefix is dropped as well.
+fn styled_status(line: String, style: OutputStyle) -> String {
+    let line = if style.color && style.emoji { line } else { crate::ui::plain_status(&line) };
+    if style.emoji { line } else { strip_emoji(&line) }
+}
+
+/// Enable or disable colored output on stdout and stderr
+pub fn set_colors_enabled(enabled: bool) {
+    console::set_colors_enabled(enabled);
+    console::set_colors_enabled_stderr(enabled);
+}
+
+/// Whether output on stderr may be colored
+pub fn colors_enabled_stderr() -> bool {
+    console::colors_enabled_stderr()
+}
+
+/// Receives status lines instead of the terminal
+///
+/// Reporters are shared with the threads that deploy files in parallel.
+pub trait Reporter: Send + Sync {
+    /// Handle one status line, without its trailing newline
+    fn status(&self, line: &str);
+}
+
+/// Reporter that drops every status line
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SilentReporter;
+
+impl Reporter for SilentReporter {
+    fn status(&self, _line: &str) {}
+}
+
+thread_local! {
+    static REPORTER: RefCell<Option<Arc<dyn Reporter>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with status lines on this thread going to `reporter`
+///
+/// The previous reporter is restored afterwards, also when `f` panics.
+pub fn with_reporter<T>(reporter: Arc<dyn Reporter>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Arc<dyn Reporter>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            REPORTER.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(REPORTER.with(|current| current.borrow_mut().replace(reporter)));
+    f()
+}
+
+/// Reporter installed on this thread by [`with_reporter`], if any
+pub fn reporter() -> Option<Arc<dyn Reporter>> {
+    REPORTER.with(|current| current.borrow().clone())
+}
+
+/// Write a status line to the reporter of this thread, or to stdout or
+/// stderr when there is none; used by [`status!`](crate::status)
+pub fn print_status(args: fmt::Arguments<'_>) {
+    if is_quiet() {
+        return;
+    }
+    // Reporters get the style that was selected; the terminal streams lose
+    // their colors, and so their status emoji, when they are not terminals
+    let line = args.to_string();
+    let emoji = EMOJI_OUTPUT.load(Ordering::Relaxed);
+    if let Some(reporter) = reporter() {
+        let color = COLOR_OUTPUT.load(Ordering::Relaxed);
+        reporter.status(&styled_status(line, OutputStyle { color, emoji }));
+    } else if stdout_reserved() {
+        let line = styled_status(line, OutputStyle { color: console::colors_enabled_stderr(), emoji });
+        crate::progress::suspend(|| eprintln!("{}", line));
+    } else {
+        let line = styled_status(line, OutputStyle { color: console::colors_enabled(), emoji });
+        crate::progress::suspend(|| println!("{}", line));
+    }
+}
+
+/// Print a human-readable status line to stdout, or to stderr when stdout is
+/// reserved; nothing is printed when quiet
+#[macro_export]
+macro_rules! status {
+    () => {
+        $crate::output::print_status(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::print_status(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::Status;
+
+    #[test]
+    fn test_verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(0, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(1, false), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(2, false), Verbosity::Debug);
+        assert_eq!(Verbosity::from_flags(5, false), Verbosity::Debug);
+        assert_eq!(Verbosity::from_flags(0, true), Verbosity::Quiet);
+        assert!(Verbosity::Quiet < Verbosity::Normal && Verbosity::Verbose < Verbosity::Debug);
+    }
+
+    #[test]
+    fn test_json_envelope() {
+        assert_eq!(
+            json_envelope(serde_json::json!({ "removed": [] })),
+            serde_json::json!({ "status": "success", "removed": [] })
+        );
+        assert_eq!(
+            json_envelope(serde_json::json!({ "status": "valid" })),
+            serde_json::json!({ "status": "valid" })
+        );
+        assert_eq!(
+            json_envelope(serde_json::json!(["a"])),
+            serde_json::json!({ "status": "success", "result": ["a"] })
+        );
+    }
+
+    #[test]
+    fn test_output_style_from_flags_and_env() {
+        let style = |no_color, no_emoji, env: Option<&str>| {
+            OutputStyle::from_flags_and_env(no_color, no_emoji, env.map(Into::into))
+        };
+        assert_eq!(style(false, false, None), OutputStyle::default());
+        assert_eq!(style(false, false, Some("1")), OutputStyle { color: false, emoji: true });
+        // An empty NO_COLOR counts as unset
+        assert_eq!(style(false, false, Some("")), OutputStyle::default());
+        assert_eq!(style(true, true, None), OutputStyle { color: false, emoji: false });
+    }
+
+    #[test]
+    fn test_strip_emoji() {
+        assert_eq!(strip_emoji("✅ Wrote CLAUDE.md"), "Wrote CLAUDE.md");
+        assert_eq!(strip_emoji("⚠️  Skipped"), "Skipped");
+        assert_eq!(strip_emoji("  📄 CLAUDE.md"), "  CLAUDE.md");
+        assert_eq!(strip_emoji("Écrit CLAUDE.md"), "Écrit CLAUDE.md");
+        assert_eq!(strip_emoji("- plain"), "- plain");
+        assert_eq!(strip_emoji(""), "");
+    }
+
+    #[test]
+    fn test_styled_status_without_colors_or_emoji() {
+        set_colors_enabled(false);
+        let plain = OutputStyle { color: false, emoji: false };
+        let lines = [
+            "✅ Successfully created Specforge configuration".to_string(),
+            format!("ℹ️  Selected agent: {}", console::style("claude").green()),
+            "⚠️  CLAUDE.md exists; skipped".to_string(),
+            "  📄 README.md".to_string(),
+            "❌ Operation cancelled by user".to_string(),
+        ];
+        let styled: Vec<String> = lines.into_iter().map(|line| styled_status(line, plain)).collect();
+
+        assert_eq!(
+            styled,
+            [
+                "[ok] Successfully created Specforge configuration",
+                "[info] Selected agent: claude",
+                "[warn] CLAUDE.md exists; skipped",
+                "  README.md",
+                "[error] Operation cancelled by user",
+            ]
+        );
+        assert!(styled.iter().all(|line| !line.contains('\x1b')));
+
+        // Without colors alone, only the status emoji are replaced
+        let no_color = OutputStyle { color: false, emoji: true };
+        assert_eq!(styled_status("  📄 README.md".to_string(), no_color), "  📄 README.md");
+        assert_eq!(styled_status("✅ Done".to_string(), OutputStyle::default()), "✅ Done");
+
+        // Without emoji alone, status lines are tagged as in plain output
+        let no_emoji = OutputStyle { color: true, emoji: false };
+        assert_eq!(styled_status("✅ Done".to_string(), no_emoji), "[ok] Done");
+        assert_eq!(styled_status("  📄 README.md".to_string(), no_emoji), "  README.md");
+    }
+
+    #[derive(Default)]
+    struct Recorder(std::sync::Mutex<Vec<String>>);
+
+    impl Reporter for Recorder {
+        fn status(&self, line: &str) {
+            self.0.lock().unwrap().push(line.to_string());
+        }
+    }
+
+    #[test]
+    fn test_with_reporter_receives_status_lines() {
+        let recorder = Arc::new(Recorder::default());
+        with_reporter(recorder.clone(), || {
+            crate::status!("{} Wrote {}", Status::Ok, "CLAUDE.md");
+            crate::status!();
+        });
+        crate::status!("not recorded");
+        assert_eq!(*recorder.0.lock().unwrap(), ["✅ Wrote CLAUDE.md", ""]);
+    }
+}
@@ -0,0 +1,110 @@
+//! Progress feedback while template files are deployed.
+//!
+//! Deploying to a slow network filesystem can take a while, so interactive
+//! runs show a spinner with the file being written. Quiet and JSON runs,
+//! programs that receive status lines through a reporter, and runs whose
+//! stdout is not a terminal, such as tests and CI jobs, get no progress.
+//!
+//! Status lines and questions are printed through [`suspend`], which hides
+//! the spinner while they are written.
+
+use crate::output;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Spinner currently on screen, hidden by [`suspend`]
+static ACTIVE: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Run `f` with the spinner, if one is shown, cleared from the screen
+///
+/// Used for everything written to the terminal while files are deployed, so
+/// the spinner never ends up in the middle of a status line or a question.
+pub fn suspend<T>(f: impl FnOnce() -> T) -> T {
+    let active = ACTIVE.lock().unwrap_or_else(PoisonError::into_inner).clone();
+    match active {
+        Some(bar) => bar.suspend(f),
+        None => f(),
+    }
+}
+
+/// Receives progress while a set of files is deployed
+///
+/// Files may be deployed from several threads at once.
+pub trait ProgressReporter: Send + Sync {
+    /// Deployment of `total` files starts
+    fn start(&self, total: usize);
+
+    /// A file is about to be written
+    fn advance(&self, file: &str);
+
+    /// Every file has been deployed, or deployment stopped early
+    fn finish(&self);
+}
+
+/// Reports nothing
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullProgressReporter;
+
+impl ProgressReporter for NullProgressReporter {
+    fn start(&self, _total: usize) {}
+
+    fn advance(&self, _file: &str) {}
+
+    fn finish(&self) {}
+}
+
+/// Shows a spinner with the file being written on stderr
+#[derive(Debug)]
+pub struct SpinnerProgressReporter {
+    bar: ProgressBar,
+}
+
+impl SpinnerProgressReporter {
+    /// Create a spinner that stays hidden until deployment starts
+    pub fn new() -> Self {
+        let bar = ProgressBar::hidden();
+        bar.set_style(
+            ProgressStyle::with_template("{spinner} [{pos}/{len}] {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+        );
+        Self { bar }
+    }
+}
+
+impl Default for SpinnerProgressReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for SpinnerProgressReporter {
+    fn start(&self, total: usize) {
+        self.bar.set_draw_target(indicatif::ProgressDrawTarget::stderr());
+        self.bar.set_length(total as u64);
+        self.bar.enable_steady_tick(Duration::from_millis(100));
+        *ACTIVE.lock().unwrap_or_else(PoisonError::into_inner) = Some(self.bar.clone());
+    }
+
+    fn advance(&self, file: &str) {
+        self.bar.set_message(file.to_string());
+        self.bar.inc(1);
+    }
+
+    fn finish(&self) {
+        // Status lines printed after deployment take the spinner's place
+        ACTIVE.lock().unwrap_or_else(PoisonError::into_inner).take();
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Progress reporter for a deployment in the current run
+pub fn for_deployment() -> Box<dyn ProgressReporter> {
+    let interactive = std::io::stdout().is_terminal();
+    if !interactive || output::is_quiet() || output::is_json() || output::reporter().is_some() {
+        Box::new(NullProgressReporter)
+    } else {
+        Box::new(SpinnerProgressReporter::new())
+    }
+}
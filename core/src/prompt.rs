@@ -0,0 +1,182 @@
+//! Questions asked while a command runs.
+//!
+//! Commands ask through the [`Prompter`] of the current thread: the terminal
+//! by default, or whatever a program embedding specforge installs with
+//! [`with_prompter`], such as [`NoPrompter`] to never ask anything.
+
+use crate::error::{ConfigError, Result};
+use crate::ui::theme;
+use dialoguer::{Confirm, Input, Select};
+use std::cell::RefCell;
+use std::io::IsTerminal;
+use std::sync::Arc;
+
+/// Answers the questions of a command
+///
+/// `None` answers mean the user cancelled the question, e.g. with Esc.
+pub trait Prompter {
+    /// Whether questions can be asked at all
+    fn is_interactive(&self) -> bool;
+
+    /// Ask a yes/no question
+    fn confirm(&self, prompt: &str, default: bool) -> Result<Option<bool>>;
+
+    /// Ask to pick one of `items`, returning its index
+    fn select(&self, prompt: &str, items: &[String], default: usize) -> Result<Option<usize>>;
+
+    /// Ask for a line of text, offering `default` when it is not empty
+    ///
+    /// Prompters that don't answer free-form questions accept the default.
+    fn input(&self, _prompt: &str, default: &str) -> Result<Option<String>> {
+        Ok(Some(default.to_string()))
+    }
+}
+
+/// Asks on the terminal, when stdin is one
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn is_interactive(&self) -> bool {
+        std::io::stdin().is_terminal()
+    }
+
+    fn confirm(&self, prompt: &str, default: bool) -> Result<Option<bool>> {
+        Confirm::with_theme(theme().as_ref())
+            .with_prompt(prompt)
+            .default(default)
+            .interact_opt()
+            .map_err(|e| ConfigError::io_error(format!("Failed to read user input: {}", e)))
+    }
+
+    fn select(&self, prompt: &str, items: &[String], default: usize) -> Result<Option<usize>> {
+        Select::with_theme(theme().as_ref())
+            .with_prompt(prompt)
+            .items(items)
+            .default(default)
+            .interact_opt()
+            .map_err(|e| ConfigError::io_error(format!("Failed to read user input: {}", e)))
+    }
+
+    fn input(&self, prompt: &str, default: &str) -> Result<Option<String>> {
+        let theme = theme();
+        let mut input = Input::<String>::with_theme(theme.as_ref());
+        input.with_prompt(prompt).allow_empty(true);
+        if !default.is_empty() {
+            input.default(default.to_string());
+        }
+        input
+            .interact_text()
+            .map(Some)
+            .map_err(|e| ConfigError::io_error(format!("Failed to read user input: {}", e)))
+    }
+}
+
+/// Never asks; commands fall back to their non-interactive behavior
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoPrompter;
+
+impl Prompter for NoPrompter {
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn confirm(&self, prompt: &str, _default: bool) -> Result<Option<bool>> {
+        Err(not_interactive(prompt))
+    }
+
+    fn select(&self, prompt: &str, _items: &[String], _default: usize) -> Result<Option<usize>> {
+        Err(not_interactive(prompt))
+    }
+
+    fn input(&self, prompt: &str, _default: &str) -> Result<Option<String>> {
+        Err(not_interactive(prompt))
+    }
+}
+
+fn not_interactive(prompt: &str) -> ConfigError {
+    ConfigError::validation_error(format!("Cannot ask '{}' without an interactive prompt", prompt))
+}
+
+thread_local! {
+    static PROMPTER: RefCell<Option<Arc<dyn Prompter>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with questions on this thread going to `prompter`
+///
+/// The previous prompter is restored afterwards, also when `f` panics.
+pub fn with_prompter<T>(prompter: Arc<dyn Prompter>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Arc<dyn Prompter>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            PROMPTER.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(PROMPTER.with(|current| current.borrow_mut().replace(prompter)));
+    f()
+}
+
+/// Prompter of this thread, the terminal unless [`with_prompter`] installed another
+pub fn current() -> Arc<dyn Prompter> {
+    PROMPTER
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| Arc::new(TerminalPrompter))
+}
+
+/// Whether the current prompter can ask questions
+pub fn is_interactive() -> bool {
+    current().is_interactive()
+}
+
+/// Ask a yes/no question with the current prompter
+pub fn confirm(prompt: &str, default: bool) -> Result<Option<bool>> {
+    crate::progress::suspend(|| current().confirm(prompt, default))
+}
+
+/// Ask to pick one of `items` with the current prompter
+pub fn select(prompt: &str, items: &[String], default: usize) -> Result<Option<usize>> {
+    crate::progress::suspend(|| current().select(prompt, items, default))
+}
+
+/// Ask for a line of text with the current prompter
+pub fn input(prompt: &str, default: &str) -> Result<Option<String>> {
+    crate::progress::suspend(|| current().input(prompt, default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Yes;
+
+    impl Prompter for Yes {
+        fn is_interactive(&self) -> bool {
+            true
+        }
+
+        fn confirm(&self, _prompt: &str, _default: bool) -> Result<Option<bool>> {
+            Ok(Some(true))
+        }
+
+        fn select(&self, _prompt: &str, _items: &[String], default: usize) -> Result<Option<usize>> {
+            Ok(Some(default))
+        }
+    }
+
+    #[test]
+    fn test_with_prompter_is_scoped() {
+        with_prompter(Arc::new(Yes), || {
+            assert!(is_interactive());
+            assert_eq!(confirm("Continue?", false).unwrap(), Some(true));
+            assert_eq!(input("Project name", "demo").unwrap().as_deref(), Some("demo"));
+            with_prompter(Arc::new(NoPrompter), || {
+                assert!(!is_interactive());
+                assert!(select("Agent", &["claude".to_string()], 0).is_err());
+                assert!(input("Project name", "demo").is_err());
+            });
+            assert_eq!(select("Agent", &["a".to_string(), "b".to_string()], 1).unwrap(), Some(1));
+        });
+    }
+}
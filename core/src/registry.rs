@@ -0,0 +1,226 @@
+//! Client for the template package registry.
+//!
+//! Registries are reached over `http://` or `https://` with the same client
+//! that downloads remote packages. Only failures to reach the registry, and
+//! responses telling to try again later, are network errors that can be
+//! retried; a registry URL that cannot work is reported as a validation error.
+
+use crate::error::{ConfigError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::time::Duration;
+
+/// Registry queried when no other registry URL is given
+pub const SPECFORGE_REGISTRY_URL: &str = "https://registry.specforge.dev/v1";
+
+/// How long to wait for the registry to accept a connection or send data
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest response body read from the registry, in bytes
+pub const MAX_RESPONSE_SIZE: u64 = 1024 * 1024;
+
+/// A package listed by the registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageSummary {
+    /// Package ID, as used in the configuration
+    pub id: String,
+    /// Short description of the templates in the package
+    #[serde(default)]
+    pub description: String,
+    /// Most recent published version
+    pub latest_version: String,
+    /// Number of times the package was downloaded
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+/// Search the registry for packages matching the query
+pub fn search_packages(registry_url: &str, query: &str) -> Result<Vec<PackageSummary>> {
+    check_registry_url(registry_url)?;
+    let body = http_get(&search_url(registry_url, query))?;
+    serde_json::from_slice(&body).map_err(|e| {
+        ConfigError::validation_error(format!(
+            "Registry at {} returned an invalid package list: {}",
+            registry_url, e
+        ))
+    })
+}
+
+/// URL of the search endpoint for a query
+fn search_url(registry_url: &str, query: &str) -> String {
+    format!(
+        "{}/packages?q={}",
+        registry_url.trim_end_matches('/'),
+        percent_encode(query)
+    )
+}
+
+/// Percent-encode everything except unreserved URL characters
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Fail when a registry URL is not an `http://` or `https://` URL with a host
+fn check_registry_url(registry_url: &str) -> Result<()> {
+    let parsed = url::Url::parse(registry_url).map_err(|e| {
+        ConfigError::validation_error(format!("Registry URL '{}' is not a valid URL: {}", registry_url, e))
+    })?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none_or(str::is_empty) {
+        return Err(ConfigError::validation_error(format!(
+            "Registry URL '{}' is not supported: registry URLs start with http:// or https:// and name a host",
+            registry_url
+        )));
+    }
+    Ok(())
+}
+
+/// Fetch a URL and return the response body, failing on non-200 responses
+/// and bodies larger than [`MAX_RESPONSE_SIZE`]
+fn http_get(url: &str) -> Result<Vec<u8>> {
+
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(REGISTRY_TIMEOUT)
+        .timeout_read(REGISTRY_TIMEOUT)
+        .user_agent(concat!("specforge/", env!("CARGO_PKG_VERSION")))
+        .build();
+    let response = agent
+        .get(url)
+        .set("Accept", "application/json")
+        .call()
+        .map_err(|e| match e {
+            // Server errors and rate limits pass; any other status will not change on a retry
+            ureq::Error::Status(status, _) if status >= 500 || status == 429 => ConfigError::network_error(
+                format!("Registry at {} responded with HTTP status {}", url, status),
+            ),
+            ureq::Error::Status(status, _) => ConfigError::validation_error(format!(
+                "Registry at {} responded with HTTP status {}; check the registry URL",
+                url, status
+            )),
+            ureq::Error::Transport(transport) if transport.kind() == ureq::ErrorKind::InvalidUrl => {
+                ConfigError::validation_error(format!("Registry URL {} is not valid: {}", url, transport))
+            }
+            ureq::Error::Transport(transport) => {
+                ConfigError::network_error(format!("Failed to reach registry at {}: {}", url, transport))
+            }
+        })?;
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_RESPONSE_SIZE + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| ConfigError::network_error(format!("Response from registry at {} was interrupted: {}", url, e)))?;
+    if body.len() as u64 > MAX_RESPONSE_SIZE {
+        return Err(ConfigError::validation_error(format!(
+            "Registry at {} sent a response larger than {} KiB",
+            url,
+            MAX_RESPONSE_SIZE / 1024
+        )));
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve a single HTTP response on a local port and return the registry URL
+    fn serve_once(status: &'static str, body: String) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let read = stream.read(&mut request).unwrap();
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                status,
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            // The client stops reading once the body is too large
+            let _ = stream.write_all(body.as_bytes());
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_search_url_encodes_query() {
+        assert_eq!(
+            search_url("http://localhost:8080/v1/", "rust api"),
+            "http://localhost:8080/v1/packages?q=rust%20api"
+        );
+        assert_eq!(percent_encode("a&b=c/ü"), "a%26b%3Dc%2F%C3%BC");
+    }
+
+    #[test]
+    fn test_unsupported_registry_urls_are_not_retried() {
+        for url in ["ftp://example.com", "https://", "registry.example.com"] {
+            let error = search_packages(url, "rust").unwrap_err();
+            assert!(matches!(error, ConfigError::ValidationError(_)), "{}: {:?}", url, error);
+            assert!(!error.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_search_packages() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"[{"id": "rust-api", "description": "REST APIs in Rust", "latest_version": "1.2.0", "downloads": 42}]"#
+                .to_string(),
+        );
+
+        let packages = search_packages(&url, "rust").unwrap();
+        assert_eq!(
+            packages,
+            vec![PackageSummary {
+                id: "rust-api".to_string(),
+                description: "REST APIs in Rust".to_string(),
+                latest_version: "1.2.0".to_string(),
+                downloads: 42,
+            }]
+        );
+        assert!(server.join().unwrap().starts_with("GET /v1/packages?q=rust HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_search_packages_reports_http_errors() {
+        let (url, server) = serve_once("503 Service Unavailable", String::new());
+        let error = search_packages(&url, "rust").unwrap_err();
+        server.join().unwrap();
+        assert!(error.is_retryable());
+        assert!(error.to_string().contains("HTTP status 503"));
+
+        let (url, server) = serve_once("404 Not Found", String::new());
+        let error = search_packages(&url, "rust").unwrap_err();
+        server.join().unwrap();
+        assert!(!error.is_retryable());
+        assert!(error.to_string().contains("check the registry URL"));
+    }
+
+    #[test]
+    fn test_search_packages_rejects_invalid_response() {
+        let (url, server) = serve_once("200 OK", "<html>not json</html>".to_string());
+        let error = search_packages(&url, "rust").unwrap_err();
+        server.join().unwrap();
+        assert!(error.to_string().contains("invalid package list"));
+
+        let (url, server) = serve_once("200 OK", " ".repeat(MAX_RESPONSE_SIZE as usize + 1));
+        let error = search_packages(&url, "rust").unwrap_err();
+        server.join().unwrap();
+        assert!(!error.is_retryable());
+        assert!(error.to_string().contains("larger than 1024 KiB"));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::error::Result;
+use crate::file_ops::{ConflictPolicy, ConflictResolver};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Amazon Q Developer template content
+const AMAZONQ_CLAUDE_MD: &str = include_str!("../../templates/amazonq/CLAUDE.md");
+const AMAZONQ_README_MD: &str = include_str!("../../templates/amazonq/README.md");
+
+/// Deploy Amazon Q Developer templates to the target directory, overwriting existing files
+pub fn deploy_amazonq_templates(
+    target_dir: &Path,
+    context: &HashMap<String, String>,
+) -> Result<Vec<PathBuf>> {
+    let mut deployed_files = Vec::new();
+    let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+
+    for (name, content) in amazonq_template_contents() {
+        let rendered = super::render_embedded(content, context);
+        deployed_files.push(super::deploy_rendered_file(&rendered, target_dir, name, &mut resolver)?.path);
+    }
+
+    Ok(deployed_files)
+}
+
+/// Processed Amazon Q Developer templates by file name, built on first use
+fn processed_templates() -> &'static HashMap<&'static str, String> {
+    static TEMPLATES: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+    TEMPLATES.get_or_init(|| {
+        super::process_embedded(&[
+            ("CLAUDE.md", AMAZONQ_CLAUDE_MD),
+            ("README.md", AMAZONQ_README_MD),
+        ])
+    })
+}
+
+/// List all Amazon Q Developer template files
+pub fn list_amazonq_templates() -> Vec<&'static str> {
+    vec![
+        "CLAUDE.md",
+        "README.md",
+    ]
+}
+
+/// Embedded Amazon Q Developer template files as (file name, content) pairs
+pub fn amazonq_template_contents() -> Vec<(&'static str, &'static str)> {
+    let templates = processed_templates();
+    list_amazonq_templates()
+        .into_iter()
+        .map(|name| (name, templates[name].as_str()))
+        .collect()
+}
+
+/// Amazon Q Developer template files deployed without a provenance line
+///
+/// README.md is the project's own front page, so it is left without one.
+pub fn amazonq_files_without_provenance() -> Vec<&'static str> {
+    vec!["README.md"]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_deploy_amazonq_templates() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = deploy_amazonq_templates(temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert_eq!(deployed.len(), 2);
+        let claude_path = temp_dir.path().join("CLAUDE.md");
+        let readme_path = temp_dir.path().join("README.md");
+
+        assert!(claude_path.exists());
+        assert!(readme_path.exists());
+
+        let claude_content = std::fs::read_to_string(&claude_path).unwrap();
+        assert!(claude_content.contains("Amazon Q Developer"));
+        assert!(claude_content.contains("specforge"));
+
+        let readme_content = std::fs::read_to_string(&readme_path).unwrap();
+        assert!(readme_content.contains("Amazon Q Developer Configuration"));
+        assert!(readme_content.contains("Setup Instructions"));
+    }
+
+    #[test]
+    fn test_amazonq_template_content() {
+        assert!(AMAZONQ_CLAUDE_MD.contains("IAM"));
+        assert!(AMAZONQ_CLAUDE_MD.contains("CloudFormation"));
+        assert!(AMAZONQ_README_MD.contains("--agent amazonq"));
+    }
+
+    #[test]
+    fn test_amazonq_template_contents_match_listing() {
+        let names: Vec<&str> = amazonq_template_contents()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(names, list_amazonq_templates());
+    }
+}
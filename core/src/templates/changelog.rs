@@ -0,0 +1,83 @@
+//! What changed in the embedded templates, release by release.
+//!
+//! The entries live in `templates/CHANGELOG.json` next to the templates they
+//! describe and are compiled into the binary. Each entry names the release
+//! that changed an agent's templates, a one-line summary and the files it
+//! touched. Add an entry whenever a release changes a template.
+
+use crate::config::{Agent, PackageVersion};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+const CHANGELOG_JSON: &str = include_str!("../../templates/CHANGELOG.json");
+
+/// One change to the templates of an agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    /// Release that shipped the change
+    pub version: PackageVersion,
+    /// Agent whose templates changed
+    pub agent: Agent,
+    /// What changed, in one line
+    pub summary: String,
+    /// Template files the change touched
+    pub files: Vec<String>,
+}
+
+/// Every changelog entry, oldest release first
+pub fn entries() -> &'static [ChangelogEntry] {
+    static ENTRIES: OnceLock<Vec<ChangelogEntry>> = OnceLock::new();
+    ENTRIES.get_or_init(|| {
+        let mut entries: Vec<ChangelogEntry> =
+            serde_json::from_str(CHANGELOG_JSON).expect("templates/CHANGELOG.json is a valid changelog");
+        entries.sort_by(|a, b| a.version.cmp(&b.version));
+        entries
+    })
+}
+
+/// Entries for `agent` from releases after `since` up to and including
+/// `until`, oldest first
+///
+/// Without `since`, every release up to `until` is included; with `agent`
+/// `None`, the entries of every agent are.
+pub fn between(
+    agent: Option<&Agent>,
+    since: Option<&PackageVersion>,
+    until: &PackageVersion,
+) -> Vec<&'static ChangelogEntry> {
+    entries()
+        .iter()
+        .filter(|entry| agent.is_none_or(|agent| entry.agent == *agent))
+        .filter(|entry| since.is_none_or(|since| entry.version > *since) && entry.version <= *until)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::TemplateSystem;
+
+    #[test]
+    fn test_embedded_changelog_is_valid() {
+        let current = PackageVersion::current();
+        for entry in entries() {
+            assert!(entry.version <= current, "{} is from a future release", entry.version);
+            assert!(!entry.summary.trim().is_empty());
+            let templates = TemplateSystem::list_template_files(&entry.agent);
+            for file in &entry.files {
+                assert!(templates.contains(&file.as_str()), "{} is not a {} template", file, entry.agent);
+            }
+        }
+        for agent in Agent::all() {
+            assert!(!between(Some(&agent), None, &current).is_empty(), "no entries for {}", agent);
+        }
+    }
+
+    #[test]
+    fn test_between_empty_range() {
+        let current = PackageVersion::current();
+        assert!(between(None, Some(&current), &current).is_empty());
+        assert!(between(Some(&Agent::Claude), Some(&current), &PackageVersion::new(0, 0, 1)).is_empty());
+        assert!(between(None, None, &PackageVersion::new(0, 0, 0)).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::error::Result;
+use crate::file_ops::{ConflictPolicy, ConflictResolver};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Codeium template content
+const CODEIUM_CLAUDE_MD: &str = include_str!("../../templates/codeium/CLAUDE.md");
+const CODEIUM_CODEIUM_JSON: &str = include_str!("../../templates/codeium/codeium.json");
+
+/// Deploy Codeium templates to the target directory, overwriting existing files
+pub fn deploy_codeium_templates(
+    target_dir: &Path,
+    context: &HashMap<String, String>,
+) -> Result<Vec<PathBuf>> {
+    let mut deployed_files = Vec::new();
+    let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+
+    for (name, content) in codeium_template_contents() {
+        let rendered = super::render_embedded(content, context);
+        deployed_files.push(super::deploy_rendered_file(&rendered, target_dir, name, &mut resolver)?.path);
+    }
+
+    Ok(deployed_files)
+}
+
+/// Processed Codeium templates by file name, built on first use
+fn processed_templates() -> &'static HashMap<&'static str, String> {
+    static TEMPLATES: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+    TEMPLATES.get_or_init(|| {
+        super::process_embedded(&[
+            ("CLAUDE.md", CODEIUM_CLAUDE_MD),
+            ("codeium.json", CODEIUM_CODEIUM_JSON),
+        ])
+    })
+}
+
+/// List all Codeium template files
+pub fn list_codeium_templates() -> Vec<&'static str> {
+    vec![
+        "CLAUDE.md",
+        "codeium.json",
+    ]
+}
+
+/// Embedded Codeium template files as (file name, content) pairs
+pub fn codeium_template_contents() -> Vec<(&'static str, &'static str)> {
+    let templates = processed_templates();
+    list_codeium_templates()
+        .into_iter()
+        .map(|name| (name, templates[name].as_str()))
+        .collect()
+}
+
+/// Codeium template files deployed without a provenance line
+pub fn codeium_files_without_provenance() -> Vec<&'static str> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_deploy_codeium_templates() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = deploy_codeium_templates(temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert_eq!(deployed.len(), 2);
+
+        // Verify files exist
+        let claude_path = temp_dir.path().join("CLAUDE.md");
+        let config_path = temp_dir.path().join("codeium.json");
+
+        assert!(claude_path.exists());
+        assert!(config_path.exists());
+
+        // Verify file contents
+        let claude_content = std::fs::read_to_string(&claude_path).unwrap();
+        assert!(claude_content.contains("Codeium"));
+        assert!(claude_content.contains("specforge"));
+
+        let config_content = std::fs::read_to_string(&config_path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&config_content).unwrap();
+        assert!(json.get("workspace").is_some());
+    }
+
+    #[test]
+    fn test_list_codeium_templates() {
+        let templates = list_codeium_templates();
+
+        assert_eq!(templates.len(), 2);
+        assert!(templates.contains(&"CLAUDE.md"));
+        assert!(templates.contains(&"codeium.json"));
+    }
+
+    #[test]
+    fn test_codeium_template_content() {
+        // Verify the embedded template content is valid
+        assert!(!CODEIUM_CLAUDE_MD.is_empty());
+        assert!(!CODEIUM_CODEIUM_JSON.is_empty());
+
+        // Verify content contains expected keywords
+        assert!(CODEIUM_CLAUDE_MD.contains("Codeium"));
+        assert!(serde_json::from_str::<serde_json::Value>(CODEIUM_CODEIUM_JSON).is_ok());
+    }
+
+    #[test]
+    fn test_codeium_template_contents_match_listing() {
+        let names: Vec<&str> = codeium_template_contents()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(names, list_codeium_templates());
+    }
+}
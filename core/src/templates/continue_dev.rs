@@ -0,0 +1,108 @@
+use crate::error::Result;
+use crate::file_ops::{ConflictPolicy, ConflictResolver, FileOps};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Continue template content
+const CONTINUE_CLAUDE_MD: &str = include_str!("../../templates/continue/CLAUDE.md");
+const CONTINUE_CONFIG_JSON: &str = include_str!("../../templates/continue/config.json");
+
+/// Directory Continue reads its project configuration from
+pub const CONTINUE_DIR: &str = ".continue";
+
+/// Deploy Continue templates to the target directory, overwriting existing files
+///
+/// `config.json` goes into the `.continue` directory, which is created first.
+pub fn deploy_continue_templates(
+    target_dir: &Path,
+    context: &HashMap<String, String>,
+) -> Result<Vec<PathBuf>> {
+    let mut deployed_files = Vec::new();
+    let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+    FileOps::ensure_directory_exists(target_dir.join(CONTINUE_DIR))?;
+
+    for (name, content) in continue_template_contents() {
+        let rendered = super::render_embedded(content, context);
+        deployed_files.push(super::deploy_rendered_file(&rendered, target_dir, name, &mut resolver)?.path);
+    }
+
+    Ok(deployed_files)
+}
+
+/// Processed Continue templates by file name, built on first use
+fn processed_templates() -> &'static HashMap<&'static str, String> {
+    static TEMPLATES: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+    TEMPLATES.get_or_init(|| {
+        super::process_embedded(&[
+            ("CLAUDE.md", CONTINUE_CLAUDE_MD),
+            (".continue/config.json", CONTINUE_CONFIG_JSON),
+        ])
+    })
+}
+
+/// List all Continue template files
+pub fn list_continue_templates() -> Vec<&'static str> {
+    vec![
+        "CLAUDE.md",
+        ".continue/config.json",
+    ]
+}
+
+/// Embedded Continue template files as (file name, content) pairs
+pub fn continue_template_contents() -> Vec<(&'static str, &'static str)> {
+    let templates = processed_templates();
+    list_continue_templates()
+        .into_iter()
+        .map(|name| (name, templates[name].as_str()))
+        .collect()
+}
+
+/// Continue template files deployed without a provenance line
+///
+/// Continue owns `config.json`, so no `_specforge` key is added to it.
+pub fn continue_files_without_provenance() -> Vec<&'static str> {
+    vec![".continue/config.json"]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_deploy_continue_templates() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = deploy_continue_templates(temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert_eq!(deployed.len(), 2);
+        let claude_path = temp_dir.path().join("CLAUDE.md");
+        let config_path = temp_dir.path().join(".continue").join("config.json");
+        assert!(claude_path.exists());
+        assert!(config_path.exists());
+
+        let claude_content = std::fs::read_to_string(&claude_path).unwrap();
+        assert!(claude_content.contains("Continue"));
+
+        let config_content = std::fs::read_to_string(&config_path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&config_content).unwrap();
+        assert!(json.get("customCommands").is_some());
+        assert!(json.get("_specforge").is_none());
+    }
+
+    #[test]
+    fn test_continue_template_content() {
+        assert!(CONTINUE_CLAUDE_MD.contains("Continue"));
+        assert!(serde_json::from_str::<serde_json::Value>(CONTINUE_CONFIG_JSON).is_ok());
+    }
+
+    #[test]
+    fn test_continue_template_contents_match_listing() {
+        let names: Vec<&str> = continue_template_contents()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(names, list_continue_templates());
+    }
+}
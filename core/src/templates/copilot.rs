@@ -1,33 +1,40 @@
 use crate::error::Result;
+use crate::file_ops::{ConflictPolicy, ConflictResolver};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 /// GitHub Copilot template content
 const COPILOT_CLAUDE_MD: &str = include_str!("../../templates/copilot/CLAUDE.md");
 const COPILOT_README_MD: &str = include_str!("../../templates/copilot/README.md");
 
-/// Deploy GitHub Copilot templates to the target directory
-pub fn deploy_copilot_templates(target_dir: &Path) -> Result<Vec<PathBuf>> {
+/// Deploy GitHub Copilot templates to the target directory, overwriting existing files
+pub fn deploy_copilot_templates(
+    target_dir: &Path,
+    context: &HashMap<String, String>,
+) -> Result<Vec<PathBuf>> {
     let mut deployed_files = Vec::new();
+    let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
 
-    // Deploy CLAUDE.md
-    let claude_path = super::deploy_template_file(
-        COPILOT_CLAUDE_MD,
-        target_dir,
-        "CLAUDE.md"
-    )?;
-    deployed_files.push(claude_path);
-
-    // Deploy README.md
-    let readme_path = super::deploy_template_file(
-        COPILOT_README_MD,
-        target_dir,
-        "README.md"
-    )?;
-    deployed_files.push(readme_path);
+    for (name, content) in copilot_template_contents() {
+        let rendered = super::render_embedded(content, context);
+        deployed_files.push(super::deploy_rendered_file(&rendered, target_dir, name, &mut resolver)?.path);
+    }
 
     Ok(deployed_files)
 }
 
+/// Processed GitHub Copilot templates by file name, built on first use
+fn processed_templates() -> &'static HashMap<&'static str, String> {
+    static TEMPLATES: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+    TEMPLATES.get_or_init(|| {
+        super::process_embedded(&[
+            ("CLAUDE.md", COPILOT_CLAUDE_MD),
+            ("README.md", COPILOT_README_MD),
+        ])
+    })
+}
+
 /// List all GitHub Copilot template files
 pub fn list_copilot_templates() -> Vec<&'static str> {
     vec![
@@ -36,6 +43,22 @@ pub fn list_copilot_templates() -> Vec<&'static str> {
     ]
 }
 
+/// Embedded GitHub Copilot template files as (file name, content) pairs
+pub fn copilot_template_contents() -> Vec<(&'static str, &'static str)> {
+    let templates = processed_templates();
+    list_copilot_templates()
+        .into_iter()
+        .map(|name| (name, templates[name].as_str()))
+        .collect()
+}
+
+/// GitHub Copilot template files deployed without a provenance line
+///
+/// README.md is the project's own front page, so it is left without one.
+pub fn copilot_files_without_provenance() -> Vec<&'static str> {
+    vec!["README.md"]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,7 +68,7 @@ mod tests {
     fn test_deploy_copilot_templates() {
         let temp_dir = TempDir::new().unwrap();
 
-        let deployed = deploy_copilot_templates(temp_dir.path()).unwrap();
+        let deployed = deploy_copilot_templates(temp_dir.path(), &HashMap::new()).unwrap();
 
         assert_eq!(deployed.len(), 2);
 
@@ -85,4 +108,13 @@ mod tests {
         assert!(COPILOT_CLAUDE_MD.contains("GitHub Copilot"));
         assert!(COPILOT_README_MD.contains("GitHub Copilot Configuration"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_copilot_template_contents_match_listing() {
+        let names: Vec<&str> = copilot_template_contents()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(names, list_copilot_templates());
+    }
+}
@@ -0,0 +1,277 @@
+//! Previews of how a file changes, shown before it is overwritten.
+//!
+//! Line diffs are unified diffs (see [`super::unified_diff`]). Word diffs
+//! mark the changed words inside a line, which reads better for Markdown
+//! prose where one sentence changes in a long paragraph:
+//!
+//! ```text
+//! --- a/CLAUDE.md
+//! +++ b/CLAUDE.md
+//! @@ -1,3 +1,3 @@
+//! # Title
+//! Run the [-unit-]{+integration+} tests before committing.
+//! end
+//! ```
+//!
+//! Without colors, removed text is wrapped in `[-…-]` and added text in
+//! `{+…+}`; with colors, removed text is red and added text green.
+
+use super::{display_diff, unified_diff};
+use crate::status;
+use similar::{ChangeTag, DiffOp, TextDiff};
+
+/// Characters of unchanged text kept on either side of a change in a word
+/// diff; the rest of a long line is replaced by an ellipsis
+pub const WORD_DIFF_CONTEXT: usize = 40;
+
+/// Unchanged lines of context around each hunk
+const CONTEXT_LINES: usize = 3;
+
+/// How a changed file is previewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffStyle {
+    /// Unified diff of whole lines
+    #[default]
+    Line,
+    /// Changed words marked inside each line
+    Word,
+}
+
+impl DiffStyle {
+    /// Style selected by a `--word-diff` flag
+    pub fn from_word_diff(word_diff: bool) -> Self {
+        if word_diff { DiffStyle::Word } else { DiffStyle::Line }
+    }
+}
+
+/// Print how a file changes from the existing to the new content, in the
+/// given style; nothing is printed when they are equal
+pub fn preview(existing: &str, new: &str, file_name: &str, style: DiffStyle) {
+    match style {
+        DiffStyle::Line => {
+            if let Some(diff) = unified_diff(existing, new, file_name) {
+                display_diff(&diff);
+            }
+        }
+        DiffStyle::Word => {
+            if let Some(diff) = word_diff(existing, new, file_name, crate::output::colors_enabled_stderr()) {
+                for line in diff.lines() {
+                    status!("{}", line);
+                }
+            }
+        }
+    }
+}
+
+/// Word diff from the existing content to the new content, or `None` when
+/// they are equal
+///
+/// Changed lines are paired up and diffed word by word; lines without a
+/// counterpart are marked as removed or added as a whole. With `color`, the
+/// changes are colored instead of bracketed.
+pub fn word_diff(existing: &str, new: &str, file_name: &str, color: bool) -> Option<String> {
+    if existing == new {
+        return None;
+    }
+
+    let diff = TextDiff::from_lines(existing, new);
+    let old_lines: Vec<&str> = diff.old_slices().iter().map(|line| trim_newline(line)).collect();
+    let new_lines: Vec<&str> = diff.new_slices().iter().map(|line| trim_newline(line)).collect();
+    let marker = Marker { color };
+
+    let mut lines = vec![
+        marker.header(&format!("--- a/{}", file_name)),
+        marker.header(&format!("+++ b/{}", file_name)),
+    ];
+    for group in diff.grouped_ops(CONTEXT_LINES) {
+        lines.push(marker.hunk(&similar::udiff::UnifiedHunkHeader::new(&group).to_string()));
+        for op in &group {
+            let (old_range, new_range) = (op.old_range(), op.new_range());
+            match op {
+                DiffOp::Equal { .. } => {
+                    lines.extend(old_lines[old_range].iter().map(|line| shorten_context(line)));
+                }
+                DiffOp::Delete { .. } => {
+                    lines.extend(old_lines[old_range].iter().map(|line| marker.removed(line)));
+                }
+                DiffOp::Insert { .. } => {
+                    lines.extend(new_lines[new_range].iter().map(|line| marker.added(line)));
+                }
+                DiffOp::Replace { .. } => {
+                    let old = &old_lines[old_range];
+                    let new = &new_lines[new_range];
+                    let paired = old.len().min(new.len());
+                    for (old_line, new_line) in old.iter().zip(new.iter()) {
+                        lines.push(diff_words(old_line, new_line, &marker));
+                    }
+                    lines.extend(old[paired..].iter().map(|line| marker.removed(line)));
+                    lines.extend(new[paired..].iter().map(|line| marker.added(line)));
+                }
+            }
+        }
+    }
+
+    let mut rendered = lines.join("\n");
+    rendered.push('\n');
+    Some(rendered)
+}
+
+/// Marks changed text with brackets or colors
+struct Marker {
+    color: bool,
+}
+
+impl Marker {
+    fn header(&self, text: &str) -> String {
+        if self.color { console::style(text).bold().force_styling(true).to_string() } else { text.to_string() }
+    }
+
+    fn hunk(&self, text: &str) -> String {
+        if self.color { console::style(text).cyan().force_styling(true).to_string() } else { text.to_string() }
+    }
+
+    fn removed(&self, text: &str) -> String {
+        if self.color {
+            console::style(text).red().force_styling(true).to_string()
+        } else {
+            format!("[-{}-]", text)
+        }
+    }
+
+    fn added(&self, text: &str) -> String {
+        if self.color {
+            console::style(text).green().force_styling(true).to_string()
+        } else {
+            format!("{{+{}+}}", text)
+        }
+    }
+}
+
+/// One line holding the words both lines share and the marked changes
+/// between them, with long unchanged stretches shortened
+fn diff_words(old: &str, new: &str, marker: &Marker) -> String {
+    let diff = TextDiff::from_words(old, new);
+    let mut runs: Vec<(ChangeTag, String)> = Vec::new();
+    for change in diff.iter_all_changes() {
+        match runs.last_mut() {
+            Some((tag, text)) if *tag == change.tag() => text.push_str(change.value()),
+            _ => runs.push((change.tag(), change.value().to_string())),
+        }
+    }
+
+    let last = runs.len().saturating_sub(1);
+    runs.iter()
+        .enumerate()
+        .map(|(index, (tag, text))| match tag {
+            ChangeTag::Equal if runs.len() == 1 => shorten_context(text),
+            ChangeTag::Equal if index == 0 => keep_end(text),
+            ChangeTag::Equal if index == last => keep_start(text),
+            ChangeTag::Equal => keep_both_ends(text),
+            ChangeTag::Delete => marker.removed(text),
+            ChangeTag::Insert => marker.added(text),
+        })
+        .collect()
+}
+
+/// Unchanged line of context, cut off after twice the word diff context
+fn shorten_context(line: &str) -> String {
+    if line.chars().count() <= 2 * WORD_DIFF_CONTEXT {
+        return line.to_string();
+    }
+    format!("{}…", line.chars().take(2 * WORD_DIFF_CONTEXT).collect::<String>())
+}
+
+/// Unchanged text leading up to a change
+fn keep_end(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= WORD_DIFF_CONTEXT {
+        return text.to_string();
+    }
+    format!("…{}", text.chars().skip(count - WORD_DIFF_CONTEXT).collect::<String>())
+}
+
+/// Unchanged text following a change
+fn keep_start(text: &str) -> String {
+    if text.chars().count() <= WORD_DIFF_CONTEXT {
+        return text.to_string();
+    }
+    format!("{}…", text.chars().take(WORD_DIFF_CONTEXT).collect::<String>())
+}
+
+/// Unchanged text between two changes
+fn keep_both_ends(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= 2 * WORD_DIFF_CONTEXT {
+        return text.to_string();
+    }
+    format!(
+        "{}…{}",
+        text.chars().take(WORD_DIFF_CONTEXT).collect::<String>(),
+        text.chars().skip(count - WORD_DIFF_CONTEXT).collect::<String>()
+    )
+}
+
+fn trim_newline(line: &str) -> &str {
+    line.trim_end_matches('\n').trim_end_matches('\r')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_diff_equal() {
+        assert_eq!(word_diff("a\nb\n", "a\nb\n", "CLAUDE.md", false), None);
+    }
+
+    #[test]
+    fn test_word_diff_bracket_format() {
+        let diff = word_diff(
+            "# Title\nRun the unit tests before committing.\nend\n",
+            "# Title\nRun the integration tests before committing.\nend\n",
+            "CLAUDE.md",
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            diff,
+            "--- a/CLAUDE.md\n+++ b/CLAUDE.md\n@@ -1,3 +1,3 @@\n# Title\n\
+             Run the [-unit-]{+integration+} tests before committing.\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_word_diff_added_and_removed_lines() {
+        let diff = word_diff("keep\ndrop\n", "keep\nfirst\nsecond\n", "notes.md", false).unwrap();
+        assert_eq!(
+            diff,
+            "--- a/notes.md\n+++ b/notes.md\n@@ -1,2 +1,3 @@\nkeep\n[-drop-]{+first+}\n{+second+}\n"
+        );
+
+        let diff = word_diff("keep\ndrop\n", "keep\n", "notes.md", false).unwrap();
+        assert_eq!(diff, "--- a/notes.md\n+++ b/notes.md\n@@ -1,2 +1 @@\nkeep\n[-drop-]\n");
+    }
+
+    #[test]
+    fn test_word_diff_shortens_long_lines() {
+        let before = "a".repeat(100);
+        let after = "b".repeat(100);
+        let old = format!("{} old {}\n", before, after);
+        let new = format!("{} new {}\n", before, after);
+
+        let diff = word_diff(&old, &new, "README.md", false).unwrap();
+        assert_eq!(
+            diff.lines().nth(3).unwrap(),
+            format!("…{} [-old-]{{+new+}} {}…", "a".repeat(39), "b".repeat(39))
+        );
+    }
+
+    #[test]
+    fn test_word_diff_color() {
+        let diff = word_diff("one two\n", "one three\n", "CLAUDE.md", true).unwrap();
+        assert!(!diff.contains("[-"));
+        assert!(!diff.contains("{+"));
+        assert!(diff.contains(&console::style("two").red().force_styling(true).to_string()));
+        assert!(diff.contains(&console::style("three").green().force_styling(true).to_string()));
+    }
+}
@@ -0,0 +1,566 @@
+//! Sections that packages contribute to a shared file, such as the agent's
+//! instructions file.
+//!
+//! A composite file is the base template followed by one managed section per
+//! fragment, ordered by weight and separated by blank lines:
+//!
+//! ```text
+//! <!-- specforge-section:begin team-prompts/testing weight=50 -->
+//! Run `cargo test` before every commit.
+//! <!-- specforge-section:end team-prompts/testing -->
+//! ```
+//!
+//! Updating a composite file only rewrites the sections of the packages being
+//! deployed. Text outside the markers and the sections of other packages are
+//! kept byte for byte, in the order the user left them.
+
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{ConflictResolver, FileOps, FileWrite, WriteOutcome, backup};
+use crate::status;
+use crate::templates::write_template_file;
+use std::fs;
+use std::path::Path;
+
+/// Target name of the agent's root instructions file, whichever file that is
+pub const INSTRUCTIONS_TARGET: &str = "instructions";
+
+/// Start of the line opening a managed section
+const BEGIN_PREFIX: &str = "<!-- specforge-section:begin ";
+
+/// Start of the line closing a managed section
+const END_PREFIX: &str = "<!-- specforge-section:end ";
+
+/// End of both marker lines
+const MARKER_SUFFIX: &str = " -->";
+
+/// A section a package contributes to a composite file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// ID of the package that owns the section
+    pub package: String,
+    /// Name of the section, unique within the package
+    pub section: String,
+    /// Sections with a lower weight come first
+    pub weight: i32,
+    /// Rendered section content, without markers
+    pub content: String,
+}
+
+impl Fragment {
+    /// ID of the section in the markers, `<package>/<section>`
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.package, self.section)
+    }
+
+    /// The section with its markers, ending in a newline
+    fn render(&self) -> String {
+        let id = self.id();
+        let mut section = format!("{}{} weight={}{}\n", BEGIN_PREFIX, id, self.weight, MARKER_SUFFIX);
+        section.push_str(&self.content);
+        if !self.content.is_empty() && !self.content.ends_with('\n') {
+            section.push('\n');
+        }
+        section.push_str(&format!("{}{}{}\n", END_PREFIX, id, MARKER_SUFFIX));
+        section
+    }
+}
+
+/// Whether a section name can be used in the markers
+pub fn is_valid_section_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Part of a composite file
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Text outside the managed sections, kept verbatim
+    Text(String),
+    /// A managed section, including its marker lines
+    Section { id: String, weight: i32, raw: String },
+}
+
+impl Segment {
+    /// Section of a fragment
+    fn from_fragment(fragment: &Fragment) -> Self {
+        Segment::Section {
+            id: fragment.id(),
+            weight: fragment.weight,
+            raw: fragment.render(),
+        }
+    }
+
+    /// Sort key of a section, `None` for text
+    fn key(&self) -> Option<(i32, &str)> {
+        match self {
+            Segment::Text(_) => None,
+            Segment::Section { id, weight, .. } => Some((*weight, id.as_str())),
+        }
+    }
+}
+
+/// Assemble a composite file from the base template and the fragments of every enabled package
+pub fn compose(base: &str, fragments: &[Fragment]) -> String {
+    let mut sorted: Vec<&Fragment> = fragments.iter().collect();
+    sorted.sort_by_key(|fragment| (fragment.weight, fragment.id()));
+
+    let mut composed = base.to_string();
+    for fragment in sorted {
+        composed.push_str(separator(&composed));
+        composed.push_str(&fragment.render());
+    }
+    composed
+}
+
+/// Update the sections of some packages in an existing composite file
+///
+/// Sections of `packages` are replaced by their fragment, or removed when the
+/// package no longer provides them; new fragments are placed after the
+/// section with the next lower weight. Everything else is kept verbatim, so
+/// sections the user moved around stay where they are.
+pub fn update(existing: &str, fragments: &[Fragment], packages: &[&str]) -> Result<String> {
+    let owned = |id: &str| {
+        id.split_once('/')
+            .is_some_and(|(package, _)| packages.contains(&package))
+    };
+
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut placed = Vec::new();
+    for segment in parse(existing)? {
+        match &segment {
+            Segment::Section { id, .. } if owned(id) => {
+                if let Some(fragment) = fragments.iter().find(|f| f.id() == *id) {
+                    segments.push(Segment::from_fragment(fragment));
+                    placed.push(id.clone());
+                } else if let Some(Segment::Text(text)) = segments.last_mut() {
+                    // Drop the blank line that separated the removed section
+                    if text == "\n" {
+                        segments.pop();
+                    } else if text.ends_with("\n\n") {
+                        text.pop();
+                    }
+                }
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let mut new: Vec<&Fragment> = fragments.iter().filter(|f| !placed.contains(&f.id())).collect();
+    new.sort_by_key(|fragment| (fragment.weight, fragment.id()));
+    for fragment in new {
+        insert(&mut segments, fragment);
+    }
+
+    Ok(segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.as_str(),
+            Segment::Section { raw, .. } => raw.as_str(),
+        })
+        .collect())
+}
+
+/// Place a new section after the last section that sorts before it, or
+/// before the first section when none does
+fn insert(segments: &mut Vec<Segment>, fragment: &Fragment) {
+    let section = Segment::from_fragment(fragment);
+    let id = fragment.id();
+    let key = (fragment.weight, id.as_str());
+
+    let after = segments
+        .iter()
+        .rposition(|segment| segment.key().is_some_and(|other| other < key));
+    if let Some(index) = after {
+        segments.insert(index + 1, Segment::Text("\n".to_string()));
+        segments.insert(index + 2, section);
+    } else if let Some(index) = segments.iter().position(|segment| segment.key().is_some()) {
+        segments.insert(index, section);
+        segments.insert(index + 1, Segment::Text("\n".to_string()));
+    } else {
+        let text: String = segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.as_str(),
+                Segment::Section { raw, .. } => raw.as_str(),
+            })
+            .collect();
+        segments.push(Segment::Text(separator(&text).to_string()));
+        segments.push(section);
+    }
+}
+
+/// Text needed after `content` so a section starts after a blank line
+fn separator(content: &str) -> &'static str {
+    if content.is_empty() || content.ends_with("\n\n") {
+        ""
+    } else if content.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    }
+}
+
+/// Split a composite file into text and managed sections
+///
+/// Fails on markers that are not properly paired, leaving the file to the user.
+fn parse(content: &str) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut open: Option<(String, i32, String)> = None;
+
+    for line in content.split_inclusive('\n') {
+        let marker = line.trim();
+        if let Some((id, weight)) = parse_begin(marker) {
+            if let Some((open_id, _, _)) = &open {
+                return Err(invalid_markers(format!("section '{}' starts inside section '{}'", id, open_id)));
+            }
+            if !text.is_empty() {
+                segments.push(Segment::Text(std::mem::take(&mut text)));
+            }
+            open = Some((id, weight, line.to_string()));
+        } else if let Some(id) = parse_end(marker) {
+            match open.take() {
+                Some((open_id, weight, mut raw)) if open_id == id => {
+                    raw.push_str(line);
+                    segments.push(Segment::Section { id: open_id, weight, raw });
+                }
+                Some((open_id, _, _)) => {
+                    return Err(invalid_markers(format!("section '{}' is closed as '{}'", open_id, id)));
+                }
+                None => return Err(invalid_markers(format!("section '{}' ends without starting", id))),
+            }
+        } else if let Some((_, _, raw)) = &mut open {
+            raw.push_str(line);
+        } else {
+            text.push_str(line);
+        }
+    }
+
+    if let Some((id, _, _)) = open {
+        return Err(invalid_markers(format!("section '{}' is never closed", id)));
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
+
+/// ID and weight of a begin marker line
+fn parse_begin(line: &str) -> Option<(String, i32)> {
+    let inner = line.strip_prefix(BEGIN_PREFIX)?.strip_suffix(MARKER_SUFFIX)?;
+    let mut words = inner.split_whitespace();
+    let id = words.next()?.to_string();
+    let weight = words
+        .find_map(|word| word.strip_prefix("weight="))
+        .and_then(|weight| weight.parse().ok())
+        .unwrap_or(0);
+    Some((id, weight))
+}
+
+/// ID of an end marker line
+fn parse_end(line: &str) -> Option<String> {
+    let inner = line.strip_prefix(END_PREFIX)?.strip_suffix(MARKER_SUFFIX)?;
+    Some(inner.trim().to_string())
+}
+
+/// Error for a composite file whose markers cannot be matched up
+fn invalid_markers(detail: String) -> ConfigError {
+    ConfigError::validation_error(format!("Managed section markers do not match: {}", detail))
+}
+
+/// Write the sections of one package into a composite file below the target directory
+///
+/// The file does not go through the conflict resolver: only the package's
+/// own sections change, so there is nothing of the user's to overwrite. It
+/// is backed up first when the resolver asks for backups.
+pub fn deploy_fragments(
+    target_path: &Path,
+    file_name: &str,
+    package: &str,
+    fragments: &[Fragment],
+    resolver: &ConflictResolver,
+) -> Result<FileWrite> {
+    let file_path = target_path.join(file_name);
+    let existing = match fs::read_to_string(&file_path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            return Err(ConfigError::io_error(format!("Failed to read '{}': {}", file_path.display(), e)));
+        }
+    };
+
+    let content = match &existing {
+        Some(existing) => update(existing, fragments, &[package])
+            .map_err(|e| e.add_context("section update", format!("Updating {}", file_path.display())))?,
+        None => compose("", fragments),
+    };
+    let sha256 = Some(FileOps::hash_tracked_content(&content));
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(FileWrite { path: file_path, outcome: WriteOutcome::Unchanged, sha256, backup: None });
+    }
+
+    let backup = if existing.is_some() && resolver.backups() {
+        let backup = backup::create(&file_path)?;
+        status!("💾 Backed up {} to {}", file_path.display(), backup.display());
+        Some(backup)
+    } else {
+        None
+    };
+    if let Some(parent) = file_path.parent() {
+        FileOps::ensure_directory_exists(parent)?;
+    }
+    let path = write_template_file(&content, target_path, file_name)?;
+    status!("🧩 Updated the {} section(s) of {} in {}", fragments.len(), package, path.display());
+    let outcome = if existing.is_some() { WriteOutcome::Overwritten } else { WriteOutcome::Created };
+    Ok(FileWrite { path, outcome, sha256, backup })
+}
+
+/// Managed sections of an existing file appended to new content that has none
+///
+/// Keeps the sections of installed packages when the base template of a
+/// composite file is redeployed. Content that already has sections, or an
+/// existing file whose markers do not match, is returned unchanged.
+pub fn carry_over(existing: &str, content: &str) -> String {
+    let has_sections = |segments: &[Segment]| segments.iter().any(|s| s.key().is_some());
+    match (parse(existing), parse(content)) {
+        (Ok(old), Ok(new)) if has_sections(&old) && !has_sections(&new) => {
+            let mut carried = content.to_string();
+            for segment in old {
+                if let Segment::Section { raw, .. } = segment {
+                    carried.push_str(separator(&carried));
+                    carried.push_str(&raw);
+                }
+            }
+            carried
+        }
+        _ => content.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::ConflictPolicy;
+    use tempfile::TempDir;
+
+    fn fragment(package: &str, section: &str, weight: i32, content: &str) -> Fragment {
+        Fragment {
+            package: package.to_string(),
+            section: section.to_string(),
+            weight,
+            content: content.to_string(),
+        }
+    }
+
+    fn begin(id: &str, weight: i32) -> String {
+        format!("<!-- specforge-section:begin {} weight={} -->\n", id, weight)
+    }
+
+    fn end(id: &str) -> String {
+        format!("<!-- specforge-section:end {} -->\n", id)
+    }
+
+    #[test]
+    fn test_compose_orders_by_weight() {
+        let composed = compose(
+            "# Instructions\n",
+            &[
+                fragment("security", "secrets", 20, "Never commit secrets."),
+                fragment("testing", "unit", 10, "Run the tests.\n"),
+                fragment("style", "rust", 20, "Use rustfmt.\n"),
+            ],
+        );
+        assert_eq!(
+            composed,
+            format!(
+                "# Instructions\n\n{}Run the tests.\n{}\n{}Never commit secrets.\n{}\n{}Use rustfmt.\n{}",
+                begin("testing/unit", 10),
+                end("testing/unit"),
+                begin("security/secrets", 20),
+                end("security/secrets"),
+                begin("style/rust", 20),
+                end("style/rust"),
+            )
+        );
+    }
+
+    #[test]
+    fn test_compose_without_base_or_fragments() {
+        assert_eq!(compose("# Base", &[]), "# Base");
+        let composed = compose("", &[fragment("p", "s", 0, "x")]);
+        assert_eq!(composed, format!("{}x\n{}", begin("p/s", 0), end("p/s")));
+        assert_eq!(compose("# Base", &[fragment("p", "s", 0, "x")]), format!("# Base\n\n{}", composed));
+    }
+
+    #[test]
+    fn test_update_replaces_only_the_changed_package() {
+        let base = "# Instructions\n\nKeep answers short.\n";
+        let existing = compose(
+            base,
+            &[fragment("testing", "unit", 10, "Run the tests.\n"), fragment("style", "rust", 20, "Use rustfmt.\n")],
+        );
+        // The user edits the other package's section; that is theirs to keep
+        let existing = existing.replace("Use rustfmt.", "Use rustfmt, and clippy.");
+
+        let updated = update(&existing, &[fragment("testing", "unit", 10, "Run cargo test.\n")], &["testing"]).unwrap();
+        assert_eq!(updated, existing.replace("Run the tests.", "Run cargo test."));
+    }
+
+    #[test]
+    fn test_update_keeps_text_outside_markers_verbatim() {
+        let existing = format!(
+            "# Instructions\r\n\r\nMy own notes\r\n\n{}old\n{}\ntrailing notes without newline",
+            begin("testing/unit", 10),
+            end("testing/unit"),
+        );
+        let updated = update(&existing, &[fragment("testing", "unit", 10, "new\n")], &["testing"]).unwrap();
+        assert_eq!(updated, existing.replace("old\n", "new\n"));
+    }
+
+    #[test]
+    fn test_update_preserves_sections_reordered_by_the_user() {
+        let existing = compose(
+            "# Instructions\n",
+            &[
+                fragment("a", "first", 10, "A\n"),
+                fragment("b", "second", 20, "B\n"),
+                fragment("c", "third", 30, "C\n"),
+            ],
+        );
+        // The user moves the last section to the top, with a note between
+        let c = format!("{}C\n{}", begin("c/third", 30), end("c/third"));
+        let reordered = existing.replace(&format!("\n{}", c), "").replace("# Instructions\n\n", &format!("# Instructions\n\n{}\nNote\n\n", c));
+        assert!(reordered.find("C\n").unwrap() < reordered.find("A\n").unwrap());
+
+        let updated = update(&reordered, &[fragment("a", "first", 10, "A2\n")], &["a"]).unwrap();
+        assert_eq!(updated, reordered.replace("A\n", "A2\n"));
+
+        // Updating the moved section keeps it where the user put it
+        let updated = update(&reordered, &[fragment("c", "third", 30, "C2\n")], &["c"]).unwrap();
+        assert_eq!(updated, reordered.replace("C\n", "C2\n"));
+        assert!(updated.find("C2\n").unwrap() < updated.find("A\n").unwrap());
+    }
+
+    #[test]
+    fn test_update_inserts_new_sections_by_weight() {
+        let existing = compose(
+            "# Instructions\n",
+            &[fragment("a", "first", 10, "A\n"), fragment("c", "third", 30, "C\n")],
+        );
+        let updated = update(&existing, &[fragment("b", "second", 20, "B\n")], &["b"]).unwrap();
+        assert_eq!(
+            updated,
+            compose(
+                "# Instructions\n",
+                &[
+                    fragment("a", "first", 10, "A\n"),
+                    fragment("b", "second", 20, "B\n"),
+                    fragment("c", "third", 30, "C\n"),
+                ],
+            )
+        );
+
+        // Lighter than every existing section: it goes before the first one
+        let updated = update(&existing, &[fragment("z", "zero", 0, "Z\n")], &["z"]).unwrap();
+        assert!(updated.starts_with(&format!("# Instructions\n\n{}Z\n{}\n{}", begin("z/zero", 0), end("z/zero"), begin("a/first", 10))));
+
+        // Into a file without sections: appended after a blank line
+        let updated = update("# Mine", &[fragment("b", "second", 20, "B\n")], &["b"]).unwrap();
+        assert_eq!(updated, compose("# Mine", &[fragment("b", "second", 20, "B\n")]));
+    }
+
+    #[test]
+    fn test_update_adds_and_removes_sections_of_a_package() {
+        let existing = compose(
+            "# Instructions\n",
+            &[
+                fragment("a", "first", 10, "A\n"),
+                fragment("b", "old", 20, "Old\n"),
+                fragment("c", "third", 30, "C\n"),
+            ],
+        );
+        let updated = update(&existing, &[fragment("b", "new", 25, "New\n")], &["b"]).unwrap();
+        assert_eq!(
+            updated,
+            compose(
+                "# Instructions\n",
+                &[
+                    fragment("a", "first", 10, "A\n"),
+                    fragment("b", "new", 25, "New\n"),
+                    fragment("c", "third", 30, "C\n"),
+                ],
+            )
+        );
+
+        // A package without fragments loses all of its sections
+        let removed = update(&updated, &[], &["b"]).unwrap();
+        assert_eq!(
+            removed,
+            compose("# Instructions\n", &[fragment("a", "first", 10, "A\n"), fragment("c", "third", 30, "C\n")])
+        );
+    }
+
+    #[test]
+    fn test_update_is_idempotent() {
+        let fragments = [fragment("a", "first", 10, "A\n"), fragment("a", "second", 5, "B\n")];
+        let composed = compose("# Instructions\n", &fragments);
+        assert_eq!(update(&composed, &fragments, &["a"]).unwrap(), composed);
+    }
+
+    #[test]
+    fn test_update_rejects_unmatched_markers() {
+        let unclosed = format!("# Notes\n{}text\n", begin("a/first", 0));
+        assert!(update(&unclosed, &[], &["a"]).unwrap_err().to_string().contains("never closed"));
+
+        let stray_end = format!("# Notes\n{}", end("a/first"));
+        assert!(update(&stray_end, &[], &["a"]).is_err());
+
+        let nested = format!("{}{}{}{}", begin("a/first", 0), begin("b/second", 0), end("b/second"), end("a/first"));
+        assert!(update(&nested, &[], &["a"]).is_err());
+
+        let mismatched = format!("{}x\n{}", begin("a/first", 0), end("a/other"));
+        assert!(update(&mismatched, &[], &["a"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_marker_weights() {
+        assert_eq!(parse_begin("<!-- specforge-section:begin a/b weight=-5 -->"), Some(("a/b".to_string(), -5)));
+        assert_eq!(parse_begin("<!-- specforge-section:begin a/b -->"), Some(("a/b".to_string(), 0)));
+        assert_eq!(parse_begin("<!-- specforge: package=x -->"), None);
+        assert_eq!(parse_end("<!-- specforge-section:end a/b -->"), Some("a/b".to_string()));
+    }
+
+    #[test]
+    fn test_carry_over_keeps_sections_when_the_base_changes() {
+        let existing = compose("# Old base\n", &[fragment("a", "first", 10, "A\n")]);
+        let carried = carry_over(&existing, "# New base\n");
+        assert_eq!(carried, compose("# New base\n", &[fragment("a", "first", 10, "A\n")]));
+
+        assert_eq!(carry_over("# No sections\n", "# New base\n"), "# New base\n");
+        assert_eq!(carry_over(&existing, &existing), existing);
+    }
+
+    #[test]
+    fn test_deploy_fragments() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "# Instructions\n\nMy notes\n").unwrap();
+        let resolver = ConflictResolver::new(ConflictPolicy::Skip).with_backups(true);
+        let fragments = [fragment("team", "testing", 10, "Run the tests.\n")];
+
+        let write = deploy_fragments(temp_dir.path(), "CLAUDE.md", "team", &fragments, &resolver).unwrap();
+        assert_eq!(write.outcome, WriteOutcome::Overwritten);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap(),
+            compose("# Instructions\n\nMy notes\n", &fragments)
+        );
+        assert_eq!(fs::read_to_string(write.backup.unwrap()).unwrap(), "# Instructions\n\nMy notes\n");
+
+        let again = deploy_fragments(temp_dir.path(), "CLAUDE.md", "team", &fragments, &resolver).unwrap();
+        assert_eq!(again.outcome, WriteOutcome::Unchanged);
+
+        let created = deploy_fragments(temp_dir.path(), "docs/AGENTS.md", "team", &fragments, &resolver).unwrap();
+        assert_eq!(created.outcome, WriteOutcome::Created);
+    }
+}
@@ -0,0 +1,1823 @@
+use crate::config::{Agent, ProjectConfig};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::{
+    ConflictPolicy, ConflictResolver, FileOps, FileWrite, PathLimits, WriteJournal, WriteOutcome, backup,
+};
+use crate::progress::{self, ProgressReporter};
+use crate::ui::Status;
+use crate::{output, status};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
+use std::fs;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+pub mod copilot;
+pub mod claude;
+pub mod codeium;
+pub mod continue_dev;
+pub mod amazonq;
+pub mod changelog;
+pub mod diff;
+pub mod fragments;
+pub mod provenance;
+#[cfg(feature = "remote")]
+pub mod remote;
+
+/// Trait for deploying agent-specific templates
+///
+/// Every method defaults to the built-in templates of [`TemplateSystem`], so
+/// an alternative deployer, such as a test double that records its calls,
+/// only overrides what it changes. The trait is object safe, so commands can
+/// take a `&dyn TemplateDeployer` or `Box<dyn TemplateDeployer>`.
+pub trait TemplateDeployer {
+    /// Deploy templates for the given agents to the target directory, filling
+    /// `{{name}}` placeholders from the context
+    fn deploy_templates(
+        &self,
+        agents: &[Agent],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        TemplateSystem::deploy_templates(agents, target_dir, context)
+    }
+
+    /// Deploy resolved templates as [`TemplateSystem::deploy_resolved`] does
+    fn deploy_resolved(
+        &self,
+        templates: &[ResolvedTemplate],
+        conflicts: &[TemplateConflict],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+        resolver: &mut ConflictResolver,
+    ) -> Result<Vec<FileWrite>> {
+        TemplateSystem::deploy_resolved(templates, conflicts, target_dir, context, resolver)
+    }
+
+    /// List template files for the given agent
+    fn list_template_files(&self, agent: &Agent) -> Vec<&'static str> {
+        TemplateSystem::list_template_files(agent)
+    }
+
+    /// Embedded template files for the given agent as (file name, content) pairs
+    fn template_contents(&self, agent: &Agent) -> Vec<(&'static str, &'static str)> {
+        TemplateSystem::template_contents(agent)
+    }
+
+    /// Template files for the given agent as (file name, content) pairs, with
+    /// the `{{agent}}` placeholder filled in
+    ///
+    /// Other placeholders need a project and are left as they are.
+    fn render(&self, agent: &Agent) -> Vec<(String, String)> {
+        let context = TemplateSystem::agent_context(&HashMap::new(), agent);
+        self.template_contents(agent)
+            .into_iter()
+            .map(|(name, content)| (name.to_string(), render_embedded(content, &context)))
+            .collect()
+    }
+}
+
+/// A template file selected for deployment
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedTemplate {
+    /// Agent that provides the file
+    pub agent: Agent,
+    /// File name relative to the target directory
+    pub name: &'static str,
+    /// Template content, embedded or read from a custom template directory
+    pub content: Cow<'static, str>,
+}
+
+/// A template file skipped because an earlier agent deploys the same file name
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateConflict {
+    /// Conflicting file name
+    pub name: &'static str,
+    /// Agent whose version of the file is deployed
+    pub kept: Agent,
+    /// Agent whose version of the file is skipped
+    pub skipped: Agent,
+}
+
+/// Kind of problem found in rendered template output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateIssueKind {
+    /// A `{{name}}` placeholder without a value
+    UnresolvedPlaceholder,
+    /// A relative Markdown link to a file that is neither deployed nor present
+    BrokenLink,
+    /// An instructions file larger than the agent's soft size limit
+    OversizedInstructions { size: usize, limit: usize },
+    /// An instructions file larger than the agent's hard size limit
+    InstructionsOverHardLimit { size: usize, limit: usize },
+}
+
+/// A problem found in a rendered template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateIssue {
+    /// Template file name
+    pub file: String,
+    /// Line number in the rendered output, starting at 1; 0 for problems
+    /// with the whole file
+    pub line: usize,
+    /// What is wrong
+    pub kind: TemplateIssueKind,
+    /// The offending placeholder, link target or agent
+    pub token: String,
+}
+
+impl TemplateIssue {
+    /// Whether the problem fails deployment even without --strict-templates
+    pub fn is_error(&self) -> bool {
+        matches!(self.kind, TemplateIssueKind::InstructionsOverHardLimit { .. })
+    }
+}
+
+impl fmt::Display for TemplateIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TemplateIssueKind::UnresolvedPlaceholder => {
+                write!(f, "{}:{}: unresolved placeholder {}", self.file, self.line, self.token)
+            }
+            TemplateIssueKind::BrokenLink => write!(
+                f,
+                "{}:{}: link to '{}' points at a file that is not deployed or present in the project",
+                self.file, self.line, self.token
+            ),
+            TemplateIssueKind::OversizedInstructions { size, limit } => write!(
+                f,
+                "{}: {} bytes is more than {} reads well ({} bytes); move parts into path-scoped \
+                 instruction files or spec documents",
+                self.file, size, self.token, limit
+            ),
+            TemplateIssueKind::InstructionsOverHardLimit { size, limit } => write!(
+                f,
+                "{}: {} bytes is more than {} reads at all ({} bytes); split it into path-scoped \
+                 instruction files or spec documents",
+                self.file, size, self.token, limit
+            ),
+        }
+    }
+}
+
+/// Metadata key holding the user-defined template variables of a project
+pub const VARIABLES_METADATA_KEY: &str = "variables";
+
+/// Template sets up to this many files are deployed one file at a time by
+/// [`TemplateSystem::deploy_templates_parallel`]
+pub const PARALLEL_DEPLOY_THRESHOLD: usize = 5;
+
+/// Main template deployment implementation
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TemplateSystem;
+
+/// Keeps the status lines of one file deployed on a worker thread
+#[derive(Default)]
+struct BufferedReporter(Mutex<Vec<String>>);
+
+impl BufferedReporter {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl output::Reporter for BufferedReporter {
+    fn status(&self, line: &str) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).push(line.to_string());
+    }
+}
+
+impl TemplateSystem {
+    /// Deployer of the built-in templates
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Combine the templates of several agents, earlier agents taking precedence
+    /// when more than one agent deploys the same file name
+    pub fn resolve_templates(agents: &[Agent]) -> (Vec<ResolvedTemplate>, Vec<TemplateConflict>) {
+        let mut resolved: Vec<ResolvedTemplate> = Vec::new();
+        let mut conflicts = Vec::new();
+
+        for agent in agents {
+            for (name, content) in Self::template_contents(agent) {
+                if let Some(existing) = resolved.iter().find(|t| t.name == name) {
+                    conflicts.push(TemplateConflict {
+                        name,
+                        kept: existing.agent.clone(),
+                        skipped: agent.clone(),
+                    });
+                } else {
+                    resolved.push(ResolvedTemplate {
+                        agent: agent.clone(),
+                        name,
+                        content: Cow::Borrowed(content),
+                    });
+                }
+            }
+        }
+
+        (resolved, conflicts)
+    }
+
+    /// Replace templates with the files of the same name in a custom template directory
+    ///
+    /// The directory holds files named like the built-in templates (see
+    /// [`TemplateDeployer::list_template_files`]), e.g. `CLAUDE.md` or
+    /// `README.md`, directly below it. Templates without a custom file, or
+    /// whose file is a symbolic link loop, keep their built-in content.
+    pub fn apply_template_dir(templates: &mut [ResolvedTemplate], template_dir: &Path) -> Result<()> {
+        for template in templates {
+            let path = template_dir.join(template.name);
+            if !path.is_file() {
+                if let Some(link) = FileOps::find_symlink_loop(&path) {
+                    status!(
+                        "{} Ignoring custom {}: {} is part of a symbolic link loop",
+                        Status::Warn,
+                        template.name,
+                        link.display()
+                    );
+                }
+                continue;
+            }
+
+            let content = fs::read_to_string(&path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&path),
+                std::io::ErrorKind::InvalidData => ConfigError::validation_error(format!(
+                    "Custom template '{}' is not valid UTF-8",
+                    path.display()
+                )),
+                _ => ConfigError::from(e),
+            })?;
+            status!("{} Using custom {} from {}", Status::Info, template.name, template_dir.display());
+            template.content = Cow::Owned(content);
+        }
+        Ok(())
+    }
+
+    /// Check that every template can be written below the target directory
+    /// before any file is deployed
+    pub fn validate_target_paths(templates: &[ResolvedTemplate], target_dir: &Path) -> Result<()> {
+        let limits = PathLimits::current();
+        for template in templates {
+            FileOps::validate_path_length(target_dir.join(template.name), &limits)?;
+        }
+        Ok(())
+    }
+
+    /// Check rendered templates for placeholders without a value and relative
+    /// Markdown links to files that are neither deployed nor already present
+    /// in the target directory
+    ///
+    /// The link check needs the full deploy set, so pass every template that
+    /// will be deployed and call this before writing any of them.
+    pub fn check_templates(
+        templates: &[ResolvedTemplate],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+    ) -> Vec<TemplateIssue> {
+        let deployed: Vec<&str> = templates.iter().map(|t| t.name).collect();
+        let mut issues = Vec::new();
+
+        for template in templates {
+            let context = Self::agent_context(context, &template.agent);
+            let rendered = render_resolved(template, &context);
+            let is_markdown = template.name.ends_with(".md");
+            let mut in_code_block = false;
+            if template.name == template.agent.instructions_file() {
+                issues.extend(Self::check_instructions_size(&template.agent, template.name, rendered.len()));
+            }
+
+            for (index, line) in rendered.lines().enumerate() {
+                let issue = |kind, token: &str| TemplateIssue {
+                    file: template.name.to_string(),
+                    line: index + 1,
+                    kind,
+                    token: token.to_string(),
+                };
+
+                for token in placeholder_tokens(line) {
+                    issues.push(issue(TemplateIssueKind::UnresolvedPlaceholder, token));
+                }
+
+                if !is_markdown {
+                    continue;
+                }
+                let fence = line.trim_start();
+                if fence.starts_with("```") || fence.starts_with("~~~") {
+                    in_code_block = !in_code_block;
+                    continue;
+                }
+                if in_code_block {
+                    continue;
+                }
+                for target in markdown_link_targets(line) {
+                    if !link_resolves(template.name, target, &deployed, target_dir) {
+                        issues.push(issue(TemplateIssueKind::BrokenLink, target));
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Check the size of an agent's instructions file against
+    /// [`Agent::instructions_size_limits`]
+    pub fn check_instructions_size(agent: &Agent, file: &str, size: usize) -> Option<TemplateIssue> {
+        let limits = agent.instructions_size_limits();
+        let kind = if size > limits.hard {
+            TemplateIssueKind::InstructionsOverHardLimit { size, limit: limits.hard }
+        } else if size > limits.soft {
+            TemplateIssueKind::OversizedInstructions { size, limit: limits.soft }
+        } else {
+            return None;
+        };
+        Some(TemplateIssue { file: file.to_string(), line: 0, kind, token: agent.to_string() })
+    }
+
+    /// Check the size of the instructions files deployed in a project for each agent
+    ///
+    /// Files that do not exist are left to the checks for missing files.
+    pub fn check_deployed_instructions(project_dir: &Path, agents: &[Agent]) -> Vec<TemplateIssue> {
+        agents
+            .iter()
+            .filter_map(|agent| {
+                let file = agent.instructions_file();
+                let size = fs::metadata(project_dir.join(file)).ok()?.len();
+                Self::check_instructions_size(agent, file, usize::try_from(size).unwrap_or(usize::MAX))
+            })
+            .collect()
+    }
+
+    /// Print template issues as warnings, failing on errors; with `strict`,
+    /// fail when there are any
+    pub fn report_template_issues(issues: &[TemplateIssue], strict: bool) -> Result<()> {
+        for issue in issues {
+            if issue.is_error() {
+                status!("{} {}", Status::Error, issue);
+            } else {
+                status!("{} {}", Status::Warn, issue);
+            }
+        }
+        let errors = issues.iter().filter(|issue| issue.is_error()).count();
+        if errors > 0 && !strict {
+            return Err(ConfigError::validation_error(format!(
+                "{} instructions file(s) exceed the agent's hard size limit",
+                errors
+            )));
+        }
+        if strict && !issues.is_empty() {
+            return Err(ConfigError::validation_error(format!(
+                "{} template problem(s) found with --strict-templates; nothing was written",
+                issues.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Placeholder values for a project: the project name and scalar metadata
+    /// entries such as `created_at`, overridden by the user-defined variables
+    ///
+    /// Projects without a name use the name of the project directory.
+    pub fn template_context(config: &ProjectConfig, project_dir: &Path) -> HashMap<String, String> {
+        let mut context = HashMap::new();
+        let dir_name = fs::canonicalize(project_dir)
+            .unwrap_or_else(|_| project_dir.to_path_buf())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+        if let Some(name) = config.project_name().map(str::to_string).or(dir_name) {
+            context.insert("project_name".to_string(), name);
+        }
+
+        for (key, value) in &config.metadata {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => continue,
+            };
+            context.insert(key.clone(), value);
+        }
+
+        if let Some(serde_json::Value::Object(variables)) = config.metadata.get(VARIABLES_METADATA_KEY) {
+            for (key, value) in variables {
+                if let Some(value) = value.as_str() {
+                    context.insert(key.clone(), value.to_string());
+                }
+            }
+        }
+
+        context
+    }
+
+    /// Deploy resolved templates to the target directory, reporting the
+    /// templates skipped because of conflicts
+    ///
+    /// Existing files are overwritten or kept as decided by the resolver.
+    /// Progress is shown as [`progress::for_deployment`] decides. Sets of
+    /// more than [`PARALLEL_DEPLOY_THRESHOLD`] files are written concurrently,
+    /// unless the resolver may ask about conflicts, which happens one file
+    /// at a time.
+    pub fn deploy_resolved(
+        templates: &[ResolvedTemplate],
+        conflicts: &[TemplateConflict],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+        resolver: &mut ConflictResolver,
+    ) -> Result<Vec<FileWrite>> {
+        if templates.len() > PARALLEL_DEPLOY_THRESHOLD && resolver.policy() != ConflictPolicy::Prompt {
+            return Self::deploy_resolved_parallel(templates, conflicts, target_dir, context, resolver);
+        }
+        let progress = progress::for_deployment();
+        Self::deploy_resolved_with_progress(templates, conflicts, target_dir, context, resolver, progress.as_ref())
+    }
+
+    /// Deploy resolved templates as [`TemplateSystem::deploy_resolved`] does,
+    /// reporting each file to `progress`
+    ///
+    /// When a file cannot be written, every file deployed before it is put
+    /// back as it was (see [`WriteJournal`]) before the error is returned.
+    pub fn deploy_resolved_with_progress(
+        templates: &[ResolvedTemplate],
+        conflicts: &[TemplateConflict],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+        resolver: &mut ConflictResolver,
+        progress: &dyn ProgressReporter,
+    ) -> Result<Vec<FileWrite>> {
+        let mut journal = Self::record_targets(templates, target_dir)?;
+        Self::prepare_target_dir(conflicts, target_dir)?;
+
+        progress.start(templates.len());
+        let mut deployed = Vec::with_capacity(templates.len());
+        for template in templates {
+            progress.advance(template.name);
+            // Placeholders without a value are reported by check_templates
+            let rendered = render_resolved(template, &Self::agent_context(context, &template.agent));
+            let content = Self::with_provenance(template, &rendered, context);
+            let written = match deploy_rendered_file(&content, target_dir, template.name, resolver) {
+                Ok(written) => written,
+                Err(e) => {
+                    progress.finish();
+                    return Err(e);
+                }
+            };
+            if let Some(backup) = &written.backup {
+                journal.track_created(backup);
+            }
+            deployed.push(written);
+        }
+        progress.finish();
+        journal.commit();
+        Ok(deployed)
+    }
+
+    /// Record the files the templates are deployed to, so a failed
+    /// deployment can be undone
+    fn record_targets(templates: &[ResolvedTemplate], target_dir: &Path) -> Result<WriteJournal> {
+        let mut journal = WriteJournal::new();
+        for template in templates {
+            journal.record(&target_dir.join(template.name))?;
+        }
+        Ok(journal)
+    }
+
+    /// Create the target directory and report the skipped conflicting templates
+    fn prepare_target_dir(conflicts: &[TemplateConflict], target_dir: &Path) -> Result<()> {
+        // Ensure target directory exists
+        if !target_dir.exists() {
+            fs::create_dir_all(target_dir).map_err(|e| {
+                ConfigError::directory_creation_failed(target_dir, e)
+            })?;
+        }
+
+        // Validate target directory is actually a directory
+        if !target_dir.is_dir() {
+            return Err(ConfigError::validation_error(format!(
+                "Target path '{}' exists but is not a directory",
+                target_dir.display()
+            )));
+        }
+
+        for conflict in conflicts {
+            status!(
+                "{} Skipping {} from {}: already provided by {}",
+                Status::Warn,
+                conflict.name, conflict.skipped, conflict.kept
+            );
+        }
+        Ok(())
+    }
+
+    /// Deploy the templates of the agents as [`TemplateDeployer::deploy_templates`]
+    /// does, writing the files concurrently
+    ///
+    /// Sets of up to [`PARALLEL_DEPLOY_THRESHOLD`] files are deployed
+    /// sequentially, as threads would not pay off. Every file is attempted;
+    /// the failures are returned together (see [`ConfigError::multiple`]).
+    /// [`TemplateSystem::deploy_resolved`] makes the same choice for `init`.
+    pub fn deploy_templates_parallel(
+        agents: &[Agent],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let (templates, conflicts) = Self::resolve_templates(agents);
+        if templates.len() <= PARALLEL_DEPLOY_THRESHOLD {
+            return Self::deploy_templates(agents, target_dir, context);
+        }
+        Self::validate_target_paths(&templates, target_dir)?;
+        Self::report_template_issues(&Self::check_templates(&templates, target_dir, context), false)?;
+        let resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let written = Self::deploy_resolved_parallel(&templates, &conflicts, target_dir, context, &resolver)?;
+        Ok(written.into_iter().map(|file| file.path).collect())
+    }
+
+    /// Deploy resolved templates concurrently, deciding about existing files
+    /// with a copy of the resolver per file
+    ///
+    /// The resolver must not prompt. Files are returned in template order.
+    /// When any file fails, all of them are put back as they were. Status
+    /// lines from the worker threads are collected per file and printed in
+    /// template order afterwards, so diff previews don't interleave.
+    fn deploy_resolved_parallel(
+        templates: &[ResolvedTemplate],
+        conflicts: &[TemplateConflict],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+        resolver: &ConflictResolver,
+    ) -> Result<Vec<FileWrite>> {
+        let mut journal = Self::record_targets(templates, target_dir)?;
+        Self::prepare_target_dir(conflicts, target_dir)?;
+
+        let progress = progress::for_deployment();
+        progress.start(templates.len());
+        let results: Vec<(Result<FileWrite>, Vec<String>)> = templates
+            .par_iter()
+            .map(|template| {
+                progress.advance(template.name);
+                let lines = Arc::new(BufferedReporter::default());
+                let result = output::with_reporter(lines.clone(), || {
+                    let rendered = render_resolved(template, &Self::agent_context(context, &template.agent));
+                    let content = Self::with_provenance(template, &rendered, context);
+                    deploy_rendered_file(&content, target_dir, template.name, &mut resolver.clone())
+                });
+                (result, lines.take())
+            })
+            .collect();
+        progress.finish();
+
+        let results: Vec<Result<FileWrite>> = results
+            .into_iter()
+            .map(|(result, lines)| {
+                for line in lines {
+                    status!("{}", line);
+                }
+                result
+            })
+            .collect();
+
+        let mut deployed_files = Vec::new();
+        let mut errors = Vec::new();
+        for result in results {
+            match result {
+                Ok(file) => {
+                    if let Some(backup) = &file.backup {
+                        journal.track_created(backup);
+                    }
+                    deployed_files.push(file);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        if !errors.is_empty() {
+            // Dropping the journal undoes the files the other threads wrote
+            return Err(ConfigError::multiple(errors));
+        }
+        journal.commit();
+        Ok(deployed_files)
+    }
+
+    /// Add the provenance line to a rendered template, unless the project
+    /// disabled provenance or the agent opts the file out
+    ///
+    /// `context` is the project context from [`TemplateSystem::template_context`],
+    /// which carries the project's provenance setting.
+    pub fn with_provenance(template: &ResolvedTemplate, rendered: &str, context: &HashMap<String, String>) -> String {
+        if !provenance::enabled(context) || !Self::records_provenance(&template.agent, template.name) {
+            return rendered.to_string();
+        }
+        provenance::Provenance::for_template(&template.agent, &template.content).apply(rendered, template.name)
+    }
+
+    /// Whether deployed copies of an agent's template carry a provenance line
+    pub fn records_provenance(agent: &Agent, file_name: &str) -> bool {
+        let opted_out = match agent {
+            Agent::Copilot => copilot::copilot_files_without_provenance(),
+            Agent::Claude => claude::claude_files_without_provenance(),
+            Agent::Codeium => codeium::codeium_files_without_provenance(),
+            Agent::Continue => continue_dev::continue_files_without_provenance(),
+            Agent::AmazonQ => amazonq::amazonq_files_without_provenance(),
+        };
+        !opted_out.contains(&file_name)
+    }
+
+    /// SHA-256 over the names and contents of an agent's embedded templates,
+    /// in `sha256:<hex>` form
+    pub fn package_checksum(agent: &Agent) -> String {
+        let mut content = Vec::new();
+        for (name, template) in Self::template_contents(agent) {
+            content.extend_from_slice(name.as_bytes());
+            content.push(0);
+            content.extend_from_slice(template.as_bytes());
+            content.push(0);
+        }
+        format!("sha256:{}", FileOps::hash_content(content))
+    }
+
+    /// Extend a context with the `agent` placeholder for one agent's templates
+    pub fn agent_context(context: &HashMap<String, String>, agent: &Agent) -> HashMap<String, String> {
+        let mut context = context.clone();
+        context.insert("agent".to_string(), agent.to_string());
+        context
+    }
+}
+
+impl TemplateSystem {
+    /// Deploy templates for the given agents to the target directory, filling
+    /// `{{name}}` placeholders from the context and overwriting existing files
+    pub fn deploy_templates(
+        agents: &[Agent],
+        target_dir: &Path,
+        context: &HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let (templates, conflicts) = Self::resolve_templates(agents);
+        Self::validate_target_paths(&templates, target_dir)?;
+        Self::report_template_issues(&Self::check_templates(&templates, target_dir, context), false)?;
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let written = Self::deploy_resolved(&templates, &conflicts, target_dir, context, &mut resolver)?;
+        Ok(written.into_iter().map(|file| file.path).collect())
+    }
+
+    /// List template files for the given agent
+    pub fn list_template_files(agent: &Agent) -> Vec<&'static str> {
+        match agent {
+            Agent::Copilot => copilot::list_copilot_templates(),
+            Agent::Claude => claude::list_claude_templates(),
+            Agent::Codeium => codeium::list_codeium_templates(),
+            Agent::Continue => continue_dev::list_continue_templates(),
+            Agent::AmazonQ => amazonq::list_amazonq_templates(),
+        }
+    }
+
+    /// Embedded template files for the given agent as (file name, content) pairs
+    pub fn template_contents(agent: &Agent) -> Vec<(&'static str, &'static str)> {
+        match agent {
+            Agent::Copilot => copilot::copilot_template_contents(),
+            Agent::Claude => claude::claude_template_contents(),
+            Agent::Codeium => codeium::codeium_template_contents(),
+            Agent::Continue => continue_dev::continue_template_contents(),
+            Agent::AmazonQ => amazonq::amazonq_template_contents(),
+        }
+    }
+}
+
+impl TemplateDeployer for TemplateSystem {}
+
+/// Whether a string is a valid placeholder or variable name
+pub fn is_placeholder_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Replace `{{name}}` placeholders with values from the context
+///
+/// Returns the rendered content together with the names of placeholders that
+/// have no value; those are left in the output unchanged.
+pub fn render_template(content: &str, context: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut rendered = String::with_capacity(content.len());
+    let mut unknown: Vec<String> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let inner = &rest[start + 2..];
+        let Some(end) = inner.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let name = inner[..end].trim();
+        if !is_placeholder_name(name) {
+            // Not a placeholder, e.g. literal braces in a code sample
+            rendered.push_str("{{");
+            rest = inner;
+            continue;
+        }
+
+        match context.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => {
+                rendered.push_str(&rest[start..start + end + 4]);
+                if !unknown.iter().any(|u| u == name) {
+                    unknown.push(name.to_string());
+                }
+            }
+        }
+        rest = &inner[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    (rendered, unknown)
+}
+
+/// Embedded templates processed for deployment, by file name
+///
+/// Line endings are normalized to `\n`, so a checkout with CRLF line endings
+/// builds a binary that deploys the same files. Agent modules keep the result
+/// in a `OnceLock`, so the `include_str!` content is processed once.
+fn process_embedded(templates: &[(&'static str, &'static str)]) -> HashMap<&'static str, String> {
+    templates
+        .iter()
+        .map(|(name, content)| (*name, content.replace("\r\n", "\n")))
+        .collect()
+}
+
+/// Rendered embedded templates, by the address and length of the template
+/// content and the hash of the context
+type RenderCache = Mutex<HashMap<(usize, usize, u64), String>>;
+
+/// Hash of a context that does not depend on the iteration order of the map
+fn context_hash(context: &HashMap<String, String>) -> u64 {
+    let mut entries: Vec<_> = context.iter().collect();
+    entries.sort();
+    let mut hasher = DefaultHasher::new();
+    entries.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Render embedded template content as [`render_template`] does, reusing the
+/// result of earlier calls with the same content and context
+///
+/// Embedded content lives for the whole program, so its address identifies
+/// it. The context is part of the key, so values from one project never end
+/// up in the files of another.
+pub fn render_embedded(content: &'static str, context: &HashMap<String, String>) -> String {
+    static RENDERED: OnceLock<RenderCache> = OnceLock::new();
+    let key = (content.as_ptr() as usize, content.len(), context_hash(context));
+    let cache = RENDERED.get_or_init(Default::default);
+
+    if let Some(rendered) = cache.lock().unwrap_or_else(PoisonError::into_inner).get(&key) {
+        return rendered.clone();
+    }
+    let (rendered, _) = render_template(content, context);
+    cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, rendered.clone());
+    rendered
+}
+
+/// Render a resolved template with the context
+///
+/// Embedded templates go through [`render_embedded`]; templates read from a
+/// custom template directory are rendered on every call.
+pub fn render_resolved(template: &ResolvedTemplate, context: &HashMap<String, String>) -> String {
+    match &template.content {
+        Cow::Borrowed(content) => render_embedded(content, context),
+        Cow::Owned(content) => render_template(content, context).0,
+    }
+}
+
+/// `{{name}}` tokens left in a rendered line
+fn placeholder_tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = line[offset..].find("{{").map(|i| offset + i) {
+        let Some(end) = line[start + 2..].find("}}").map(|i| start + 2 + i) else {
+            break;
+        };
+        if is_placeholder_name(line[start + 2..end].trim()) {
+            tokens.push(&line[start..end + 2]);
+            offset = end + 2;
+        } else {
+            offset = start + 2;
+        }
+    }
+    tokens
+}
+
+/// Targets of inline links and images and of reference definitions in a
+/// Markdown line, ignoring code spans
+fn markdown_link_targets(line: &str) -> Vec<&str> {
+    let mut targets = Vec::new();
+
+    // Reference definition: [label]: target
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('[')
+        && let Some(end) = trimmed.find("]:")
+    {
+        if let Some(target) = trimmed[end + 2..].split_whitespace().next() {
+            targets.push(target.trim_start_matches('<').trim_end_matches('>'));
+        }
+        return targets;
+    }
+
+    let mut in_code_span = false;
+    let mut index = 0;
+    while index < line.len() {
+        let rest = &line[index..];
+        if rest.starts_with('`') {
+            in_code_span = !in_code_span;
+        } else if !in_code_span
+            && rest.starts_with("](")
+            && let Some(end) = rest.find(')')
+        {
+            let target = rest[2..end].trim();
+            // Drop an optional title, e.g. [text](path "title")
+            let target = target.split_whitespace().next().unwrap_or_default();
+            targets.push(target.trim_start_matches('<').trim_end_matches('>'));
+            index += end;
+        }
+        index += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    targets
+}
+
+/// Whether a link in `file_name` points at a deployed file, an existing path
+/// below the target directory, or somewhere specforge does not check
+fn link_resolves(file_name: &str, target: &str, deployed: &[&str], target_dir: &Path) -> bool {
+    let path = target.split(['#', '?']).next().unwrap_or_default();
+    // Anchors, absolute paths, URLs such as https:// or mailto: and leftover placeholders
+    if path.is_empty() || path.starts_with('/') || path.contains(':') || path.contains("{{") {
+        return true;
+    }
+
+    let base = Path::new(file_name).parent().unwrap_or(Path::new(""));
+    if target_dir.join(base).join(path).exists() {
+        return true;
+    }
+
+    let mut components: Vec<String> = Vec::new();
+    for component in base.join(path).components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy().into_owned()),
+            // Outside the target directory and not present on disk
+            Component::ParentDir if components.pop().is_none() => return false,
+            _ => {}
+        }
+    }
+    let normalized = components.join("/");
+    deployed.iter().any(|name| {
+        *name == normalized || name.strip_prefix(normalized.as_str()).is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Render template content, warning about placeholders without a value
+pub fn render_template_file(
+    content: &str,
+    file_name: &str,
+    context: &HashMap<String, String>,
+) -> String {
+    let (rendered, unknown) = render_template(content, context);
+    for name in unknown {
+        status!(
+            "{} Unknown placeholder {{{{{}}}}} in {} left as-is",
+            Status::Warn,
+            name, file_name
+        );
+    }
+    rendered
+}
+
+/// Deploy template content to a file, letting the resolver decide about existing files
+///
+/// When an existing file differs from the rendered template, a diff is shown
+/// before the resolver is asked. Files that already match are left untouched.
+/// Written files carry the hash of the rendered content, for tracking.
+pub fn deploy_template_file(
+    content: &str,
+    target_path: &Path,
+    file_name: &str,
+    context: &HashMap<String, String>,
+    resolver: &mut ConflictResolver,
+) -> Result<FileWrite> {
+    // Placeholders without a value are reported by TemplateSystem::check_templates
+    let (content, _) = render_template(content, context);
+    deploy_rendered_file(&content, target_path, file_name, resolver)
+}
+
+/// Deploy already rendered content to a file, as [`deploy_template_file`] does
+pub fn deploy_rendered_file(
+    content: &str,
+    target_path: &Path,
+    file_name: &str,
+    resolver: &mut ConflictResolver,
+) -> Result<FileWrite> {
+    let file_path = target_path.join(file_name);
+    let _span = tracing::debug_span!("deploy_file", path = %file_path.display()).entered();
+
+    let exists = file_path.exists();
+    let mut content = content.to_string();
+    if exists {
+        let existing = fs::read(&file_path).map_err(|e| {
+            ConfigError::io_error(format!("Failed to read '{}': {}", file_path.display(), e))
+        })?;
+        let existing = String::from_utf8_lossy(&existing);
+        // Sections contributed by packages survive a new version of the base template
+        content = fragments::carry_over(&existing, &content);
+        if existing == content {
+            // Leaving identical files alone keeps repeated runs from touching the project
+            return Ok(FileWrite {
+                path: file_path,
+                outcome: WriteOutcome::Unchanged,
+                sha256: Some(FileOps::hash_tracked_content(&content)),
+                backup: None,
+            });
+        }
+        if resolver.policy() != ConflictPolicy::Skip {
+            diff::preview(&existing, &content, file_name, resolver.diff_style());
+        }
+        if !resolver.allow_overwrite(&file_path)? {
+            status!("{} Keeping existing file: {}", Status::Info, file_path.display());
+            return Ok(FileWrite {
+                path: file_path,
+                outcome: WriteOutcome::Skipped,
+                sha256: None,
+                backup: None,
+            });
+        }
+    }
+
+    let backup = if exists && resolver.backups() {
+        let backup = backup::create(&file_path)?;
+        status!("💾 Backed up {} to {}", file_path.display(), backup.display());
+        Some(backup)
+    } else {
+        None
+    };
+    let path = write_template_file(&content, target_path, file_name)?;
+    let outcome = if exists {
+        WriteOutcome::Overwritten
+    } else {
+        WriteOutcome::Created
+    };
+    tracing::debug!(bytes = content.len(), outcome = ?outcome, "wrote template file");
+    Ok(FileWrite {
+        path,
+        outcome,
+        sha256: Some(FileOps::hash_tracked_content(&content)),
+        backup,
+    })
+}
+
+/// Unified diff from the existing content to the new content, or `None` when they are equal
+pub fn unified_diff(existing: &str, new: &str, file_name: &str) -> Option<String> {
+    if existing == new {
+        return None;
+    }
+
+    let existing_lines: Vec<_> = existing.lines().collect();
+    let new_lines: Vec<_> = new.lines().collect();
+    let hunks = difflib::unified_diff(&existing_lines, &new_lines, "", "", "", "", 3);
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", file_name, file_name);
+    // The first two lines are difflib's own file headers
+    for line in hunks.iter().skip(2) {
+        diff.push_str(line.trim_end_matches('\n'));
+        diff.push('\n');
+    }
+    Some(diff)
+}
+
+/// Print a unified diff, coloring added lines green and removed lines red
+pub fn display_diff(diff: &str) {
+    for line in diff.lines() {
+        let styled = if line.starts_with("+++") || line.starts_with("---") {
+            console::style(line).bold()
+        } else if line.starts_with('+') {
+            console::style(line).green()
+        } else if line.starts_with('-') {
+            console::style(line).red()
+        } else if line.starts_with("@@") {
+            console::style(line).cyan()
+        } else {
+            console::style(line)
+        };
+        status!("{}", styled);
+    }
+}
+
+/// Write already rendered template content to a file
+pub fn write_template_file(
+    content: &str,
+    target_path: &Path,
+    file_name: &str,
+) -> Result<PathBuf> {
+    let file_path = target_path.join(file_name);
+
+    // Check if file already exists
+    if file_path.exists() {
+        // For now, we'll overwrite existing template files
+        // This behavior could be made configurable in the future
+        status!("{} Overwriting existing file: {}", Status::Warn, file_path.display());
+    }
+
+    // Templates such as `.continue/config.json` live in a subdirectory
+    if let Some(parent) = file_path.parent() {
+        FileOps::ensure_directory_exists(parent)?;
+    }
+
+    // Write the template content
+    fs::write(&file_path, content).map_err(|e| {
+        match e.kind() {
+            std::io::ErrorKind::PermissionDenied => {
+                ConfigError::permission_denied(&file_path)
+            }
+            _ => ConfigError::io_error(format!(
+                "Failed to write template file '{}': {}",
+                file_path.display(),
+                e
+            ))
+        }
+    })?;
+
+    Ok(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_target_paths() {
+        let (templates, _) = TemplateSystem::resolve_templates(&[Agent::Claude]);
+        assert!(TemplateSystem::validate_target_paths(&templates, Path::new("/projects/app")).is_ok());
+
+        let long_dir = Path::new("/projects").join("x".repeat(300));
+        let error = TemplateSystem::validate_target_paths(&templates, &long_dir).unwrap_err();
+        assert!(matches!(error, ConfigError::PathTooLong(_, _)));
+    }
+
+    #[test]
+    fn test_deploy_templates_copilot() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = TemplateSystem::deploy_templates(&[Agent::Copilot], temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert!(!deployed.is_empty());
+        for file_path in &deployed {
+            assert!(file_path.exists());
+            assert!(file_path.is_file());
+        }
+
+        // Check that CLAUDE.md and README.md exist
+        assert!(temp_dir.path().join("CLAUDE.md").exists());
+        assert!(temp_dir.path().join("README.md").exists());
+    }
+
+    #[test]
+    fn test_deploy_templates_claude() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = TemplateSystem::deploy_templates(&[Agent::Claude], temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert!(!deployed.is_empty());
+        for file_path in &deployed {
+            assert!(file_path.exists());
+            assert!(file_path.is_file());
+        }
+
+        // Check that CLAUDE.md and README.md exist
+        assert!(temp_dir.path().join("CLAUDE.md").exists());
+        assert!(temp_dir.path().join("README.md").exists());
+    }
+
+    #[test]
+    fn test_deploy_templates_codeium() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = TemplateSystem::deploy_templates(&[Agent::Codeium], temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert_eq!(deployed.len(), 2);
+        assert!(temp_dir.path().join("CLAUDE.md").exists());
+        assert!(temp_dir.path().join("codeium.json").exists());
+    }
+
+    #[test]
+    fn test_deploy_templates_continue() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = TemplateSystem::deploy_templates(&[Agent::Continue], temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert_eq!(deployed.len(), 2);
+        assert!(temp_dir.path().join("CLAUDE.md").exists());
+        assert!(temp_dir.path().join(".continue").join("config.json").is_file());
+    }
+
+    #[test]
+    fn test_deploy_templates_amazonq() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed = TemplateSystem::deploy_templates(&[Agent::AmazonQ], temp_dir.path(), &HashMap::new()).unwrap();
+
+        assert_eq!(deployed.len(), 2);
+        let claude_content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+        assert!(claude_content.contains("Amazon Q"));
+        assert!(temp_dir.path().join("README.md").exists());
+    }
+
+    #[test]
+    fn test_deploy_templates_multiple_agents() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let deployed =
+            TemplateSystem::deploy_templates(
+                &[Agent::Codeium, Agent::Claude],
+                temp_dir.path(),
+                &HashMap::new(),
+            )
+            .unwrap();
+
+        // CLAUDE.md comes from Codeium, README.md from Claude, codeium.json from Codeium
+        assert_eq!(deployed.len(), 3);
+        let claude_content = fs::read_to_string(temp_dir.path().join("CLAUDE.md")).unwrap();
+        assert!(claude_content.contains("Codeium"));
+        assert!(temp_dir.path().join("README.md").exists());
+        assert!(temp_dir.path().join("codeium.json").exists());
+    }
+
+    #[test]
+    fn test_resolve_templates_reports_conflicts() {
+        let (templates, conflicts) =
+            TemplateSystem::resolve_templates(&[Agent::Copilot, Agent::Claude]);
+
+        assert_eq!(templates.len(), 2);
+        assert!(templates.iter().all(|t| t.agent == Agent::Copilot));
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.iter().all(|c| c.kept == Agent::Copilot && c.skipped == Agent::Claude));
+
+        let (templates, conflicts) = TemplateSystem::resolve_templates(&[Agent::Claude]);
+        assert_eq!(templates.len(), 2);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_list_template_files() {
+        let copilot_templates = TemplateSystem::list_template_files(&Agent::Copilot);
+        assert!(!copilot_templates.is_empty());
+        assert!(copilot_templates.contains(&"CLAUDE.md"));
+        assert!(copilot_templates.contains(&"README.md"));
+
+        let claude_templates = TemplateSystem::list_template_files(&Agent::Claude);
+        assert!(!claude_templates.is_empty());
+        assert!(claude_templates.contains(&"CLAUDE.md"));
+        assert!(claude_templates.contains(&"README.md"));
+
+        let codeium_templates = TemplateSystem::list_template_files(&Agent::Codeium);
+        assert!(codeium_templates.contains(&"CLAUDE.md"));
+        assert!(codeium_templates.contains(&"codeium.json"));
+    }
+
+    #[test]
+    fn test_render_fills_agent_placeholder() {
+        struct Inline;
+        impl TemplateDeployer for Inline {
+            fn template_contents(&self, _agent: &Agent) -> Vec<(&'static str, &'static str)> {
+                vec![("AGENTS.md", "Agent: {{agent}} for {{project_name}}")]
+            }
+        }
+
+        let deployers: Vec<Box<dyn TemplateDeployer>> = vec![Box::new(TemplateSystem::new()), Box::new(Inline)];
+        let rendered = deployers[1].render(&Agent::Codeium);
+        assert_eq!(rendered, [("AGENTS.md".to_string(), "Agent: codeium for {{project_name}}".to_string())]);
+
+        let builtin = deployers[0].render(&Agent::Claude);
+        let names: Vec<_> = builtin.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, TemplateSystem::list_template_files(&Agent::Claude));
+        assert!(builtin.iter().all(|(_, content)| !content.contains("{{agent}}")));
+    }
+
+    #[test]
+    fn test_deploy_resolved_reports_progress() {
+        #[derive(Default)]
+        struct Recording(Mutex<Vec<String>>);
+        impl ProgressReporter for Recording {
+            fn start(&self, total: usize) {
+                self.0.lock().unwrap().push(format!("start {}", total));
+            }
+            fn advance(&self, file: &str) {
+                self.0.lock().unwrap().push(file.to_string());
+            }
+            fn finish(&self) {
+                self.0.lock().unwrap().push("finish".to_string());
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let (templates, conflicts) = TemplateSystem::resolve_templates(&[Agent::Codeium]);
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let progress = Recording::default();
+        TemplateSystem::deploy_resolved_with_progress(
+            &templates,
+            &conflicts,
+            temp_dir.path(),
+            &HashMap::new(),
+            &mut resolver,
+            &progress,
+        )
+        .unwrap();
+
+        assert_eq!(*progress.0.lock().unwrap(), ["start 2", "CLAUDE.md", "codeium.json", "finish"]);
+    }
+
+    #[test]
+    fn test_deploy_templates_nonexistent_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let nonexistent_dir = temp_dir.path().join("nonexistent");
+
+        // Should create directory and succeed
+        let result = TemplateSystem::deploy_templates(&[Agent::Copilot], &nonexistent_dir, &HashMap::new());
+        assert!(result.is_ok());
+        assert!(nonexistent_dir.exists());
+        assert!(nonexistent_dir.is_dir());
+    }
+
+    #[test]
+    fn test_deploy_templates_file_conflict() {
+        use std::fs;
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create a regular file at the target path
+        let file_path = temp_dir.path().join("not_a_dir");
+        fs::write(&file_path, "test content").unwrap();
+
+        let result = TemplateSystem::deploy_templates(&[Agent::Copilot], &file_path, &HashMap::new());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not a directory"));
+    }
+
+    #[test]
+    fn test_deploy_template_file_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing_file = temp_dir.path().join("test.txt");
+
+        // Create existing file
+        fs::write(&existing_file, "old content").unwrap();
+
+        // Deploy new content
+        let result = deploy_template_file(
+            "new content",
+            temp_dir.path(),
+            "test.txt",
+            &HashMap::new(),
+            &mut ConflictResolver::new(ConflictPolicy::Overwrite),
+        );
+        let written = result.unwrap();
+        assert_eq!(written.outcome, WriteOutcome::Overwritten);
+        assert_eq!(written.sha256, Some(FileOps::hash_content("new content")));
+
+        // Verify content was overwritten
+        let content = fs::read_to_string(&existing_file).unwrap();
+        assert_eq!(content, "new content");
+    }
+
+    #[test]
+    fn test_deploy_template_file_backs_up_overwritten_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing_file = temp_dir.path().join("CLAUDE.md");
+        let original = b"# My edits\r\nkept \xe2\x9c\x93 exactly\n\n".to_vec();
+        fs::write(&existing_file, &original).unwrap();
+
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite).with_backups(true);
+        let written =
+            deploy_template_file("new content", temp_dir.path(), "CLAUDE.md", &HashMap::new(), &mut resolver)
+                .unwrap();
+        assert_eq!(written.outcome, WriteOutcome::Overwritten);
+        let backup = written.backup.unwrap();
+        assert_eq!(fs::read(&backup).unwrap(), original);
+        assert_eq!(fs::read_to_string(&existing_file).unwrap(), "new content");
+
+        // Unchanged and new files need no backup
+        let unchanged =
+            deploy_template_file("new content", temp_dir.path(), "CLAUDE.md", &HashMap::new(), &mut resolver)
+                .unwrap();
+        assert_eq!(unchanged.backup, None);
+        let created =
+            deploy_template_file("new content", temp_dir.path(), "README.md", &HashMap::new(), &mut resolver)
+                .unwrap();
+        assert_eq!(created.backup, None);
+        assert_eq!(backup::find(&existing_file).unwrap(), vec![backup]);
+    }
+
+    #[test]
+    fn test_deploy_template_file_skip_keeps_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing_file = temp_dir.path().join("test.txt");
+        fs::write(&existing_file, "old content").unwrap();
+
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Skip);
+        let skipped =
+            deploy_template_file("new content", temp_dir.path(), "test.txt", &HashMap::new(), &mut resolver)
+                .unwrap();
+        assert_eq!(skipped.outcome, WriteOutcome::Skipped);
+        assert!(!skipped.written());
+        assert_eq!(skipped.sha256, None);
+        assert_eq!(fs::read_to_string(&existing_file).unwrap(), "old content");
+
+        // Missing files are still created
+        let created =
+            deploy_template_file("new content", temp_dir.path(), "new.txt", &HashMap::new(), &mut resolver)
+                .unwrap();
+        assert_eq!(created.outcome, WriteOutcome::Created);
+    }
+
+    #[test]
+    fn test_deploy_template_file_unchanged_does_not_prompt() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("test.txt"), "same content").unwrap();
+
+        // Prompting would fail without a terminal, so this only passes if no question is asked
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Prompt);
+        let result =
+            deploy_template_file("same content", temp_dir.path(), "test.txt", &HashMap::new(), &mut resolver)
+                .unwrap();
+        assert_eq!(result.outcome, WriteOutcome::Unchanged);
+        assert!(result.written());
+        assert_eq!(result.sha256, Some(FileOps::hash_content("same content")));
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "CLAUDE.md"), None);
+
+        let diff = unified_diff("# Title\nold line\nend\n", "# Title\nnew line\nend\n", "CLAUDE.md").unwrap();
+        assert_eq!(
+            diff,
+            "--- a/CLAUDE.md\n+++ b/CLAUDE.md\n@@ -1,3 +1,3 @@\n # Title\n-old line\n+new line\n end\n"
+        );
+    }
+
+    #[test]
+    fn test_package_checksum() {
+        let checksum = TemplateSystem::package_checksum(&Agent::Codeium);
+        assert!(checksum.starts_with("sha256:"));
+        assert_eq!(checksum.len(), "sha256:".len() + 64);
+        assert_eq!(checksum, TemplateSystem::package_checksum(&Agent::Codeium));
+        assert_ne!(checksum, TemplateSystem::package_checksum(&Agent::Claude));
+    }
+
+    #[test]
+    fn test_render_embedded_keys_on_context() {
+        let content: &'static str = "# {{project_name}} for {{agent}}";
+        let context = |name: &str| {
+            HashMap::from([
+                ("project_name".to_string(), name.to_string()),
+                ("agent".to_string(), "claude".to_string()),
+            ])
+        };
+
+        assert_eq!(render_embedded(content, &context("alpha")), "# alpha for claude");
+        assert_eq!(render_embedded(content, &context("beta")), "# beta for claude");
+        assert_eq!(render_embedded(content, &context("alpha")), "# alpha for claude");
+        assert_eq!(context_hash(&context("alpha")), context_hash(&context("alpha")));
+        assert_ne!(context_hash(&context("alpha")), context_hash(&context("beta")));
+    }
+
+    #[test]
+    fn test_render_resolved_reads_custom_templates_each_time() {
+        let context = HashMap::from([("agent".to_string(), "claude".to_string())]);
+        let mut template = markdown_template("CLAUDE.md", "Built-in for {{agent}}");
+        assert_eq!(render_resolved(&template, &context), "Built-in for claude");
+
+        template.content = Cow::Owned("Custom for {{agent}}".to_string());
+        assert_eq!(render_resolved(&template, &context), "Custom for claude");
+    }
+
+    #[test]
+    fn test_process_embedded_normalizes_line_endings() {
+        let processed = process_embedded(&[("CLAUDE.md", "# Title\r\n\r\nBody\n")]);
+        assert_eq!(processed["CLAUDE.md"], "# Title\n\nBody\n");
+
+        // Processed once, so every call hands out the same string
+        let (_, content) = TemplateSystem::template_contents(&Agent::Claude)[0];
+        assert!(std::ptr::eq(content, TemplateSystem::template_contents(&Agent::Claude)[0].1));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let context = HashMap::from([
+            ("project_name".to_string(), "demo".to_string()),
+            ("agent".to_string(), "claude".to_string()),
+        ]);
+
+        let (rendered, unknown) =
+            render_template("# {{project_name}} ({{ agent }}) by {{author}}", &context);
+        assert_eq!(rendered, "# demo (claude) by {{author}}");
+        assert_eq!(unknown, vec!["author".to_string()]);
+
+        // Braces that do not enclose a placeholder name are copied verbatim
+        let (rendered, unknown) = render_template("{{}} {{a b}} {{open", &context);
+        assert_eq!(rendered, "{{}} {{a b}} {{open");
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn test_template_context() {
+        let mut config = ProjectConfig::new(Agent::Claude);
+        let context = TemplateSystem::template_context(&config, Path::new("/projects/app"));
+        assert_eq!(context["project_name"], "app");
+
+        config.project_name = Some("demo".to_string());
+        let context = TemplateSystem::template_context(&config, Path::new("/projects/app"));
+        assert_eq!(context["project_name"], "demo");
+
+        config.set_metadata(
+            VARIABLES_METADATA_KEY,
+            serde_json::json!({ "team": "platform", "project_name": "override" }),
+        );
+
+        let context = TemplateSystem::template_context(&config, Path::new("/projects/app"));
+        assert_eq!(context["team"], "platform");
+        assert_eq!(context["project_name"], "override");
+        assert_eq!(context["created_at"], config.created_at().unwrap());
+        assert!(!context.contains_key(VARIABLES_METADATA_KEY));
+
+        let context = TemplateSystem::agent_context(&context, &Agent::Codeium);
+        assert_eq!(context["agent"], "codeium");
+    }
+
+    #[test]
+    fn test_deploy_template_file_interpolates_context() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = HashMap::from([("project_name".to_string(), "demo".to_string())]);
+
+        let written = deploy_template_file(
+            "# {{project_name}}\n{{unknown}}",
+            temp_dir.path(),
+            "test.md",
+            &context,
+            &mut ConflictResolver::new(ConflictPolicy::Prompt),
+        )
+        .unwrap();
+        assert_eq!(written.outcome, WriteOutcome::Created);
+        assert_eq!(fs::read_to_string(written.path).unwrap(), "# demo\n{{unknown}}");
+    }
+
+    #[test]
+    fn test_apply_template_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "# Our own instructions").unwrap();
+        fs::write(temp_dir.path().join("unrelated.md"), "ignored").unwrap();
+
+        let (mut templates, _) = TemplateSystem::resolve_templates(&[Agent::Codeium]);
+        TemplateSystem::apply_template_dir(&mut templates, temp_dir.path()).unwrap();
+
+        let claude = templates.iter().find(|t| t.name == "CLAUDE.md").unwrap();
+        assert_eq!(claude.content, "# Our own instructions");
+
+        // Files missing from the custom directory keep the built-in content
+        let codeium = templates.iter().find(|t| t.name == "codeium.json").unwrap();
+        assert!(matches!(codeium.content, Cow::Borrowed(_)));
+        assert_eq!(templates.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_template_dir_rejects_invalid_utf8() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let (mut templates, _) = TemplateSystem::resolve_templates(&[Agent::Codeium]);
+        let error = TemplateSystem::apply_template_dir(&mut templates, temp_dir.path()).unwrap_err();
+        assert!(error.to_string().contains("not valid UTF-8"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_template_dir_skips_symlink_loops() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        symlink(temp_dir.path().join("codeium.json"), temp_dir.path().join("CLAUDE.md")).unwrap();
+        symlink(temp_dir.path().join("CLAUDE.md"), temp_dir.path().join("codeium.json")).unwrap();
+
+        let (mut templates, _) = TemplateSystem::resolve_templates(&[Agent::Codeium]);
+        TemplateSystem::apply_template_dir(&mut templates, temp_dir.path()).unwrap();
+        assert!(templates.iter().all(|t| matches!(t.content, Cow::Borrowed(_))));
+    }
+
+    fn overwrite() -> ConflictResolver {
+        ConflictResolver::new(ConflictPolicy::Overwrite)
+    }
+
+    fn markdown_template(name: &'static str, content: &'static str) -> ResolvedTemplate {
+        ResolvedTemplate {
+            agent: Agent::Claude,
+            name,
+            content: Cow::Borrowed(content),
+        }
+    }
+
+    #[test]
+    fn test_deploy_resolved_parallel() {
+        let temp_dir = TempDir::new().unwrap();
+        let names = ["A.md", "B.md", "C.md", "D.md", "E.md", "F.md", "G.md", "H.md"];
+        let templates: Vec<_> = names.iter().map(|name| markdown_template(name, "# {{project_name}}\n")).collect();
+        let context = HashMap::from([("project_name".to_string(), "demo".to_string())]);
+
+        let written =
+            TemplateSystem::deploy_resolved_parallel(&templates, &[], temp_dir.path(), &context, &overwrite()).unwrap();
+        let written: Vec<_> = written.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(written, names.map(|name| temp_dir.path().join(name)));
+        for name in names {
+            assert!(fs::read_to_string(temp_dir.path().join(name)).unwrap().starts_with("# demo\n"));
+        }
+    }
+
+    #[test]
+    fn test_failed_deployment_is_undone() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = [markdown_template("A.md", "# new\n"), markdown_template("B.md", "# new\n")];
+        fs::write(temp_dir.path().join("A.md"), "original").unwrap();
+        // A directory where the second file goes makes its write fail
+        fs::create_dir(temp_dir.path().join("B.md")).unwrap();
+
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let result = TemplateSystem::deploy_resolved(&templates, &[], temp_dir.path(), &HashMap::new(), &mut resolver);
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("A.md")).unwrap(), "original");
+
+        fs::remove_file(temp_dir.path().join("A.md")).unwrap();
+        let result = TemplateSystem::deploy_resolved_parallel(&templates, &[], temp_dir.path(), &HashMap::new(), &overwrite());
+        assert!(result.is_err());
+        assert!(!temp_dir.path().join("A.md").exists());
+        assert!(temp_dir.path().join("B.md").is_dir());
+    }
+
+    #[test]
+    fn test_deploy_resolved_parallel_collects_every_error() {
+        let temp_dir = TempDir::new().unwrap();
+        // Directories in place of two of the files make their writes fail
+        fs::create_dir(temp_dir.path().join("B.md")).unwrap();
+        fs::create_dir(temp_dir.path().join("D.md")).unwrap();
+        let templates: Vec<_> =
+            ["A.md", "B.md", "C.md", "D.md"].iter().map(|name| markdown_template(name, "# Title\n")).collect();
+
+        let error = TemplateSystem::deploy_resolved_parallel(&templates, &[], temp_dir.path(), &HashMap::new(), &overwrite())
+            .unwrap_err();
+        match error {
+            ConfigError::Multiple(errors) => assert_eq!(errors.len(), 2),
+            other => panic!("expected two errors, got {}", other),
+        }
+        // The files that were written are removed again
+        assert!(!temp_dir.path().join("A.md").exists());
+        assert!(!temp_dir.path().join("C.md").exists());
+    }
+
+    #[test]
+    fn test_failed_parallel_deployment_restores_overwritten_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let names = ["A.md", "B.md", "C.md", "D.md", "E.md", "F.md"];
+        assert!(names.len() > PARALLEL_DEPLOY_THRESHOLD);
+        for name in &names[1..] {
+            fs::write(temp_dir.path().join(name), format!("original {}", name)).unwrap();
+        }
+        // A directory where the first file goes makes its write fail
+        fs::create_dir(temp_dir.path().join(names[0])).unwrap();
+        let templates: Vec<_> = names.iter().map(|name| markdown_template(name, "# new\n")).collect();
+
+        let result = TemplateSystem::deploy_resolved_parallel(&templates, &[], temp_dir.path(), &HashMap::new(), &overwrite());
+
+        assert!(result.is_err());
+        for name in &names[1..] {
+            assert_eq!(fs::read_to_string(temp_dir.path().join(name)).unwrap(), format!("original {}", name));
+        }
+    }
+
+    #[test]
+    fn test_deploy_resolved_large_sets_in_parallel() {
+        let temp_dir = TempDir::new().unwrap();
+        let names = ["A.md", "B.md", "C.md", "D.md", "E.md", "F.md"];
+        fs::write(temp_dir.path().join("C.md"), "mine").unwrap();
+        let templates: Vec<_> = names.iter().map(|name| markdown_template(name, "# new\n")).collect();
+
+        // Each copy of the resolver applies its policy, and status lines reach the caller's reporter
+        let lines = Arc::new(BufferedReporter::default());
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Skip);
+        let written = output::with_reporter(lines.clone(), || {
+            TemplateSystem::deploy_resolved(&templates, &[], temp_dir.path(), &HashMap::new(), &mut resolver)
+        })
+        .unwrap();
+
+        assert_eq!(written.len(), names.len());
+        assert!(matches!(written[2].outcome, WriteOutcome::Skipped));
+        assert_eq!(fs::read_to_string(temp_dir.path().join("C.md")).unwrap(), "mine");
+        assert!(lines.take().iter().any(|line| line.contains("Keeping existing file")));
+    }
+
+    /// Compares sequential and parallel deployment of a large template set;
+    /// run with `cargo test -p specforge-core --release -- --ignored --nocapture`
+    #[test]
+    #[ignore = "timing comparison, not a correctness check"]
+    fn bench_parallel_deployment_against_sequential() {
+        use std::time::Instant;
+
+        // /dev/shm is a RAM disk on Linux, which isolates the CPU-bound part
+        let ram_disk = Path::new("/dev/shm");
+        let new_dir = || if ram_disk.is_dir() { TempDir::new_in(ram_disk) } else { TempDir::new() }.unwrap();
+        let content: &'static str = "# {{project_name}}\n\nRun the tests before committing.\n".repeat(200).leak();
+        let templates: Vec<_> =
+            (0..500).map(|i| markdown_template(format!("docs/{}/T{}.md", i % 10, i).leak(), content)).collect();
+        let context = HashMap::from([("project_name".to_string(), "bench".to_string())]);
+
+        for round in 0..3 {
+            let sequential_dir = new_dir();
+            let start = Instant::now();
+            TemplateSystem::deploy_resolved_with_progress(
+                &templates,
+                &[],
+                sequential_dir.path(),
+                &context,
+                &mut overwrite(),
+                &progress::NullProgressReporter,
+            )
+            .unwrap();
+            let sequential = start.elapsed();
+
+            let parallel_dir = new_dir();
+            let start = Instant::now();
+            TemplateSystem::deploy_resolved_parallel(&templates, &[], parallel_dir.path(), &context, &overwrite())
+                .unwrap();
+            let parallel = start.elapsed();
+
+            println!(
+                "round {}: {} files sequential {:?}, parallel {:?} ({:.1}x)",
+                round,
+                templates.len(),
+                sequential,
+                parallel,
+                sequential.as_secs_f64() / parallel.as_secs_f64()
+            );
+        }
+    }
+
+    #[test]
+    fn test_deploy_templates_parallel_small_sets_match_sequential() {
+        let parallel = TempDir::new().unwrap();
+        let sequential = TempDir::new().unwrap();
+        let agents = [Agent::Claude, Agent::Codeium];
+        let context = HashMap::new();
+
+        let files = TemplateSystem::deploy_templates_parallel(&agents, parallel.path(), &context).unwrap();
+        TemplateSystem::deploy_templates(&agents, sequential.path(), &context).unwrap();
+        for file in files {
+            let name = file.strip_prefix(parallel.path()).unwrap();
+            assert_eq!(fs::read(&file).unwrap(), fs::read(sequential.path().join(name)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_check_templates_flags_unresolved_placeholders() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = [markdown_template(
+            "CLAUDE.md",
+            "# {{project_name}}\n\nBuilt for {{agent}} by {{projct_name}} and {{ owner }}.\n```\n{{ 1 + 2 }}\n```\n",
+        )];
+        let context = HashMap::from([("project_name".to_string(), "demo".to_string())]);
+
+        let issues = TemplateSystem::check_templates(&templates, temp_dir.path(), &context);
+        let found: Vec<_> = issues.iter().map(|i| (i.line, i.kind, i.token.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (3, TemplateIssueKind::UnresolvedPlaceholder, "{{projct_name}}"),
+                (3, TemplateIssueKind::UnresolvedPlaceholder, "{{ owner }}"),
+            ]
+        );
+        assert_eq!(issues[0].to_string(), "CLAUDE.md:3: unresolved placeholder {{projct_name}}");
+    }
+
+    #[test]
+    fn test_check_templates_flags_broken_links() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("docs/specifications")).unwrap();
+        fs::write(temp_dir.path().join("docs/spec.md"), "spec").unwrap();
+
+        let templates = [
+            markdown_template(
+                "CLAUDE.md",
+                concat!(
+                    "See [the readme](README.md \"Readme\") and [the spec](docs/spec.md#scope).\n",
+                    "Specifications live in [docs](docs/specifications/) and [setup](.github/setup.md).\n",
+                    "Broken: [missing](docs/missing.md), ![diagram](img/flow.png) and [up](../outside.md).\n",
+                    "Ignored: [site](https://example.com), [mail](mailto:a@example.com), [top](#top), `[x](nope.md)`.\n",
+                    "```markdown\n[example](example.md)\n```\n",
+                    "[guide]: ./guide.md\n",
+                ),
+            ),
+            markdown_template("README.md", "Back to [instructions](CLAUDE.md)\n"),
+            markdown_template(".github/setup.md", "Up to [readme](../README.md)\n"),
+        ];
+
+        let issues = TemplateSystem::check_templates(&templates, temp_dir.path(), &HashMap::new());
+        let found: Vec<_> = issues.iter().map(|i| (i.line, i.token.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (3, "docs/missing.md"),
+                (3, "img/flow.png"),
+                (3, "../outside.md"),
+                (8, "./guide.md"),
+            ]
+        );
+        assert!(issues.iter().all(|i| i.kind == TemplateIssueKind::BrokenLink && i.file == "CLAUDE.md"));
+    }
+
+    #[test]
+    fn test_report_template_issues() {
+        let issues = [TemplateIssue {
+            file: "CLAUDE.md".to_string(),
+            line: 1,
+            kind: TemplateIssueKind::BrokenLink,
+            token: "missing.md".to_string(),
+        }];
+        assert!(TemplateSystem::report_template_issues(&issues, false).is_ok());
+        assert!(TemplateSystem::report_template_issues(&[], true).is_ok());
+        let error = TemplateSystem::report_template_issues(&issues, true).unwrap_err();
+        assert_eq!(error.exit_code(), 22);
+    }
+
+    #[test]
+    fn test_check_instructions_size_thresholds() {
+        for agent in Agent::all() {
+            let limits = agent.instructions_size_limits();
+            assert!(limits.soft < limits.hard, "{}", agent);
+            let kind = |size| TemplateSystem::check_instructions_size(&agent, "CLAUDE.md", size).map(|issue| issue.kind);
+
+            assert_eq!(kind(limits.soft), None);
+            assert_eq!(
+                kind(limits.soft + 1),
+                Some(TemplateIssueKind::OversizedInstructions { size: limits.soft + 1, limit: limits.soft })
+            );
+            assert_eq!(
+                kind(limits.hard),
+                Some(TemplateIssueKind::OversizedInstructions { size: limits.hard, limit: limits.soft })
+            );
+            assert_eq!(
+                kind(limits.hard + 1),
+                Some(TemplateIssueKind::InstructionsOverHardLimit { size: limits.hard + 1, limit: limits.hard })
+            );
+        }
+    }
+
+    #[test]
+    fn test_oversized_instructions_fail_deployment() {
+        let temp_dir = TempDir::new().unwrap();
+        let limits = Agent::Claude.instructions_size_limits();
+        let template = |size: usize| ResolvedTemplate {
+            agent: Agent::Claude,
+            name: "CLAUDE.md",
+            content: Cow::Owned("x".repeat(size)),
+        };
+
+        let issues = TemplateSystem::check_templates(&[template(limits.soft)], temp_dir.path(), &HashMap::new());
+        assert!(issues.is_empty());
+
+        let issues = TemplateSystem::check_templates(&[template(limits.soft + 1)], temp_dir.path(), &HashMap::new());
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].to_string().contains("path-scoped instruction files"), "{}", issues[0]);
+        assert!(TemplateSystem::report_template_issues(&issues, false).is_ok());
+        assert!(TemplateSystem::report_template_issues(&issues, true).is_err());
+
+        let issues = TemplateSystem::check_templates(&[template(limits.hard + 1)], temp_dir.path(), &HashMap::new());
+        assert!(issues[0].is_error());
+        assert!(TemplateSystem::report_template_issues(&issues, false).is_err());
+
+        // Other files are not instructions files
+        let readme = ResolvedTemplate { name: "README.md", ..template(limits.hard + 1) };
+        assert!(TemplateSystem::check_templates(&[readme], temp_dir.path(), &HashMap::new()).is_empty());
+    }
+
+    /// Broken embedded templates fail the test suite rather than users' CI
+    #[test]
+    fn test_embedded_templates_pass_strict_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut agent_sets: Vec<Vec<Agent>> = Agent::all().iter().map(|agent| vec![agent.clone()]).collect();
+        agent_sets.push(Agent::all());
+
+        for agents in agent_sets {
+            let config = ProjectConfig::with_project_name(agents[0].clone(), "demo");
+            let context = TemplateSystem::template_context(&config, temp_dir.path());
+            let (templates, _) = TemplateSystem::resolve_templates(&agents);
+            let issues = TemplateSystem::check_templates(&templates, temp_dir.path(), &context);
+            assert!(issues.is_empty(), "{:?}: {:?}", agents, issues);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Provenance lines recording which template a deployed file came from.
+//!
+//! Deployed Markdown files end with an HTML comment and JSON files start with
+//! a `_specforge` key naming the template package, its version and the hash
+//! of the template source, so a file that has drifted far from its template
+//! can still be traced back to it. Formats without comments get no line.
+//!
+//! Tracked file hashes treat a provenance line as a fixed placeholder (see
+//! [`normalize`]): rewriting the line is not a modification, removing it is.
+
+use crate::config::Agent;
+use crate::file_ops::FileOps;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Metadata key that disables provenance lines for a project when set to `false`
+pub const PROVENANCE_METADATA_KEY: &str = "provenance";
+
+/// What a provenance line is hashed as
+pub const PROVENANCE_PLACEHOLDER: &str = "specforge-provenance";
+
+/// Start of a provenance comment in Markdown files
+const MARKDOWN_PREFIX: &str = "<!-- specforge:";
+
+/// Key of the provenance entry in JSON files
+const JSON_KEY: &str = "\"_specforge\":";
+
+/// Template a deployed file was rendered from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// ID of the template package
+    pub package: String,
+    /// Version of the template package
+    pub version: String,
+    /// Hash of the template source in `sha256:<hex>` form
+    pub source: String,
+}
+
+impl Provenance {
+    /// Provenance of a template deployed for an agent from this release
+    pub fn for_template(agent: &Agent, source: &str) -> Self {
+        Self {
+            package: agent.default_package_id().to_string(),
+            version: crate::drift::BUNDLED_VERSION.to_string(),
+            source: format!("sha256:{}", FileOps::hash_content(source)),
+        }
+    }
+
+    /// Provenance line for a file, or `None` for formats without comments
+    pub fn line(&self, file_name: &str) -> Option<String> {
+        match extension(file_name)?.as_str() {
+            "md" | "markdown" => Some(format!(
+                "{} package={} version={} source={} -->",
+                MARKDOWN_PREFIX, self.package, self.version, self.source
+            )),
+            "json" => Some(format!(
+                "  {} {{\"package\": \"{}\", \"version\": \"{}\", \"source\": \"{}\"}}",
+                JSON_KEY, self.package, self.version, self.source
+            )),
+            _ => None,
+        }
+    }
+
+    /// Add the provenance line to rendered content
+    ///
+    /// Markdown gets a last line; JSON objects get a first key. Content in other
+    /// formats, and JSON that is not an object, is returned unchanged.
+    pub fn apply(&self, content: &str, file_name: &str) -> String {
+        let Some(line) = self.line(file_name) else {
+            return content.to_string();
+        };
+
+        if line.starts_with(MARKDOWN_PREFIX) {
+            let separator = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+            return format!("{}{}{}\n", content, separator, line);
+        }
+
+        let Some(open) = content.find('{').filter(|&i| content[..i].trim().is_empty()) else {
+            return content.to_string();
+        };
+        let rest = &content[open + 1..];
+        let comma = if rest.trim_start().starts_with('}') { "" } else { "," };
+        format!("{}\n{}{}{}", &content[..=open], line, comma, rest)
+    }
+}
+
+/// Whether provenance lines are enabled for the project a context was built from
+pub fn enabled(context: &HashMap<String, String>) -> bool {
+    context.get(PROVENANCE_METADATA_KEY).is_none_or(|value| value != "false")
+}
+
+/// Whether a line is a provenance line
+pub fn is_provenance_line(line: &str) -> bool {
+    let line = line.trim();
+    (line.starts_with(MARKDOWN_PREFIX) && line.ends_with("-->")) || line.starts_with(JSON_KEY)
+}
+
+/// Content with every provenance line replaced by [`PROVENANCE_PLACEHOLDER`]
+pub fn normalize(content: &str) -> Cow<'_, str> {
+    if !content.lines().any(is_provenance_line) {
+        return Cow::Borrowed(content);
+    }
+    let mut normalized = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if is_provenance_line(line) {
+            normalized.push_str(PROVENANCE_PLACEHOLDER);
+            if line.ends_with('\n') {
+                normalized.push('\n');
+            }
+        } else {
+            normalized.push_str(line);
+        }
+    }
+    Cow::Owned(normalized)
+}
+
+/// Lowercase extension of a file name
+fn extension(file_name: &str) -> Option<String> {
+    let (_, extension) = file_name.rsplit_once('.')?;
+    Some(extension.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provenance() -> Provenance {
+        Provenance {
+            package: "specforge-claude-templates".to_string(),
+            version: "1.2.3".to_string(),
+            source: "sha256:abc".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_markdown_footer() {
+        let content = provenance().apply("# Title\n\nBody", "CLAUDE.md");
+        assert_eq!(
+            content,
+            "# Title\n\nBody\n<!-- specforge: package=specforge-claude-templates version=1.2.3 source=sha256:abc -->\n"
+        );
+        assert!(content.lines().last().is_some_and(is_provenance_line));
+    }
+
+    #[test]
+    fn test_json_key() {
+        let content = provenance().apply("{\n  \"chat\": true\n}\n", "codeium.json");
+        let value: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value["_specforge"]["version"], "1.2.3");
+        assert_eq!(value["chat"], true);
+
+        let empty = provenance().apply("{}", "empty.json");
+        let value: serde_json::Value = serde_json::from_str(&empty).unwrap();
+        assert_eq!(value["_specforge"]["source"], "sha256:abc");
+
+        assert_eq!(provenance().apply("[1, 2]", "list.json"), "[1, 2]");
+    }
+
+    #[test]
+    fn test_formats_without_comments_are_unchanged() {
+        assert_eq!(provenance().apply("a: b\n", "config.yaml"), "a: b\n");
+        assert_eq!(provenance().apply("text", "LICENSE"), "text");
+    }
+
+    #[test]
+    fn test_normalize_ignores_provenance_changes() {
+        let original = provenance().apply("# Title\n", "CLAUDE.md");
+        let mut other = provenance();
+        other.version = "2.0.0".to_string();
+        let updated = other.apply("# Title\n", "CLAUDE.md");
+
+        assert_ne!(original, updated);
+        assert_eq!(normalize(&original), normalize(&updated));
+        assert_ne!(normalize(&original), normalize("# Title\n"));
+        assert!(matches!(normalize("# Title\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_enabled() {
+        let mut context = HashMap::new();
+        assert!(enabled(&context));
+        context.insert(PROVENANCE_METADATA_KEY.to_string(), "false".to_string());
+        assert!(!enabled(&context));
+    }
+}
@@ -0,0 +1,809 @@
+//! Template packages downloaded from a package URL.
+//!
+//! A remote package is a `.tar.gz`, `.tar` or `.zip` archive of text templates
+//! laid out as they should appear in the project. Archives are fetched over
+//! HTTP(S), or read from disk for `file://` URLs, checked against the package
+//! checksum and unpacked in memory. Every entry is checked before anything is
+//! written, so an archive with an entry outside the project, or one that would
+//! replace a file Specforge or git manages, is rejected as a whole.
+//!
+//! A package can also contribute sections to shared files, such as the
+//! agent's instructions file, by listing them in `specforge-fragments.json`:
+//!
+//! ```json
+//! { "fragments": [{ "target": "instructions", "section": "testing", "weight": 50, "source": "fragments/testing.md" }] }
+//! ```
+//!
+//! The source files are rendered and merged into their target as managed
+//! sections instead of being deployed themselves.
+
+use crate::config::{Agent, LockedPackage, Package};
+use crate::error::{ConfigError, Result};
+use crate::file_ops::backup::BACKUP_MARKER;
+use crate::file_ops::{
+    ConflictResolver, FileOps, FileWrite, PathLimits, ProjectPaths, CONFIG_BACKUP_MARKER, LOCK_FILE_NAME,
+};
+use crate::templates::fragments::{self, Fragment, INSTRUCTIONS_TARGET};
+use crate::templates::{deploy_template_file, render_template};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long to wait for a server to accept a connection or send data
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest archive that is downloaded (10 MiB)
+pub const MAX_ARCHIVE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Largest total size of the unpacked templates (50 MiB), against archive bombs
+pub const MAX_UNPACKED_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Most files a package may contain
+pub const MAX_ARCHIVE_ENTRIES: usize = 1000;
+
+/// Directories a package may not write into
+const RESERVED_DIRECTORIES: [&str; 2] = [".git", ProjectPaths::state_dir_name()];
+
+/// Archive entry listing the sections a package contributes to shared files
+pub const FRAGMENT_MANIFEST: &str = "specforge-fragments.json";
+
+/// Archive formats a package can be published in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Gzip-compressed tar archive
+    TarGz,
+    /// Uncompressed tar archive
+    Tar,
+    /// Zip archive
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Recognize the format from the first bytes of the archive
+    pub fn detect(content: &[u8]) -> Option<Self> {
+        if content.starts_with(&[0x1f, 0x8b]) {
+            Some(ArchiveFormat::TarGz)
+        } else if content.starts_with(b"PK\x03\x04") || content.starts_with(b"PK\x05\x06") {
+            Some(ArchiveFormat::Zip)
+        } else if content.get(257..262) == Some(b"ustar") {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// A template file from a package archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path relative to the project directory, with `/` separators
+    pub path: String,
+    /// Template content
+    pub content: String,
+}
+
+/// A section a package contributes to a shared file, from its fragment manifest
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageFragment {
+    /// Path of the shared file, or `instructions` for the agents' instructions file
+    pub target: String,
+    /// Name of the section, unique within the package
+    pub section: String,
+    /// Sections with a lower weight come first
+    #[serde(default)]
+    pub weight: i32,
+    /// Archive entry with the section template
+    pub source: String,
+    /// Template content of the source entry
+    #[serde(skip)]
+    pub content: String,
+}
+
+/// Content of [`FRAGMENT_MANIFEST`]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FragmentManifest {
+    fragments: Vec<PackageFragment>,
+}
+
+/// A downloaded and verified package, ready to deploy
+#[derive(Debug, Clone)]
+pub struct RemotePackage {
+    /// The package as it is recorded in the configuration
+    pub package: Package,
+    /// Template files in the archive, in archive order
+    pub entries: Vec<ArchiveEntry>,
+    /// Sections contributed to shared files; their sources are not in `entries`
+    pub fragments: Vec<PackageFragment>,
+    /// SHA-256 digest of the downloaded archive in `sha256:<hex>` form
+    pub digest: String,
+}
+
+impl RemotePackage {
+    /// Download the package from its URL, verify it against the package
+    /// checksum and unpack its templates
+    pub fn fetch(package: &Package, timeout: Duration) -> Result<Self> {
+        let url = package.url.as_deref().ok_or_else(|| {
+            ConfigError::invalid_package(format!("Package '{}' has no URL to download it from", package.id))
+        })?;
+        package.validate()?;
+
+        let archive = download(url, timeout)?;
+        package.verify_checksum(&archive)?;
+        let entries = read_archive(&archive).map_err(|e| {
+            e.add_context("archive extraction", format!("Unpacking package '{}' from {}", package.id, url))
+        })?;
+        let (entries, fragments) = split_fragments(entries).map_err(|e| {
+            e.add_context("archive extraction", format!("Reading {} of package '{}'", FRAGMENT_MANIFEST, package.id))
+        })?;
+
+        Ok(Self {
+            package: package.clone(),
+            entries,
+            fragments,
+            digest: format!("sha256:{}", FileOps::hash_content(&archive)),
+        })
+    }
+
+    /// Pin the package to the archive that was downloaded, unless it is
+    /// configured with a checksum of its own
+    pub fn locked_package(&self) -> LockedPackage {
+        let mut locked = LockedPackage::from_package(&self.package);
+        locked.checksum.get_or_insert_with(|| self.digest.clone());
+        locked
+    }
+
+    /// Deploy the templates below the target directory, letting the resolver
+    /// decide about existing files
+    ///
+    /// Fragments are merged into their target files afterwards, replacing
+    /// only this package's sections; `instructions` targets the instructions
+    /// file of each agent. Nothing is written when a target lies below a
+    /// symlinked directory, since writing there would follow the link.
+    pub fn deploy(
+        &self,
+        target_dir: &Path,
+        agents: &[Agent],
+        context: &HashMap<String, String>,
+        resolver: &mut ConflictResolver,
+    ) -> Result<Vec<FileWrite>> {
+        let targets = self.fragment_targets(agents);
+        let limits = PathLimits::current();
+        for path in self.entries.iter().map(|entry| &entry.path).chain(targets.iter().map(|(target, _)| target)) {
+            FileOps::validate_path_length(target_dir.join(path), &limits)?;
+            check_no_linked_directory(target_dir, path)?;
+        }
+
+        let mut deployed_files = Vec::new();
+        for entry in &self.entries {
+            if let Some(parent) = target_dir.join(&entry.path).parent() {
+                FileOps::ensure_directory_exists(parent)?;
+            }
+            deployed_files.push(deploy_template_file(
+                &entry.content,
+                target_dir,
+                &entry.path,
+                context,
+                resolver,
+            )?);
+        }
+        for (target, fragments) in targets {
+            let fragments: Vec<Fragment> = fragments
+                .iter()
+                .map(|fragment| Fragment {
+                    package: self.package.id.clone(),
+                    section: fragment.section.clone(),
+                    weight: fragment.weight,
+                    content: render_template(&fragment.content, context).0,
+                })
+                .collect();
+            deployed_files.push(fragments::deploy_fragments(
+                target_dir,
+                &target,
+                &self.package.id,
+                &fragments,
+                resolver,
+            )?);
+        }
+        Ok(deployed_files)
+    }
+
+    /// Fragments grouped by the file they are merged into, in manifest order
+    fn fragment_targets(&self, agents: &[Agent]) -> Vec<(String, Vec<&PackageFragment>)> {
+        let mut targets: Vec<(String, Vec<&PackageFragment>)> = Vec::new();
+        for fragment in &self.fragments {
+            let files = if fragment.target == INSTRUCTIONS_TARGET {
+                agents.iter().map(|agent| agent.instructions_file().to_string()).collect()
+            } else {
+                vec![fragment.target.clone()]
+            };
+            for file in files {
+                match targets.iter_mut().find(|(target, _)| *target == file) {
+                    Some((_, fragments)) if fragments.contains(&fragment) => {}
+                    Some((_, fragments)) => fragments.push(fragment),
+                    None => targets.push((file, vec![fragment])),
+                }
+            }
+        }
+        targets
+    }
+}
+
+/// Separate the fragment manifest and the fragment sources from the
+/// templates that are deployed as files
+fn split_fragments(mut entries: Vec<ArchiveEntry>) -> Result<(Vec<ArchiveEntry>, Vec<PackageFragment>)> {
+    let Some(index) = entries.iter().position(|entry| entry.path == FRAGMENT_MANIFEST) else {
+        return Ok((entries, Vec::new()));
+    };
+    let manifest = entries.remove(index);
+    let manifest: FragmentManifest = serde_json::from_str(&manifest.content)?;
+
+    let mut fragments = Vec::new();
+    for mut fragment in manifest.fragments {
+        if !fragments::is_valid_section_name(&fragment.section) {
+            return Err(ConfigError::validation_error(format!(
+                "Fragment section '{}' may only contain letters, digits, '-', '_' and '.'",
+                fragment.section
+            )));
+        }
+        if fragments.iter().any(|other: &PackageFragment| other.section == fragment.section) {
+            return Err(ConfigError::validation_error(format!(
+                "Fragment section '{}' is listed more than once",
+                fragment.section
+            )));
+        }
+        if fragment.target != INSTRUCTIONS_TARGET {
+            fragment.target = entry_path(&fragment.target)?;
+        }
+        let source = entry_path(&fragment.source)?;
+        fragment.content = entries
+            .iter()
+            .find(|entry| entry.path == source)
+            .map(|entry| entry.content.clone())
+            .ok_or_else(|| {
+                ConfigError::validation_error(format!(
+                    "Fragment source '{}' is not in the package archive",
+                    fragment.source
+                ))
+            })?;
+        fragment.source = source;
+        fragments.push(fragment);
+    }
+
+    entries.retain(|entry| !fragments.iter().any(|fragment| fragment.source == entry.path));
+    Ok((entries, fragments))
+}
+
+/// Package ID derived from the archive name in a URL, e.g. `team-prompts`
+/// for `https://example.com/team-prompts.tar.gz`
+pub fn package_id_from_url(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url.trim()).ok()?;
+    let name = parsed.path_segments()?.next_back()?;
+    let stem = [".tar.gz", ".tgz", ".tar", ".zip"]
+        .iter()
+        .find_map(|extension| name.strip_suffix(extension))
+        .unwrap_or(name);
+    (!stem.is_empty() && !stem.contains(char::is_whitespace)).then(|| stem.to_string())
+}
+
+/// Fetch the archive at a URL, failing when it is larger than [`MAX_ARCHIVE_SIZE`]
+///
+/// `file://` URLs are read from disk without touching the network. Failures
+/// to reach a server are network errors, so callers can offer a retry.
+pub fn download(url: &str, timeout: Duration) -> Result<Vec<u8>> {
+    let parsed = url::Url::parse(url.trim())
+        .map_err(|e| ConfigError::validation_error(format!("'{}' is not a valid URL: {}", url, e)))?;
+
+    let mut content = Vec::new();
+    match parsed.scheme() {
+        "file" => {
+            let path = parsed.to_file_path().map_err(|_| {
+                ConfigError::validation_error(format!("'{}' does not point at a file on this machine", url))
+            })?;
+            let file = fs::File::open(&path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => ConfigError::not_found(&path),
+                std::io::ErrorKind::PermissionDenied => ConfigError::permission_denied(&path),
+                _ => ConfigError::io_error(format!("Failed to read '{}': {}", path.display(), e)),
+            })?;
+            file.take(MAX_ARCHIVE_SIZE + 1)
+                .read_to_end(&mut content)
+                .map_err(|e| ConfigError::io_error(format!("Failed to read '{}': {}", path.display(), e)))?;
+        }
+        "http" | "https" => {
+            let agent = ureq::AgentBuilder::new()
+                .timeout_connect(timeout)
+                .timeout_read(timeout)
+                .user_agent(concat!("specforge/", env!("CARGO_PKG_VERSION")))
+                .build();
+            let response = agent.get(parsed.as_str()).call().map_err(|e| match e {
+                ureq::Error::Status(status, _) => ConfigError::network_error(format!(
+                    "{} responded with HTTP status {}",
+                    url, status
+                )),
+                ureq::Error::Transport(transport) => ConfigError::network_error(format!(
+                    "Could not download {}: {}. Check your network connection, or use a file:// URL \
+                     to install from a local archive",
+                    url, transport
+                )),
+            })?;
+            response
+                .into_reader()
+                .take(MAX_ARCHIVE_SIZE + 1)
+                .read_to_end(&mut content)
+                .map_err(|e| ConfigError::network_error(format!("Download of {} was interrupted: {}", url, e)))?;
+        }
+        scheme => {
+            return Err(ConfigError::validation_error(format!(
+                "Cannot download '{}': unsupported scheme '{}'",
+                url, scheme
+            )));
+        }
+    }
+
+    if content.len() as u64 > MAX_ARCHIVE_SIZE {
+        return Err(ConfigError::validation_error(format!(
+            "Package archive at {} is larger than {} MiB",
+            url,
+            MAX_ARCHIVE_SIZE / 1024 / 1024
+        )));
+    }
+    Ok(content)
+}
+
+/// Unpack the template files in an archive, skipping directory entries
+///
+/// Fails on links, absolute paths, `..` components, non-UTF-8 files and
+/// archives that exceed the entry or size limits.
+pub fn read_archive(archive: &[u8]) -> Result<Vec<ArchiveEntry>> {
+    let format = ArchiveFormat::detect(archive).ok_or_else(|| {
+        ConfigError::validation_error("Package is not a .tar.gz, .tar or .zip archive")
+    })?;
+    let mut entries = EntryCollector::default();
+
+    match format {
+        ArchiveFormat::TarGz => read_tar(flate2::read::GzDecoder::new(archive), &mut entries)?,
+        ArchiveFormat::Tar => read_tar(archive, &mut entries)?,
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipArchive::new(Cursor::new(archive)).map_err(invalid_archive)?;
+            for index in 0..zip.len() {
+                let file = zip.by_index(index).map_err(invalid_archive)?;
+                let name = file.name().to_string();
+                if file.is_dir() {
+                    continue;
+                }
+                if file.is_symlink() {
+                    return Err(link_entry(&name));
+                }
+                entries.add(&name, file)?;
+            }
+        }
+    }
+
+    if entries.entries.is_empty() {
+        return Err(ConfigError::validation_error("Package archive does not contain any files"));
+    }
+    Ok(entries.entries)
+}
+
+fn read_tar<R: Read>(reader: R, entries: &mut EntryCollector) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().map_err(invalid_archive)? {
+        let entry = entry.map_err(invalid_archive)?;
+        let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => continue,
+            // Extended headers are applied to the next entry by the tar reader
+            tar::EntryType::XGlobalHeader | tar::EntryType::XHeader => continue,
+            tar::EntryType::Regular | tar::EntryType::Continuous => entries.add(&name, entry)?,
+            tar::EntryType::Symlink | tar::EntryType::Link => return Err(link_entry(&name)),
+            _ => {
+                return Err(ConfigError::validation_error(format!(
+                    "Package archive entry '{}' is not a regular file",
+                    name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks entries against the archive limits as they are read
+#[derive(Default)]
+struct EntryCollector {
+    entries: Vec<ArchiveEntry>,
+    unpacked_size: u64,
+}
+
+impl EntryCollector {
+    fn add<R: Read>(&mut self, name: &str, reader: R) -> Result<()> {
+        if self.entries.len() >= MAX_ARCHIVE_ENTRIES {
+            return Err(ConfigError::validation_error(format!(
+                "Package archive contains more than {} files",
+                MAX_ARCHIVE_ENTRIES
+            )));
+        }
+        let path = entry_path(name)?;
+
+        // Read one byte past the remaining budget to notice archives that exceed it
+        let remaining = MAX_UNPACKED_SIZE - self.unpacked_size;
+        let mut content = Vec::new();
+        reader
+            .take(remaining + 1)
+            .read_to_end(&mut content)
+            .map_err(invalid_archive)?;
+        if content.len() as u64 > remaining {
+            return Err(ConfigError::validation_error(format!(
+                "Package archive unpacks to more than {} MiB",
+                MAX_UNPACKED_SIZE / 1024 / 1024
+            )));
+        }
+        self.unpacked_size += content.len() as u64;
+
+        let content = String::from_utf8(content).map_err(|_| {
+            ConfigError::validation_error(format!(
+                "Package archive entry '{}' is not a UTF-8 text file",
+                path
+            ))
+        })?;
+        if self.entries.iter().any(|entry| entry.path == path) {
+            return Err(ConfigError::validation_error(format!(
+                "Package archive contains '{}' more than once",
+                path
+            )));
+        }
+        self.entries.push(ArchiveEntry { path, content });
+        Ok(())
+    }
+}
+
+/// Normalize an archive entry name to a path inside the project, rejecting
+/// names that would escape it
+fn entry_path(name: &str) -> Result<String> {
+    let outside = || {
+        ConfigError::validation_error(format!(
+            "Package archive entry '{}' points outside the project directory",
+            name
+        ))
+    };
+
+    // Backslashes are separators on Windows, and drive letters make a path absolute there
+    if name.starts_with('/') || name.contains('\\') || name.contains(':') {
+        return Err(outside());
+    }
+
+    let mut components = Vec::new();
+    for component in name.split('/') {
+        match component {
+            "" | "." => continue,
+            ".." => return Err(outside()),
+            _ => components.push(component),
+        }
+    }
+    if components.is_empty() {
+        return Err(outside());
+    }
+    let path = components.join("/");
+    if is_reserved_path(&path) {
+        return Err(ConfigError::validation_error(format!(
+            "Package archive entry '{}' would replace a file managed by Specforge or git",
+            name
+        )));
+    }
+    Ok(path)
+}
+
+/// Whether a normalized entry path would replace a file Specforge or git manages
+///
+/// Covers `.git/` and `.specforge/`, the configuration in every format and
+/// with overlays, the lock file and backups. Names are compared without case,
+/// as case-insensitive file systems would resolve them to the same file.
+fn is_reserved_path(path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let reserved_prefixes = [".specforge.", ".specforge-", ".reforge.", LOCK_FILE_NAME];
+
+    path.split('/').any(|component| RESERVED_DIRECTORIES.contains(&component))
+        || reserved_prefixes.iter().any(|prefix| file_name.starts_with(prefix))
+        || file_name.contains(CONFIG_BACKUP_MARKER)
+        || file_name.contains(BACKUP_MARKER)
+}
+
+/// Fail when a directory between the target directory and an entry is a
+/// symlink that writing the entry would follow
+fn check_no_linked_directory(target_dir: &Path, path: &str) -> Result<()> {
+    let mut directory = target_dir.to_path_buf();
+    let mut components: Vec<&str> = path.split('/').collect();
+    components.pop();
+    for component in components {
+        directory.push(component);
+        if directory.symlink_metadata().is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            return Err(ConfigError::validation_error(format!(
+                "Package archive entry '{}' would be written through the symbolic link '{}'",
+                path,
+                directory.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn link_entry(name: &str) -> ConfigError {
+    ConfigError::validation_error(format!(
+        "Package archive entry '{}' is a link; packages may only contain regular files",
+        name
+    ))
+}
+
+fn invalid_archive<E: std::fmt::Display>(error: E) -> ConfigError {
+    ConfigError::validation_error(format!("Package archive is damaged: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::ConflictPolicy;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// Build a tar archive from (path, content) pairs, writing names as-is
+    fn tar_archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_entry_type(tar::EntryType::Regular);
+            // set_path refuses `..`, which is exactly what these tests need to write
+            let name = &mut header.as_old_mut().name;
+            name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn gzip(content: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zip_archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, content) in files {
+            writer.start_file(*path, zip::write::SimpleFileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn file_url(path: &Path) -> String {
+        url::Url::from_file_path(path).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_read_archive_formats() {
+        let files = [("CLAUDE.md", "# {{project_name}}\n"), ("./docs/guide.md", "Guide\n")];
+        for archive in [tar_archive(&files), gzip(&tar_archive(&files)), zip_archive(&files)] {
+            let entries = read_archive(&archive).unwrap();
+            let paths: Vec<_> = entries.iter().map(|e| e.path.as_str()).collect();
+            assert_eq!(paths, ["CLAUDE.md", "docs/guide.md"]);
+            assert_eq!(entries[0].content, "# {{project_name}}\n");
+        }
+
+        assert!(read_archive(b"not an archive").is_err());
+        assert!(read_archive(&tar_archive(&[])).is_err());
+    }
+
+    #[test]
+    fn test_read_archive_rejects_paths_outside_project() {
+        for name in ["../escape.md", "docs/../../escape.md", "/etc/passwd", "C:/evil.md", "docs\\..\\evil.md"] {
+            for archive in [tar_archive(&[(name, "x")]), zip_archive(&[(name, "x")])] {
+                let error = read_archive(&archive).unwrap_err();
+                assert!(error.to_string().contains("outside the project"), "{}: {}", name, error);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_archive_rejects_managed_files() {
+        for name in [
+            ".git/hooks/pre-commit",
+            "./.GIT/config",
+            "docs/.git/hooks/pre-commit",
+            ".specforge/config.json",
+            ".specforge.json",
+            ".specforge.toml",
+            ".specforge.yaml",
+            ".specforge.ci.json",
+            ".specforge-workspace.json",
+            ".reforge.json",
+            "packages/api/.specforge.json",
+            "specforge.lock",
+            ".specforge.json.backup.20260101T000000Z",
+            "CLAUDE.md.specforge-backup-20260101T000000Z",
+        ] {
+            for archive in [tar_archive(&[(name, "x")]), zip_archive(&[(name, "x")])] {
+                let error = read_archive(&archive).unwrap_err();
+                assert!(error.to_string().contains("managed by Specforge or git"), "{}: {}", name, error);
+            }
+        }
+        assert!(read_archive(&tar_archive(&[(".github/copilot-instructions.md", "x")])).is_ok());
+    }
+
+    #[test]
+    fn test_deploy_does_not_follow_linked_directories() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("p.tar");
+        fs::write(&archive_path, tar_archive(&[("CLAUDE.md", "x"), ("docs/hooks/pre-commit", "x")])).unwrap();
+        let package = Package::with_url("p", file_url(&archive_path).as_str(), "1.0.0").unwrap();
+        let remote = RemotePackage::fetch(&package, DEFAULT_TIMEOUT).unwrap();
+
+        let project_dir = temp_dir.path().join("project");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(project_dir.join("docs")).unwrap();
+        fs::create_dir(&outside).unwrap();
+        symlink(&outside, project_dir.join("docs").join("hooks")).unwrap();
+
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let error = remote.deploy(&project_dir, &[Agent::Claude], &HashMap::new(), &mut resolver).unwrap_err();
+
+        assert!(error.to_string().contains("symbolic link"), "{}", error);
+        assert!(!outside.join("pre-commit").exists());
+        assert!(!project_dir.join("CLAUDE.md").exists());
+    }
+
+    #[test]
+    fn test_read_archive_rejects_links_and_binary_files() {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "CLAUDE.md", "/etc/passwd").unwrap();
+        let error = read_archive(&builder.into_inner().unwrap()).unwrap_err();
+        assert!(error.to_string().contains("is a link"));
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("logo.png", zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(&[0x89, 0x50, 0xff, 0xfe]).unwrap();
+        let error = read_archive(&writer.finish().unwrap().into_inner()).unwrap_err();
+        assert!(error.to_string().contains("not a UTF-8 text file"));
+    }
+
+    #[test]
+    fn test_package_id_from_url() {
+        assert_eq!(package_id_from_url("https://example.com/team-prompts.tar.gz").as_deref(), Some("team-prompts"));
+        assert_eq!(package_id_from_url("file:///srv/prompts.zip").as_deref(), Some("prompts"));
+        assert_eq!(package_id_from_url("https://example.com/").as_deref(), None);
+        assert_eq!(package_id_from_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_fetch_and_deploy_from_file_url() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("team-prompts.tar.gz");
+        let archive = gzip(&tar_archive(&[("docs/prompts/review.md", "Review {{project_name}}\n")]));
+        fs::write(&archive_path, &archive).unwrap();
+
+        let package = Package::with_url("team-prompts", file_url(&archive_path).as_str(), "1.0.0")
+            .unwrap()
+            .with_checksum(format!("sha256:{}", FileOps::hash_content(&archive)));
+        let remote = RemotePackage::fetch(&package, DEFAULT_TIMEOUT).unwrap();
+
+        let project_dir = temp_dir.path().join("project");
+        let context = HashMap::from([("project_name".to_string(), "demo".to_string())]);
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Overwrite);
+        let written = remote.deploy(&project_dir, &[Agent::Claude], &context, &mut resolver).unwrap();
+
+        assert_eq!(written.len(), 1);
+        assert_eq!(
+            fs::read_to_string(project_dir.join("docs/prompts/review.md")).unwrap(),
+            "Review demo\n"
+        );
+    }
+
+    #[test]
+    fn test_deploy_fragments_into_instructions() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = r#"{"fragments": [
+            {"target": "instructions", "section": "testing", "weight": 50, "source": "fragments/testing.md"},
+            {"target": "docs/STYLE.md", "section": "rust", "source": "fragments/rust.md"}
+        ]}"#;
+        let archive_path = temp_dir.path().join("team-prompts.tar");
+        fs::write(
+            &archive_path,
+            tar_archive(&[
+                (FRAGMENT_MANIFEST, manifest),
+                ("fragments/testing.md", "Test {{project_name}} before committing.\n"),
+                ("fragments/rust.md", "Use rustfmt.\n"),
+                ("docs/prompts/review.md", "Review\n"),
+            ]),
+        )
+        .unwrap();
+        let package = Package::with_url("team-prompts", file_url(&archive_path).as_str(), "1.0.0").unwrap();
+        let remote = RemotePackage::fetch(&package, DEFAULT_TIMEOUT).unwrap();
+        assert_eq!(remote.entries.len(), 1);
+        assert_eq!(remote.fragments.len(), 2);
+        assert_eq!(remote.locked_package().checksum, Some(remote.digest.clone()));
+
+        let project_dir = temp_dir.path().join("project");
+        fs::create_dir(&project_dir).unwrap();
+        fs::write(project_dir.join("CLAUDE.md"), "# Instructions\n").unwrap();
+        let context = HashMap::from([("project_name".to_string(), "demo".to_string())]);
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Skip);
+        let agents = [Agent::Claude, Agent::Copilot];
+        let written = remote.deploy(&project_dir, &agents, &context, &mut resolver).unwrap();
+
+        // Both agents share CLAUDE.md, which gets the section once
+        assert_eq!(written.len(), 3);
+        assert_eq!(
+            fs::read_to_string(project_dir.join("CLAUDE.md")).unwrap(),
+            "# Instructions\n\n\
+             <!-- specforge-section:begin team-prompts/testing weight=50 -->\n\
+             Test demo before committing.\n\
+             <!-- specforge-section:end team-prompts/testing -->\n"
+        );
+        assert!(fs::read_to_string(project_dir.join("docs/STYLE.md")).unwrap().contains("Use rustfmt."));
+        assert!(!project_dir.join("fragments").exists());
+        assert!(!project_dir.join(FRAGMENT_MANIFEST).exists());
+    }
+
+    #[test]
+    fn test_fragment_manifest_is_checked() {
+        let entry = |path: &str, content: &str| ArchiveEntry { path: path.to_string(), content: content.to_string() };
+        let missing_source = vec![entry(
+            FRAGMENT_MANIFEST,
+            r#"{"fragments": [{"target": "instructions", "section": "testing", "source": "testing.md"}]}"#,
+        )];
+        assert!(split_fragments(missing_source).unwrap_err().to_string().contains("not in the package archive"));
+
+        let outside = vec![
+            entry(FRAGMENT_MANIFEST, r#"{"fragments": [{"target": "../x.md", "section": "s", "source": "s.md"}]}"#),
+            entry("s.md", "s"),
+        ];
+        assert!(split_fragments(outside).is_err());
+
+        let bad_name = vec![
+            entry(FRAGMENT_MANIFEST, r#"{"fragments": [{"target": "instructions", "section": "a b", "source": "s.md"}]}"#),
+            entry("s.md", "s"),
+        ];
+        assert!(split_fragments(bad_name).is_err());
+
+        let plain = vec![entry("a.md", "a")];
+        assert_eq!(split_fragments(plain.clone()).unwrap(), (plain, Vec::new()));
+    }
+
+    #[test]
+    fn test_fetch_verifies_checksum() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("p.zip");
+        fs::write(&archive_path, zip_archive(&[("a.md", "a")])).unwrap();
+
+        let package = Package::with_url("p", file_url(&archive_path).as_str(), "1.0.0")
+            .unwrap()
+            .with_checksum(format!("sha256:{}", "0".repeat(64)));
+        let error = RemotePackage::fetch(&package, DEFAULT_TIMEOUT).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    fn test_download_failures_are_retryable() {
+        // Nothing listens on a port that was just released
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/p.tar.gz", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = download(&url, Duration::from_secs(5)).unwrap_err();
+        assert!(matches!(error, ConfigError::NetworkError(_)), "{:?}", error);
+        assert!(error.is_retryable());
+
+        let missing = download("file:///nonexistent/specforge/p.tar.gz", DEFAULT_TIMEOUT).unwrap_err();
+        assert!(!missing.is_retryable());
+    }
+}
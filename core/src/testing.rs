@@ -0,0 +1,179 @@
+//! Proptest strategies for generating valid configuration values.
+//!
+//! Enable the `test-util` feature to reuse these strategies in downstream
+//! integration tests. Every value produced here passes `validate()`.
+
+use crate::config::{Agent, Condition, Package, ProjectConfig, TrackedFile};
+use proptest::collection::{btree_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+
+/// Generate any supported agent
+pub fn arb_agent() -> impl Strategy<Value = Agent> {
+    prop::sample::select(Agent::all())
+}
+
+/// Generate a non-empty list of distinct agents in any order
+pub fn arb_agents() -> impl Strategy<Value = Vec<Agent>> {
+    let count = Agent::all().len();
+    prop::sample::subsequence(Agent::all(), 1..=count).prop_shuffle()
+}
+
+/// Generate a semantic version, optionally with pre-release and build metadata
+pub fn arb_version() -> impl Strategy<Value = String> {
+    (
+        "(0|[1-9][0-9]{0,3})",
+        "(0|[1-9][0-9]{0,3})",
+        "(0|[1-9][0-9]{0,3})",
+        option::of("(0|[1-9][0-9]{0,6}|[0-9]{0,3}[A-Za-z][0-9A-Za-z]{0,4})(\\.(0|[1-9][0-9]{0,6}|[A-Za-z][0-9A-Za-z]{0,7})){0,2}"),
+        option::of("[0-9A-Za-z]{1,8}"),
+    )
+        .prop_map(|(major, minor, patch, pre_release, build)| {
+            let mut version = format!("{}.{}.{}", major, minor, patch);
+            if let Some(pre_release) = pre_release {
+                version.push('-');
+                version.push_str(&pre_release);
+            }
+            if let Some(build) = build {
+                version.push('+');
+                version.push_str(&build);
+            }
+            version
+        })
+}
+
+/// Generate a package URL accepted by package validation
+///
+/// Host labels never contain `--`, so they cannot turn into punycode (`xn--`)
+/// labels that fail to decode.
+pub fn arb_url() -> impl Strategy<Value = String> {
+    "https?://[a-z][a-z0-9]{0,14}(-[a-z0-9]{1,15})?\\.[a-z]{2,6}(/[A-Za-z0-9._~-]{1,20}){0,4}"
+}
+
+/// Generate a package condition
+pub fn arb_condition() -> impl Strategy<Value = Condition> {
+    let leaf = prop_oneof![
+        "[A-Z_][A-Z0-9_]{0,15}".prop_map(Condition::EnvSet),
+        prop::sample::select(vec!["linux", "macos", "windows", "unix"])
+            .prop_map(|os| Condition::Os(os.to_string())),
+        "[a-z][a-z0-9 \"\\\\-]{0,11}".prop_map(Condition::Profile),
+    ];
+
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        prop_oneof![
+            inner.clone().prop_map(|c| Condition::Not(Box::new(c))),
+            vec(inner.clone(), 2..4).prop_map(Condition::All),
+            vec(inner, 2..4).prop_map(Condition::Any),
+        ]
+    })
+}
+
+/// Generate a valid package
+pub fn arb_package() -> impl Strategy<Value = Package> {
+    (
+        "[A-Za-z0-9][A-Za-z0-9._-]{0,63}",
+        option::of(arb_url()),
+        arb_version(),
+        option::of(arb_condition()),
+        option::of("[0-9a-f]{64}".prop_map(|digest| format!("sha256:{}", digest))),
+        option::of("[A-Za-z0-9][A-Za-z0-9 ,.é漢-]{0,80}"),
+    )
+        .prop_map(|(id, url, version, when, checksum, description)| Package {
+            id,
+            url,
+            version: version.parse().expect("arb_version generates valid versions"),
+            when,
+            checksum,
+            dependencies: Vec::new(),
+            description,
+        })
+}
+
+/// Generate a list of packages with unique IDs
+pub fn arb_packages() -> impl Strategy<Value = Vec<Package>> {
+    vec(arb_package(), 0..8).prop_map(|packages| {
+        let mut seen = std::collections::HashSet::new();
+        packages
+            .into_iter()
+            .filter(|package| seen.insert(package.id.clone()))
+            .collect()
+    })
+}
+
+/// Generate a tracked file with a relative path and SHA-256 hash
+pub fn arb_tracked_file() -> impl Strategy<Value = TrackedFile> {
+    (
+        "[A-Za-z0-9_][A-Za-z0-9._-]{0,20}(/[A-Za-z0-9_][A-Za-z0-9._-]{0,20}){0,3}",
+        "[0-9a-f]{64}",
+    )
+        .prop_map(|(path, sha256)| TrackedFile { path, sha256, mode: None })
+}
+
+/// Generate a list of tracked files with unique paths
+pub fn arb_tracked_files() -> impl Strategy<Value = Vec<TrackedFile>> {
+    vec(arb_tracked_file(), 0..6).prop_map(|files| {
+        let mut seen = std::collections::HashSet::new();
+        files
+            .into_iter()
+            .filter(|file| seen.insert(file.path.clone()))
+            .collect()
+    })
+}
+
+/// Generate an arbitrary JSON value for use as free-form metadata
+pub fn arb_metadata_value() -> impl Strategy<Value = serde_json::Value> {
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::Bool),
+        any::<i64>().prop_map(serde_json::Value::from),
+        any::<u64>().prop_map(serde_json::Value::from),
+        any::<f64>()
+            .prop_filter("JSON numbers must be finite", |f| f.is_finite())
+            .prop_map(serde_json::Value::from),
+        "\\PC{0,64}".prop_map(serde_json::Value::String),
+    ];
+
+    leaf.prop_recursive(3, 32, 4, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..4).prop_map(serde_json::Value::Array),
+            btree_map("[a-z_]{1,12}", inner, 0..4)
+                .prop_map(|map| serde_json::Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+/// Generate an RFC 3339 timestamp as written into `created_at`
+pub fn arb_timestamp() -> impl Strategy<Value = String> {
+    // Between 1970-01-01 and 2100-01-01
+    (0i64..4_102_444_800, 0u32..1_000_000_000).prop_map(|(secs, nanos)| {
+        chrono::DateTime::from_timestamp(secs, nanos)
+            .expect("timestamp within range")
+            .to_rfc3339()
+    })
+}
+
+/// Generate a valid project configuration
+pub fn arb_project_config() -> impl Strategy<Value = ProjectConfig> {
+    (
+        arb_agents(),
+        arb_packages(),
+        arb_tracked_files(),
+        arb_timestamp(),
+        option::of("[A-Za-z0-9][A-Za-z0-9 _-]{0,60}[A-Za-z0-9]"),
+        btree_map("[a-z][a-z0-9_]{0,30}", arb_metadata_value(), 0..10),
+    )
+        .prop_map(|(agents, packages, files, created_at, project_name, extra)| {
+            let mut config = ProjectConfig::with_agents(agents);
+            config.packages = packages;
+            config.files = files;
+            for (key, value) in extra {
+                // project_name is a field since schema version 3 and is generated below
+                if key != "project_name" {
+                    config.set_metadata(key, value);
+                }
+            }
+            config.set_metadata("created_at", created_at);
+            config.project_name = project_name;
+            config
+        })
+}
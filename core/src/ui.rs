@@ -0,0 +1,112 @@
+//! Terminal decoration that depends on whether colors are on.
+//!
+//! Colors are off with `--no-color`, with `NO_COLOR` and when output does not
+//! go to a terminal (see [`output`](crate::output)). Questions then use
+//! dialoguer's plain theme, and the [`Status`] emoji that tell success from
+//! failure in front of status lines become tags such as `[ok]`, so CI logs
+//! stay readable. `--no-emoji` tags status lines the same way.
+
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use std::fmt;
+
+/// What a status line reports, shown as an emoji in front of it
+///
+/// Status lines start with one of these rather than a literal emoji, e.g.
+/// `status!("{} Wrote {}", Status::Ok, path)`, so that plain output can
+/// replace it with its tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Something succeeded or is as it should be
+    Ok,
+    /// Something needs attention but did not fail
+    Warn,
+    /// Something failed
+    Error,
+    /// Progress or other information
+    Info,
+    /// Something was repaired
+    Fixed,
+}
+
+impl Status {
+    /// Every status, in the order their tags are looked up
+    pub const ALL: [Status; 5] = [Status::Ok, Status::Warn, Status::Error, Status::Info, Status::Fixed];
+
+    /// Emoji shown in front of the status line
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Status::Ok => "✅",
+            Status::Warn => "⚠️",
+            Status::Error => "❌",
+            Status::Info => "ℹ️",
+            Status::Fixed => "🔧",
+        }
+    }
+
+    /// Plain-text tag replacing the emoji
+    pub fn tag(self) -> &'static str {
+        match self {
+            Status::Ok => "[ok]",
+            Status::Warn => "[warn]",
+            Status::Error => "[error]",
+            Status::Info => "[info]",
+            Status::Fixed => "[fixed]",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    /// The emoji; those that most terminals draw one column wide get a space
+    /// of padding so the text after them lines up
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Warn | Status::Info => write!(f, "{} ", self.emoji()),
+            _ => f.write_str(self.emoji()),
+        }
+    }
+}
+
+/// Theme for terminal questions, without colors when they are turned off
+pub fn theme() -> Box<dyn Theme> {
+    if crate::output::style().color {
+        Box::new(ColorfulTheme::default())
+    } else {
+        Box::new(SimpleTheme)
+    }
+}
+
+/// Replace the status emoji in front of a line with its tag, keeping the
+/// indentation; other lines are returned as they are
+pub fn plain_status(line: &str) -> String {
+    let text = line.trim_start_matches(' ');
+    let indent = &line[..line.len() - text.len()];
+    let Some((tag, rest)) = Status::ALL
+        .iter()
+        .find_map(|status| Some((status.tag(), text.strip_prefix(status.emoji())?.trim_start_matches(' '))))
+    else {
+        return line.to_string();
+    };
+    if rest.is_empty() {
+        format!("{}{}", indent, tag)
+    } else {
+        format!("{}{} {}", indent, tag, rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_status() {
+        assert_eq!(plain_status("✅ Wrote CLAUDE.md"), "[ok] Wrote CLAUDE.md");
+        assert_eq!(plain_status("⚠️  Skipped"), "[warn] Skipped");
+        assert_eq!(plain_status("❌ CLAUDE.md (modified)"), "[error] CLAUDE.md (modified)");
+        assert_eq!(plain_status("ℹ️  Selected agent: claude"), "[info] Selected agent: claude");
+        assert_eq!(plain_status("🔧 CLAUDE.md (mode restored)"), "[fixed] CLAUDE.md (mode restored)");
+        assert_eq!(plain_status(&format!("{} Done", Status::Info)), "[info] Done");
+        assert_eq!(plain_status("  ✅"), "  [ok]");
+        assert_eq!(plain_status("  📄 CLAUDE.md"), "  📄 CLAUDE.md");
+        assert_eq!(plain_status("- plain"), "- plain");
+    }
+}